@@ -0,0 +1,166 @@
+//! Reference types, for state that changes;  the atom, whose changes are validated and
+//! watched, and the volatile, which is just a box, for state kept to one place
+//!
+//! ```clojure
+//!   (def counter (atom 0 :validator (fn [n] (gte n 0))))
+//!   (add-watch counter :log (fn [key ref old new] (println old "->" new)))
+//!   (swap! counter inc)
+//!   ;; 0->1
+//!   @counter
+//!   ;; => 1
+//! ```
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list_map::PersistentListMap;
+use crate::traits;
+use crate::value::{ToValue, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// (key watch-fn) pairs, in the order they were added
+type Watches = Vec<(Rc<Value>, Rc<dyn IFn>)>;
+
+#[derive(Debug)]
+pub struct Atom {
+    state: RefCell<Rc<Value>>,
+    meta: RefCell<PersistentListMap>,
+    validator: RefCell<Option<Rc<dyn IFn>>>,
+    watches: RefCell<Watches>,
+}
+impl Atom {
+    pub fn new(state: Rc<Value>) -> Atom {
+        Atom {
+            state: RefCell::new(state),
+            meta: RefCell::new(PersistentListMap::Empty),
+            validator: RefCell::new(None),
+            watches: RefCell::new(vec![]),
+        }
+    }
+    pub fn deref(&self) -> Rc<Value> {
+        Rc::clone(&self.state.borrow())
+    }
+    pub fn reset_meta(&self, meta: PersistentListMap) {
+        self.meta.replace(meta);
+    }
+    pub fn validator(&self) -> Option<Rc<dyn IFn>> {
+        self.validator.borrow().clone()
+    }
+    /// Sets our validator, which our current state must already pass
+    pub fn set_validator(&self, validator: Option<Rc<dyn IFn>>) -> Value {
+        if let Some(validator) = &validator {
+            if let Err(condition) = Atom::validate(validator, &self.deref()) {
+                return condition.to_value();
+            }
+        }
+        self.validator.replace(validator);
+        Value::Nil
+    }
+    fn validate(validator: &Rc<dyn IFn>, state: &Rc<Value>) -> Result<(), Rc<Value>> {
+        match validator.invoke(vec![Rc::clone(state)]) {
+            condition if condition.short_circuits() => Err(Rc::new(condition)),
+            Value::Nil | Value::Boolean(false) => Err(Rc::new(error_message::error(
+                "illegal-state",
+                String::from("Invalid reference state"),
+            ))),
+            _ => Ok(()),
+        }
+    }
+    /// Watches are called as (watch-fn key atom old new);  adding one under a key we
+    /// already have replaces that one
+    pub fn add_watch(&self, key: Rc<Value>, watch: Rc<dyn IFn>) {
+        let mut watches = self.watches.borrow_mut();
+        match watches.iter_mut().find(|(watch_key, _)| *watch_key == key) {
+            Some(existing) => existing.1 = watch,
+            None => watches.push((key, watch)),
+        }
+    }
+    pub fn remove_watch(&self, key: &Rc<Value>) {
+        self.watches
+            .borrow_mut()
+            .retain(|(watch_key, _)| watch_key != key);
+    }
+    /// Changes our state to new_state, should it be valid, and tells our watches.
+    /// Returns the state we had before
+    fn change_state(self: &Rc<Self>, new_state: Rc<Value>) -> Result<Rc<Value>, Rc<Value>> {
+        if let Some(validator) = self.validator() {
+            Atom::validate(&validator, &new_state)?;
+        }
+        let old_state = self.state.replace(Rc::clone(&new_state));
+        // Cloned, so that a watch can itself add or remove watches
+        let watches = self.watches.borrow().clone();
+        for (key, watch) in watches {
+            let result = watch.invoke(vec![
+                key,
+                Value::Atom(Rc::clone(self)).to_rc_value(),
+                Rc::clone(&old_state),
+                Rc::clone(&new_state),
+            ]);
+            if result.short_circuits() {
+                return Err(Rc::new(result));
+            }
+        }
+        Ok(old_state)
+    }
+    /// (reset! atom new-state);  returns new-state
+    pub fn reset(self: &Rc<Self>, new_state: Rc<Value>) -> Value {
+        match self.change_state(Rc::clone(&new_state)) {
+            Ok(_) => new_state.to_value(),
+            Err(condition) => condition.to_value(),
+        }
+    }
+    /// (swap-vals! atom f & args);  gives us the state (apply f state args), and returns
+    /// the states we went from and to
+    pub fn swap_vals(
+        self: &Rc<Self>,
+        f: &Rc<dyn IFn>,
+        args: Vec<Rc<Value>>,
+    ) -> Result<(Rc<Value>, Rc<Value>), Rc<Value>> {
+        let mut f_args = vec![self.deref()];
+        f_args.extend(args);
+        let new_state = f.invoke(f_args);
+        if new_state.short_circuits() {
+            return Err(Rc::new(new_state));
+        }
+        let new_state = new_state.to_rc_value();
+        let old_state = self.change_state(Rc::clone(&new_state))?;
+        Ok((old_state, new_state))
+    }
+    /// (compare-and-set! atom old-state new-state);  as our state can only be changed
+    /// by this thread, this is whether our state is equal to old-state, rather than the
+    /// very same value
+    pub fn compare_and_set(self: &Rc<Self>, old_state: &Rc<Value>, new_state: Rc<Value>) -> Value {
+        if self.deref() != *old_state {
+            return Value::Boolean(false);
+        }
+        match self.change_state(new_state) {
+            Ok(_) => Value::Boolean(true),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+impl traits::IMeta for Atom {
+    fn meta(&self) -> PersistentListMap {
+        self.meta.borrow().clone()
+    }
+}
+
+/// Like an atom, without the validators and watches, and so cheaper to change
+#[derive(Debug)]
+pub struct Volatile {
+    state: RefCell<Rc<Value>>,
+}
+impl Volatile {
+    pub fn new(state: Rc<Value>) -> Volatile {
+        Volatile {
+            state: RefCell::new(state),
+        }
+    }
+    pub fn deref(&self) -> Rc<Value> {
+        Rc::clone(&self.state.borrow())
+    }
+    /// (vreset! volatile new-state);  returns new-state
+    pub fn reset(&self, new_state: Rc<Value>) -> Value {
+        self.state.replace(Rc::clone(&new_state));
+        new_state.to_value()
+    }
+}
@@ -0,0 +1,166 @@
+//! The Clojure level call stack;  which fns we are in the middle of calling, and from
+//! where.  When an error comes back out of a call, it is given the stack as it was at
+//! the innermost call it passed through, as its trace
+//!
+//! ```text
+//!   src/app/core.clj:2:3: Index out of bounds: Index (5), Length: (1)
+//!     at nth (src/app/core.clj:2:3)
+//!     at user/f (src/app/core.clj:3:1)
+//! ```
+use crate::condition::Condition;
+use crate::ifn::IFn;
+use crate::persistent_list::PersistentList;
+use crate::position::Position;
+use crate::symbol::Symbol;
+use crate::value::Value;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+/// A call in progress;  the fn called, and the form that called it.  We only work out
+/// its name and position should we need them for a trace, so a frame costs little to push
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub ifn: Rc<dyn IFn>,
+    pub form: Rc<Value>,
+}
+impl Frame {
+    pub fn new(ifn: &Rc<dyn IFn>, form: &Rc<Value>) -> Frame {
+        Frame {
+            ifn: Rc::clone(ifn),
+            form: Rc::clone(form),
+        }
+    }
+    /// The fn's own name, as in user/foo, or else the symbol it was called by, as in nth
+    pub fn name(&self) -> Option<Symbol> {
+        self.ifn.name().or_else(|| match &*self.form {
+            Value::PersistentList(PersistentList::Cons(head, ..)) => match &**head {
+                Value::Symbol(sym) => Some(sym.clone()),
+                _ => None,
+            },
+            _ => None,
+        })
+    }
+    /// Where the call was made
+    pub fn position(&self) -> Option<Position> {
+        Position::of(&self.form)
+    }
+}
+/// user/f (src/app/core.clj:3:1)
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "{}", name)?,
+            None => write!(f, "fn")?,
+        }
+        match self.position() {
+            Some(position) => write!(f, " ({})", position),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CallStack {
+    frames: RefCell<Vec<Frame>>,
+}
+impl CallStack {
+    /// Makes call as frame;  should it return an error with no trace yet, it is given
+    /// the stack as it is now, innermost frame first
+    pub fn call(&self, frame: Frame, call: impl FnOnce() -> Value) -> Value {
+        self.frames.borrow_mut().push(frame);
+        let result = match call() {
+            Value::Condition(error) if error.trace.is_empty() => {
+                Value::Condition(error.with_trace(self.frames()))
+            }
+            result => result,
+        };
+        self.frames.borrow_mut().pop();
+        result
+    }
+    /// The calls in progress, innermost first
+    pub fn frames(&self) -> Vec<Frame> {
+        self.frames.borrow().iter().rev().cloned().collect()
+    }
+}
+
+/// An error, followed by its trace, one frame to a line
+pub fn describe(error: &Condition) -> String {
+    let mut description = error.to_string();
+    for frame in error.trace.iter() {
+        description.push_str(&format!("\n  at {}", frame));
+    }
+    description
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::call_stack;
+    use crate::reader::{read_source, Source};
+    use crate::repl::Repl;
+    use crate::value::Value;
+
+    fn eval(repl: &Repl, source: &str) -> Value {
+        let mut source_file = Source::new("src/app/core.clj");
+        let mut text = source.as_bytes();
+        let mut result = Value::Nil;
+        while !text.is_empty() {
+            result = repl.eval(&read_source(&mut text, &mut source_file));
+        }
+        result
+    }
+
+    #[test]
+    fn errors_carry_the_calls_they_happened_in() {
+        let repl = Repl::default();
+        let result = eval(
+            &repl,
+            "(defn f [x]\n  (nth [1] x))\n(defn g [x]\n  (+ 1 (f x)))\n(g 5)",
+        );
+        match result {
+            Value::Condition(error) => assert_eq!(
+                "src/app/core.clj:2:3: Index out of bounds: Index (5), Length: (1)\n  \
+                 at nth (src/app/core.clj:2:3)\n  \
+                 at clojure.core/f (src/app/core.clj:4:8)\n  \
+                 at clojure.core/g (src/app/core.clj:5:1)",
+                call_stack::describe(&error)
+            ),
+            _ => panic!("Expected an error, found {}", result),
+        }
+    }
+
+    #[test]
+    fn tail_calls_keep_their_callers_in_the_trace() {
+        let repl = Repl::default();
+        let result = eval(
+            &repl,
+            "(defn inner [x]\n  (nth [1] x))\n(defn outer [x]\n  (inner x))\n(outer 5)",
+        );
+        match result {
+            Value::Condition(error) => assert_eq!(
+                "src/app/core.clj:2:3: Index out of bounds: Index (5), Length: (1)\n  \
+                 at nth (src/app/core.clj:2:3)\n  \
+                 at clojure.core/inner (src/app/core.clj:4:3)\n  \
+                 at clojure.core/outer (src/app/core.clj:5:1)",
+                call_stack::describe(&error)
+            ),
+            _ => panic!("Expected an error, found {}", result),
+        }
+    }
+
+    #[test]
+    fn the_last_error_is_kept_in_star_e() {
+        let repl = Repl::default();
+        assert_eq!(Value::Nil, eval(&repl, "*e"));
+        eval(&repl, "(defn f [] (nth [] 0))\n(f)");
+        assert_eq!(
+            Value::String(String::from("Index out of bounds: Index (0), Length: (0)")),
+            eval(&repl, "(get *e :message)")
+        );
+        let trace = "[\"nth (src/app/core.clj:1:12)\" \"clojure.core/f (src/app/core.clj:2:1)\"]";
+        assert_eq!(trace, eval(&repl, "(get *e :trace)").to_string());
+        // Evaluating without error leaves it be
+        eval(&repl, "(+ 1 2)");
+        assert_eq!(trace, eval(&repl, "(get *e :trace)").to_string());
+    }
+}
@@ -1,15 +1,14 @@
-(def *flush-on-newline* true)
-(def *print-readably* true)
+(def ^:dynamic *flush-on-newline* true)
+(def ^:dynamic *print-readably* true)
+(def ^:dynamic *e nil)
 
 (defmacro when [test & body]
-  (list 'if test (concat (list 'do) body)))
+  `(if ~test (do ~@body)))
 
 (def list (fn [& ls] ls))
 
-(defmacro defn [name args & body]
-  (list (quote def) name 
-        (list (quote fn) args 
-              (concat (list (quote do)) body))))
+(defmacro defn [name & fdecl]
+  `(def ~name (fn ~name ~@fdecl)))
 
 
 ";; @TODO Make more like Clojure Proper's apply"
@@ -52,11 +51,17 @@
 (defn dec [x]
   (- x 1))
 
+(defn inc' [x]
+  (+' x 1))
+
+(defn dec' [x]
+  (-' x 1))
+
 (defmacro time [expr]
-  (list (quote let) [(quote start) (quote (System/nanoTime)) (quote ret) expr]
-        (quote (do
-        (println (str "Elapsed time: " (/ (- (System/nanoTime) start) 1000000.0) " msecs"))
-        ret))))
+  `(let [start# (System/nanoTime)
+         ret# ~expr]
+     (println (str "Elapsed time: " (/ (- (System/nanoTime) start#) 1000000.0) " msecs"))
+     ret#))
 
 (defn slurp [f & opts]
   (rust-slurp f opts))
@@ -66,13 +71,56 @@
 (defn rest [x]
   (more x))
 
-(defn next [x]
-  (let [result (rest x)]
-    (if (= '() result)
-      nil
-      result)))
-
 (defn ffirst [x]
   (first (first x)))
-(defmacro var [name]
-  (var-fn* name))
+
+(defmacro lazy-seq [& body]
+  `(lazy-seq* (fn [] ~@body)))
+
+(defmacro delay [& body]
+  `(delay* (fn [] ~@body)))
+(defmacro declare [& names]
+  `(do ~@(map (fn [name] `(def ~name)) names)))
+
+(defmacro defonce [name expr]
+  `(if (bound? (def ~name))
+     nil
+     (def ~name ~expr)))
+
+"dynamic binding"
+
+(defmacro binding [bindings & body]
+  `(with-bindings* [~@(loop [pairs bindings
+                             var-vals '()]
+                        (if (= 0 (count pairs))
+                          var-vals
+                          (recur (rest (rest pairs))
+                                 (concat var-vals
+                                         (list (list 'var (first pairs)) (second pairs))))))]
+                   (fn ~(with-meta [] {:recur-across "binding"}) ~@body)))
+
+(defmacro with-bindings [binding-map & body]
+  `(with-bindings* ~binding-map (fn ~(with-meta [] {:recur-across "with-bindings"}) ~@body)))
+
+(defmacro set! [sym val]
+  `(var-set (var ~sym) ~val))
+
+(defmacro bound-fn [& fntail]
+  `(bound-fn* (fn ~@fntail)))
+
+"conditions and restarts"
+
+(defmacro handler-bind [bindings & body]
+  `(handler-bind* ~bindings (fn ~(with-meta [] {:recur-across "handler-bind"}) ~@body)))
+
+(defmacro handler-case [expr & clauses]
+  `(handler-case* (fn ~(with-meta [] {:recur-across "handler-case"}) ~expr)
+                  [~@(apply concat
+                            (map (fn [clause] `(~(first clause) (fn ~@(rest clause))))
+                                 clauses))]))
+
+(defmacro restart-case [expr & clauses]
+  `(restart-case* (fn ~(with-meta [] {:recur-across "restart-case"}) ~expr)
+                  [~@(map (fn [clause]
+                            `['~(first clause) '~(second clause) (fn ~@(rest clause))])
+                          clauses)]))
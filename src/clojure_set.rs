@@ -0,0 +1,65 @@
+use crate::error_message;
+use crate::maps::{self, MapEntry};
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::persistent_list_map::IPersistentMap;
+use crate::protocol::ProtocolCastable;
+use crate::protocols;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+pub(crate) mod difference;
+pub(crate) mod index;
+pub(crate) mod intersection;
+pub(crate) mod join;
+pub(crate) mod project;
+pub(crate) mod rename_keys;
+pub(crate) mod select;
+pub(crate) mod subset_qmark_;
+pub(crate) mod union;
+
+/// The values of coll as a set;  coll can be a set, nil, or anything else seqable
+pub(crate) fn to_set(coll: &Rc<Value>) -> Result<PersistentHashSet, Rc<Value>> {
+    if let Value::PersistentHashSet(phashset) = &**coll {
+        return Ok(phashset.clone());
+    }
+    match coll.try_as_protocol::<protocols::ISeq>() {
+        Some(seq) => seq.iter().collect(),
+        None => Err(Rc::new(error_message::type_mismatch(
+            TypeTag::PersistentHashSet,
+            coll,
+        ))),
+    }
+}
+
+/// The values of coll, like the keys given to project or index
+pub(crate) fn to_vec(coll: &Rc<Value>) -> Result<Vec<Rc<Value>>, Rc<Value>> {
+    match coll.try_as_protocol::<protocols::ISeq>() {
+        Some(seq) => seq.iter().collect(),
+        None => Err(Rc::new(error_message::type_mismatch(TypeTag::ISeq, coll))),
+    }
+}
+
+/// val as a map, as each member of a relation like #{{:a 1} {:a 2}} must be
+pub(crate) fn to_map(val: &Rc<Value>) -> Result<protocols::IPersistentMap, Rc<Value>> {
+    match val.try_as_protocol::<protocols::IPersistentMap>() {
+        Some(pmap) => Ok(pmap),
+        None => Err(Rc::new(error_message::type_mismatch(
+            TypeTag::PersistentListMap,
+            val,
+        ))),
+    }
+}
+
+/// A map of just those entries of pmap with one of keys
+pub(crate) fn select_keys(pmap: &protocols::IPersistentMap, keys: &[Rc<Value>]) -> Rc<Value> {
+    maps::map_of(
+        keys.iter()
+            .filter(|key| pmap.contains_key(key))
+            .map(|key| MapEntry {
+                key: Rc::clone(key),
+                val: pmap.get(key),
+            }),
+    )
+    .to_rc_value()
+}
@@ -0,0 +1,57 @@
+use crate::clojure_set;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// clojure.set/difference ; set1, without the values in any of sets
+/// (difference set1 & sets)
+#[derive(Debug, Clone)]
+pub struct DifferenceFn {}
+impl ToValue for DifferenceFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for DifferenceFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], args.len());
+        }
+        let mut difference = match clojure_set::to_set(&args[0]) {
+            Ok(set) => set,
+            Err(condition) => return condition.to_value(),
+        };
+        for set in args[1..].iter() {
+            match clojure_set::to_set(set) {
+                Ok(set) => {
+                    difference = set
+                        .iter()
+                        .fold(difference, |difference, val| difference.disj(&val))
+                }
+                Err(condition) => return condition.to_value(),
+            }
+        }
+        difference.to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod difference_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn difference_of_sets() {
+            let repl = Repl::default();
+            assert_eq!(
+                repl.eval_str("#{1}"),
+                repl.eval_str("(clojure.set/difference #{1 2 3} #{2} #{3 4})")
+            );
+            assert_eq!(
+                repl.eval_str("#{1 2}"),
+                repl.eval_str("(clojure.set/difference #{1 2})")
+            );
+        }
+    }
+}
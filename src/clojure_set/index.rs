@@ -0,0 +1,79 @@
+use crate::clojure_set;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap};
+use crate::protocol::{Protocol, ProtocolCastable};
+use crate::protocols;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// clojure.set/index ; xrel, a set of maps, grouped by their values for the keys ks;
+/// a map of each of those {k v ..} to the set of maps that have them
+/// (index xrel ks)
+#[derive(Debug, Clone)]
+pub struct IndexFn {}
+impl ToValue for IndexFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for IndexFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let (xrel, ks) = match (clojure_set::to_set(&args[0]), clojure_set::to_vec(&args[1])) {
+            (Ok(xrel), Ok(ks)) => (xrel, ks),
+            (Err(condition), _) | (_, Err(condition)) => return condition.to_value(),
+        };
+        match index(&xrel, &ks) {
+            Ok(index) => index.unwrap().to_value(),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// The maps of xrel grouped by their values for the keys ks
+pub(crate) fn index(
+    xrel: &PersistentHashSet,
+    ks: &[Rc<Value>],
+) -> Result<protocols::IPersistentMap, Rc<Value>> {
+    let mut index = PersistentListMap::Empty
+        .to_rc_value()
+        .as_protocol::<protocols::IPersistentMap>();
+    for x in xrel.iter() {
+        let ik = clojure_set::select_keys(&clojure_set::to_map(&x)?, ks);
+        let group = match &*index.get(&ik) {
+            Value::PersistentHashSet(group) => group.conj(x),
+            _ => PersistentHashSet::empty().conj(x),
+        };
+        index = index.assoc(ik, group.to_rc_value());
+    }
+    Ok(index)
+}
+
+#[cfg(test)]
+mod tests {
+    mod index_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn index_groups_maps_by_their_keys() {
+            let repl = Repl::default();
+            repl.eval_str(
+                "(def idx (clojure.set/index #{{:a 1 :b 1} {:a 1 :b 2} {:a 2 :b 3}} [:a]))",
+            );
+            assert_eq!(Value::I64(2), repl.eval_str("(count idx)"));
+            assert_eq!(
+                repl.eval_str("#{{:a 1 :b 1} {:a 1 :b 2}}"),
+                repl.eval_str("(get idx {:a 1})")
+            );
+            assert_eq!(
+                repl.eval_str("#{{:a 2 :b 3}}"),
+                repl.eval_str("(get idx {:a 2})")
+            );
+        }
+    }
+}
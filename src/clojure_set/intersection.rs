@@ -0,0 +1,55 @@
+use crate::clojure_set;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// clojure.set/intersection ; the set of the values in each of sets
+/// (intersection set1 & sets)
+#[derive(Debug, Clone)]
+pub struct IntersectionFn {}
+impl ToValue for IntersectionFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for IntersectionFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], args.len());
+        }
+        let mut sets = vec![];
+        for set in args.iter() {
+            match clojure_set::to_set(set) {
+                Ok(set) => sets.push(set),
+                Err(condition) => return condition.to_value(),
+            }
+        }
+        let (first, rest) = sets.split_first().unwrap();
+        first
+            .iter()
+            .filter(|val| !rest.iter().all(|set| set.contains(val)))
+            .fold(first.clone(), |intersection, val| intersection.disj(&val))
+            .to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod intersection_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn intersection_of_sets() {
+            let repl = Repl::default();
+            assert_eq!(
+                repl.eval_str("#{2 3}"),
+                repl.eval_str("(clojure.set/intersection #{1 2 3} #{2 3 4} #{2 3})")
+            );
+            assert_eq!(
+                repl.eval_str("#{}"),
+                repl.eval_str("(clojure.set/intersection #{1} #{2})")
+            );
+        }
+    }
+}
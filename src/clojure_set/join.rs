@@ -0,0 +1,116 @@
+use crate::clojure_set;
+use crate::clojure_set::index::index;
+use crate::clojure_set::rename_keys::rename_keys;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::maps::MapEntry;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::persistent_list_map::IPersistentMap;
+use crate::protocol::Protocol;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// clojure.set/join ; xrel and yrel, sets of maps, joined;  each x merged with each y
+/// that has the same values for the keys they share, or, given km, for the keys of km
+/// in x and the keys they map to in y
+/// (join xrel yrel)
+/// (join xrel yrel km)
+#[derive(Debug, Clone)]
+pub struct JoinFn {}
+impl ToValue for JoinFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for JoinFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 && args.len() != 3 {
+            return error_message::wrong_varg_count(&[2, 3], args.len());
+        }
+        match join(&args[0], &args[1], args.get(2)) {
+            Ok(joined) => joined.to_value(),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+fn join(
+    xrel: &Rc<Value>,
+    yrel: &Rc<Value>,
+    km: Option<&Rc<Value>>,
+) -> Result<PersistentHashSet, Rc<Value>> {
+    let xrel = clojure_set::to_set(xrel)?;
+    let yrel = clojure_set::to_set(yrel)?;
+    let (x, y) = match (xrel.iter().next(), yrel.iter().next()) {
+        (Some(x), Some(y)) => (x, y),
+        _ => return Ok(PersistentHashSet::empty()),
+    };
+    // The keys of x, and the keys of y they're joined on
+    let (xks, yks) = match km {
+        Some(km) => {
+            let entries = clojure_set::to_map(km)?.iter().collect::<Vec<MapEntry>>();
+            (
+                entries.iter().map(|entry| Rc::clone(&entry.key)).collect(),
+                entries.into_iter().map(|entry| entry.val).collect(),
+            )
+        }
+        None => {
+            let y = clojure_set::to_map(&y)?;
+            let ks = clojure_set::to_map(&x)?
+                .iter()
+                .map(|entry| entry.key)
+                .filter(|key| y.contains_key(key))
+                .collect::<Vec<Rc<Value>>>();
+            (ks.clone(), ks)
+        }
+    };
+    let idx = index(&yrel, &yks)?;
+    let mut joined = PersistentHashSet::empty();
+    for x in xrel.iter() {
+        let x = clojure_set::to_map(&x)?;
+        let mut ik = clojure_set::select_keys(&x, &xks);
+        if let Some(km) = km {
+            ik = rename_keys(&ik, km)?;
+        }
+        if let Value::PersistentHashSet(found) = &*idx.get(&ik) {
+            for y in found.iter() {
+                let merged = x.iter().fold(clojure_set::to_map(&y)?, |merged, entry| {
+                    merged.assoc(entry.key, entry.val)
+                });
+                joined = joined.conj(merged.unwrap());
+            }
+        }
+    }
+    Ok(joined)
+}
+
+#[cfg(test)]
+mod tests {
+    mod join_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn join_on_shared_keys() {
+            let repl = Repl::default();
+            assert_eq!(
+                repl.eval_str("#{{:a 1 :b 2 :c 3} {:a 1 :b 4 :c 3}}"),
+                repl.eval_str(
+                    "(clojure.set/join #{{:a 1 :b 2} {:a 1 :b 4} {:a 5 :b 6}} #{{:a 1 :c 3}})"
+                )
+            );
+            assert_eq!(
+                repl.eval_str("#{}"),
+                repl.eval_str("(clojure.set/join #{{:a 1}} #{})")
+            );
+        }
+
+        #[test]
+        fn join_on_a_key_map() {
+            let repl = Repl::default();
+            assert_eq!(
+                repl.eval_str("#{{:a 1 :x 1 :c 3}}"),
+                repl.eval_str("(clojure.set/join #{{:a 1} {:a 2}} #{{:x 1 :c 3}} {:a :x})")
+            );
+        }
+    }
+}
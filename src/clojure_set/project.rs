@@ -0,0 +1,51 @@
+use crate::clojure_set;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// clojure.set/project ; xrel, a set of maps, with each map cut down to the keys ks
+/// (project xrel ks)
+#[derive(Debug, Clone)]
+pub struct ProjectFn {}
+impl ToValue for ProjectFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for ProjectFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let (xrel, ks) = match (clojure_set::to_set(&args[0]), clojure_set::to_vec(&args[1])) {
+            (Ok(xrel), Ok(ks)) => (xrel, ks),
+            (Err(condition), _) | (_, Err(condition)) => return condition.to_value(),
+        };
+        let mut projection = PersistentHashSet::empty();
+        for x in xrel.iter() {
+            match clojure_set::to_map(&x) {
+                Ok(x) => projection = projection.conj(clojure_set::select_keys(&x, &ks)),
+                Err(condition) => return condition.to_value(),
+            }
+        }
+        projection.to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod project_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn project_cuts_each_map_down() {
+            let repl = Repl::default();
+            assert_eq!(
+                repl.eval_str("#{{:a 1} {:a 2}}"),
+                repl.eval_str("(clojure.set/project #{{:a 1 :b 1} {:a 1 :b 2} {:a 2}} [:a])")
+            );
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::clojure_set;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list_map::IPersistentMap;
+use crate::protocol::Protocol;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// clojure.set/rename-keys ; map, with each key of kmap renamed to its value in kmap
+/// (rename-keys map kmap)
+#[derive(Debug, Clone)]
+pub struct RenameKeysFn {}
+impl ToValue for RenameKeysFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for RenameKeysFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match rename_keys(&args[0], &args[1]) {
+            Ok(renamed) => renamed.to_value(),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// map, with each key of kmap renamed to its value in kmap
+pub(crate) fn rename_keys(map: &Rc<Value>, kmap: &Rc<Value>) -> Result<Rc<Value>, Rc<Value>> {
+    let map = clojure_set::to_map(map)?;
+    let kmap = clojure_set::to_map(kmap)?;
+    // All the old keys go first, so that renaming :a to :b and :b to :a swaps them
+    let renamed = kmap
+        .iter()
+        .fold(map.clone(), |renamed, entry| renamed.dissoc(&entry.key));
+    Ok(kmap
+        .iter()
+        .filter(|entry| map.contains_key(&entry.key))
+        .fold(renamed, |renamed, entry| {
+            renamed.assoc(entry.val, map.get(&entry.key))
+        })
+        .unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    mod rename_keys_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn rename_keys_renames_those_present() {
+            let repl = Repl::default();
+            assert_eq!(
+                repl.eval_str("{:x 1 :b 2}"),
+                repl.eval_str("(clojure.set/rename-keys {:a 1 :b 2} {:a :x :c :y})")
+            );
+            assert_eq!(
+                repl.eval_str("{:a 2 :b 1}"),
+                repl.eval_str("(clojure.set/rename-keys {:a 1 :b 2} {:a :b :b :a})")
+            );
+        }
+    }
+}
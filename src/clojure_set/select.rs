@@ -0,0 +1,58 @@
+use crate::clojure_set;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// clojure.set/select ; the set of the values of xset that pred is true of
+/// (select pred xset)
+#[derive(Debug, Clone)]
+pub struct SelectFn {}
+impl ToValue for SelectFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for SelectFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let pred = match &*args[0] {
+            Value::IFn(pred) => Rc::clone(pred),
+            _ => return error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        };
+        let xset = match clojure_set::to_set(&args[1]) {
+            Ok(xset) => xset,
+            Err(condition) => return condition.to_value(),
+        };
+        let mut selected = xset.clone();
+        for val in xset.iter() {
+            let keep = pred.invoke(vec![Rc::clone(&val)]);
+            if keep.short_circuits() {
+                return keep;
+            }
+            if !keep.is_truthy() {
+                selected = selected.disj(&val);
+            }
+        }
+        selected.to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod select_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn select_keeps_what_pred_is_true_of() {
+            let repl = Repl::default();
+            assert_eq!(
+                repl.eval_str("#{2 3}"),
+                repl.eval_str("(clojure.set/select (fn [x] (gt x 1)) #{1 2 3})")
+            );
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::clojure_set;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// clojure.set/subset? ; whether each value of set1 is also in set2
+/// (subset? set1 set2)
+#[derive(Debug, Clone)]
+pub struct SubsetFn {}
+impl ToValue for SubsetFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for SubsetFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let (set1, set2) = match (clojure_set::to_set(&args[0]), clojure_set::to_set(&args[1])) {
+            (Ok(set1), Ok(set2)) => (set1, set2),
+            (Err(condition), _) | (_, Err(condition)) => return condition.to_value(),
+        };
+        Value::Boolean(set1.count() <= set2.count() && set1.iter().all(|val| set2.contains(&val)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod subset_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn subset_of_sets() {
+            let repl = Repl::default();
+            assert_eq!(
+                Value::Boolean(true),
+                repl.eval_str("(clojure.set/subset? #{1 2} #{1 2 3})")
+            );
+            assert_eq!(
+                Value::Boolean(true),
+                repl.eval_str("(clojure.set/subset? #{} #{1})")
+            );
+            assert_eq!(
+                Value::Boolean(false),
+                repl.eval_str("(clojure.set/subset? #{1 4} #{1 2 3})")
+            );
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use crate::clojure_set;
+use crate::ifn::IFn;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// clojure.set/union ; the set of the values in any of sets
+/// (union & sets)
+#[derive(Debug, Clone)]
+pub struct UnionFn {}
+impl ToValue for UnionFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for UnionFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        let mut union = PersistentHashSet::empty();
+        for set in args.iter() {
+            match clojure_set::to_set(set) {
+                Ok(set) => union = set.iter().fold(union, |union, val| union.conj(val)),
+                Err(condition) => return condition.to_value(),
+            }
+        }
+        union.to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod union_tests {
+        use crate::repl::Repl;
+
+        #[test]
+        fn union_of_sets() {
+            let repl = Repl::default();
+            assert_eq!(repl.eval_str("#{}"), repl.eval_str("(clojure.set/union)"));
+            assert_eq!(
+                repl.eval_str("#{1 2 3}"),
+                repl.eval_str("(clojure.set/union #{1 2} #{2 3} nil)")
+            );
+        }
+    }
+}
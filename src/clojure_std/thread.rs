@@ -20,11 +20,11 @@ impl IFn for SleepFn {
         if args.len() == 1 {
             let arg = &**args.get(0).unwrap();
             match arg {
-                Value::I32(i) => {
+                Value::I64(i) => {
                     std::thread::sleep(time::Duration::new(0, (*i as u32) * 100_0000));
                     Value::Nil
                 }
-                _ => error_message::type_mismatch(TypeTag::I32, args.get(0).unwrap()),
+                _ => error_message::type_mismatch(TypeTag::I64, &args[0]),
             }
         } else {
             error_message::wrong_arg_count(1, args.len());
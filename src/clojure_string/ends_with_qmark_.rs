@@ -25,6 +25,7 @@ impl IFn for EndsWithFn {
                 (Value::String(s), Value::String(substring)) => {
                     Value::Boolean(s.ends_with(&substring))
                 }
+                (Value::String(s), Value::Char(c)) => Value::Boolean(s.ends_with(c)),
                 _a => error_message::type_mismatch(TypeTag::String, &_a.1.to_value()),
             }
         }
@@ -74,5 +75,15 @@ mod tests {
             ];
             assert_eq!(Value::Boolean(true), ends_with.invoke(args));
         }
+
+        #[test]
+        fn hello_does_not_end_with_char_h() {
+            let ends_with = EndsWithFn {};
+            let args = vec![
+                Rc::new(Value::String(String::from("hello"))),
+                Rc::new(Value::Char('h')),
+            ];
+            assert_eq!(Value::Boolean(false), ends_with.invoke(args));
+        }
     }
 }
@@ -25,6 +25,7 @@ impl IFn for IncludesFn {
                 (Value::String(s), Value::String(substring)) => {
                     Value::Boolean(s.contains(&substring))
                 }
+                (Value::String(s), Value::Char(c)) => Value::Boolean(s.contains(c)),
                 _a => error_message::type_mismatch(TypeTag::String, &_a.1.to_value()),
             }
         }
@@ -74,5 +75,15 @@ mod tests {
             ];
             assert_eq!(Value::Boolean(true), includes.invoke(args));
         }
+
+        #[test]
+        fn hello_includes_char_l() {
+            let includes = IncludesFn {};
+            let args = vec![
+                Rc::new(Value::String(String::from("hello"))),
+                Rc::new(Value::Char('l')),
+            ];
+            assert_eq!(Value::Boolean(true), includes.invoke(args));
+        }
     }
 }
@@ -86,7 +86,7 @@ mod tests {
             let args = vec![Rc::new(Value::PersistentList(
                 vec![
                     Rc::new(Value::String(String::from(s))),
-                    Rc::new(Value::I32(5)),
+                    Rc::new(Value::I64(5)),
                     Rc::new(Value::String(String::from(s))),
                 ]
                 .into_iter()
@@ -107,7 +107,7 @@ mod tests {
                 Rc::new(Value::PersistentList(
                     vec![
                         Rc::new(Value::String(String::from(s))),
-                        Rc::new(Value::I32(5)),
+                        Rc::new(Value::I64(5)),
                         Rc::new(Value::String(String::from(s))),
                     ]
                     .into_iter()
@@ -129,7 +129,7 @@ mod tests {
                 Rc::new(Value::PersistentVector(
                     vec![
                         Rc::new(Value::String(String::from(s))),
-                        Rc::new(Value::I32(5)),
+                        Rc::new(Value::I64(5)),
                         Rc::new(Value::String(String::from(s))),
                     ]
                     .into_iter()
@@ -141,5 +141,29 @@ mod tests {
                 join.invoke(args)
             );
         }
+
+        #[test]
+        fn join_with_char_separator() {
+            let join = JoinFn {};
+            let args = vec![
+                Rc::new(Value::Char(',')),
+                Rc::new(Value::PersistentVector(
+                    vec![Rc::new(Value::I64(1)), Rc::new(Value::I64(2))]
+                        .into_iter()
+                        .collect::<PersistentVector>(),
+                )),
+            ];
+            assert_eq!(Value::String(String::from("1,2")), join.invoke(args));
+        }
+
+        #[test]
+        fn join_the_chars_of_a_string() {
+            let join = JoinFn {};
+            let args = vec![
+                Rc::new(Value::String(String::from("-"))),
+                Rc::new(Value::String(String::from("abc"))),
+            ];
+            assert_eq!(Value::String(String::from("a-b-c")), join.invoke(args));
+        }
     }
 }
@@ -6,8 +6,8 @@ use crate::error_message;
 use crate::persistent_vector::PersistentVector;
 use crate::type_tag::TypeTag;
 
-/// clojure.string/split [s re & [limit]] splits strings by pattern, optionally maximum of limit
-/// amount
+/// clojure.string/split [s re & [limit]] splits strings by pattern, or by a char, optionally
+/// maximum of limit amount
 #[derive(Debug, Clone)]
 pub struct SplitFn {}
 impl ToValue for SplitFn {
@@ -36,7 +36,15 @@ impl IFn for SplitFn {
                         splits.into_iter().collect::<PersistentVector>(),
                     );
                 }
-                (_a, Value::Pattern(_)) => error_message::type_mismatch(TypeTag::String, &_a),
+                (Value::String(s), Value::Char(c)) => s
+                    .split(c)
+                    .filter(|ss| !ss.is_empty())
+                    .map(|ss| Rc::new(Value::String(ss.to_string())))
+                    .collect::<PersistentVector>()
+                    .to_value(),
+                (_a, Value::Pattern(_)) | (_a, Value::Char(_)) => {
+                    error_message::type_mismatch(TypeTag::String, &_a)
+                }
                 (Value::String(_), _b) => error_message::type_mismatch(TypeTag::Pattern, &_b),
                 (_, _) => error_message::unknown_err(String::from("Unknown error")),
             }
@@ -116,5 +124,26 @@ mod tests {
                 split.invoke(args)
             );
         }
+
+        #[test]
+        fn split_by_char() {
+            let split = SplitFn {};
+            let args = vec![
+                Rc::new(Value::String(String::from("a,b,,c"))),
+                Rc::new(Value::Char(',')),
+            ];
+            assert_eq!(
+                Value::PersistentVector(
+                    vec![
+                        Rc::new(Value::String(String::from("a"))),
+                        Rc::new(Value::String(String::from("b"))),
+                        Rc::new(Value::String(String::from("c")))
+                    ]
+                    .into_iter()
+                    .collect::<PersistentVector>()
+                ),
+                split.invoke(args)
+            );
+        }
     }
 }
@@ -25,6 +25,7 @@ impl IFn for StartsWithFn {
                 (Value::String(s), Value::String(substring)) => {
                     Value::Boolean(s.starts_with(&substring))
                 }
+                (Value::String(s), Value::Char(c)) => Value::Boolean(s.starts_with(c)),
                 _a => error_message::type_mismatch(TypeTag::String, &_a.1.to_value()),
             }
         }
@@ -74,5 +75,15 @@ mod tests {
             ];
             assert_eq!(Value::Boolean(true), starts_with.invoke(args));
         }
+
+        #[test]
+        fn hello_starts_with_char_h() {
+            let starts_with = StartsWithFn {};
+            let args = vec![
+                Rc::new(Value::String(String::from("hello"))),
+                Rc::new(Value::Char('h')),
+            ];
+            assert_eq!(Value::Boolean(true), starts_with.invoke(args));
+        }
     }
 }
@@ -0,0 +1,33 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// clojure.walk/macroexpand-all [form]
+///
+/// Recursively expands every macro call in form
+#[derive(Debug, Clone)]
+pub struct MacroexpandAllFn {
+    enclosing_environment: Rc<Environment>,
+}
+impl MacroexpandAllFn {
+    pub fn new(enclosing_environment: Rc<Environment>) -> MacroexpandAllFn {
+        MacroexpandAllFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for MacroexpandAllFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for MacroexpandAllFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        Value::macroexpand_all(args.first().unwrap(), &self.enclosing_environment).to_value()
+    }
+}
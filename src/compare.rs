@@ -0,0 +1,96 @@
+//! Ordering values;  the total order compare gives numbers, strings, keywords, symbols
+//! and vectors, and the orders our sorted collections keep their keys in
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::numbers::{self, Number};
+use crate::symbol::Symbol;
+use crate::value::Value;
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+/// How a sorted collection orders its keys;  by compare, or by a fn of two keys given to
+/// sorted-map-by or sorted-set-by
+#[derive(Debug, Clone)]
+pub enum Comparator {
+    Default,
+    Fn(Rc<dyn IFn>),
+}
+impl Comparator {
+    pub fn compare(&self, a: &Rc<Value>, b: &Rc<Value>) -> Result<Ordering, Rc<Value>> {
+        match self {
+            Comparator::Default => compare(a, b),
+            Comparator::Fn(f) => {
+                // Like Clojure, a comparator can return a number, as compare does, or can
+                // be a predicate like lt, true if a comes first
+                match f.invoke(vec![Rc::clone(a), Rc::clone(b)]) {
+                    Value::Boolean(true) => Ok(Ordering::Less),
+                    Value::Boolean(false) => match f.invoke(vec![Rc::clone(b), Rc::clone(a)]) {
+                        Value::Boolean(true) => Ok(Ordering::Greater),
+                        Value::Boolean(false) => Ok(Ordering::Equal),
+                        result => ordering_of(result),
+                    },
+                    result => ordering_of(result),
+                }
+            }
+        }
+    }
+}
+
+/// The ordering a comparator's number stands for;  negative for less, and so on
+fn ordering_of(result: Value) -> Result<Ordering, Rc<Value>> {
+    match result {
+        result if numbers::is_number(&result) => {
+            let n = Number::of(&result)?;
+            Ok(numbers::compare(&n, &Number::I64(0)).unwrap_or(Ordering::Equal))
+        }
+        condition if condition.short_circuits() => Err(Rc::new(condition)),
+        result => Err(Rc::new(error_message::error(
+            "type-mismatch",
+            format!(
+                "Comparator must return a number or boolean, found: {}",
+                result.type_tag()
+            ),
+        ))),
+    }
+}
+
+/// How a compares to b;  nil comes before anything else, and otherwise a and b must
+/// be the same sort of thing, both numbers, both strings, and so on
+pub fn compare(a: &Rc<Value>, b: &Rc<Value>) -> Result<Ordering, Rc<Value>> {
+    match (&**a, &**b) {
+        (Value::Nil, Value::Nil) => Ok(Ordering::Equal),
+        (Value::Nil, _) => Ok(Ordering::Less),
+        (_, Value::Nil) => Ok(Ordering::Greater),
+        (a, b) if numbers::is_number(a) && numbers::is_number(b) => {
+            Ok(numbers::compare(&Number::of(a)?, &Number::of(b)?).unwrap_or(Ordering::Equal))
+        }
+        (Value::Boolean(a), Value::Boolean(b)) => Ok(a.cmp(b)),
+        (Value::String(a), Value::String(b)) => Ok(a.cmp(b)),
+        (Value::Char(a), Value::Char(b)) => Ok(a.cmp(b)),
+        (Value::Keyword(a), Value::Keyword(b)) => Ok(compare_symbols(&a.sym, &b.sym)),
+        (Value::Symbol(a), Value::Symbol(b)) => Ok(compare_symbols(a, b)),
+        // A shorter vector comes first, and then it's their first values that differ
+        (Value::PersistentVector(a), Value::PersistentVector(b)) => {
+            match a.len().cmp(&b.len()) {
+                Ordering::Equal => {}
+                ordering => return Ok(ordering),
+            }
+            for (a, b) in a.iter().zip(b.iter()) {
+                match compare(&a, &b)? {
+                    Ordering::Equal => {}
+                    ordering => return Ok(ordering),
+                }
+            }
+            Ok(Ordering::Equal)
+        }
+        _ => Err(Rc::new(error_message::error(
+            "type-mismatch",
+            format!("Cannot compare {} to {}", a.type_tag(), b.type_tag()),
+        ))),
+    }
+}
+
+/// By namespace, with no namespace first, and then by name
+fn compare_symbols(a: &Symbol, b: &Symbol) -> Ordering {
+    a.ns.cmp(&b.ns).then_with(|| a.name.cmp(&b.name))
+}
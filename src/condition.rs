@@ -0,0 +1,545 @@
+//! Conditions and restarts, after Common Lisp's.  Signalling a condition looks for a
+//! handler for it up the dynamic stack of handler-binds and handler-cases *before*
+//! anything is unwound, so that a handler can still pick one of the restarts
+//! established around where it happened
+//!
+//! ```clojure
+//!   (defn div [x y]
+//!     (restart-case
+//!       (if (= y 0)
+//!         (error :divide-by-zero {:message "Tried to divide by zero"})
+//!         (/ x y))
+//!       (return-zero [] 0)
+//!       (return-value [r] r)))
+//!
+//!   (handler-bind [:divide-by-zero (fn [condition] (invoke-restart :return-value 10))]
+//!     (div 5 0))
+//!   ;; => 10
+//! ```
+//!
+//! A condition is a map of its data, plus its :type.  Unwinding, to a handler-case or
+//! a restart, is done by returning a Value::Unwind, which carries its arguments back up
+//! through evaluation to whichever established it
+//!
+//! Errors that Rust code returns, rather than signals, are Value::Conditions;  these
+//! carry a Condition, keyed by its kind, as in :arity or :reader/eof, so that what we do
+//! with an error needn't depend on the wording of its message.  Handlers see these too;
+//! they're signalled on their way out of the innermost handler-bind, handler-case or
+//! restart-case around where they happened
+use crate::call_stack::Frame;
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap};
+use crate::persistent_vector::ToPersistentVector;
+use crate::position::Position;
+use crate::protocol::{Protocol, ProtocolCastable};
+use crate::protocols;
+use crate::repl::Repl;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+/// An error as a value;  what a Value::Condition holds
+#[derive(Debug, Clone)]
+pub struct Condition {
+    /// What sort of error this is, as in :type-mismatch;  its condition type, should it
+    /// be caught
+    pub kind: Keyword,
+    pub message: String,
+    pub data: PersistentListMap,
+    pub cause: Option<Rc<Value>>,
+    /// Where in the source it happened, once we know
+    pub position: Option<Position>,
+    /// The calls it happened in, innermost first
+    pub trace: Vec<Frame>,
+    /// Whether its handlers have had their chance at it already;  so they don't again,
+    /// with each handler-bind, handler-case or restart-case it makes its way out of
+    pub signalled: bool,
+}
+impl Condition {
+    pub fn new(kind: Keyword, message: String) -> Condition {
+        Condition {
+            kind,
+            message,
+            data: PersistentListMap::Empty,
+            cause: None,
+            position: None,
+            trace: vec![],
+            signalled: false,
+        }
+    }
+    pub fn with_data(self, data: PersistentListMap) -> Condition {
+        Condition { data, ..self }
+    }
+    pub fn with_cause(self, cause: Rc<Value>) -> Condition {
+        Condition {
+            cause: Some(cause),
+            ..self
+        }
+    }
+    pub fn at(self, position: Position) -> Condition {
+        Condition {
+            position: Some(position),
+            ..self
+        }
+    }
+    pub fn with_trace(self, trace: Vec<Frame>) -> Condition {
+        Condition { trace, ..self }
+    }
+    pub fn signalled(self) -> Condition {
+        Condition {
+            signalled: true,
+            ..self
+        }
+    }
+    /// Whether this is an error of kind, as in is("reader/eof")
+    pub fn is(&self, kind: &str) -> bool {
+        self.kind == Keyword::intern(kind)
+    }
+}
+// Like meta, where an error happened, and in which calls, doesn't factor into equality;
+// nor does whether it's been signalled
+impl PartialEq for Condition {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.message == other.message
+            && self.data == other.data
+            && self.cause == other.cause
+    }
+}
+impl Hash for Condition {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (&self.kind, &self.message, &self.data, &self.cause).hash(state);
+    }
+}
+/// src/app/core.clj:42:7: Undefined symbol foo
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.position {
+            Some(position) => write!(f, "{}: {}", position, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// The id an Unwind to the top level, out of everything, is given
+pub const TOP_LEVEL: usize = 0;
+
+/// What a handler does with a condition it is given
+#[derive(Debug, Clone)]
+pub enum HandlerAction {
+    /// Calls a fn on it, as handler-bind's handlers do;  should that return, the handler
+    /// has declined, and the condition goes on to the next
+    Call(Rc<dyn IFn>),
+    /// Unwinds to the handler-case clause of this id
+    Unwind(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct Handler {
+    pub condition_type: Rc<Value>,
+    pub action: HandlerAction,
+}
+impl Handler {
+    /// A handler for :condition handles anything
+    pub fn handles(&self, condition_type: &Rc<Value>) -> bool {
+        self.condition_type == *condition_type
+            || *self.condition_type == Keyword::intern("condition").to_value()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Restart {
+    pub name: Symbol,
+    pub id: usize,
+    /// Its parameter vector, for when we ask the user for its arguments
+    pub params: Rc<Value>,
+}
+
+/// The handlers and restarts in effect right now, innermost last
+#[derive(Debug, Clone, Default)]
+pub struct ConditionSystem {
+    handler_clusters: RefCell<Vec<Vec<Handler>>>,
+    restarts: RefCell<Vec<Restart>>,
+    last_id: Cell<usize>,
+    restart_prompt: Cell<bool>,
+}
+impl ConditionSystem {
+    /// A fresh id for a handler-case clause or restart to be unwound to
+    pub fn new_id(&self) -> usize {
+        self.last_id.set(self.last_id.get() + 1);
+        self.last_id.get()
+    }
+    /// Runs body with handlers in effect, as one cluster
+    pub fn with_handlers(&self, handlers: Vec<Handler>, body: impl FnOnce() -> Value) -> Value {
+        self.handler_clusters.borrow_mut().push(handlers);
+        let result = self.signal_returned(body());
+        self.handler_clusters.borrow_mut().pop();
+        result
+    }
+    /// Runs body with restarts available
+    pub fn with_restarts(&self, restarts: Vec<Restart>, body: impl FnOnce() -> Value) -> Value {
+        let restart_count = restarts.len();
+        // Kept innermost last, and so the first of those given last
+        self.restarts
+            .borrow_mut()
+            .extend(restarts.into_iter().rev());
+        let result = self.signal_returned(body());
+        let remaining = self.restarts.borrow().len() - restart_count;
+        self.restarts.borrow_mut().truncate(remaining);
+        result
+    }
+    /// Offers condition to each handler for it, innermost first.  Returns the Unwind of the
+    /// first to unwind, or the error of the first to fail, if any;  otherwise, every handler
+    /// declined, and we return None
+    pub fn signal(&self, condition: &Rc<Value>) -> Option<Value> {
+        let condition_type = condition_type(condition);
+        let mut cluster_index = self.handler_clusters.borrow().len();
+        while cluster_index > 0 {
+            cluster_index -= 1;
+            let cluster = self.handler_clusters.borrow()[cluster_index].clone();
+            for handler in cluster
+                .iter()
+                .filter(|handler| handler.handles(&condition_type))
+            {
+                match &handler.action {
+                    HandlerAction::Unwind(id) => {
+                        return Some(Value::Unwind(*id, vec![Rc::clone(condition)]))
+                    }
+                    HandlerAction::Call(ifn) => {
+                        // A handler runs with only the handlers that were around it when
+                        // it was bound, so that it may signal without finding itself again
+                        let inner_clusters =
+                            self.handler_clusters.borrow_mut().split_off(cluster_index);
+                        let result = ifn.invoke(vec![Rc::clone(condition)]);
+                        self.handler_clusters.borrow_mut().extend(inner_clusters);
+                        if result.short_circuits() {
+                            return Some(result);
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+    /// Signals the error result is, should it be one that was returned, as Rust code's
+    /// errors are, rather than signalled;  on its way out of the innermost handlers or
+    /// restarts around where it happened, so those restarts are still there to be taken
+    fn signal_returned(&self, result: Value) -> Value {
+        match result {
+            Value::Condition(error) if !error.signalled => {
+                let error = error.signalled();
+                match self.signal(&from_error(&error)) {
+                    Some(unwind) => unwind,
+                    None => Value::Condition(error),
+                }
+            }
+            result => result,
+        }
+    }
+    /// The innermost restart named name
+    pub fn find_restart(&self, name: &str) -> Option<Restart> {
+        self.restarts
+            .borrow()
+            .iter()
+            .rev()
+            .find(|restart| restart.name.name == name)
+            .cloned()
+    }
+    /// Every restart available, innermost first
+    pub fn restarts(&self) -> Vec<Restart> {
+        self.restarts.borrow().iter().rev().cloned().collect()
+    }
+    /// Whether an error nothing handles should ask the user what to do, rather than
+    /// just returning;  only so at the REPL
+    pub fn restart_prompt_enabled(&self) -> bool {
+        self.restart_prompt.get()
+    }
+    pub fn enable_restart_prompt(&self) {
+        self.restart_prompt.set(true);
+    }
+}
+
+/// Makes a condition out of the arguments to signal or error, which are one of
+///   (signal :type), (signal :type {:some data}), or (error "message"), which is of type :error
+pub fn make_condition(args: &[Rc<Value>]) -> Result<Rc<Value>, Rc<Value>> {
+    let type_kw = Keyword::intern("type").to_rc_value();
+    match args {
+        [message] if matches!(&**message, Value::String(_)) => Ok(from_error(&Condition::new(
+            Keyword::intern("error"),
+            message.to_string(),
+        ))),
+        [condition_type] => Ok(PersistentListMap::Empty
+            .assoc(type_kw, Rc::clone(condition_type))
+            .to_rc_value()),
+        [condition_type, data] => match data.try_as_protocol::<protocols::IPersistentMap>() {
+            Some(data) => Ok(data.assoc(type_kw, Rc::clone(condition_type)).unwrap()),
+            None if **data == Value::Nil => Ok(PersistentListMap::Empty
+                .assoc(type_kw, Rc::clone(condition_type))
+                .to_rc_value()),
+            None => Err(Rc::new(error_message::type_mismatch(
+                TypeTag::PersistentListMap,
+                data,
+            ))),
+        },
+        _ => Err(Rc::new(error_message::wrong_varg_count(
+            &[1, 2],
+            args.len(),
+        ))),
+    }
+}
+
+/// Signals condition as an error, which must be handled;  if nothing does, at the REPL
+/// we ask which restart to take, and otherwise just return it as a Value::Condition
+pub fn raise(environment: &Rc<Environment>, condition: &Rc<Value>) -> Value {
+    let conditions = environment.conditions();
+    if let Some(unwind) = conditions.signal(condition) {
+        return unwind;
+    }
+    if conditions.restart_prompt_enabled() {
+        return Repl::restart_prompt(environment, condition);
+    }
+    unhandled(condition)
+}
+
+/// The error a condition nothing handled becomes;  of its type, if a keyword, with the
+/// condition itself as its data
+pub fn unhandled(condition: &Rc<Value>) -> Value {
+    // Its handlers had their chance at it as it was signalled
+    if let Value::Condition(error) = &**condition {
+        return Value::Condition(error.clone().signalled());
+    }
+    let kind = match &*condition_type(condition) {
+        Value::Keyword(kind) => kind.clone(),
+        _ => Keyword::intern("error"),
+    };
+    let data = match &**condition {
+        Value::PersistentListMap(pmap) => pmap.clone(),
+        Value::PersistentHashMap(phashmap) => phashmap.iter().collect(),
+        _ => PersistentListMap::Empty,
+    };
+    Value::Condition(
+        Condition::new(kind, describe(condition))
+            .with_data(data)
+            .signalled(),
+    )
+}
+
+/// The condition, a map of :type, :message and, if any, :data and :cause, for the error
+/// of a Value::Condition;  which, as it short circuits, we can't hand to Clojure code as is
+pub fn from_error(error: &Condition) -> Rc<Value> {
+    let mut condition = PersistentListMap::Empty
+        .assoc(
+            Keyword::intern("type").to_rc_value(),
+            error.kind.to_rc_value(),
+        )
+        .assoc(
+            Keyword::intern("message").to_rc_value(),
+            Value::String(error.message.clone()).to_rc_value(),
+        );
+    if error.data != PersistentListMap::Empty {
+        condition = condition.assoc(
+            Keyword::intern("data").to_rc_value(),
+            error.data.to_rc_value(),
+        );
+    }
+    if let Some(cause) = &error.cause {
+        condition = condition.assoc(Keyword::intern("cause").to_rc_value(), Rc::clone(cause));
+    }
+    if !error.trace.is_empty() {
+        condition = condition.assoc(
+            Keyword::intern("trace").to_rc_value(),
+            error
+                .trace
+                .iter()
+                .map(|frame| Value::String(frame.to_string()).to_rc_value())
+                .collect::<Vec<Rc<Value>>>()
+                .into_vector()
+                .to_rc_value(),
+        );
+    }
+    condition.to_rc_value()
+}
+
+pub fn condition_type(condition: &Rc<Value>) -> Rc<Value> {
+    match condition.try_as_protocol::<protocols::IPersistentMap>() {
+        Some(pmap) => pmap.get(&Keyword::intern("type").to_rc_value()),
+        None => Rc::new(Value::Nil),
+    }
+}
+
+/// What we say of a condition nothing handled, as in
+///   Unhandled condition :divide-by-zero: Tried to divide by zero
+pub fn describe(condition: &Rc<Value>) -> String {
+    let message = match condition.try_as_protocol::<protocols::IPersistentMap>() {
+        Some(pmap) => pmap.get(&Keyword::intern("message").to_rc_value()),
+        None => Rc::new(Value::Nil),
+    };
+    match &*message {
+        Value::String(message) => format!(
+            "Unhandled condition {}: {}",
+            condition_type(condition),
+            message
+        ),
+        _ => format!("Unhandled condition {}", condition),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error_message;
+    use crate::repl::Repl;
+    use crate::value::Value;
+
+    fn div_repl() -> Repl {
+        let repl = Repl::default();
+        repl.eval_str(
+            "(defn div [x y]
+               (restart-case
+                 (if (= y 0)
+                   (error :divide-by-zero {:message \"Tried to divide by zero\"})
+                   (/ x y))
+                 (return-zero [] 0)
+                 (return-value [r] r)))",
+        );
+        repl
+    }
+
+    #[test]
+    fn handler_bind_invokes_restart() {
+        let repl = div_repl();
+        assert_eq!(
+            Value::I64(11),
+            repl.eval_str(
+                "(handler-bind [:divide-by-zero (fn [c] (invoke-restart :return-value 10))]
+                   (+ 1 (div 5 0)))"
+            )
+        );
+        assert_eq!(
+            Value::I64(1),
+            repl.eval_str(
+                "(handler-bind [:condition (fn [c] (invoke-restart 'return-zero))]
+                   (+ 1 (div 5 0)))"
+            )
+        );
+    }
+
+    #[test]
+    fn declining_handlers_pass_condition_on() {
+        let repl = div_repl();
+        assert_eq!(
+            Value::I64(7),
+            repl.eval_str(
+                "(handler-bind [:divide-by-zero (fn [c] (invoke-restart :return-value 7))]
+                   (handler-bind [:divide-by-zero (fn [c] :declined)]
+                     (div 5 0)))"
+            )
+        );
+    }
+
+    #[test]
+    fn handler_case_unwinds() {
+        let repl = div_repl();
+        assert_eq!(
+            Repl::read_string(":divide-by-zero"),
+            repl.eval_str(
+                "(handler-case (+ 1 (div 5 0))
+                   (:other [c] :other)
+                   (:divide-by-zero [c] (get c :type)))"
+            )
+        );
+        assert_eq!(
+            Value::I64(3),
+            repl.eval_str("(handler-case (div 6 2) (:divide-by-zero [c] 0))")
+        );
+    }
+
+    #[test]
+    fn handlers_see_errors_rust_code_returns() {
+        let repl = Repl::default();
+        assert_eq!(
+            Repl::read_string(":h"),
+            repl.eval_str("(handler-case (+ 1 :a) (:type-mismatch [c] :h))")
+        );
+        assert_eq!(
+            Value::I64(0),
+            repl.eval_str(
+                "(handler-bind [:type-mismatch (fn [c] (invoke-restart :use-value 0))]
+                   (restart-case (+ 1 :a)
+                     (:use-value [v] v)))"
+            )
+        );
+        // Declined only the once, however many handlers it passes on the way out
+        repl.eval_str("(def declines (atom 0))");
+        assert!(matches!(
+            repl.eval_str(
+                "(handler-bind [:condition (fn [c] (swap! declines inc))]
+                   (handler-case (handler-bind [] (+ 1 :a))
+                     (:other [c] :other)))"
+            ),
+            Value::Condition(_)
+        ));
+        assert_eq!(Value::I64(1), repl.eval_str("@declines"));
+    }
+
+    #[test]
+    fn restarts_can_be_named_by_keywords() {
+        let repl = Repl::default();
+        assert_eq!(
+            Value::I64(5),
+            repl.eval_str(
+                "(handler-bind [:oops (fn [c] (invoke-restart :use-value 5))]
+                   (restart-case (error :oops)
+                     (:use-value [v] v)))"
+            )
+        );
+    }
+
+    #[test]
+    fn signal_returns_nil_when_unhandled() {
+        let repl = Repl::default();
+        assert_eq!(Value::Nil, repl.eval_str("(signal :just-saying {:a 1})"));
+        match repl.eval_str("(error \"oops\")") {
+            Value::Condition(error) => {
+                assert!(error.is("error"));
+                assert_eq!("Unhandled condition :error: oops", error.message);
+            }
+            result => panic!("Expected an error, found {}", result),
+        }
+    }
+
+    #[test]
+    fn recur_cannot_cross_a_handler_or_restart_case() {
+        let repl = Repl::default();
+        for (src, form) in &[
+            ("(loop [x 0] (handler-bind [] (recur 1)))", "handler-bind"),
+            ("(loop [x 0] (handler-case (recur 1)))", "handler-case"),
+            ("(loop [x 0] (restart-case (recur 1)))", "restart-case"),
+        ] {
+            match repl.eval_str(src) {
+                Value::Condition(error) => {
+                    assert_eq!(format!("Cannot recur across {}", form), error.message)
+                }
+                result => panic!("Expected {} to be an error, found {}", src, result),
+            }
+        }
+    }
+
+    #[test]
+    fn invoke_restart_requires_active_restart() {
+        let repl = Repl::default();
+        assert_eq!(
+            error_message::error(
+                "no-restart",
+                String::from("No restart named nope is active")
+            ),
+            repl.eval_str("(invoke-restart :nope)")
+        );
+    }
+}
@@ -0,0 +1,25 @@
+use crate::value::Value;
+use std::rc::Rc;
+
+/// A value consed onto any seq;  most often a lazy one, as in
+/// (lazy-seq (cons x (more-values)))
+#[derive(Debug)]
+pub struct Cons {
+    first: Rc<Value>,
+    // nil, or anything we can take a seq of
+    more: Rc<Value>,
+}
+impl Cons {
+    pub fn new(first: Rc<Value>, more: Rc<Value>) -> Cons {
+        Cons { first, more }
+    }
+    pub fn first(&self) -> Rc<Value> {
+        Rc::clone(&self.first)
+    }
+    pub fn more(&self) -> Rc<Value> {
+        Rc::clone(&self.more)
+    }
+    pub fn into_more(self) -> Rc<Value> {
+        self.more
+    }
+}
@@ -0,0 +1,48 @@
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Debug, Clone)]
+enum DelayState {
+    Unrealized(Rc<dyn IFn>),
+    Realized(Rc<Value>),
+}
+
+/// A value only worked out the first time it's asked for, and then kept;  what
+/// (delay ..) makes
+#[derive(Debug)]
+pub struct Delay {
+    state: RefCell<DelayState>,
+}
+impl Delay {
+    pub fn new(thunk: Rc<dyn IFn>) -> Delay {
+        Delay {
+            state: RefCell::new(DelayState::Unrealized(thunk)),
+        }
+    }
+    pub fn is_realized(&self) -> bool {
+        matches!(&*self.state.borrow(), DelayState::Realized(_))
+    }
+    /// Our value, working it out should this be the first time.  Should that fail, we
+    /// stay unrealized, and will try again next time
+    pub fn deref(&self) -> Value {
+        let thunk = match &*self.state.borrow() {
+            DelayState::Unrealized(thunk) => Rc::clone(thunk),
+            DelayState::Realized(value) => return value.to_value(),
+        };
+        let value = thunk.invoke(vec![]);
+        if !value.short_circuits() {
+            self.state
+                .replace(DelayState::Realized(value.to_rc_value()));
+        }
+        value
+    }
+    /// Our value, if we've worked it out yet
+    pub fn value(&self) -> Option<Rc<Value>> {
+        match &*self.state.borrow() {
+            DelayState::Realized(value) => Some(Rc::clone(value)),
+            DelayState::Unrealized(_) => None,
+        }
+    }
+}
@@ -0,0 +1,332 @@
+//! Destructuring;  binding the parts of a value to the parts of a binding form,
+//! as in
+//!
+//! ```clojure
+//!   (let [[a b & more :as all] [1 2 3 4]] ..)
+//!   (let [{:keys [x y] :or {y 0} :as point} {:x 1}] ..)
+//!   (fn [a & {:keys [verbose]}] ..)
+//! ```
+//!
+//! Rather than expanding into nested lets as Clojure proper does, we walk the
+//! binding form and the value together, and insert directly into the local environment
+use crate::environment::Environment;
+use crate::error_message;
+use crate::keyword::Keyword;
+use crate::maps::{self, MapEntry};
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap};
+use crate::protocol::ProtocolCastable;
+use crate::protocols;
+use crate::symbol::Symbol;
+use crate::traits::ISeq as _;
+use crate::value::{Evaluable, ToValue, Value};
+use std::rc::Rc;
+
+/// Binds value to the binding form pattern in (local) environment;  a plain symbol
+/// is bound as is, while vectors and maps are destructured, recursively
+pub fn bind(
+    pattern: &Rc<Value>,
+    value: Rc<Value>,
+    environment: &Rc<Environment>,
+) -> Result<(), Rc<Value>> {
+    match &**pattern {
+        Value::Symbol(sym) => {
+            if sym.has_ns() {
+                return Err(Rc::new(error_message::error(
+                    "syntax",
+                    format!("Can't let qualified name: {}", sym),
+                )));
+            }
+            environment.insert(sym.clone(), value);
+            Ok(())
+        }
+        Value::PersistentVector(pvector) => bind_sequential(&pvector.to_vec(), value, environment),
+        Value::PersistentListMap(_) | Value::PersistentHashMap(_) => bind_associative(
+            &pattern.as_protocol::<protocols::IPersistentMap>(),
+            value,
+            environment,
+        ),
+        _ => Err(Rc::new(error_message::error(
+            "syntax",
+            format!("Unsupported binding form: {}", pattern),
+        ))),
+    }
+}
+
+/// Every value of a seq, realizing it in full
+fn seq_items(seq: &Rc<Value>) -> Result<Vec<Rc<Value>>, Rc<Value>> {
+    seq.as_protocol::<protocols::ISeq>().iter().collect()
+}
+
+// [a b & more :as all]
+fn bind_sequential(
+    patterns: &[Rc<Value>],
+    value: Rc<Value>,
+    environment: &Rc<Environment>,
+) -> Result<(), Rc<Value>> {
+    if !value.instanceof::<protocols::ISeq>() {
+        return Err(Rc::new(error_message::error(
+            "type-mismatch",
+            format!("Cannot destructure {} sequentially", value.type_tag()),
+        )));
+    }
+    let amp = Symbol::intern("&").to_value();
+    let as_kw = Keyword::intern("as").to_value();
+
+    // What's left of value, walked only as far as our patterns go, so that a lazy seq,
+    // even an infinite one, is only realized that far
+    let mut rest = Rc::clone(&value);
+    let mut patterns = patterns.iter();
+    while let Some(pattern) = patterns.next() {
+        if **pattern == amp {
+            let rest_pattern = match patterns.next() {
+                Some(rest_pattern) => rest_pattern,
+                None => {
+                    return Err(Rc::new(error_message::error(
+                        "syntax",
+                        String::from("Expected a binding form after & in destructuring"),
+                    )))
+                }
+            };
+            // Like Clojure's nthnext;  no rest is nil, not ()
+            let more = rest.as_protocol::<protocols::ISeq>().seq()?;
+            bind(rest_pattern, more, environment)?;
+            rest = Rc::new(Value::Nil);
+        } else if **pattern == as_kw {
+            match patterns.next() {
+                Some(as_pattern) => bind(as_pattern, Rc::clone(&value), environment)?,
+                None => {
+                    return Err(Rc::new(error_message::error(
+                        "syntax",
+                        String::from("Expected a binding form after :as in destructuring"),
+                    )))
+                }
+            }
+        } else {
+            let item = match rest.as_protocol::<protocols::ISeq>().uncons()? {
+                Some((first, more)) => {
+                    rest = more;
+                    first
+                }
+                None => Rc::new(Value::Nil),
+            };
+            bind(pattern, item, environment)?;
+        }
+    }
+    Ok(())
+}
+
+/// The value we destructure associatively;  a seq, such as the rest args of
+/// (fn [& {:keys [a]}] ..), is read as a map of alternating keys and values.
+/// Anything else is taken as is, with nothing to be found in it
+fn as_map(value: &Rc<Value>) -> Result<Rc<Value>, Rc<Value>> {
+    match &**value {
+        Value::PersistentList(_) | Value::LazySeq(_) | Value::Cons(_) => {
+            let items = seq_items(value)?;
+            Ok(maps::map_of(items.chunks(2).map(|pair| {
+                MapEntry {
+                    key: Rc::clone(&pair[0]),
+                    val: pair
+                        .get(1)
+                        .map(Rc::clone)
+                        .unwrap_or_else(|| Rc::new(Value::Nil)),
+                }
+            }))
+            .to_rc_value())
+        }
+        _ => Ok(Rc::clone(value)),
+    }
+}
+
+// {:keys [a b] :strs [c] :syms [d] :or {a 1} :as m, e :e, [f g] :fg}
+fn bind_associative(
+    patterns: &protocols::IPersistentMap,
+    value: Rc<Value>,
+    environment: &Rc<Environment>,
+) -> Result<(), Rc<Value>> {
+    let empty_map = || {
+        PersistentListMap::Empty
+            .to_rc_value()
+            .as_protocol::<protocols::IPersistentMap>()
+    };
+    let map_value = as_map(&value)?;
+    let pmap = map_value
+        .try_as_protocol::<protocols::IPersistentMap>()
+        .unwrap_or_else(empty_map);
+    let defaults = patterns.get(&Keyword::intern("or").to_rc_value());
+    let defaults = match defaults.try_as_protocol::<protocols::IPersistentMap>() {
+        Some(defaults) => defaults,
+        None if *defaults == Value::Nil => empty_map(),
+        None => {
+            return Err(Rc::new(error_message::error(
+                "syntax",
+                String::from(":or in destructuring should be a map"),
+            )))
+        }
+    };
+    // Looks up key, falling back on the :or default for sym
+    let lookup = |key: Rc<Value>, sym: Option<&Symbol>| -> Rc<Value> {
+        if pmap.contains_key(&key) {
+            return pmap.get(&key);
+        }
+        match sym {
+            Some(sym) => {
+                let sym = sym.to_rc_value();
+                if defaults.contains_key(&sym) {
+                    defaults.get(&sym).eval_to_rc(Rc::clone(environment))
+                } else {
+                    Rc::new(Value::Nil)
+                }
+            }
+            None => Rc::new(Value::Nil),
+        }
+    };
+
+    for entry in patterns.iter() {
+        match &*entry.key {
+            Value::Keyword(kw) if kw.sym.ns.is_empty() && kw.name() == "as" => {
+                bind(&entry.val, Rc::clone(&map_value), environment)?
+            }
+            Value::Keyword(kw) if kw.sym.ns.is_empty() && kw.name() == "or" => {}
+            Value::Keyword(kw)
+                if kw.name() == "keys" || kw.name() == "strs" || kw.name() == "syms" =>
+            {
+                let names = match &*entry.val {
+                    Value::PersistentVector(pvector) => pvector.to_vec(),
+                    _ => {
+                        return Err(Rc::new(error_message::error(
+                            "syntax",
+                            format!("{} in destructuring should be a vector", kw),
+                        )))
+                    }
+                };
+                for name in names.iter() {
+                    // :keys [a b/c :d] binds a, c and d;  the namespace, if given, is
+                    // part of the key looked up
+                    let key_sym = match &**name {
+                        Value::Symbol(sym) => sym.clone(),
+                        Value::Keyword(key_kw) => key_kw.sym.clone(),
+                        _ => {
+                            return Err(Rc::new(error_message::error(
+                                "syntax",
+                                format!("Unsupported binding form in {}: {}", kw, name),
+                            )))
+                        }
+                    };
+                    // A :ns/keys gives its namespace to each unqualified key
+                    let key_ns = if key_sym.has_ns() {
+                        key_sym.ns.clone()
+                    } else {
+                        kw.sym.ns.clone()
+                    };
+                    let key = match kw.name() {
+                        "keys" => Keyword::intern_with_ns(&key_ns, key_sym.name()).to_rc_value(),
+                        "strs" => Value::String(key_sym.name().to_string()).to_rc_value(),
+                        _ => Symbol::intern_with_ns(&key_ns, key_sym.name()).to_rc_value(),
+                    };
+                    let local_sym = Symbol::intern(key_sym.name());
+                    let val = lookup(key, Some(&local_sym));
+                    environment.insert(local_sym, val);
+                }
+            }
+            // {a :a, [b c] :bc}
+            _ => {
+                let key = entry.val.eval_to_rc(Rc::clone(environment));
+                let val = match &*entry.key {
+                    Value::Symbol(sym) => lookup(key, Some(sym)),
+                    _ => lookup(key, None),
+                };
+                bind(&entry.key, val, environment)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::keyword::Keyword;
+    use crate::repl::Repl;
+    use crate::value::{ToValue, Value};
+
+    #[test]
+    fn sequential_destructuring() {
+        let repl = Repl::default();
+        assert_eq!(
+            Repl::read_string("[1 2 (3 4) [1 2 3 4]]"),
+            repl.eval_str("(let [[a b & more :as all] [1 2 3 4]] [a b more all])")
+        );
+        assert_eq!(
+            Repl::read_string("[1 nil nil]"),
+            repl.eval_str("(let [[a b & more] '(1)] [a b more])")
+        );
+        assert_eq!(
+            Repl::read_string("[1 2 3]"),
+            repl.eval_str("(let [[a [b c]] [1 [2 3]]] [a b c])")
+        );
+    }
+
+    #[test]
+    fn associative_destructuring() {
+        let repl = Repl::default();
+        assert_eq!(
+            Repl::read_string("[1 0 \"s\" sym 2]"),
+            repl.eval_str(
+                "(let [{:keys [a b] :strs [c] :syms [d] :or {b 0} e :e} \
+                        {:a 1 \"c\" \"s\" 'd 'sym :e 2}] \
+                   [a b c d e])"
+            )
+        );
+        assert_eq!(
+            Keyword::intern("x").to_value(),
+            repl.eval_str("(let [{:as m} :x] m)")
+        );
+    }
+
+    #[test]
+    fn nested_and_keyword_args_destructuring() {
+        let repl = Repl::default();
+        repl.eval_str("(defn f [a [b {:keys [c]}] & {:keys [d] :or {d 4}}] [a b c d])");
+        assert_eq!(
+            Repl::read_string("[1 2 3 4]"),
+            repl.eval_str("(f 1 [2 {:c 3}])")
+        );
+        assert_eq!(
+            Repl::read_string("[1 2 3 5]"),
+            repl.eval_str("(f 1 [2 {:c 3}] :d 5)")
+        );
+    }
+
+    #[test]
+    fn lazy_seqs_are_only_realized_as_far_as_patterns_go() {
+        let repl = Repl::default();
+        assert_eq!(
+            Repl::read_string("[0 1]"),
+            repl.eval_str("(let [[a b] (range)] [a b])")
+        );
+        assert_eq!(
+            Repl::read_string("[0 1 2]"),
+            repl.eval_str("(let [[x & r] (iterate inc 0)] [x (first r) (second r)])")
+        );
+        assert_eq!(
+            Repl::read_string("[1 nil]"),
+            repl.eval_str("(let [[a & more] (map inc [0])] [a more])")
+        );
+        assert_eq!(
+            Repl::read_string("[1 2]"),
+            repl.eval_str("(let [[a & {:keys [b]}] (map (fn [x] x) [1 :b 2])] [a b])")
+        );
+        assert!(matches!(
+            repl.eval_str("(let [[a] 1] a)"),
+            Value::Condition(_)
+        ));
+    }
+
+    #[test]
+    fn loop_destructuring() {
+        let repl = Repl::default();
+        assert_eq!(
+            Value::I64(6),
+            repl.eval_str("(loop [[x & xs] [1 2 3] acc 0] (if x (recur xs (+ acc x)) acc))")
+        );
+    }
+}
@@ -1,10 +1,17 @@
+use crate::call_stack::CallStack;
+use crate::clojure_set;
 use crate::clojure_std;
 use crate::clojure_string;
+use crate::clojure_walk;
+use crate::condition::ConditionSystem;
+use crate::macro_expansions::MacroExpansions;
 use crate::namespace::Namespaces;
+use crate::persistent_queue::PersistentQueue;
 use crate::repl::Repl;
 use crate::rust_core;
 use crate::symbol::Symbol;
 use crate::value::{ToValue, Value};
+use crate::var::Var;
 
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -21,6 +28,9 @@ pub struct EnvironmentVal {
     // significant
     curr_ns_sym: RefCell<Symbol>,
     namespaces: Namespaces,
+    conditions: ConditionSystem,
+    call_stack: CallStack,
+    macro_expansions: MacroExpansions,
 }
 impl EnvironmentVal {
     // @TODO is this wrapper really necessary, or is it just inviting an invariant break?
@@ -55,6 +65,9 @@ impl EnvironmentVal {
     fn has_namespace(&self, namespace: &Symbol) -> bool {
         self.namespaces.has_namespace(namespace)
     }
+    fn intern_into_current_namespace(&self, sym: &Symbol) -> Rc<Var> {
+        self.namespaces.intern(&self.curr_ns_sym.borrow(), sym)
+    }
     fn get_var_from_namespace(&self, namespace: &Symbol, sym: &Symbol) -> Rc<Value> {
         self.namespaces.get_var(namespace, sym)
     }
@@ -64,6 +77,14 @@ impl EnvironmentVal {
     fn get_current_namespace(&self) -> Symbol {
         self.curr_ns_sym.borrow().clone()
     }
+    fn resolve_symbol(&self, sym: &Symbol) -> Symbol {
+        let curr_ns_sym = self.get_current_namespace();
+        let namespace_sym = self
+            .namespaces
+            .resolve_namespace(&curr_ns_sym, sym)
+            .unwrap_or(curr_ns_sym);
+        Symbol::intern_with_ns(&namespace_sym.name, &sym.name)
+    }
 
     fn create_namespace(&self, symbol: &Symbol) {
         self.namespaces.create_namespace(symbol);
@@ -79,6 +100,9 @@ impl EnvironmentVal {
         EnvironmentVal {
             curr_ns_sym: RefCell::new(curr_ns_sym),
             namespaces,
+            conditions: ConditionSystem::default(),
+            call_stack: CallStack::default(),
+            macro_expansions: MacroExpansions::default(),
         }
     }
 }
@@ -92,8 +116,13 @@ impl EnvironmentVal {
 pub enum Environment {
     MainEnvironment(EnvironmentVal),
     /// Points to parent environment
-    /// Introduced by Closures, and by let
-    LocalEnvironment(Rc<Environment>, RefCell<HashMap<Symbol, Rc<Value>>>),
+    /// Introduced by Closures, and by let.  A fn's also keeps the namespace it was
+    /// defined in, which the free symbols of its body mean what they do in
+    LocalEnvironment(
+        Rc<Environment>,
+        RefCell<HashMap<Symbol, Rc<Value>>>,
+        Option<Symbol>,
+    ),
 }
 use Environment::*;
 impl Environment {
@@ -171,15 +200,68 @@ impl Environment {
     pub fn get_current_namespace_name(&self) -> String {
         self.get_current_namespace().name.clone()
     }
+    /// The handlers and restarts in effect
+    pub fn conditions(&self) -> &ConditionSystem {
+        match self.get_main_environment() {
+            MainEnvironment(EnvironmentVal { conditions, .. }) => conditions,
+            LocalEnvironment(..) => panic!(
+                "get_main_environment() returns LocalEnvironment,\
+		             but by definition should only return MainEnvironment"
+            ),
+        }
+    }
+    /// The calls in progress
+    pub fn call_stack(&self) -> &CallStack {
+        match self.get_main_environment() {
+            MainEnvironment(EnvironmentVal { call_stack, .. }) => call_stack,
+            LocalEnvironment(..) => panic!(
+                "get_main_environment() returns LocalEnvironment,\
+		             but by definition should only return MainEnvironment"
+            ),
+        }
+    }
+    /// What the macro calls evaluated so far have expanded to
+    pub fn macro_expansions(&self) -> &MacroExpansions {
+        match self.get_main_environment() {
+            MainEnvironment(EnvironmentVal {
+                macro_expansions, ..
+            }) => macro_expansions,
+            LocalEnvironment(..) => panic!(
+                "get_main_environment() returns LocalEnvironment,\
+		             but by definition should only return MainEnvironment"
+            ),
+        }
+    }
 
     pub fn new_main_environment() -> Environment {
         MainEnvironment(EnvironmentVal::new_main_val())
     }
     pub fn new_local_environment(outer_environment: Rc<Environment>) -> Environment {
-        LocalEnvironment(outer_environment, RefCell::new(HashMap::new()))
+        LocalEnvironment(outer_environment, RefCell::new(HashMap::new()), None)
+    }
+    /// The environment a fn closes over;  a local environment, in which anything not
+    /// bound locally is looked up in namespace, wherever the fn is called from
+    pub fn new_fn_environment(
+        outer_environment: Rc<Environment>,
+        namespace: Symbol,
+    ) -> Environment {
+        LocalEnvironment(
+            outer_environment,
+            RefCell::new(HashMap::new()),
+            Some(namespace),
+        )
+    }
+    /// The namespace our free symbols mean what they do in;  that of the innermost fn
+    /// around us, or else the current namespace
+    pub fn namespace(&self) -> Symbol {
+        match self {
+            MainEnvironment(env_val) => env_val.get_current_namespace(),
+            LocalEnvironment(_, _, Some(namespace)) => namespace.clone(),
+            LocalEnvironment(parent_env, _, None) => parent_env.namespace(),
+        }
     }
     /// Insert a binding into an arbitrary namespace
-    fn insert_into_namespace(&self, namespace: &Symbol, sym: Symbol, val: Rc<Value>) {
+    pub fn insert_into_namespace(&self, namespace: &Symbol, sym: Symbol, val: Rc<Value>) {
         match self.get_main_environment() {
             MainEnvironment(env_val) => env_val.insert_into_namespace(namespace, sym, val),
             LocalEnvironment(..) => panic!(
@@ -208,7 +290,7 @@ impl Environment {
             MainEnvironment(_) => {
                 self.insert_into_current_namespace(sym, val);
             }
-            LocalEnvironment(_, mappings) => {
+            LocalEnvironment(_, mappings, _) => {
                 mappings.borrow_mut().insert(sym, val);
             }
         }
@@ -234,14 +316,49 @@ impl Environment {
                     )
                 }
             }
-            LocalEnvironment(parent_env, mappings) => {
-                if sym.ns != "" {
-                    return self.get_main_environment().get(sym);
-                }
-                match mappings.borrow().get(sym) {
-                    Some(val) => Rc::clone(val),
-                    None => parent_env.get(sym),
+            // Locals aren't vars;  only a namespace holds those
+            LocalEnvironment(..) => match self.get_main_environment() {
+                MainEnvironment(env_val) if !sym.has_ns() => {
+                    env_val.get_var_from_namespace(&self.namespace(), sym)
                 }
+                main_environment => main_environment.get_var(sym),
+            },
+        }
+    }
+    /// The Var sym names in our current namespace (or its own, if qualified), made,
+    /// unbound, should there not be one yet;  what def defines
+    pub fn intern(&self, sym: &Symbol) -> Rc<Var> {
+        match self.get_main_environment() {
+            MainEnvironment(env_val) => env_val.intern_into_current_namespace(sym),
+            LocalEnvironment(..) => panic!(
+                "get_main_environment() returns LocalEnvironment,\
+		                 but by definition should only return MainEnvironment"
+            ),
+        }
+    }
+    /// Qualifies a symbol with the namespace it means from our current namespace,
+    /// as in `+ -> clojure.core/+`;  if it means nothing yet, our current namespace.
+    /// Already qualified symbols are left as they are
+    pub fn resolve_symbol(&self, sym: &Symbol) -> Symbol {
+        if sym.has_ns() {
+            return sym.clone();
+        }
+        match self.get_main_environment() {
+            MainEnvironment(env_val) => env_val.resolve_symbol(sym),
+            LocalEnvironment(..) => panic!(
+                "get_main_environment() returns LocalEnvironment,\
+		                 but by definition should only return MainEnvironment"
+            ),
+        }
+    }
+    /// The names of the locals around us, innermost first;  at top level, there are none
+    pub fn local_names(&self) -> Vec<Symbol> {
+        match self {
+            MainEnvironment(_) => vec![],
+            LocalEnvironment(parent_env, mappings, _) => {
+                let mut local_names = mappings.borrow().keys().cloned().collect::<Vec<Symbol>>();
+                local_names.extend(parent_env.local_names());
+                local_names
             }
         }
     }
@@ -261,17 +378,33 @@ impl Environment {
                     env_val.get_from_namespace(&env_val.get_current_namespace(), &sym)
                 }
             }
-            LocalEnvironment(parent_env, mappings) => {
+            LocalEnvironment(..) => {
                 if sym.ns != "" {
                     return self.get_main_environment().get(sym);
                 }
-                match mappings.borrow().get(sym) {
-                    Some(val) => Rc::clone(val),
-                    None => parent_env.get(sym),
+                if let Some(val) = self.get_local(sym) {
+                    return val;
+                }
+                match self.get_main_environment() {
+                    MainEnvironment(env_val) => env_val.get_from_namespace(&self.namespace(), sym),
+                    LocalEnvironment(..) => panic!(
+                        "get_main_environment() returns LocalEnvironment,\
+		                 but by definition should only return MainEnvironment"
+                    ),
                 }
             }
         }
     }
+    /// What sym is bound to locally, in us or around us, if anything
+    fn get_local(&self, sym: &Symbol) -> Option<Rc<Value>> {
+        match self {
+            MainEnvironment(_) => None,
+            LocalEnvironment(parent_env, mappings, _) => match mappings.borrow().get(sym) {
+                Some(val) => Some(Rc::clone(val)),
+                None => parent_env.get_local(sym),
+            },
+        }
+    }
     pub fn clojure_core_environment() -> Rc<Environment> {
         // Register our macros / functions ahead of time
         let add_fn = rust_core::AddFn {};
@@ -279,21 +412,61 @@ impl Environment {
         let multiply_fn = rust_core::MultiplyFn {};
         let divide_fn = rust_core::DivideFn {};
         let rem_fn = rust_core::RemFn {};
+        let add_promoting_fn = rust_core::AddPromotingFn {};
+        let subtract_promoting_fn = rust_core::SubtractPromotingFn {};
+        let multiply_promoting_fn = rust_core::MultiplyPromotingFn {};
+        let unchecked_add_fn = rust_core::UncheckedAddFn {};
+        let unchecked_subtract_fn = rust_core::UncheckedSubtractFn {};
+        let unchecked_multiply_fn = rust_core::UncheckedMultiplyFn {};
+        let unchecked_inc_fn = rust_core::UncheckedIncFn {};
+        let unchecked_dec_fn = rust_core::UncheckedDecFn {};
+        let unchecked_negate_fn = rust_core::UncheckedNegateFn {};
+        let bigint_fn = rust_core::BigintFn {};
+        let bigdec_fn = rust_core::BigdecFn {};
+        let numerator_fn = rust_core::NumeratorFn {};
+        let denominator_fn = rust_core::DenominatorFn {};
         let rand_fn = rust_core::RandFn {};
         let rand_int_fn = rust_core::RandIntFn {};
         let str_fn = rust_core::StrFn {};
+        let char_fn = rust_core::CharFn {};
+        let int_fn = rust_core::IntFn {};
+        let is_char_fn = rust_core::IsCharFn {};
         let do_fn = rust_core::DoFn {};
         let nth_fn = rust_core::NthFn {};
-        let do_macro = rust_core::DoMacro {};
         let concat_fn = rust_core::ConcatFn {};
         let flush_stdout_fn = rust_core::FlushStdoutFn {};
         let system_newline_fn = rust_core::SystemNewlineFn {};
         let print_string_fn = rust_core::PrintStringFn {};
         let read_line_fn = rust_core::ReadLineFn {};
         let assoc_fn = rust_core::AssocFn {};
+        let dissoc_fn = rust_core::DissocFn {};
+        let conj_fn = rust_core::ConjFn {};
+        let pop_fn = rust_core::PopFn {};
+        let peek_fn = rust_core::PeekFn {};
+        let set_fn = rust_core::SetFn {};
+        let hash_set_fn = rust_core::HashSetFn {};
+        let disj_fn = rust_core::DisjFn {};
+        let contains_fn = rust_core::ContainsFn {};
+        let sorted_map_fn = rust_core::SortedMapFn {};
+        let sorted_map_by_fn = rust_core::SortedMapByFn {};
+        let sorted_set_fn = rust_core::SortedSetFn {};
+        let sorted_set_by_fn = rust_core::SortedSetByFn {};
+        let subseq_fn = rust_core::SubseqFn {};
+        let rsubseq_fn = rust_core::RsubseqFn {};
+        let compare_fn = rust_core::CompareFn {};
+        let transient_fn = rust_core::TransientFn {};
+        let persistent_fn = rust_core::PersistentFn {};
+        let transient_conj_fn = rust_core::TransientConjFn {};
+        let transient_assoc_fn = rust_core::TransientAssocFn {};
+        let transient_dissoc_fn = rust_core::TransientDissocFn {};
+        let transient_pop_fn = rust_core::TransientPopFn {};
+        let transient_disj_fn = rust_core::TransientDisjFn {};
+        let into_fn = rust_core::IntoFn {};
+        let queue_fn = rust_core::QueueFn {};
         let more_fn = rust_core::MoreFn {};
         let first_fn = rust_core::FirstFn {};
         let second_fn = rust_core::SecondFn {};
+        let last_fn = rust_core::LastFn {};
 
         // rust implementations of core functions
         let slurp_fn = rust_core::slurp::SlurpFn {};
@@ -305,6 +478,8 @@ impl Environment {
 
         let get_fn = rust_core::GetFn {};
         let map_fn = rust_core::MapFn {};
+        let trampoline_fn = rust_core::TrampolineFn {};
+        let gensym_fn = rust_core::GensymFn {};
 
         // clojure.string
         let reverse_fn = clojure_string::reverse::ReverseFn {};
@@ -321,25 +496,88 @@ impl Environment {
         let trim_newline_fn = clojure_string::trim_newline::TrimNewlineFn {};
         let split_fn = clojure_string::split::SplitFn {};
 
+        // clojure.set
+        let set_union_fn = clojure_set::union::UnionFn {};
+        let set_intersection_fn = clojure_set::intersection::IntersectionFn {};
+        let set_difference_fn = clojure_set::difference::DifferenceFn {};
+        let set_subset_fn = clojure_set::subset_qmark_::SubsetFn {};
+        let set_select_fn = clojure_set::select::SelectFn {};
+        let set_project_fn = clojure_set::project::ProjectFn {};
+        let set_rename_keys_fn = clojure_set::rename_keys::RenameKeysFn {};
+        let set_index_fn = clojure_set::index::IndexFn {};
+        let set_join_fn = clojure_set::join::JoinFn {};
+
         // Hardcoded fns
         let lexical_eval_fn = Value::LexicalEvalFn {};
         // Hardcoded macros
         let let_macro = Value::LetMacro {};
         let quote_macro = Value::QuoteMacro {};
+        let syntax_quote_macro = Value::SyntaxQuoteMacro {};
         let def_macro = Value::DefMacro {};
         let fn_macro = Value::FnMacro {};
         let defmacro_macro = Value::DefmacroMacro {};
         let if_macro = Value::IfMacro {};
+        let do_macro = Value::DoMacro {};
+        let loop_macro = Value::LoopMacro {};
+        let recur_macro = Value::RecurMacro {};
+        let letfn_macro = Value::LetfnMacro {};
         let environment = Rc::new(Environment::new_main_environment());
 
         let equals_fn = rust_core::EqualsFn {};
         let eval_fn = rust_core::EvalFn::new(Rc::clone(&environment));
+        let macroexpand_1_fn = rust_core::Macroexpand1Fn::new(Rc::clone(&environment));
+        let macroexpand_fn = rust_core::MacroexpandFn::new(Rc::clone(&environment));
+        let macroexpand_all_fn =
+            clojure_walk::macroexpand_all::MacroexpandAllFn::new(Rc::clone(&environment));
+        let signal_fn = rust_core::SignalFn::new(Rc::clone(&environment));
+        let error_fn = rust_core::ErrorFn::new(Rc::clone(&environment));
+        let handler_bind_fn = rust_core::HandlerBindFn::new(Rc::clone(&environment));
+        let handler_case_fn = rust_core::HandlerCaseFn::new(Rc::clone(&environment));
+        let restart_case_fn = rust_core::RestartCaseFn::new(Rc::clone(&environment));
+        let invoke_restart_fn = rust_core::InvokeRestartFn::new(Rc::clone(&environment));
+        let throw_fn = rust_core::ThrowFn::new(Rc::clone(&environment));
+        let try_fn = rust_core::TryFn::new(Rc::clone(&environment));
+        let try_macro = rust_core::TryMacro {};
+        let ex_info_fn = rust_core::ExInfoFn {};
+        let ex_data_fn = rust_core::ExDataFn {};
+        let ex_message_fn = rust_core::ExMessageFn {};
+        let ex_cause_fn = rust_core::ExCauseFn {};
         let ns_macro = rust_core::NsMacro::new(Rc::clone(&environment));
         let load_file_fn = rust_core::LoadFileFn::new(Rc::clone(&environment));
         let refer_fn = rust_core::ReferFn::new(Rc::clone(&environment));
         let meta_fn = rust_core::MetaFn::new(Rc::clone(&environment));
         let with_meta_fn = rust_core::WithMetaFn::new(Rc::clone(&environment));
-        let var_fn = rust_core::special_form::VarFn::new(Rc::clone(&environment));
+        let var_macro = Value::VarMacro {};
+        let var_get_fn = rust_core::VarGetFn {};
+        let alter_var_root_fn = rust_core::AlterVarRootFn {};
+        let is_bound_fn = rust_core::IsBoundFn {};
+        let intern_fn = rust_core::InternFn::new(Rc::clone(&environment));
+        let deref_fn = rust_core::DerefFn {};
+        let atom_fn = rust_core::AtomFn {};
+        let swap_fn = rust_core::SwapFn {};
+        let swap_vals_fn = rust_core::SwapValsFn {};
+        let reset_fn = rust_core::ResetFn {};
+        let compare_and_set_fn = rust_core::CompareAndSetFn {};
+        let set_validator_fn = rust_core::SetValidatorFn {};
+        let get_validator_fn = rust_core::GetValidatorFn {};
+        let add_watch_fn = rust_core::AddWatchFn {};
+        let remove_watch_fn = rust_core::RemoveWatchFn {};
+        let volatile_fn = rust_core::VolatileFn {};
+        let vswap_fn = rust_core::VswapFn {};
+        let vreset_fn = rust_core::VresetFn {};
+        let delay_fn = rust_core::DelayFn {};
+        let force_fn = rust_core::ForceFn {};
+        let is_realized_fn = rust_core::IsRealizedFn {};
+        let seq_fn = rust_core::SeqFn {};
+        let next_fn = rust_core::NextFn {};
+        let cons_fn = rust_core::ConsFn {};
+        let lazy_seq_fn = rust_core::LazySeqFn {};
+        let iterate_fn = rust_core::IterateFn {};
+        let range_fn = rust_core::RangeFn {};
+        let take_fn = rust_core::TakeFn {};
+        let with_bindings_fn = rust_core::WithBindingsFn {};
+        let var_set_fn = rust_core::VarSetFn {};
+        let bound_fn_fn = rust_core::BoundFnFn {};
         let count_fn = rust_core::count::CountFn {};
         let lt_fn = rust_core::lt::LtFn {};
         let gt_fn = rust_core::gt::GtFn {};
@@ -355,19 +593,140 @@ impl Environment {
         environment.insert(Symbol::intern("*"), multiply_fn.to_rc_value());
         environment.insert(Symbol::intern("/"), divide_fn.to_rc_value());
         environment.insert(Symbol::intern("rem"), rem_fn.to_rc_value());
+        environment.insert(Symbol::intern("+'"), add_promoting_fn.to_rc_value());
+        environment.insert(Symbol::intern("-'"), subtract_promoting_fn.to_rc_value());
+        environment.insert(Symbol::intern("*'"), multiply_promoting_fn.to_rc_value());
+        environment.insert(
+            Symbol::intern("unchecked-add"),
+            unchecked_add_fn.to_rc_value(),
+        );
+        environment.insert(
+            Symbol::intern("unchecked-subtract"),
+            unchecked_subtract_fn.to_rc_value(),
+        );
+        environment.insert(
+            Symbol::intern("unchecked-multiply"),
+            unchecked_multiply_fn.to_rc_value(),
+        );
+        environment.insert(
+            Symbol::intern("unchecked-inc"),
+            unchecked_inc_fn.to_rc_value(),
+        );
+        environment.insert(
+            Symbol::intern("unchecked-dec"),
+            unchecked_dec_fn.to_rc_value(),
+        );
+        environment.insert(
+            Symbol::intern("unchecked-negate"),
+            unchecked_negate_fn.to_rc_value(),
+        );
+        environment.insert(Symbol::intern("bigint"), bigint_fn.to_rc_value());
+        environment.insert(Symbol::intern("bigdec"), bigdec_fn.to_rc_value());
+        environment.insert(Symbol::intern("numerator"), numerator_fn.to_rc_value());
+        environment.insert(Symbol::intern("denominator"), denominator_fn.to_rc_value());
         environment.insert(Symbol::intern("rand"), rand_fn.to_rc_value());
         environment.insert(Symbol::intern("rand-int"), rand_int_fn.to_rc_value());
         environment.insert(Symbol::intern("let"), let_macro.to_rc_value());
+        environment.insert(Symbol::intern("letfn"), letfn_macro.to_rc_value());
         environment.insert(Symbol::intern("map"), map_fn.to_rc_value());
+        environment.insert(Symbol::intern("trampoline"), trampoline_fn.to_rc_value());
+        environment.insert(Symbol::intern("signal"), signal_fn.to_rc_value());
+        environment.insert(Symbol::intern("error"), error_fn.to_rc_value());
+        environment.insert(
+            Symbol::intern("handler-bind*"),
+            handler_bind_fn.to_rc_value(),
+        );
+        environment.insert(
+            Symbol::intern("handler-case*"),
+            handler_case_fn.to_rc_value(),
+        );
+        environment.insert(
+            Symbol::intern("restart-case*"),
+            restart_case_fn.to_rc_value(),
+        );
+        environment.insert(
+            Symbol::intern("invoke-restart"),
+            invoke_restart_fn.to_rc_value(),
+        );
+        environment.insert(
+            Symbol::intern("with-bindings*"),
+            with_bindings_fn.to_rc_value(),
+        );
+        environment.insert(Symbol::intern("var-set"), var_set_fn.to_rc_value());
+        environment.insert(Symbol::intern("bound-fn*"), bound_fn_fn.to_rc_value());
+        environment.insert(Symbol::intern("throw"), throw_fn.to_rc_value());
+        environment.insert(Symbol::intern("try*"), try_fn.to_rc_value());
+        environment.insert(Symbol::intern("try"), try_macro.to_rc_value());
+        environment.insert(Symbol::intern("ex-info"), ex_info_fn.to_rc_value());
+        environment.insert(Symbol::intern("ex-data"), ex_data_fn.to_rc_value());
+        environment.insert(Symbol::intern("ex-message"), ex_message_fn.to_rc_value());
+        environment.insert(Symbol::intern("ex-cause"), ex_cause_fn.to_rc_value());
         environment.insert(Symbol::intern("str"), str_fn.to_rc_value());
+        environment.insert(Symbol::intern("char"), char_fn.to_rc_value());
+        environment.insert(Symbol::intern("int"), int_fn.to_rc_value());
+        environment.insert(Symbol::intern("char?"), is_char_fn.to_rc_value());
         environment.insert(Symbol::intern("quote"), quote_macro.to_rc_value());
+        environment.insert(
+            Symbol::intern("syntax-quote"),
+            syntax_quote_macro.to_rc_value(),
+        );
+        environment.insert(Symbol::intern("gensym"), gensym_fn.to_rc_value());
         environment.insert(Symbol::intern("def"), def_macro.to_rc_value());
         environment.insert(Symbol::intern("fn"), fn_macro.to_rc_value());
         environment.insert(Symbol::intern("defmacro"), defmacro_macro.to_rc_value());
         environment.insert(Symbol::intern("eval"), eval_fn.to_rc_value());
+        environment.insert(
+            Symbol::intern("macroexpand-1"),
+            macroexpand_1_fn.to_rc_value(),
+        );
+        environment.insert(Symbol::intern("macroexpand"), macroexpand_fn.to_rc_value());
         environment.insert(Symbol::intern("meta"), meta_fn.to_rc_value());
         environment.insert(Symbol::intern("with-meta"), with_meta_fn.to_rc_value());
-        environment.insert(Symbol::intern("var-fn*"), var_fn.to_rc_value());
+        environment.insert(Symbol::intern("var"), var_macro.to_rc_value());
+        environment.insert(Symbol::intern("var-get"), var_get_fn.to_rc_value());
+        environment.insert(
+            Symbol::intern("alter-var-root"),
+            alter_var_root_fn.to_rc_value(),
+        );
+        environment.insert(Symbol::intern("bound?"), is_bound_fn.to_rc_value());
+        environment.insert(Symbol::intern("intern"), intern_fn.to_rc_value());
+        // reference types
+        environment.insert(Symbol::intern("deref"), deref_fn.to_rc_value());
+        environment.insert(Symbol::intern("atom"), atom_fn.to_rc_value());
+        environment.insert(Symbol::intern("swap!"), swap_fn.to_rc_value());
+        environment.insert(Symbol::intern("swap-vals!"), swap_vals_fn.to_rc_value());
+        environment.insert(Symbol::intern("reset!"), reset_fn.to_rc_value());
+        environment.insert(
+            Symbol::intern("compare-and-set!"),
+            compare_and_set_fn.to_rc_value(),
+        );
+        environment.insert(
+            Symbol::intern("set-validator!"),
+            set_validator_fn.to_rc_value(),
+        );
+        environment.insert(
+            Symbol::intern("get-validator"),
+            get_validator_fn.to_rc_value(),
+        );
+        environment.insert(Symbol::intern("add-watch"), add_watch_fn.to_rc_value());
+        environment.insert(
+            Symbol::intern("remove-watch"),
+            remove_watch_fn.to_rc_value(),
+        );
+        environment.insert(Symbol::intern("volatile!"), volatile_fn.to_rc_value());
+        environment.insert(Symbol::intern("vswap!"), vswap_fn.to_rc_value());
+        environment.insert(Symbol::intern("vreset!"), vreset_fn.to_rc_value());
+        environment.insert(Symbol::intern("delay*"), delay_fn.to_rc_value());
+        environment.insert(Symbol::intern("force"), force_fn.to_rc_value());
+        environment.insert(Symbol::intern("realized?"), is_realized_fn.to_rc_value());
+        // seqs
+        environment.insert(Symbol::intern("seq"), seq_fn.to_rc_value());
+        environment.insert(Symbol::intern("next"), next_fn.to_rc_value());
+        environment.insert(Symbol::intern("cons"), cons_fn.to_rc_value());
+        environment.insert(Symbol::intern("lazy-seq*"), lazy_seq_fn.to_rc_value());
+        environment.insert(Symbol::intern("iterate"), iterate_fn.to_rc_value());
+        environment.insert(Symbol::intern("range"), range_fn.to_rc_value());
+        environment.insert(Symbol::intern("take"), take_fn.to_rc_value());
 
         environment.insert_into_namespace(
             &Symbol::intern("clojure.core"),
@@ -390,6 +749,13 @@ impl Environment {
             thread_sleep_fn.to_rc_value(),
         );
 
+        // clojure.lang.PersistentQueue/EMPTY, the empty queue
+        environment.insert_into_namespace(
+            &Symbol::intern("clojure.lang.PersistentQueue"),
+            Symbol::intern("EMPTY"),
+            PersistentQueue::empty().to_rc_value(),
+        );
+
         // System namespace
         environment.insert_into_namespace(
             &Symbol::intern("System"),
@@ -406,6 +772,13 @@ impl Environment {
         // @TODO add this to clojure.rs.core namespace as clojure.rs.core/slurp
         environment.insert(Symbol::intern("rust-slurp"), slurp_fn.to_rc_value());
 
+        // clojure.walk
+        environment.insert_into_namespace(
+            &Symbol::intern("clojure.walk"),
+            Symbol::intern("macroexpand-all"),
+            macroexpand_all_fn.to_rc_value(),
+        );
+
         // clojure.string
         environment.insert_into_namespace(
             &Symbol::intern("clojure.string"),
@@ -485,11 +858,68 @@ impl Environment {
             split_fn.to_rc_value(),
         );
 
+        // clojure.set
+        environment.insert_into_namespace(
+            &Symbol::intern("clojure.set"),
+            Symbol::intern("union"),
+            set_union_fn.to_rc_value(),
+        );
+
+        environment.insert_into_namespace(
+            &Symbol::intern("clojure.set"),
+            Symbol::intern("intersection"),
+            set_intersection_fn.to_rc_value(),
+        );
+
+        environment.insert_into_namespace(
+            &Symbol::intern("clojure.set"),
+            Symbol::intern("difference"),
+            set_difference_fn.to_rc_value(),
+        );
+
+        environment.insert_into_namespace(
+            &Symbol::intern("clojure.set"),
+            Symbol::intern("subset?"),
+            set_subset_fn.to_rc_value(),
+        );
+
+        environment.insert_into_namespace(
+            &Symbol::intern("clojure.set"),
+            Symbol::intern("select"),
+            set_select_fn.to_rc_value(),
+        );
+
+        environment.insert_into_namespace(
+            &Symbol::intern("clojure.set"),
+            Symbol::intern("project"),
+            set_project_fn.to_rc_value(),
+        );
+
+        environment.insert_into_namespace(
+            &Symbol::intern("clojure.set"),
+            Symbol::intern("rename-keys"),
+            set_rename_keys_fn.to_rc_value(),
+        );
+
+        environment.insert_into_namespace(
+            &Symbol::intern("clojure.set"),
+            Symbol::intern("index"),
+            set_index_fn.to_rc_value(),
+        );
+
+        environment.insert_into_namespace(
+            &Symbol::intern("clojure.set"),
+            Symbol::intern("join"),
+            set_join_fn.to_rc_value(),
+        );
+
         environment.insert(Symbol::intern("quote"), quote_macro.to_rc_value());
         environment.insert(Symbol::intern("do-fn*"), do_fn.to_rc_value());
         environment.insert(Symbol::intern("do"), do_macro.to_rc_value());
         environment.insert(Symbol::intern("def"), def_macro.to_rc_value());
         environment.insert(Symbol::intern("if"), if_macro.to_rc_value());
+        environment.insert(Symbol::intern("loop"), loop_macro.to_rc_value());
+        environment.insert(Symbol::intern("recur"), recur_macro.to_rc_value());
         environment.insert(Symbol::intern("ns"), ns_macro.to_rc_value());
         environment.insert(
             Symbol::intern("lexical-eval"),
@@ -498,11 +928,42 @@ impl Environment {
         environment.insert(Symbol::intern("load-file"), load_file_fn.to_rc_value());
         environment.insert(Symbol::intern("nth"), nth_fn.to_rc_value());
         environment.insert(Symbol::intern("assoc"), assoc_fn.to_rc_value());
+        environment.insert(Symbol::intern("dissoc"), dissoc_fn.to_rc_value());
+        environment.insert(Symbol::intern("conj"), conj_fn.to_rc_value());
+        environment.insert(Symbol::intern("pop"), pop_fn.to_rc_value());
+        environment.insert(Symbol::intern("peek"), peek_fn.to_rc_value());
         environment.insert(Symbol::intern("get"), get_fn.to_rc_value());
+        environment.insert(Symbol::intern("contains?"), contains_fn.to_rc_value());
+        environment.insert(Symbol::intern("set"), set_fn.to_rc_value());
+        environment.insert(Symbol::intern("hash-set"), hash_set_fn.to_rc_value());
+        environment.insert(Symbol::intern("disj"), disj_fn.to_rc_value());
+        environment.insert(Symbol::intern("sorted-map"), sorted_map_fn.to_rc_value());
+        environment.insert(
+            Symbol::intern("sorted-map-by"),
+            sorted_map_by_fn.to_rc_value(),
+        );
+        environment.insert(Symbol::intern("sorted-set"), sorted_set_fn.to_rc_value());
+        environment.insert(
+            Symbol::intern("sorted-set-by"),
+            sorted_set_by_fn.to_rc_value(),
+        );
+        environment.insert(Symbol::intern("subseq"), subseq_fn.to_rc_value());
+        environment.insert(Symbol::intern("rsubseq"), rsubseq_fn.to_rc_value());
+        environment.insert(Symbol::intern("compare"), compare_fn.to_rc_value());
+        environment.insert(Symbol::intern("transient"), transient_fn.to_rc_value());
+        environment.insert(Symbol::intern("persistent!"), persistent_fn.to_rc_value());
+        environment.insert(Symbol::intern("conj!"), transient_conj_fn.to_rc_value());
+        environment.insert(Symbol::intern("assoc!"), transient_assoc_fn.to_rc_value());
+        environment.insert(Symbol::intern("dissoc!"), transient_dissoc_fn.to_rc_value());
+        environment.insert(Symbol::intern("pop!"), transient_pop_fn.to_rc_value());
+        environment.insert(Symbol::intern("disj!"), transient_disj_fn.to_rc_value());
+        environment.insert(Symbol::intern("into"), into_fn.to_rc_value());
+        environment.insert(Symbol::intern("queue"), queue_fn.to_rc_value());
         environment.insert(Symbol::intern("concat"), concat_fn.to_rc_value());
         environment.insert(Symbol::intern("more"), more_fn.to_rc_value());
         environment.insert(Symbol::intern("first"), first_fn.to_rc_value());
         environment.insert(Symbol::intern("second"), second_fn.to_rc_value());
+        environment.insert(Symbol::intern("last"), last_fn.to_rc_value());
         // input and output
         environment.insert(
             Symbol::intern("system-newline"),
@@ -658,10 +1119,9 @@ mod tests {
             environment.insert(Symbol::intern("+"), add_fn.to_rc_value());
 
             let plus: Rc<Value> = match &*environment {
-                MainEnvironment(EnvironmentVal {
-                    curr_ns_sym: _,
-                    namespaces,
-                }) => namespaces.get(&Symbol::intern("user"), &Symbol::intern("+")),
+                MainEnvironment(EnvironmentVal { namespaces, .. }) => {
+                    namespaces.get(&Symbol::intern("user"), &Symbol::intern("+"))
+                }
                 _ => panic!("new_main_environment() should return Main"),
             };
 
@@ -1,53 +1,158 @@
+use crate::condition::Condition;
+use crate::keyword::Keyword;
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap};
+use crate::symbol::Symbol;
 use crate::type_tag::TypeTag;
-use crate::value::Value;
+use crate::value::{ToValue, Value};
 use std::error::Error;
 use std::fmt;
 
+/// An error of kind, as in error("syntax", ..) or error("reader/eof", ..)
+pub fn error(kind: &str, message: String) -> Value {
+    Value::Condition(Condition::new(Keyword::intern(kind), message))
+}
+
+/// An error of kind, with data about it, as in {:index 5 :count 2}
+fn error_with_data(kind: &str, message: String, data: Vec<(&str, Value)>) -> Value {
+    let data = data
+        .into_iter()
+        .fold(PersistentListMap::Empty, |data, (key, val)| {
+            data.assoc(Keyword::intern(key).to_rc_value(), val.to_rc_value())
+        });
+    Value::Condition(Condition::new(Keyword::intern(kind), message).with_data(data))
+}
+
 pub fn type_mismatch(expected: TypeTag, got: &Value) -> Value {
-    Value::Condition(format!(
-        "Type mismatch; Expected instance of {},  Recieved type {}",
-        expected, got
-    ))
+    error_with_data(
+        "type-mismatch",
+        format!(
+            "Type mismatch; Expected instance of {},  Recieved type {}",
+            expected, got
+        ),
+        vec![
+            ("expected", Value::String(expected.to_string())),
+            ("found", Value::String(got.type_tag().to_string())),
+        ],
+    )
 }
 
 pub fn wrong_arg_count(expected: usize, got: usize) -> Value {
-    Value::Condition(format!(
-        "Wrong number of arguments given to function (Given: {}, Expected: {})",
-        got, expected
-    ))
+    error_with_data(
+        "arity",
+        format!(
+            "Wrong number of arguments given to function (Given: {}, Expected: {})",
+            got, expected
+        ),
+        vec![
+            ("given", Value::I64(got as i64)),
+            ("expected", Value::I64(expected as i64)),
+        ],
+    )
 }
 
 pub fn wrong_varg_count(expected: &[usize], got: usize) -> Value {
-    Value::Condition(format!(
-        "Wrong number of arguments given to function (Given: {}, Expected: {:?})",
-        got, expected
-    ))
+    error_with_data(
+        "arity",
+        format!(
+            "Wrong number of arguments given to function (Given: {}, Expected: {:?})",
+            got, expected
+        ),
+        vec![("given", Value::I64(got as i64))],
+    )
+}
+
+/// For when what we expect takes describing, as in "2 or more"
+pub fn wrong_arg_count_described(expected: &str, got: usize) -> Value {
+    error_with_data(
+        "arity",
+        format!(
+            "Wrong number of arguments given to function (Given: {}, Expected: {})",
+            got, expected
+        ),
+        vec![("given", Value::I64(got as i64))],
+    )
 }
 
 pub fn zero_arg_count(got: usize) -> Value {
-    Value::Condition(format!(
-        "Wrong number of arguments given to function (Given: {})",
-        got
-    ))
+    error_with_data(
+        "arity",
+        format!(
+            "Wrong number of arguments given to function (Given: {})",
+            got
+        ),
+        vec![("given", Value::I64(got as i64))],
+    )
 }
 
 pub fn index_out_of_bounds(ind: usize, count: usize) -> Value {
-    Value::Condition(format!(
-        "Index out of bounds: Index ({}), Length: ({})",
-        ind, count
-    ))
+    error_with_data(
+        "index-out-of-bounds",
+        format!("Index out of bounds: Index ({}), Length: ({})", ind, count),
+        vec![
+            ("index", Value::I64(ind as i64)),
+            ("count", Value::I64(count as i64)),
+        ],
+    )
+}
+
+pub fn index_cannot_be_negative(ind: i64) -> Value {
+    error_with_data(
+        "index-out-of-bounds",
+        format!("Index cannot be negative; Index ({})", ind),
+        vec![("index", Value::I64(ind))],
+    )
+}
+
+pub fn recur_not_in_tail_position() -> Value {
+    error("syntax", String::from("Can only recur from tail position"))
+}
+
+/// A recur in tail position of a form, like try, whose body is a fn of its own, as that
+/// fn would otherwise be what it recurs to
+pub fn recur_across(form: &str) -> Value {
+    error("syntax", format!("Cannot recur across {}", form))
+}
+
+pub fn recur_arg_count(expected: usize, got: usize) -> Value {
+    error_with_data(
+        "arity",
+        format!(
+            "Mismatched argument count to recur (Given: {}, Expected: {})",
+            got, expected
+        ),
+        vec![
+            ("given", Value::I64(got as i64)),
+            ("expected", Value::I64(expected as i64)),
+        ],
+    )
 }
 
-pub fn index_cannot_be_negative(ind: usize) -> Value {
-    Value::Condition(format!("Index cannot be negative; Index ({})", ind))
+pub fn undefined_symbol(sym: &Symbol) -> Value {
+    error_with_data(
+        "undefined-symbol",
+        format!("Undefined symbol {}", sym.name),
+        vec![("symbol", sym.to_value())],
+    )
+}
+
+pub fn divide_by_zero() -> Value {
+    error("divide-by-zero", String::from("Divide by zero"))
+}
+
+/// Hit the end of what we're reading;  not always a mistake, as when reading a file to its end
+pub fn reader_eof() -> Value {
+    error(
+        "reader/eof",
+        String::from("Tried to read empty stream; unexpected EOF"),
+    )
 }
 
 pub fn generic_err(error: Box<dyn Error>) -> Value {
-    Value::Condition(error.to_string())
+    self::error("error", error.to_string())
 }
 
 pub fn unknown_err(error: String) -> Value {
-    Value::Condition(error)
+    self::error("error", error)
 }
 //
 // This module will likely be rewritten to look like everything below this line
@@ -75,10 +180,84 @@ pub fn unknown_err(error: String) -> Value {
 // We currently don't have any type that represents an interface type name, so we
 pub struct Cast<'a>(pub &'a str);
 pub fn cast(expected: Cast, found: TypeTag) -> Value {
-    Value::Condition(format!("Cannot cast {} to {}", found, expected.0))
+    error(
+        "type-mismatch",
+        format!("Cannot cast {} to {}", found, expected.0),
+    )
 }
 
 /// For one off errors
 pub fn custom(message: &str) -> Value {
-    Value::Condition(String::from(message))
+    error("error", String::from(message))
+}
+
+#[cfg(test)]
+mod tests {
+    mod error_message_tests {
+        use crate::condition::Condition;
+        use crate::keyword::Keyword;
+        use crate::persistent_list_map::IPersistentMap;
+        use crate::repl::Repl;
+        use crate::symbol::Symbol;
+        use crate::value::{ToValue, Value};
+        use std::rc::Rc;
+
+        fn condition(value: Value) -> Condition {
+            match value {
+                Value::Condition(condition) => condition,
+                value => panic!("Expected an error, found {}", value),
+            }
+        }
+
+        fn data(condition: &Condition, key: &str) -> Rc<Value> {
+            condition.data.get(&Keyword::intern(key).to_rc_value())
+        }
+
+        fn condition_of(repl: &Repl, src: &str) -> Condition {
+            condition(repl.eval_str(src))
+        }
+
+        #[test]
+        fn undefined_symbols_say_which() {
+            let condition = condition_of(&Repl::default(), "not-defined-anywhere");
+            assert!(condition.is("undefined-symbol"));
+            assert_eq!(
+                Symbol::intern("not-defined-anywhere").to_rc_value(),
+                data(&condition, "symbol")
+            );
+        }
+
+        #[test]
+        fn arity_errors_say_how_many_were_given() {
+            let repl = Repl::default();
+            for (src, given) in &[
+                ("((fn [x] x))", 0),
+                ("((fn [x & xs] x))", 0),
+                ("((fn ([] 0) ([x y] x)) 1)", 1),
+                ("(subseq)", 0),
+            ] {
+                let condition = condition_of(&repl, src);
+                assert!(condition.is("arity"), "{}", src);
+                assert_eq!(Value::I64(*given), *data(&condition, "given"), "{}", src);
+            }
+        }
+
+        #[test]
+        fn reading_nothing_is_reader_eof() {
+            assert!(condition(Repl::read_string("")).is("reader/eof"));
+            assert!(condition(Repl::read_string("(1 2")).is("reader/eof"));
+        }
+
+        #[test]
+        fn index_errors_say_which_index_and_count() {
+            let repl = Repl::default();
+            let condition = condition_of(&repl, "(nth [1 2] 5)");
+            assert!(condition.is("index-out-of-bounds"));
+            assert_eq!(Value::I64(5), *data(&condition, "index"));
+            assert_eq!(Value::I64(2), *data(&condition, "count"));
+            let condition = condition_of(&repl, "([1 2] -1)");
+            assert!(condition.is("index-out-of-bounds"));
+            assert_eq!(Value::I64(-1), *data(&condition, "index"));
+        }
+    }
 }
@@ -10,6 +10,7 @@
 //! a map
 //!    ({:name "Blah" :age 20} :name)
 //! As well as a few more types.
+use crate::symbol::Symbol;
 use crate::value::Value;
 
 use dyn_clone::DynClone;
@@ -37,6 +38,18 @@ use std::rc::Rc;
 //
 
 pub trait IFn: Debug + DynClone {
+    /// Should this return a Value::Condition, evaluation stops there;  the error goes
+    /// straight back up to whoever can handle it, rather than on to the next fn as an argument
     fn invoke(&self, args: Vec<Rc<Value>>) -> Value;
+    /// Like invoke, except a call this IFn would make in tail position may be handed back
+    /// to us as a Value::TailCall, for us to make in turn.  Most IFns make no such calls,
+    /// and so this is just invoke
+    fn tail_invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        self.invoke(args)
+    }
+    /// The name we print with, as in #function[user/foo];  most IFns go unnamed
+    fn name(&self) -> Option<Symbol> {
+        None
+    }
 }
 dyn_clone::clone_trait_object!(IFn);
@@ -1,12 +1,17 @@
 use crate::define_protocol;
+use crate::persistent_hash_set::PersistentHashSetIter;
 use crate::persistent_list::PersistentListIter;
 use crate::persistent_list::ToPersistentListIter;
-use crate::persistent_list_map::PersistentListMapIter;
-use crate::persistent_list_map::ToPersistentListMapIter;
+use crate::persistent_queue::PersistentQueueIter;
+use crate::persistent_tree_map::PersistentTreeMapIter;
+use crate::persistent_tree_set::PersistentTreeSetIter;
 use crate::persistent_vector::PersistentVectorIter;
 use crate::persistent_vector::ToPersistentVector;
 use crate::persistent_vector::ToPersistentVectorIter;
 use crate::protocol::Protocol;
+use crate::protocol::ProtocolCastable;
+use crate::protocols;
+use crate::protocols::SeqIter;
 use crate::value::ToValue;
 use crate::value::Value;
 use std::rc::Rc;
@@ -16,13 +21,26 @@ define_protocol!(
     Iterable,
     PersistentList,
     PersistentListMap,
-    PersistentVector
+    PersistentHashMap,
+    PersistentHashSet,
+    PersistentTreeMap,
+    PersistentTreeSet,
+    PersistentQueue,
+    PersistentVector,
+    String,
+    LazySeq,
+    Cons
 );
 
 pub enum IterableIter {
     PersistentList(PersistentListIter),
     PersistentVector(PersistentVectorIter),
-    PersistentListMap(PersistentListMapIter),
+    Map(protocols::IPersistentMapIter),
+    Set(PersistentHashSetIter),
+    SortedMap(PersistentTreeMapIter),
+    SortedSet(PersistentTreeSetIter),
+    Queue(PersistentQueueIter),
+    Seq(SeqIter),
 }
 impl Iterator for IterableIter {
     type Item = Rc<Value>;
@@ -30,8 +48,8 @@ impl Iterator for IterableIter {
         match self {
             IterableIter::PersistentList(plist_giter) => plist_giter.next(),
             IterableIter::PersistentVector(pvector_iter) => pvector_iter.next(),
-            IterableIter::PersistentListMap(plist_map_iter) => {
-                let maybe_map_entry = plist_map_iter.next();
+            IterableIter::Map(map_iter) => {
+                let maybe_map_entry = map_iter.next();
                 if let Some(map_entry) = maybe_map_entry {
                     // In Clojure: [key val]
                     return Some(
@@ -42,6 +60,18 @@ impl Iterator for IterableIter {
                 }
                 None
             }
+            IterableIter::Set(set_iter) => set_iter.next(),
+            IterableIter::SortedMap(map_iter) => map_iter.next().map(|map_entry| {
+                vec![map_entry.key, map_entry.val]
+                    .into_vector()
+                    .to_rc_value()
+            }),
+            IterableIter::SortedSet(set_iter) => set_iter.next(),
+            IterableIter::Queue(queue_iter) => queue_iter.next(),
+            // Should realizing a value fail, its error takes its place, and ends us
+            IterableIter::Seq(seq_iter) => seq_iter.next().map(|val| match val {
+                Ok(val) | Err(val) => val,
+            }),
         }
     }
 }
@@ -54,8 +84,16 @@ impl Iterable {
             Value::PersistentVector(pvector) => {
                 IterableIter::PersistentVector(Rc::new(pvector.clone()).iter())
             }
-            Value::PersistentListMap(pmap) => {
-                IterableIter::PersistentListMap(Rc::new(pmap.clone()).iter())
+            Value::PersistentListMap(_) | Value::PersistentHashMap(_) => {
+                IterableIter::Map(self.value.as_protocol::<protocols::IPersistentMap>().iter())
+            }
+            Value::PersistentHashSet(phashset) => IterableIter::Set(phashset.iter()),
+            Value::PersistentTreeMap(ptreemap) => IterableIter::SortedMap(ptreemap.iter()),
+            Value::PersistentTreeSet(ptreeset) => IterableIter::SortedSet(ptreeset.iter()),
+            Value::PersistentQueue(pqueue) => IterableIter::Queue(pqueue.iter()),
+            // A string's values are its chars
+            Value::String(_) | Value::LazySeq(_) | Value::Cons(_) => {
+                IterableIter::Seq(self.value.as_protocol::<protocols::ISeq>().iter())
             }
             // We are ok panicking in this case because an invariant on the type is the assumption
             // that we only have an Iterable if we were able to convert
@@ -1,54 +1,70 @@
+use crate::call_stack::Frame;
 use crate::environment::Environment;
+use crate::error_message;
 use crate::ifn::IFn;
 use crate::persistent_list::ToPersistentList;
+use crate::position;
 use crate::symbol::Symbol;
-use crate::value::{Evaluable, ToValue, Value};
+use crate::value::{ToValue, Value};
 use std::rc::Rc;
 
+/// One [args] body pair of a fn;  (fn [x] ..) has one, while
+/// (fn ([x] ..) ([x y] ..)) has two
 #[derive(Debug, Clone)]
-pub struct Fn {
+pub struct FnArity {
     pub body: Rc<Value>,
-    // Closed over variables
-    pub enclosing_environment: Rc<Environment>,
     pub arg_syms: Vec<Symbol>,
+    // The form, like try, whose body we hold, should a macro have made us of one;  we
+    // are then no place to recur to, and one in our tail is an error
+    pub recur_across: Option<String>,
 }
-impl ToValue for Fn {
-    fn to_value(&self) -> Value {
-        Value::IFn(Rc::new(self.clone()))
+impl FnArity {
+    // (fn [x y & more] ..) is variadic
+    pub fn is_variadic(&self) -> bool {
+        let argc = self.arg_syms.len();
+        argc >= 2 && self.arg_syms.get(argc - 2).unwrap().to_string() == "&"
     }
-}
-impl IFn for Fn {
-    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+    /// The number of arguments we require;  for a variadic arity, the least we'll take
+    pub fn required_argc(&self) -> usize {
+        if self.is_variadic() {
+            self.arg_syms.len() - 2
+        } else {
+            self.arg_syms.len()
+        }
+    }
+    pub fn accepts(&self, argc: usize) -> bool {
+        if self.is_variadic() {
+            argc >= self.required_argc()
+        } else {
+            argc == self.required_argc()
+        }
+    }
+    /// Binds the arguments of a call into a new local environment
+    fn bind_args(
+        &self,
+        enclosing_environment: &Rc<Environment>,
+        args: Vec<Rc<Value>>,
+    ) -> Result<Rc<Environment>, Rc<Value>> {
         let local_environment = Rc::new(Environment::new_local_environment(Rc::clone(
-            &self.enclosing_environment,
+            enclosing_environment,
         )));
 
         let argc = self.arg_syms.len();
 
-        let mut var_args = false;
-        if argc >= 2 {
-            if let Some(sym) = self.arg_syms.get(argc - 2) {
-                if sym.to_string() == "&" {
-                    var_args = true;
-                    let last_sym = self.arg_syms.get(argc - 1).unwrap();
-                    local_environment.insert(last_sym.clone(), Rc::new(Value::Nil));
-                }
-            }
+        let var_args = self.is_variadic();
+        if var_args {
+            let last_sym = self.arg_syms.get(argc - 1).unwrap();
+            local_environment.insert(last_sym.clone(), Rc::new(Value::Nil));
         }
 
         if var_args && args.len() < argc - 2 {
-            return Value::Condition(format!(
-                "Wrong number of arguments given to function (Given: {}, Expected: {} or more)",
+            return Err(Rc::new(error_message::wrong_arg_count_described(
+                &format!("{} or more", argc - 2),
                 args.len(),
-                argc - 2
-            ));
+            )));
         }
         if !var_args && args.len() != argc {
-            return Value::Condition(format!(
-                "Wrong number of arguments given to function (Given: {}, Expected: {})",
-                args.len(),
-                argc
-            ));
+            return Err(Rc::new(error_message::wrong_arg_count(argc, args.len())));
         }
 
         for (i, arg) in args.iter().enumerate() {
@@ -56,7 +72,7 @@ impl IFn for Fn {
             // We can bind the rest of the arguments, then, to the next variable and blow this popsicle stand
             if curr_sym.to_string() == "&" {
                 if !var_args {
-                    return Value::Condition(String::from("Invalid function argument '&' in non-variable-argument function definition"));
+                    return Err(Rc::new(error_message::error("syntax", String::from("Invalid function argument '&' in non-variable-argument function definition"))));
                 }
                 let last_sym = self.arg_syms.get(i + 1).unwrap();
                 let rest_args = args.get(i..).unwrap().to_vec().into_list().to_rc_value();
@@ -65,7 +81,140 @@ impl IFn for Fn {
             }
             local_environment.insert(curr_sym.clone(), arg.to_rc_value());
         }
-        self.body.eval(local_environment)
+        Ok(local_environment)
+    }
+    /// Binds the arguments of a (recur ..) back to the top of this arity.  Unlike a call,
+    /// a recur always gives exactly one value per param, and the rest param of a
+    /// variadic fn is given its seq directly
+    fn bind_recur_args(
+        &self,
+        enclosing_environment: &Rc<Environment>,
+        recur_args: Vec<Rc<Value>>,
+    ) -> Result<Rc<Environment>, Rc<Value>> {
+        let params = self
+            .arg_syms
+            .iter()
+            .filter(|sym| sym.to_string() != "&")
+            .collect::<Vec<&Symbol>>();
+        if recur_args.len() != params.len() {
+            return Err(Rc::new(error_message::recur_arg_count(
+                params.len(),
+                recur_args.len(),
+            )));
+        }
+
+        let local_environment = Rc::new(Environment::new_local_environment(Rc::clone(
+            enclosing_environment,
+        )));
+        for (sym, arg) in params.into_iter().zip(recur_args) {
+            local_environment.insert(sym.clone(), arg);
+        }
+        Ok(local_environment)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Fn {
+    // Namespace qualified, as in user/foo, if we were given one like (fn foo [x] ..)
+    pub name: Option<Symbol>,
+    pub arities: Vec<FnArity>,
+    // Closed over variables
+    pub enclosing_environment: Rc<Environment>,
+}
+impl ToValue for Fn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl Fn {
+    /// Picks the arity to call on argc arguments;  an exact match is preferred
+    /// over a variadic one
+    fn arity_for(&self, argc: usize) -> Result<&FnArity, Rc<Value>> {
+        // With one arity, we let it report its own mismatch when binding
+        if self.arities.len() == 1 {
+            return Ok(self.arities.first().unwrap());
+        }
+        self.arities
+            .iter()
+            .find(|arity| !arity.is_variadic() && arity.accepts(argc))
+            .or_else(|| self.arities.iter().find(|arity| arity.accepts(argc)))
+            .ok_or_else(|| {
+                let expected = self
+                    .arities
+                    .iter()
+                    .map(|arity| {
+                        if arity.is_variadic() {
+                            format!("{} or more", arity.required_argc())
+                        } else {
+                            arity.required_argc().to_string()
+                        }
+                    })
+                    .collect::<Vec<String>>();
+                Rc::new(error_message::wrong_arg_count_described(
+                    &expected.join(" or "),
+                    argc,
+                ))
+            })
+    }
+}
+impl IFn for Fn {
+    // Our trampoline;  any call in tail position of a body comes back to us as a
+    // Value::TailCall, which we make here, one after the other, rather than each
+    // call making the next one itself.  This way, even mutually recursive fns
+    // run in constant stack space.  As for the call stack, the fn a tail call came from
+    // keeps its frame while that call is made, each replacing the one before it, so that
+    // a trace still shows who called what
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        let call_stack = self.enclosing_environment.call_stack();
+        let mut result = self.tail_invoke(args);
+        // The frame of the fn we tail called last, whose body the next tail call is from
+        let mut caller: Option<Frame> = None;
+        while let Value::TailCall(ifn, args, form) = result {
+            let frame = Frame::new(&ifn, &form);
+            let call = || call_stack.call(frame.clone(), || ifn.tail_invoke(args));
+            result = match caller {
+                Some(caller) => call_stack.call(caller, call),
+                None => call(),
+            };
+            caller = Some(frame);
+            if let Some(located) = position::located(&result, &form) {
+                result = located;
+            }
+        }
+        result
+    }
+    fn tail_invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        let arity = match self.arity_for(args.len()) {
+            Ok(arity) => arity,
+            Err(condition) => return condition.to_value(),
+        };
+        let mut local_environment = match arity.bind_args(&self.enclosing_environment, args) {
+            Ok(local_environment) => local_environment,
+            Err(condition) => return condition.to_value(),
+        };
+        // A (recur ..) in tail position of our body comes back to us as a Value::Recur;
+        // rather than calling ourselves again, we rebind and loop, so self recursion
+        // through recur runs in constant stack space
+        loop {
+            let result = Value::eval_form(&arity.body, Rc::clone(&local_environment), true);
+            match &*result {
+                Value::Recur(recur_args) => {
+                    if let Some(form) = &arity.recur_across {
+                        return error_message::recur_across(form);
+                    }
+                    local_environment = match arity
+                        .bind_recur_args(&self.enclosing_environment, recur_args.clone())
+                    {
+                        Ok(local_environment) => local_environment,
+                        Err(condition) => return condition.to_value(),
+                    }
+                }
+                _ => return result.to_value(),
+            }
+        }
+    }
+    fn name(&self) -> Option<Symbol> {
+        self.name.clone()
     }
 }
 
@@ -76,6 +225,7 @@ impl IFn for Fn {
 #[cfg(test)]
 mod tests {
     use crate::environment::Environment;
+    use crate::error_message;
     use crate::ifn::IFn;
     use crate::lambda;
     use crate::symbol::Symbol;
@@ -86,11 +236,15 @@ mod tests {
     fn test_only_vararg() {
         // (defn func [& vararg] "Works")
         let func = lambda::Fn {
-            body: Rc::new(Value::String(String::from("Works"))),
+            name: None,
+            arities: vec![lambda::FnArity {
+                body: Rc::new(Value::String(String::from("Works"))),
+                arg_syms: vec![Symbol::intern("&"), Symbol::intern("varargs")],
+                recur_across: None,
+            }],
             enclosing_environment: Rc::new(Environment::new_local_environment(
                 Environment::clojure_core_environment(),
             )),
-            arg_syms: vec![Symbol::intern("&"), Symbol::intern("varargs")],
         };
 
         // (func)
@@ -98,13 +252,13 @@ mod tests {
         assert_eq!(val, Value::String("Works".to_string()));
 
         // (func 1)
-        let val = func.invoke(vec![Rc::new(Value::I32(1_i32))]);
+        let val = func.invoke(vec![Rc::new(Value::I64(1))]);
         assert_eq!(val, Value::String("Works".to_string()));
 
         let val = func.invoke(vec![
             //  (func 1 2)
-            Rc::new(Value::I32(1_i32)),
-            Rc::new(Value::I32(2_i32)),
+            Rc::new(Value::I64(1)),
+            Rc::new(Value::I64(2)),
         ]);
         assert_eq!(val, Value::String("Works".to_string()));
     }
@@ -113,33 +267,34 @@ mod tests {
     fn test_vararg_one() {
         // (defn func [x & vararg] "Works")
         let func = lambda::Fn {
-            body: Rc::new(Value::String(String::from("Works"))),
+            name: None,
+            arities: vec![lambda::FnArity {
+                body: Rc::new(Value::String(String::from("Works"))),
+                arg_syms: vec![
+                    Symbol::intern("x"),
+                    Symbol::intern("&"),
+                    Symbol::intern("varargs"),
+                ],
+                recur_across: None,
+            }],
             enclosing_environment: Rc::new(Environment::new_local_environment(
                 Environment::clojure_core_environment(),
             )),
-            arg_syms: vec![
-                Symbol::intern("x"),
-                Symbol::intern("&"),
-                Symbol::intern("varargs"),
-            ],
         };
 
         let val = func.invoke(vec![]); // (func)
         assert_eq!(
             val,
-            Value::Condition(
-                "Wrong number of arguments given to function (Given: 0, Expected: 1 or more)"
-                    .to_string()
-            )
+            error_message::wrong_arg_count_described("1 or more", 0)
         );
 
-        let val = func.invoke(vec![Rc::new(Value::I32(1_i32))]); // (func 1)
+        let val = func.invoke(vec![Rc::new(Value::I64(1))]); // (func 1)
         assert_eq!(val, Value::String("Works".to_string()));
 
         let val = func.invoke(vec![
             //  (func 1 2)
-            Rc::new(Value::I32(1_i32)),
-            Rc::new(Value::I32(2_i32)),
+            Rc::new(Value::I64(1)),
+            Rc::new(Value::I64(2)),
         ]);
         assert_eq!(val, Value::String("Works".to_string()));
     }
@@ -148,47 +303,45 @@ mod tests {
     fn test_vararg_two() {
         // (defn func [x y & vararg] "Works")
         let func = lambda::Fn {
-            body: Rc::new(Value::String(String::from("Works"))),
+            name: None,
+            arities: vec![lambda::FnArity {
+                body: Rc::new(Value::String(String::from("Works"))),
+                arg_syms: vec![
+                    Symbol::intern("x"),
+                    Symbol::intern("y"),
+                    Symbol::intern("&"),
+                    Symbol::intern("varargs"),
+                ],
+                recur_across: None,
+            }],
             enclosing_environment: Rc::new(Environment::new_local_environment(
                 Environment::clojure_core_environment(),
             )),
-            arg_syms: vec![
-                Symbol::intern("x"),
-                Symbol::intern("y"),
-                Symbol::intern("&"),
-                Symbol::intern("varargs"),
-            ],
         };
 
         let val = func.invoke(vec![]); // (func)
         assert_eq!(
             val,
-            Value::Condition(
-                "Wrong number of arguments given to function (Given: 0, Expected: 2 or more)"
-                    .to_string()
-            )
+            error_message::wrong_arg_count_described("2 or more", 0)
         );
 
-        let val = func.invoke(vec![Rc::new(Value::I32(1_i32))]); // (func 1)
+        let val = func.invoke(vec![Rc::new(Value::I64(1))]); // (func 1)
         assert_eq!(
             val,
-            Value::Condition(
-                "Wrong number of arguments given to function (Given: 1, Expected: 2 or more)"
-                    .to_string()
-            )
+            error_message::wrong_arg_count_described("2 or more", 1)
         );
 
         let val = func.invoke(vec![
             //  (func 1 2)
-            Rc::new(Value::I32(1_i32)),
-            Rc::new(Value::I32(2_i32)),
+            Rc::new(Value::I64(1)),
+            Rc::new(Value::I64(2)),
         ]);
         assert_eq!(val, Value::String("Works".to_string()));
 
         let val = func.invoke(vec![
             //  (func 1 2 "vararg here")
-            Rc::new(Value::I32(1_i32)),
-            Rc::new(Value::I32(2_i32)),
+            Rc::new(Value::I64(1)),
+            Rc::new(Value::I64(2)),
             Rc::new(Value::String(String::from("vararg here"))),
         ]);
         assert_eq!(val, Value::String("Works".to_string()));
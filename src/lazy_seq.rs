@@ -0,0 +1,104 @@
+//! A seq whose values are only worked out once they're asked for, by calling the fn it
+//! was made with;  what (lazy-seq ..) makes.  As a fn that conses one value onto another
+//! lazy seq only goes one value further, a lazy seq can go on forever
+//!
+//! ```clojure
+//!   (defn naturals [n] (lazy-seq (cons n (naturals (inc n)))))
+//!   (take 3 (naturals 0))
+//!   ;; => (0 1 2)
+//! ```
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::protocol::ProtocolCastable;
+use crate::protocols;
+use crate::traits::ISeq;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Debug, Clone)]
+enum LazySeqState {
+    Unrealized(Rc<dyn IFn>),
+    // What our fn returned, as a seq;  nil, or a seq of at least one value
+    Realized(Rc<Value>),
+}
+
+#[derive(Debug)]
+pub struct LazySeq {
+    state: RefCell<LazySeqState>,
+}
+impl LazySeq {
+    pub fn new(thunk: Rc<dyn IFn>) -> LazySeq {
+        LazySeq {
+            state: RefCell::new(LazySeqState::Unrealized(thunk)),
+        }
+    }
+    pub fn is_realized(&self) -> bool {
+        matches!(&*self.state.borrow(), LazySeqState::Realized(_))
+    }
+    /// Calls our fn, the first time we're asked, and keeps what it returns.  Should it
+    /// fail, we stay unrealized, and will call it again next time
+    pub fn seq(&self) -> Result<Rc<Value>, Rc<Value>> {
+        let thunk = match &*self.state.borrow() {
+            LazySeqState::Unrealized(thunk) => Rc::clone(thunk),
+            LazySeqState::Realized(seq) => return Ok(Rc::clone(seq)),
+        };
+        let result = thunk.invoke(vec![]).to_rc_value();
+        if result.short_circuits() {
+            return Err(result);
+        }
+        let seq = match result.try_as_protocol::<protocols::ISeq>() {
+            Some(iseq) => iseq.seq()?,
+            None => {
+                return Err(Rc::new(error_message::type_mismatch(
+                    TypeTag::ISeq,
+                    &result,
+                )))
+            }
+        };
+        self.state.replace(LazySeqState::Realized(Rc::clone(&seq)));
+        Ok(seq)
+    }
+    /// What we realized, leaving us as nil
+    fn take_seq(&mut self) -> Option<Rc<Value>> {
+        match std::mem::replace(
+            self.state.get_mut(),
+            LazySeqState::Realized(Rc::new(Value::Nil)),
+        ) {
+            LazySeqState::Realized(seq) => Some(seq),
+            LazySeqState::Unrealized(_) => None,
+        }
+    }
+}
+/// A long realized lazy seq is a chain of conses onto lazy seqs, which, dropped as
+/// usual, would be dropped recursively, and overflow the stack.  So we drop it one
+/// link at a time, for as long as nothing else holds on to the rest
+impl Drop for LazySeq {
+    fn drop(&mut self) {
+        let mut rest = match self.take_seq() {
+            Some(seq) => seq,
+            None => return,
+        };
+        loop {
+            let cons = match Rc::try_unwrap(rest) {
+                Ok(Value::Cons(cons)) => cons,
+                _ => return,
+            };
+            let more = match Rc::try_unwrap(cons) {
+                Ok(cons) => cons.into_more(),
+                Err(_) => return,
+            };
+            rest = match Rc::try_unwrap(more) {
+                Ok(Value::LazySeq(lazy_seq)) => match Rc::try_unwrap(lazy_seq) {
+                    Ok(mut lazy_seq) => match lazy_seq.take_seq() {
+                        Some(seq) => seq,
+                        None => return,
+                    },
+                    Err(_) => return,
+                },
+                _ => return,
+            };
+        }
+    }
+}
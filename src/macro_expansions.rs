@@ -0,0 +1,86 @@
+//! What each macro call has expanded to, so that a form evaluated over and over, like
+//! the body of a fn or loop, has its macros expanded once rather than on every pass
+//!
+//! An expansion is kept against the very form that was expanded (not just an equal one),
+//! and is only given back for the same macro and the same locals (and so the same &env)
+//! it was expanded with
+use crate::ifn::IFn;
+use crate::symbol::Symbol;
+use crate::value::Value;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+
+/// Expansions are dropped along with their forms, which we check for once we hold this many
+const PRUNE_AT: usize = 1024;
+
+/// One macro call's expansion, and what it was expanded with
+#[derive(Debug, Clone)]
+struct Expansion {
+    // Weak, so that we do not keep a form alive;  it also keeps the form's address from
+    // being reused for another while we still have it as a key
+    form: Weak<Value>,
+    ifn: Rc<dyn IFn>,
+    locals: Vec<Symbol>,
+    expansion: Rc<Value>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MacroExpansions {
+    expansions: RefCell<HashMap<*const Value, Expansion>>,
+    prune_at: RefCell<usize>,
+}
+impl Default for MacroExpansions {
+    fn default() -> Self {
+        MacroExpansions {
+            expansions: RefCell::new(HashMap::new()),
+            prune_at: RefCell::new(PRUNE_AT),
+        }
+    }
+}
+impl MacroExpansions {
+    /// What form expanded to last time, if it was expanded by ifn with these same locals
+    pub fn get(&self, form: &Rc<Value>, ifn: &Rc<dyn IFn>, locals: &[Symbol]) -> Option<Rc<Value>> {
+        match self.expansions.borrow().get(&Rc::as_ptr(form)) {
+            Some(expansion)
+                if expansion.form.upgrade().is_some()
+                    && same_ifn(&expansion.ifn, ifn)
+                    && same_locals(&expansion.locals, locals) =>
+            {
+                Some(Rc::clone(&expansion.expansion))
+            }
+            _ => None,
+        }
+    }
+    /// Remembers what form expanded to, replacing what it expanded to before
+    pub fn insert(
+        &self,
+        form: &Rc<Value>,
+        ifn: &Rc<dyn IFn>,
+        locals: Vec<Symbol>,
+        expansion: Rc<Value>,
+    ) {
+        let mut expansions = self.expansions.borrow_mut();
+        expansions.insert(
+            Rc::as_ptr(form),
+            Expansion {
+                form: Rc::downgrade(form),
+                ifn: Rc::clone(ifn),
+                locals,
+                expansion,
+            },
+        );
+        if expansions.len() >= *self.prune_at.borrow() {
+            expansions.retain(|_, expansion| expansion.form.upgrade().is_some());
+            self.prune_at
+                .replace(std::cmp::max(PRUNE_AT, expansions.len() * 2));
+        }
+    }
+}
+fn same_ifn(ifn: &Rc<dyn IFn>, ifn2: &Rc<dyn IFn>) -> bool {
+    Rc::as_ptr(ifn) as *const () == Rc::as_ptr(ifn2) as *const ()
+}
+// Our locals come out of HashMaps, and so in no set order
+fn same_locals(locals: &[Symbol], locals2: &[Symbol]) -> bool {
+    locals.len() == locals2.len() && locals.iter().all(|local| locals2.contains(local))
+}
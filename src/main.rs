@@ -12,22 +12,42 @@ mod protocol;
 mod symbol;
 #[macro_use]
 mod var;
+mod atom;
+mod call_stack;
+mod clojure_set;
 mod clojure_std;
 mod clojure_string;
+mod clojure_walk;
+mod compare;
+mod condition;
+mod cons;
+mod delay;
+mod destructure;
 mod environment;
 mod error_message;
 mod ifn;
 mod iterable;
 mod keyword;
 mod lambda;
+mod lazy_seq;
+mod macro_expansions;
 mod maps;
 mod namespace;
+mod numbers;
+mod persistent_hash_map;
+mod persistent_hash_set;
+mod persistent_queue;
+mod persistent_tree_map;
+mod persistent_tree_set;
 mod persistent_vector;
+mod position;
 mod protocols;
 mod reader;
 mod repl;
 mod rust_core;
+mod syntax_quote;
 mod traits;
+mod transient;
 mod type_tag;
 mod user_action;
 mod util;
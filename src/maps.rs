@@ -1,5 +1,10 @@
 //! General map utilities
-use crate::value::Value;
+use crate::persistent_hash_map::PersistentHashMap;
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap, ToPersistentListMapIter};
+use crate::traits;
+use crate::value::{ToValue, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::rc::Rc;
 
 #[derive(Debug, Clone, PartialEq, Hash)]
@@ -10,3 +15,63 @@ pub struct MapEntry {
     pub key: Rc<Value>,
     pub val: Rc<Value>,
 }
+
+/// How many entries a map can have and still be a PersistentListMap;  past this, a
+/// PersistentListMap's linear lookups cost more than a PersistentHashMap's trie, and
+/// an assoc gives a PersistentHashMap instead
+pub const LIST_MAP_THRESHOLD: usize = 8;
+
+/// A map of entries, as a PersistentListMap while it's small, or a PersistentHashMap.
+/// A later entry replaces an earlier one with the same key, as in {:a 1 :a 2}
+pub fn map_of<I: IntoIterator<Item = MapEntry>>(entries: I) -> Value {
+    let mut entries = entries.into_iter();
+    let mut plist_map = PersistentListMap::Empty;
+    for entry in entries.by_ref() {
+        if plist_map.count() >= LIST_MAP_THRESHOLD && !plist_map.contains_key(&entry.key) {
+            // Past its threshold, the rest go into a hash map, which we alone own, and so
+            // can build in place
+            let mut phash_map = to_hash_map(&plist_map);
+            phash_map.assoc_mut(entry.key, entry.val);
+            for entry in entries {
+                phash_map.assoc_mut(entry.key, entry.val);
+            }
+            return phash_map.to_value();
+        }
+        plist_map = plist_map.assoc(entry.key, entry.val);
+    }
+    plist_map.to_value()
+}
+
+/// A list map, grown past its threshold, as the hash map it becomes;  with the same
+/// entries and meta
+pub fn to_hash_map(plist_map: &PersistentListMap) -> PersistentHashMap {
+    let mut entries = plist_map.iter().collect::<Vec<MapEntry>>();
+    entries.reverse();
+    traits::IObj::with_meta(
+        &entries.into_iter().collect::<PersistentHashMap>(),
+        traits::IMeta::meta(plist_map),
+    )
+}
+
+/// Whether a map's entries are all in other, and other has no more;  the order either
+/// keeps them in doesn't matter
+pub fn entries_equal<I, M>(entries: I, count: usize, other: &M) -> bool
+where
+    I: Iterator<Item = MapEntry>,
+    M: IPersistentMap,
+{
+    count == other.count()
+        && entries
+            .into_iter()
+            .all(|entry| other.contains_key(&entry.key) && other.get(&entry.key) == entry.val)
+}
+
+/// A hash of a map's entries that doesn't depend on their order, so that maps that are
+/// equal hash the same, whatever kind of map they are
+pub fn hash_entries<I: Iterator<Item = MapEntry>>(entries: I) -> u64 {
+    entries.fold(0, |hash, entry| {
+        let mut hasher = DefaultHasher::new();
+        entry.hash(&mut hasher);
+        hash.wrapping_add(hasher.finish())
+    })
+}
@@ -1,3 +1,4 @@
+use crate::error_message;
 use crate::persistent_list_map::PersistentListMap;
 use crate::symbol::Symbol;
 use crate::value::{ToValue, Value};
@@ -129,17 +130,13 @@ impl Default for Refers {
 pub struct Namespace {
     pub name: Symbol,
     pub refers: RefCell<Refers>,
-    // @TODO decide to make Var a Rc<Var> ?
-    // On one hand,  its internals (except its ns and name) are Rcs, so cloning is still
-    // cloning a reference
-    //
-    // On the other hand,  the Var itself, regardless, lives in one spot and is referenced by many
-    // This explicitly shows its a reference
-    mappings: RefCell<HashMap<Symbol, Var>>,
+    // Each sym maps to its one Var;  everything that refers to it, from a (var x) to a
+    // fn that calls x, goes through that Var, so redefining x is seen by all of them
+    mappings: RefCell<HashMap<Symbol, Rc<Var>>>,
 }
 
 impl Namespace {
-    fn new(name: &Symbol, mappings: HashMap<Symbol, Var>, refers: Refers) -> Namespace {
+    fn new(name: &Symbol, mappings: HashMap<Symbol, Rc<Var>>, refers: Refers) -> Namespace {
         Namespace {
             name: name.unqualified(),
             mappings: RefCell::new(mappings),
@@ -166,44 +163,39 @@ impl Namespace {
     fn contains_key(&self, sym: &Symbol) -> bool {
         self.mappings.borrow_mut().contains_key(sym)
     }
+    /// The Var sym names here, made, unbound, should there not be one yet
+    pub fn intern(&self, sym: &Symbol) -> Rc<Var> {
+        let mut mappings = self.mappings.borrow_mut();
+        let var = mappings
+            .entry(sym.unqualified())
+            .or_insert_with(|| Rc::new(var!(&self.name.name, &sym.name)));
+        Rc::clone(var)
+    }
     pub fn insert(&self, sym: &Symbol, val: Rc<Value>) {
-        if !self.contains_key(sym) {
-            let var = var!(&self.name.name, &sym.name);
-            var.bind_root(val);
-            var.set_meta(sym.meta());
-            self.mappings.borrow_mut().insert(sym.unqualified(), var);
-        } else {
-            let mappings = self.mappings.borrow_mut();
-            let var = mappings.get(&sym.unqualified()).unwrap();
+        let var = self.intern(sym);
+        var.bind_root(val);
+        var.set_meta(sym.meta());
+    }
 
-            var.bind_root(val);
-            var.set_meta(sym.meta());
-        }
+    pub fn try_get_var(&self, sym: &Symbol) -> Option<Rc<Var>> {
+        self.mappings.borrow().get(&sym.unqualified()).cloned()
     }
 
     pub fn get_var(&self, sym: &Symbol) -> Rc<Value> {
-        match self
-            .mappings
-            .borrow_mut()
-            .get(&sym.unqualified())
-            .map(|var| var.clone())
-        {
+        match self.try_get_var(sym) {
             Some(var) => Rc::new(Value::Var(var)),
-            None => Rc::new(Value::Condition(format!("Undefined symbol {}", sym.name))),
+            None => Rc::new(error_message::undefined_symbol(sym)),
         }
     }
 
     pub fn try_get(&self, sym: &Symbol) -> Option<Rc<Value>> {
-        match self.mappings.borrow_mut().get(&sym.unqualified()) {
-            Some(var) => Some(var.deref()),
-            None => None,
-        }
+        self.try_get_var(sym).map(|var| var.deref())
     }
 
     pub fn get(&self, sym: &Symbol) -> Rc<Value> {
         match self.try_get(sym) {
             Some(val) => val,
-            None => Rc::new(Value::Condition(format!("Undefined symbol {}", sym.name))),
+            None => Rc::new(error_message::undefined_symbol(sym)),
         }
     }
 }
@@ -296,31 +288,35 @@ impl Namespaces {
         }
     }
 
-    // TODO write this similar to try_get, and rewrite try_get in terms of this
-    pub fn get_var(&self, namespace_sym: &Symbol, sym: &Symbol) -> Rc<Value> {
-        // When storing / retrieving from namespaces, we want
-        // namespace_sym unqualified keys
-        let mut namespace_sym = namespace_sym.unqualified();
-
-        // @TODO just make it an Optional<String>
-        // If our sym is namespace qualified,  use that as our namespace
-        if sym.has_ns() {
-            namespace_sym = Symbol::intern(&sym.ns);
+    /// The Var sym names in namespace, made, unbound, should there not be one yet.  If
+    /// namespace doesn't exist, create it
+    pub fn intern(&self, namespace_sym: &Symbol, sym: &Symbol) -> Rc<Var> {
+        let namespace_sym = if sym.has_ns() {
+            Symbol::intern(&sym.ns)
+        } else {
+            namespace_sym.unqualified()
+        };
+        if !self.has_namespace(&namespace_sym) {
+            self.create_namespace(&namespace_sym);
         }
-
-        let sym = sym.unqualified();
         let namespaces = self.0.borrow();
-        let namespace = namespaces.get(&namespace_sym);
+        namespaces.get(&namespace_sym).unwrap().intern(sym)
+    }
 
-        match namespace {
-            Some(namespace) => namespace.get_var(&sym),
-            // @TODO should this be a condition or nil?
-            _ => Rc::new(Value::Condition(format!("Undefined symbol {}", sym.name))),
+    /// The Var sym means in namespace, as a Value::Var;  see try_get_var
+    pub fn get_var(&self, namespace_sym: &Symbol, sym: &Symbol) -> Rc<Value> {
+        match self.try_get_var(namespace_sym, sym) {
+            Some(var) => Rc::new(Value::Var(var)),
+            None => Rc::new(error_message::undefined_symbol(sym)),
         }
     }
     /// Like get, but slightly lower level; returns a None on failure rather than a
     /// Value::Condition. See docs for get
     pub fn try_get(&self, namespace_sym: &Symbol, sym: &Symbol) -> Option<Rc<Value>> {
+        self.try_get_var(namespace_sym, sym).map(|var| var.deref())
+    }
+    /// The Var sym means in namespace;  whether its own, or one it refers.  See docs for get
+    pub fn try_get_var(&self, namespace_sym: &Symbol, sym: &Symbol) -> Option<Rc<Var>> {
         // When storing / retrieving from namespaces, we want
         // namespace_sym unqualified keys
         let mut namespace_sym = namespace_sym.unqualified();
@@ -339,7 +335,7 @@ impl Namespaces {
 
         // If we cannot find the symbol, and its not a direct grab from a specific namespace,
         // we should see if we can find it in one of our referred namespaces or symbols
-        let val = namespace.try_get(&sym);
+        let val = namespace.try_get_var(&sym);
         match val {
             Some(_) => val,
             None => {
@@ -356,7 +352,7 @@ impl Namespaces {
                         continue;
                     }
                     // Ex: let's try to get, then, from "clojure.core or "clojure.string"
-                    let try_get_sym_from_other_ns = self.try_get(&referred_namespace_sym, &sym);
+                    let try_get_sym_from_other_ns = self.try_get_var(referred_namespace_sym, &sym);
                     // And if we get a value, return it
                     if let Some(_) = &try_get_sym_from_other_ns {
                         return try_get_sym_from_other_ns;
@@ -386,7 +382,7 @@ impl Namespaces {
                     // If we *have* referred the sym we're looking for from this ns
                     // let's try to get it
                     // Ex:  try_get('clojure.string, '+)
-                    let try_get_sym_from_other_ns = self.try_get(&referred_namespace_sym, &sym);
+                    let try_get_sym_from_other_ns = self.try_get_var(referred_namespace_sym, &sym);
                     // And if we get a value, return it
                     if let Some(_) = &try_get_sym_from_other_ns {
                         return try_get_sym_from_other_ns;
@@ -397,6 +393,36 @@ impl Namespaces {
         }
     }
 
+    /// The namespace an unqualified sym would be found in, looking from namespace_sym;
+    /// namespace_sym itself, or one of the namespaces it refers.  None if it's in neither
+    pub fn resolve_namespace(&self, namespace_sym: &Symbol, sym: &Symbol) -> Option<Symbol> {
+        let namespace_sym = namespace_sym.unqualified();
+        let sym = sym.unqualified();
+        let namespaces = self.0.borrow();
+        let namespace = namespaces.get(&namespace_sym)?;
+        if namespace.contains_key(&sym) {
+            return Some(namespace_sym);
+        }
+        let refers = namespace.refers.borrow();
+        let referred_namespaces = refers.namespaces.iter().chain(
+            refers
+                .syms
+                .iter()
+                .filter(|(_, referred_syms)| referred_syms.contains(&sym))
+                .map(|(referred_namespace_sym, _)| referred_namespace_sym),
+        );
+        for referred_namespace_sym in referred_namespaces {
+            let has_sym = namespaces
+                .get(referred_namespace_sym)
+                .map(|referred_namespace| referred_namespace.contains_key(&sym))
+                .unwrap_or(false);
+            if has_sym {
+                return Some(referred_namespace_sym.unqualified());
+            }
+        }
+        None
+    }
+
     /// Get value of sym in namespace
     /// Note;
     /// ```
@@ -423,7 +449,7 @@ impl Namespaces {
         match self.try_get(namespace_sym, sym) {
             Some(val) => val,
             // @TODO should this be a condition or nil?
-            None => Rc::new(Value::Condition(format!("Undefined symbol {}", sym.name))),
+            None => Rc::new(error_message::undefined_symbol(sym)),
         }
     }
 }
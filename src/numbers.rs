@@ -0,0 +1,420 @@
+//! Our numeric tower;  i64s, BigInts, Ratios, BigDecimals and f64s, and the arithmetic
+//! between them.  As in Clojure, arithmetic on two numbers is done in the wider of their
+//! two categories, so that (+ 1 1/2) is a Ratio, and (+ 1/2 0.5) an f64
+//!
+//!   i64 < BigInt < Ratio < BigDecimal < f64
+//!
+//! An i64 only becomes a BigInt when asked to;  plain + - * are an error on overflow,
+//! +' -' *' promote to a BigInt instead, and the unchecked- fns wrap around.  Dividing
+//! two integers is exact, giving a Ratio when they don't divide evenly
+//!
+//! ```clojure
+//!   (/ 1 3)                     ;; => 1/3
+//!   (+ 1/3 2/3)                 ;; => 1N
+//!   (+ 9223372036854775807 1)   ;; => #Condition["integer overflow"]
+//!   (+' 9223372036854775807 1)  ;; => 9223372036854775808N
+//!   (* 1.10M 3)                 ;; => 3.30M
+//! ```
+use crate::error_message;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use bigdecimal::BigDecimal;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{FromPrimitive, ToPrimitive, Zero};
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+/// What arithmetic on i64s does when its result doesn't fit in one
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Overflow {
+    /// An integer overflow error, as with + - *
+    Error,
+    /// Promote to a BigInt, as with +' -' *'
+    Promote,
+    /// Wrap around, as with unchecked-add and the like
+    Wrap,
+}
+
+#[derive(Debug, Clone)]
+pub enum Number {
+    I64(i64),
+    BigInt(BigInt),
+    Ratio(BigRational),
+    BigDecimal(BigDecimal),
+    F64(f64),
+}
+
+// Two numbers, brought up to the same category
+enum Pair {
+    I64(i64, i64),
+    BigInt(BigInt, BigInt),
+    Ratio(BigRational, BigRational),
+    BigDecimal(BigDecimal, BigDecimal),
+    F64(f64, f64),
+}
+
+impl Number {
+    /// value, as a number;  or a type mismatch, should it be anything else
+    pub fn of(value: &Value) -> Result<Number, Rc<Value>> {
+        match value {
+            Value::I64(n) => Ok(Number::I64(*n)),
+            Value::BigInt(n) => Ok(Number::BigInt(n.clone())),
+            Value::Ratio(n) => Ok(Number::Ratio(n.clone())),
+            Value::BigDecimal(n) => Ok(Number::BigDecimal(n.clone())),
+            Value::F64(n) => Ok(Number::F64(*n)),
+            _ => Err(Rc::new(error_message::type_mismatch(
+                TypeTag::Number,
+                value,
+            ))),
+        }
+    }
+    /// n, as an i64 if it fits in one
+    pub fn integer(n: BigInt) -> Number {
+        match n.to_i64() {
+            Some(n) => Number::I64(n),
+            None => Number::BigInt(n),
+        }
+    }
+    // A Ratio that's really a whole number is a BigInt, as in Clojure
+    fn ratio(n: BigRational) -> Number {
+        if n.is_integer() {
+            Number::BigInt(n.to_integer())
+        } else {
+            Number::Ratio(n)
+        }
+    }
+    fn rank(&self) -> u8 {
+        match self {
+            Number::I64(_) => 0,
+            Number::BigInt(_) => 1,
+            Number::Ratio(_) => 2,
+            Number::BigDecimal(_) => 3,
+            Number::F64(_) => 4,
+        }
+    }
+    // Ints and BigInts are the one category for equality, so that (= 1 1N)
+    fn category(&self) -> u8 {
+        match self {
+            Number::BigInt(_) => 0,
+            _ => self.rank(),
+        }
+    }
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Number::I64(n) => *n == 0,
+            Number::BigInt(n) => n.is_zero(),
+            Number::Ratio(n) => n.is_zero(),
+            Number::BigDecimal(n) => n.is_zero(),
+            Number::F64(n) => *n == 0.0,
+        }
+    }
+    // The conversions below are for bringing a number up to a wider category, and are
+    // exact for any number narrower than it;  a wider one is truncated
+    pub fn to_big_int(&self) -> BigInt {
+        match self {
+            Number::I64(n) => BigInt::from(*n),
+            Number::BigInt(n) => n.clone(),
+            Number::Ratio(n) => n.to_integer(),
+            Number::BigDecimal(n) => n.with_scale(0).into_bigint_and_exponent().0,
+            Number::F64(n) => BigInt::from_f64(n.trunc()).unwrap_or_default(),
+        }
+    }
+    fn to_ratio(&self) -> BigRational {
+        match self {
+            Number::Ratio(n) => n.clone(),
+            _ => BigRational::from_integer(self.to_big_int()),
+        }
+    }
+    pub fn to_big_decimal(&self) -> BigDecimal {
+        match self {
+            Number::I64(n) => BigDecimal::from(*n),
+            Number::BigInt(n) => BigDecimal::from(n.clone()),
+            Number::Ratio(n) => {
+                BigDecimal::from(n.numer().clone()) / BigDecimal::from(n.denom().clone())
+            }
+            Number::BigDecimal(n) => n.clone(),
+            // As it prints, so that 0.1 is 0.1M, rather than the f64 nearest it
+            Number::F64(n) => n.to_string().parse().unwrap_or_default(),
+        }
+    }
+    pub fn to_f64(&self) -> f64 {
+        match self {
+            Number::I64(n) => *n as f64,
+            Number::BigInt(n) => n.to_f64().unwrap_or(f64::NAN),
+            Number::Ratio(n) => n.to_f64().unwrap_or(f64::NAN),
+            Number::BigDecimal(n) => n.to_f64().unwrap_or(f64::NAN),
+            Number::F64(n) => *n,
+        }
+    }
+}
+impl ToValue for Number {
+    fn to_value(&self) -> Value {
+        match self {
+            Number::I64(n) => Value::I64(*n),
+            Number::BigInt(n) => Value::BigInt(n.clone()),
+            Number::Ratio(n) => Value::Ratio(n.clone()),
+            Number::BigDecimal(n) => Value::BigDecimal(n.clone()),
+            Number::F64(n) => Value::F64(*n),
+        }
+    }
+}
+
+fn pair(a: &Number, b: &Number) -> Pair {
+    match a.rank().max(b.rank()) {
+        0 => match (a, b) {
+            (Number::I64(a), Number::I64(b)) => Pair::I64(*a, *b),
+            _ => unreachable!(),
+        },
+        1 => Pair::BigInt(a.to_big_int(), b.to_big_int()),
+        2 => Pair::Ratio(a.to_ratio(), b.to_ratio()),
+        3 => Pair::BigDecimal(a.to_big_decimal(), b.to_big_decimal()),
+        _ => Pair::F64(a.to_f64(), b.to_f64()),
+    }
+}
+
+pub fn integer_overflow() -> Value {
+    error_message::error("arithmetic", String::from("integer overflow"))
+}
+
+/// The result of an op on two i64s;  checked, or should that overflow, whatever overflow
+/// asks for
+fn on_i64s(
+    checked: Option<i64>,
+    promoted: impl FnOnce() -> BigInt,
+    wrapped: impl FnOnce() -> i64,
+    overflow: Overflow,
+) -> Result<Number, Rc<Value>> {
+    match (checked, overflow) {
+        (Some(n), _) => Ok(Number::I64(n)),
+        (None, Overflow::Error) => Err(Rc::new(integer_overflow())),
+        (None, Overflow::Promote) => Ok(Number::BigInt(promoted())),
+        (None, Overflow::Wrap) => Ok(Number::I64(wrapped())),
+    }
+}
+
+pub fn add(a: &Number, b: &Number, overflow: Overflow) -> Result<Number, Rc<Value>> {
+    Ok(match pair(a, b) {
+        Pair::I64(a, b) => {
+            return on_i64s(
+                a.checked_add(b),
+                || BigInt::from(a) + b,
+                || a.wrapping_add(b),
+                overflow,
+            )
+        }
+        Pair::BigInt(a, b) => Number::BigInt(a + b),
+        Pair::Ratio(a, b) => Number::ratio(a + b),
+        Pair::BigDecimal(a, b) => Number::BigDecimal(a + b),
+        Pair::F64(a, b) => Number::F64(a + b),
+    })
+}
+
+pub fn subtract(a: &Number, b: &Number, overflow: Overflow) -> Result<Number, Rc<Value>> {
+    Ok(match pair(a, b) {
+        Pair::I64(a, b) => {
+            return on_i64s(
+                a.checked_sub(b),
+                || BigInt::from(a) - b,
+                || a.wrapping_sub(b),
+                overflow,
+            )
+        }
+        Pair::BigInt(a, b) => Number::BigInt(a - b),
+        Pair::Ratio(a, b) => Number::ratio(a - b),
+        Pair::BigDecimal(a, b) => Number::BigDecimal(a - b),
+        Pair::F64(a, b) => Number::F64(a - b),
+    })
+}
+
+pub fn multiply(a: &Number, b: &Number, overflow: Overflow) -> Result<Number, Rc<Value>> {
+    Ok(match pair(a, b) {
+        Pair::I64(a, b) => {
+            return on_i64s(
+                a.checked_mul(b),
+                || BigInt::from(a) * b,
+                || a.wrapping_mul(b),
+                overflow,
+            )
+        }
+        Pair::BigInt(a, b) => Number::BigInt(a * b),
+        Pair::Ratio(a, b) => Number::ratio(a * b),
+        Pair::BigDecimal(a, b) => Number::BigDecimal(a * b),
+        Pair::F64(a, b) => Number::F64(a * b),
+    })
+}
+
+/// a divided by b;  exact, for anything but f64s, so that integers that don't divide
+/// evenly give a Ratio
+pub fn divide(a: &Number, b: &Number) -> Result<Number, Rc<Value>> {
+    if b.is_zero() {
+        return Err(Rc::new(error_message::divide_by_zero()));
+    }
+    Ok(match pair(a, b) {
+        // i64::MIN rem -1 overflows too, to a remainder of 0
+        Pair::I64(a, b) if a.wrapping_rem(b) == 0 => match a.checked_div(b) {
+            Some(n) => Number::I64(n),
+            // i64::MIN / -1
+            None => Number::BigInt(-BigInt::from(a)),
+        },
+        Pair::I64(a, b) => Number::Ratio(BigRational::new(BigInt::from(a), BigInt::from(b))),
+        Pair::BigInt(a, b) => Number::ratio(BigRational::new(a, b)),
+        Pair::Ratio(a, b) => Number::ratio(a / b),
+        Pair::BigDecimal(a, b) => Number::BigDecimal(a / b),
+        Pair::F64(a, b) => Number::F64(a / b),
+    })
+}
+
+/// What's left of a once b is taken out of it as many whole times as it can be;  with
+/// the sign of a, as with Rust's %
+pub fn remainder(a: &Number, b: &Number) -> Result<Number, Rc<Value>> {
+    if b.is_zero() {
+        return Err(Rc::new(error_message::divide_by_zero()));
+    }
+    Ok(match pair(a, b) {
+        // i64::MIN rem -1 overflows, to a remainder of 0
+        Pair::I64(a, b) => Number::I64(a.wrapping_rem(b)),
+        Pair::BigInt(a, b) => Number::BigInt(a % b),
+        Pair::Ratio(a, b) => Number::ratio(&a - (&a / &b).trunc() * &b),
+        Pair::BigDecimal(a, b) => Number::BigDecimal(a % b),
+        Pair::F64(a, b) => Number::F64(a % b),
+    })
+}
+
+/// How a compares to b;  None, should either be NaN
+pub fn compare(a: &Number, b: &Number) -> Option<Ordering> {
+    match pair(a, b) {
+        Pair::I64(a, b) => Some(a.cmp(&b)),
+        Pair::BigInt(a, b) => Some(a.cmp(&b)),
+        Pair::Ratio(a, b) => Some(a.cmp(&b)),
+        Pair::BigDecimal(a, b) => Some(a.cmp(&b)),
+        Pair::F64(a, b) => a.partial_cmp(&b),
+    }
+}
+
+/// Whether a and b are =;  as in Clojure, they must be the same category of number, so
+/// while (= 1 1N) and (= 1.0M 1.00M), (= 1 1.0) is false
+pub fn equiv(a: &Number, b: &Number) -> bool {
+    a.category() == b.category() && compare(a, b) == Some(Ordering::Equal)
+}
+
+pub fn is_number(value: &Value) -> bool {
+    matches!(
+        value,
+        Value::I64(_) | Value::BigInt(_) | Value::Ratio(_) | Value::BigDecimal(_) | Value::F64(_)
+    )
+}
+
+/// args, folded left to right with op from init, as + and * do
+pub fn fold<F>(args: &[Rc<Value>], init: Number, op: F) -> Value
+where
+    F: Fn(&Number, &Number) -> Result<Number, Rc<Value>>,
+{
+    let result = args.iter().try_fold(init, |a, b| op(&a, &Number::of(b)?));
+    match result {
+        Ok(n) => n.to_value(),
+        Err(condition) => condition.to_value(),
+    }
+}
+
+/// (f x), if given one arg, as with (- x);  otherwise args, folded left to right with op
+/// from the first of them, as with (- x y z)
+pub fn fold_from_first<F, G>(args: &[Rc<Value>], f: F, op: G) -> Value
+where
+    F: Fn(&Number) -> Result<Number, Rc<Value>>,
+    G: Fn(&Number, &Number) -> Result<Number, Rc<Value>>,
+{
+    let result = match args {
+        [] => return error_message::zero_arg_count(0),
+        [x] => Number::of(x).and_then(|x| f(&x)),
+        [x, rest @ ..] => {
+            Number::of(x).and_then(|x| rest.iter().try_fold(x, |a, b| op(&a, &Number::of(b)?)))
+        }
+    };
+    match result {
+        Ok(n) => n.to_value(),
+        Err(condition) => condition.to_value(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::numbers::*;
+
+    fn big(n: &str) -> Number {
+        Number::BigInt(n.parse().unwrap())
+    }
+
+    #[test]
+    fn overflow_does_what_its_asked() {
+        let max = Number::I64(i64::MAX);
+        let one = Number::I64(1);
+        assert!(add(&max, &one, Overflow::Error).is_err());
+        assert!(equiv(
+            &big("9223372036854775808"),
+            &add(&max, &one, Overflow::Promote).unwrap()
+        ));
+        assert!(equiv(
+            &Number::I64(i64::MIN),
+            &add(&max, &one, Overflow::Wrap).unwrap()
+        ));
+        assert!(multiply(&max, &Number::I64(2), Overflow::Error).is_err());
+        assert!(subtract(&Number::I64(i64::MIN), &one, Overflow::Error).is_err());
+    }
+
+    #[test]
+    fn division_is_exact() {
+        match divide(&Number::I64(1), &Number::I64(3)).unwrap() {
+            Number::Ratio(n) => assert_eq!("1/3", n.to_string()),
+            n => panic!("Expected a ratio, found {:?}", n),
+        }
+        let third = divide(&Number::I64(1), &Number::I64(3)).unwrap();
+        let whole = add(
+            &third,
+            &divide(&Number::I64(2), &Number::I64(3)).unwrap(),
+            Overflow::Error,
+        );
+        assert!(matches!(whole, Ok(Number::BigInt(_))));
+        assert!(matches!(
+            divide(&Number::I64(6), &Number::I64(3)),
+            Ok(Number::I64(2))
+        ));
+        assert!(divide(&Number::I64(1), &Number::I64(0)).is_err());
+        assert!(equiv(
+            &big("9223372036854775808"),
+            &divide(&Number::I64(i64::MIN), &Number::I64(-1)).unwrap()
+        ));
+    }
+
+    #[test]
+    fn wider_categories_win() {
+        let half = divide(&Number::I64(1), &Number::I64(2)).unwrap();
+        assert!(matches!(
+            add(&half, &Number::F64(0.5), Overflow::Error),
+            Ok(Number::F64(n)) if n == 1.0
+        ));
+        let decimal = Number::BigDecimal("1.10".parse().unwrap());
+        match multiply(&decimal, &Number::I64(3), Overflow::Error).unwrap() {
+            Number::BigDecimal(n) => assert_eq!("3.30", n.to_string()),
+            n => panic!("Expected a BigDecimal, found {:?}", n),
+        }
+    }
+
+    #[test]
+    fn equality_is_within_a_category() {
+        assert!(equiv(&Number::I64(1), &big("1")));
+        assert!(!equiv(&Number::I64(1), &Number::F64(1.0)));
+        assert!(equiv(
+            &Number::BigDecimal("1.0".parse().unwrap()),
+            &Number::BigDecimal("1.00".parse().unwrap())
+        ));
+        assert!(!equiv(
+            &Number::I64(1),
+            &Number::BigDecimal("1".parse().unwrap())
+        ));
+        assert_eq!(
+            Some(Ordering::Less),
+            compare(&Number::I64(1), &Number::F64(1.5))
+        );
+    }
+}
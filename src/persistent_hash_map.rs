@@ -0,0 +1,519 @@
+//! A persistent hash map;  a hash array mapped trie, as in Clojure.  Each node branches
+//! 32 ways on the next 5 bits of a key's hash, but only keeps the branches in use, so
+//! finding a key takes O(log32 n) steps, without a node of 32 mostly empty slots at
+//! every level.  An assoc or dissoc copies just the nodes on the way to its key, sharing
+//! the rest with the map it was made from
+//!
+//!            [ bitmap ..01010 ]
+//!              /            \
+//!         (:a 1)       [ bitmap ..10001 ]
+//!                         /            \
+//!                     (:b 2)          (:c 3)
+//! -------------------
+//! => {:a 1 :b 2 :c 3}
+
+use crate::maps::{self, MapEntry};
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap};
+use crate::traits;
+use crate::value::Value;
+
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::rc::Rc;
+
+/// How many bits of a hash each level of the trie branches on
+const BITS: u32 = 5;
+const MASK: u64 = 0x1f;
+
+/// The hash that places key in the trie
+fn hash_of(key: &Value) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+/// The bit standing for hash's branch, on the level shift bits down
+fn bit_of(hash: u64, shift: u32) -> u32 {
+    1 << ((hash >> shift) & MASK)
+}
+/// Where the child for bit is kept;  after one child for each branch before it in use
+fn index_of(bitmap: u32, bit: u32) -> usize {
+    (bitmap & (bit - 1)).count_ones() as usize
+}
+
+#[derive(Debug, Clone)]
+enum Child {
+    // An entry, and the hash of its key
+    Entry(u64, MapEntry),
+    Node(Rc<Node>),
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    // Which of our 32 branches are in use, and a child for each, in order
+    Branch(u32, Vec<Child>),
+    // Entries whose keys all have the same hash;  no amount of branching would part them
+    Collision(u64, Vec<MapEntry>),
+}
+impl Node {
+    fn get(&self, shift: u32, hash: u64, key: &Rc<Value>) -> Option<Rc<Value>> {
+        match self {
+            Node::Branch(bitmap, children) => {
+                let bit = bit_of(hash, shift);
+                if bitmap & bit == 0 {
+                    return None;
+                }
+                match &children[index_of(*bitmap, bit)] {
+                    Child::Entry(_, entry) if entry.key == *key => Some(Rc::clone(&entry.val)),
+                    Child::Entry(..) => None,
+                    Child::Node(node) => node.get(shift + BITS, hash, key),
+                }
+            }
+            Node::Collision(_, entries) => entries
+                .iter()
+                .find(|entry| entry.key == *key)
+                .map(|entry| Rc::clone(&entry.val)),
+        }
+    }
+    /// Puts entry in this node, on the level shift bits down.  added is set should its
+    /// key be new, rather than one already here whose val it replaces.  Like all our _mut
+    /// fns, this edits a child node in place only if we're its one owner, as Rc::make_mut
+    /// does, and otherwise edits a copy, leaving the maps we share it with alone
+    fn assoc_mut(&mut self, shift: u32, hash: u64, entry: MapEntry, added: &mut bool) {
+        match self {
+            Node::Branch(bitmap, children) => {
+                let bit = bit_of(hash, shift);
+                let index = index_of(*bitmap, bit);
+                if *bitmap & bit == 0 {
+                    *added = true;
+                    children.insert(index, Child::Entry(hash, entry));
+                    *bitmap |= bit;
+                    return;
+                }
+                let child = match &mut children[index] {
+                    Child::Entry(_, existing) if existing.key == entry.key => {
+                        Child::Entry(hash, entry)
+                    }
+                    // Two keys on the same branch;  they go a level further down
+                    Child::Entry(existing_hash, existing) => {
+                        *added = true;
+                        Child::Node(Rc::new(Node::pair(
+                            shift + BITS,
+                            (*existing_hash, existing.clone()),
+                            (hash, entry),
+                        )))
+                    }
+                    Child::Node(node) => {
+                        Rc::make_mut(node).assoc_mut(shift + BITS, hash, entry, added);
+                        return;
+                    }
+                };
+                children[index] = child;
+            }
+            Node::Collision(collision_hash, entries) if hash == *collision_hash => {
+                match entries
+                    .iter()
+                    .position(|existing| existing.key == entry.key)
+                {
+                    Some(index) => entries[index] = entry,
+                    None => {
+                        *added = true;
+                        entries.push(entry);
+                    }
+                }
+            }
+            // We go a level down, into a branch, to make room for entry beside us
+            Node::Collision(collision_hash, _) => {
+                let branch = Node::Branch(bit_of(*collision_hash, shift), vec![]);
+                let collision = std::mem::replace(self, branch);
+                if let Node::Branch(_, children) = self {
+                    children.push(Child::Node(Rc::new(collision)));
+                }
+                self.assoc_mut(shift, hash, entry, added)
+            }
+        }
+    }
+    /// A node, on the level shift bits down, of two entries with different keys
+    fn pair(shift: u32, (hash, entry): (u64, MapEntry), (hash2, entry2): (u64, MapEntry)) -> Node {
+        if hash == hash2 {
+            return Node::Collision(hash, vec![entry, entry2]);
+        }
+        let mut added = false;
+        let mut node = Node::Branch(0, vec![]);
+        node.assoc_mut(shift, hash, entry, &mut added);
+        node.assoc_mut(shift, hash2, entry2, &mut added);
+        node
+    }
+    /// Takes key, which must be in it, out of this node, on the level shift bits down;
+    /// true, should that leave it empty
+    fn without_mut(&mut self, shift: u32, hash: u64, key: &Rc<Value>) -> bool {
+        match self {
+            Node::Branch(bitmap, children) => {
+                let bit = bit_of(hash, shift);
+                let index = index_of(*bitmap, bit);
+                let emptied = match &mut children[index] {
+                    Child::Entry(..) => true,
+                    Child::Node(node) => Rc::make_mut(node).without_mut(shift + BITS, hash, key),
+                };
+                if emptied {
+                    children.remove(index);
+                    *bitmap ^= bit;
+                } else if let Some(entry) = children[index].lone_entry() {
+                    children[index] = entry;
+                }
+                children.is_empty()
+            }
+            Node::Collision(_, entries) => {
+                entries.retain(|entry| entry.key != *key);
+                entries.is_empty()
+            }
+        }
+    }
+}
+impl Child {
+    /// Should this be a node with just one entry left, that entry, to take its place so
+    /// that it's no further down than it needs to be
+    fn lone_entry(&self) -> Option<Child> {
+        match self {
+            Child::Node(node) => match &**node {
+                Node::Branch(_, children) if children.len() == 1 => match &children[0] {
+                    Child::Entry(..) => Some(children[0].clone()),
+                    Child::Node(_) => None,
+                },
+                Node::Collision(hash, entries) if entries.len() == 1 => {
+                    Some(Child::Entry(*hash, entries[0].clone()))
+                }
+                _ => None,
+            },
+            Child::Entry(..) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PersistentHashMap {
+    count: usize,
+    // None while we're empty
+    root: Option<Rc<Node>>,
+    meta: PersistentListMap,
+}
+impl PersistentHashMap {
+    pub fn empty() -> PersistentHashMap {
+        PersistentHashMap {
+            count: 0,
+            root: None,
+            meta: PersistentListMap::Empty,
+        }
+    }
+    /// Associates key with val, in place;  see Node::assoc_mut
+    pub fn assoc_mut(&mut self, key: Rc<Value>, val: Rc<Value>) {
+        let hash = hash_of(&key);
+        let entry = MapEntry { key, val };
+        let mut added = false;
+        match &mut self.root {
+            Some(root) => Rc::make_mut(root).assoc_mut(0, hash, entry, &mut added),
+            None => {
+                let mut root = Node::Branch(0, vec![]);
+                root.assoc_mut(0, hash, entry, &mut added);
+                self.root = Some(Rc::new(root));
+            }
+        }
+        if added {
+            self.count += 1;
+        }
+    }
+    /// Takes key out, in place
+    pub fn dissoc_mut(&mut self, key: &Rc<Value>) {
+        if !self.contains_key(key) {
+            return;
+        }
+        if let Some(root) = &mut self.root {
+            if Rc::make_mut(root).without_mut(0, hash_of(key), key) {
+                self.root = None;
+            }
+        }
+        self.count -= 1;
+    }
+    pub fn iter(&self) -> PersistentHashMapIter {
+        PersistentHashMapIter {
+            stack: self.root.iter().map(|root| (Rc::clone(root), 0)).collect(),
+        }
+    }
+}
+impl IPersistentMap for PersistentHashMap {
+    fn get(&self, key: &Rc<Value>) -> Rc<Value> {
+        self.root
+            .as_ref()
+            .and_then(|root| root.get(0, hash_of(key), key))
+            .unwrap_or_else(|| Rc::new(Value::Nil))
+    }
+    fn assoc(&self, key: Rc<Value>, val: Rc<Value>) -> PersistentHashMap {
+        // Like Clojure, we keep our meta through an assoc
+        let mut map = self.clone();
+        map.assoc_mut(key, val);
+        map
+    }
+    fn contains_key(&self, key: &Rc<Value>) -> bool {
+        match &self.root {
+            Some(root) => root.get(0, hash_of(key), key).is_some(),
+            None => false,
+        }
+    }
+    fn dissoc(&self, key: &Rc<Value>) -> PersistentHashMap {
+        let mut map = self.clone();
+        map.dissoc_mut(key);
+        map
+    }
+    fn count(&self) -> usize {
+        self.count
+    }
+}
+impl PartialEq for PersistentHashMap {
+    // Remember; meta doesn't factor into equality
+    fn eq(&self, other: &Self) -> bool {
+        self.count == other.count
+            && self
+                .iter()
+                .all(|entry| other.contains_key(&entry.key) && other.get(&entry.key) == entry.val)
+    }
+}
+impl Eq for PersistentHashMap {}
+impl Hash for PersistentHashMap {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        maps::hash_entries(self.iter()).hash(state)
+    }
+}
+impl FromIterator<MapEntry> for PersistentHashMap {
+    fn from_iter<I: IntoIterator<Item = MapEntry>>(iter: I) -> Self {
+        let mut map = PersistentHashMap::empty();
+        for entry in iter {
+            map.assoc_mut(entry.key, entry.val);
+        }
+        map
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Iterating
+//
+////////////////////////////////////////////////////////////////////////////////////////////////////
+pub struct PersistentHashMapIter {
+    // The nodes we're partway through, innermost last, and how far through each we are
+    stack: Vec<(Rc<Node>, usize)>,
+}
+impl Iterator for PersistentHashMapIter {
+    type Item = MapEntry;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, index) = self.stack.last_mut()?;
+            let child = match &**node {
+                Node::Branch(_, children) => children.get(*index).cloned(),
+                Node::Collision(hash, entries) => entries
+                    .get(*index)
+                    .map(|entry| Child::Entry(*hash, entry.clone())),
+            };
+            *index += 1;
+            match child {
+                Some(Child::Entry(_, entry)) => return Some(entry),
+                Some(Child::Node(node)) => self.stack.push((node, 0)),
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// End Iteration
+////////////////////////////////////////////////////////////////////////////////////////////////////
+impl traits::IMeta for PersistentHashMap {
+    fn meta(&self) -> PersistentListMap {
+        self.meta.clone()
+    }
+}
+impl traits::IObj for PersistentHashMap {
+    fn with_meta(&self, meta: PersistentListMap) -> PersistentHashMap {
+        PersistentHashMap {
+            meta,
+            ..self.clone()
+        }
+    }
+}
+impl fmt::Display for PersistentHashMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let entries = self
+            .iter()
+            .map(|mapentry| {
+                format!(
+                    "{} {}",
+                    mapentry.key.to_string_explicit(),
+                    mapentry.val.to_string_explicit()
+                )
+            })
+            .collect::<Vec<String>>();
+        write!(f, "{{{}}}", entries.join(", "))
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::persistent_hash_map::*;
+    use crate::value::ToValue;
+
+    fn key(i: i32) -> Rc<Value> {
+        i.to_rc_value()
+    }
+
+    #[test]
+    fn assoc_get_and_dissoc_many_keys() {
+        let map = (0..1000).fold(PersistentHashMap::empty(), |map, i| {
+            map.assoc(key(i), key(i * 2))
+        });
+        assert_eq!(1000, map.count());
+        assert_eq!(1000, map.iter().count());
+        for i in 0..1000 {
+            assert_eq!(key(i * 2), map.get(&key(i)));
+        }
+        assert_eq!(Value::Nil, *map.get(&key(1000)));
+
+        let fewer = (0..1000)
+            .filter(|i| i % 3 == 0)
+            .fold(map.clone(), |map, i| map.dissoc(&key(i)));
+        assert_eq!(666, fewer.count());
+        assert!(!fewer.contains_key(&key(3)));
+        assert!(fewer.contains_key(&key(4)));
+        // The map we dissoc'd from is untouched
+        assert_eq!(1000, map.count());
+        assert!(map.contains_key(&key(3)));
+    }
+
+    #[test]
+    fn assoc_replaces_an_existing_key() {
+        let map = PersistentHashMap::empty()
+            .assoc(key(1), key(1))
+            .assoc(key(2), key(2));
+        let replaced = map.assoc(key(1), key(100));
+        assert_eq!(2, replaced.count());
+        assert_eq!(key(100), replaced.get(&key(1)));
+        assert_eq!(key(1), map.get(&key(1)));
+    }
+
+    #[test]
+    fn equality_ignores_insertion_order() {
+        let map = (0..100).collect::<Vec<i32>>();
+        let forwards = map
+            .iter()
+            .map(|i| MapEntry {
+                key: key(*i),
+                val: key(*i),
+            })
+            .collect::<PersistentHashMap>();
+        let backwards = map
+            .iter()
+            .rev()
+            .map(|i| MapEntry {
+                key: key(*i),
+                val: key(*i),
+            })
+            .collect::<PersistentHashMap>();
+        assert_eq!(forwards, backwards);
+        assert_eq!(
+            hash_of(&Value::PersistentHashMap(forwards)),
+            hash_of(&Value::PersistentHashMap(backwards))
+        );
+    }
+
+    #[test]
+    fn colliding_keys_are_kept_apart() {
+        let entry = |i: i32| MapEntry {
+            key: key(i),
+            val: key(i),
+        };
+        let mut node = Node::pair(0, (7, entry(1)), (7, entry(2)));
+        let mut added = false;
+        node.assoc_mut(0, 7, entry(3), &mut added);
+        assert!(added);
+        node.assoc_mut(0, 8, entry(4), &mut added);
+        assert_eq!(Some(key(2)), node.get(0, 7, &key(2)));
+        assert_eq!(Some(key(4)), node.get(0, 8, &key(4)));
+        assert!(!node.without_mut(0, 7, &key(1)));
+        assert_eq!(None, node.get(0, 7, &key(1)));
+        assert_eq!(Some(key(3)), node.get(0, 7, &key(3)));
+    }
+
+    #[test]
+    fn editing_in_place_leaves_sharers_alone() {
+        let map = (0..1000).fold(PersistentHashMap::empty(), |map, i| {
+            map.assoc(key(i), key(i))
+        });
+        let mut edited = map.clone();
+        for i in 0..1000 {
+            edited.assoc_mut(key(i), key(-i));
+        }
+        for i in (0..1000).filter(|i| i % 2 == 0) {
+            edited.dissoc_mut(&key(i));
+        }
+        assert_eq!(500, edited.count());
+        assert_eq!(key(-7), edited.get(&key(7)));
+        assert!(!edited.contains_key(&key(8)));
+        assert_eq!(1000, map.count());
+        assert!((0..1000).all(|i| map.get(&key(i)) == key(i)));
+    }
+
+    mod persistent_hash_map_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn large_map_literals_are_hash_maps() {
+            let repl = Repl::default();
+            repl.eval_str("(def m {:a 1 :b 2 :c 3 :d 4 :e 5 :f 6 :g 7 :h 8 :i 9})");
+            assert!(matches!(repl.eval_str("m"), Value::PersistentHashMap(_)));
+            assert_eq!(Value::I64(9), repl.eval_str("(count m)"));
+            assert_eq!(Value::I64(9), repl.eval_str("(get m :i)"));
+            assert!(matches!(
+                repl.eval_str("{:a 1}"),
+                Value::PersistentListMap(_)
+            ));
+        }
+
+        #[test]
+        fn assoc_replaces_rather_than_grows() {
+            let repl = Repl::default();
+            assert_eq!(Value::I64(1), repl.eval_str("(count (assoc {:a 1} :a 2))"));
+            assert_eq!(Value::I64(2), repl.eval_str("(get (assoc {:a 1} :a 2) :a)"));
+            assert_eq!(
+                repl.eval_str("{:a 1 :b 2 :c 3}"),
+                repl.eval_str("(assoc {:a 0} :a 1 :b 2 :c 3)")
+            );
+            repl.eval_str(
+                "(def m (loop [i 0 m {}] (if (= i 1000) m (recur (inc i) (assoc m i (* 2 i))))))",
+            );
+            assert_eq!(Value::I64(1000), repl.eval_str("(count m)"));
+            assert_eq!(Value::I64(1998), repl.eval_str("(get m 999)"));
+            assert_eq!(Value::I64(1000), repl.eval_str("(count (assoc m 5 0))"));
+            assert_eq!(
+                Value::I64(997),
+                repl.eval_str("(count (dissoc m 5 6 7 1000))")
+            );
+        }
+
+        #[test]
+        fn maps_are_equal_whatever_their_order_or_kind() {
+            let repl = Repl::default();
+            assert_eq!(
+                Value::Boolean(true),
+                repl.eval_str("(= {:a 1 :b 2} {:b 2 :a 1})")
+            );
+            repl.eval_str("(def m {:a 1 :b 2 :c 3 :d 4 :e 5 :f 6 :g 7 :h 8 :i 9})");
+            assert_eq!(
+                Value::Boolean(true),
+                repl.eval_str("(= {:a 1 :b 2} (dissoc m :c :d :e :f :g :h :i))")
+            );
+            assert_eq!(
+                Value::Boolean(false),
+                repl.eval_str("(= m (assoc m :i 10))")
+            );
+        }
+    }
+}
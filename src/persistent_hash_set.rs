@@ -0,0 +1,202 @@
+//! A persistent hash set;  like Clojure's, a PersistentHashMap of each of our values to
+//! itself, so that membership is a lookup in its trie, and we share its structure
+//!
+//!   #{:a :b} => {:a :a, :b :b}
+use crate::maps::{self, MapEntry};
+use crate::persistent_hash_map::{PersistentHashMap, PersistentHashMapIter};
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap};
+use crate::traits;
+use crate::value::Value;
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::rc::Rc;
+
+#[derive(Debug, Clone)]
+pub struct PersistentHashSet {
+    map: PersistentHashMap,
+}
+impl PersistentHashSet {
+    pub fn empty() -> PersistentHashSet {
+        PersistentHashSet {
+            map: PersistentHashMap::empty(),
+        }
+    }
+    pub fn count(&self) -> usize {
+        self.map.count()
+    }
+    pub fn contains(&self, val: &Rc<Value>) -> bool {
+        self.map.contains_key(val)
+    }
+    /// The value of ours equal to val, if there is one
+    pub fn get(&self, val: &Rc<Value>) -> Option<Rc<Value>> {
+        if self.contains(val) {
+            Some(self.map.get(val))
+        } else {
+            None
+        }
+    }
+    /// Us, with val
+    pub fn conj(&self, val: Rc<Value>) -> PersistentHashSet {
+        let mut set = self.clone();
+        set.conj_mut(val);
+        set
+    }
+    /// Adds val, in place;  see PersistentHashMap::assoc_mut
+    pub fn conj_mut(&mut self, val: Rc<Value>) {
+        if !self.contains(&val) {
+            self.map.assoc_mut(Rc::clone(&val), val);
+        }
+    }
+    /// Us, without val
+    pub fn disj(&self, val: &Rc<Value>) -> PersistentHashSet {
+        let mut set = self.clone();
+        set.disj_mut(val);
+        set
+    }
+    /// Takes val out, in place
+    pub fn disj_mut(&mut self, val: &Rc<Value>) {
+        self.map.dissoc_mut(val);
+    }
+    pub fn iter(&self) -> PersistentHashSetIter {
+        PersistentHashSetIter {
+            map_iter: self.map.iter(),
+        }
+    }
+}
+impl PartialEq for PersistentHashSet {
+    // Remember; meta doesn't factor into equality
+    fn eq(&self, other: &Self) -> bool {
+        self.count() == other.count() && self.iter().all(|val| other.contains(&val))
+    }
+}
+impl Eq for PersistentHashSet {}
+impl Hash for PersistentHashSet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hashed as our map, whose entries are just our values, twice
+        maps::hash_entries(self.map.iter()).hash(state)
+    }
+}
+impl FromIterator<Rc<Value>> for PersistentHashSet {
+    fn from_iter<I: IntoIterator<Item = Rc<Value>>>(iter: I) -> Self {
+        let mut set = PersistentHashSet::empty();
+        for val in iter {
+            set.conj_mut(val);
+        }
+        set
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Iterating
+//
+////////////////////////////////////////////////////////////////////////////////////////////////////
+pub struct PersistentHashSetIter {
+    map_iter: PersistentHashMapIter,
+}
+impl Iterator for PersistentHashSetIter {
+    type Item = Rc<Value>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.map_iter.next().map(|MapEntry { key, .. }| key)
+    }
+}
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// End Iteration
+////////////////////////////////////////////////////////////////////////////////////////////////////
+impl traits::IMeta for PersistentHashSet {
+    fn meta(&self) -> PersistentListMap {
+        self.map.meta()
+    }
+}
+impl traits::IObj for PersistentHashSet {
+    fn with_meta(&self, meta: PersistentListMap) -> PersistentHashSet {
+        PersistentHashSet {
+            map: self.map.with_meta(meta),
+        }
+    }
+}
+impl fmt::Display for PersistentHashSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let str = self
+            .iter()
+            .map(|val| val.to_string_explicit())
+            .collect::<Vec<String>>()
+            .join(" ");
+        write!(f, "#{{{}}}", str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod persistent_hash_set_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn sets_hold_each_value_once() {
+            let repl = Repl::default();
+            assert_eq!(Value::I64(3), repl.eval_str("(count (set [1 2 1 3 2]))"));
+            assert_eq!(Value::I64(2), repl.eval_str("(count (hash-set :a :b :a))"));
+            assert_eq!(Value::I64(2), repl.eval_str("(count (conj #{1 2} 2))"));
+            assert_eq!("#{}", repl.eval_str("(disj #{1} 1)").to_string());
+            assert_eq!("#{1}", repl.eval_str("(disj #{1 2 3} 2 3 4)").to_string());
+        }
+
+        #[test]
+        fn sets_are_equal_whatever_their_order() {
+            let repl = Repl::default();
+            assert_eq!(repl.eval_str("#{1 2 3}"), repl.eval_str("#{3 1 2}"));
+            assert_eq!(repl.eval_str("#{1 2 3}"), repl.eval_str("(set '(3 2 1 1))"));
+            assert_ne!(repl.eval_str("#{1 2 3}"), repl.eval_str("#{1 2}"));
+        }
+
+        #[test]
+        fn membership() {
+            let repl = Repl::default();
+            assert_eq!(Value::Boolean(true), repl.eval_str("(contains? #{1 2} 1)"));
+            assert_eq!(Value::Boolean(false), repl.eval_str("(contains? #{1 2} 3)"));
+            assert_eq!(Value::I64(2), repl.eval_str("(get #{1 2} 2)"));
+            assert_eq!(Value::Nil, repl.eval_str("(get #{1 2} 3)"));
+            assert_eq!(Value::I64(1), repl.eval_str("(#{1 2} 1)"));
+            assert_eq!(Value::Nil, repl.eval_str("(#{1 2} 3)"));
+            assert_eq!(
+                Value::Boolean(true),
+                repl.eval_str("(contains? {:a nil} :a)")
+            );
+            assert_eq!(Value::Boolean(true), repl.eval_str("(contains? [:a :b] 1)"));
+        }
+
+        #[test]
+        fn fns_are_members_as_themselves() {
+            let repl = Repl::default();
+            assert_eq!(
+                repl.eval_str("[true true 1]"),
+                repl.eval_str(
+                    "(let [f (fn [] 1)] [(= f f) (contains? #{f} f) (count (conj #{f} f))])"
+                )
+            );
+            assert_eq!(
+                Value::Boolean(false),
+                repl.eval_str("(= (fn [] 1) (fn [] 1))")
+            );
+            // Past 8 entries, a map is a hash map
+            repl.eval_str("(def f (fn [] 1))");
+            repl.eval_str("(def m (into {f :f} (map (fn [i] [i i]) (range 10))))");
+            assert_eq!(repl.eval_str(":f"), repl.eval_str("(get m f)"));
+        }
+
+        #[test]
+        fn duplicate_literal_values_are_an_error() {
+            let repl = Repl::default();
+            match Repl::read_string("#{1 2 1}") {
+                Value::Condition(error) => {
+                    assert!(error.is("reader/duplicate-key"));
+                    assert_eq!("Duplicate key: 1", error.message);
+                }
+                result => panic!("Expected an error, found {}", result),
+            }
+            assert_eq!(Value::I64(2), repl.eval_str("(count #{(inc 0) 2})"));
+        }
+    }
+}
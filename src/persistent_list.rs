@@ -4,16 +4,43 @@ use std::iter::FromIterator;
 use std::rc::Rc;
 
 use crate::persistent_list_map::PersistentListMap;
+use crate::protocols;
 use crate::traits;
 use crate::value::{ToValue, Value};
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
 
-#[derive(Debug, Clone, PartialEq, Hash)]
+#[derive(Debug, Clone)]
 pub enum PersistentList {
     // @TODO refactor i32 (our len) into a usize
-    Cons(Rc<Value>, Rc<PersistentList>, i32),
+    // The meta of a list is kept on its first cons
+    Cons(Rc<Value>, Rc<PersistentList>, i32, PersistentListMap),
     Empty,
 }
+impl PartialEq for PersistentList {
+    // Remember; meta doesn't factor into equality
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Cons(head, tail, count, _), Cons(head2, tail2, count2, _)) => {
+                count == count2 && head == head2 && tail == tail2
+            }
+            (Empty, Empty) => true,
+            _ => false,
+        }
+    }
+}
+impl Hash for PersistentList {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let heads = std::iter::successors(Some(self), |list| match list {
+            Cons(_, tail, _, _) => Some(&**tail),
+            Empty => None,
+        })
+        .filter_map(|list| match list {
+            Cons(head, _, _, _) => Some(head),
+            Empty => None,
+        });
+        protocols::seq_hash(heads, state)
+    }
+}
 // Experimental
 /// list!(sym!("+") 1 2);
 /// Meant to look closer to Clojure's native list syntax, to give us some Clojuresque sugar
@@ -50,10 +77,20 @@ struct EmptyHash {}
 
 use crate::persistent_list::PersistentList::{Cons, Empty};
 pub fn cons_rc(head: Rc<Value>, tail: Rc<PersistentList>) -> PersistentList {
-    if let Cons(_, _, old_count) = &*tail {
-        Cons(Rc::clone(&head), Rc::clone(&tail), old_count + 1)
+    if let Cons(_, _, old_count, _) = &*tail {
+        Cons(
+            Rc::clone(&head),
+            Rc::clone(&tail),
+            old_count + 1,
+            PersistentListMap::Empty,
+        )
     } else {
-        Cons(Rc::clone(&head), Rc::clone(&tail), 1)
+        Cons(
+            Rc::clone(&head),
+            Rc::clone(&tail),
+            1,
+            PersistentListMap::Empty,
+        )
     }
 }
 
@@ -64,7 +101,7 @@ pub fn cons(head: Value, tail: PersistentList) -> PersistentList {
 impl PersistentList {
     pub fn len(&self) -> i32 {
         match self {
-            Cons(_, _, count) => *count,
+            Cons(_, _, count, _) => *count,
             _ => 0,
         }
     }
@@ -100,20 +137,25 @@ impl ToPersistentList for Vec<Rc<Value>> {
 }
 impl traits::IMeta for PersistentList {
     fn meta(&self) -> PersistentListMap {
-        // @TODO implement
-        PersistentListMap::Empty
+        match self {
+            Cons(_, _, _, meta) => meta.clone(),
+            Empty => PersistentListMap::Empty,
+        }
     }
 }
 impl traits::IObj for PersistentList {
     fn with_meta(&self, meta: PersistentListMap) -> PersistentList {
-        // @TODO implement
-        self.clone()
+        match self {
+            Cons(head, tail, count, _) => Cons(Rc::clone(head), Rc::clone(tail), *count, meta),
+            // @TODO give () somewhere to keep meta
+            Empty => Empty,
+        }
     }
 }
 impl fmt::Display for PersistentList {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let str = match self {
-            Cons(head, tail, count) => {
+            Cons(head, tail, count, _) => {
                 let tail_str = tail
                     .iter()
                     .map(|rc_arg| rc_arg.to_string_explicit())
@@ -173,7 +215,7 @@ impl Iterator for PersistentListIter {
     type Item = Rc<Value>;
     fn next(&mut self) -> Option<Self::Item> {
         match &*(self.list.clone()) {
-            Cons(first, rest, _) => {
+            Cons(first, rest, _, _) => {
                 self.list = Rc::clone(&rest);
                 Some(Rc::clone(&first))
             }
@@ -194,7 +236,12 @@ impl FromIterator<Rc<Value>> for PersistentList {
         }
         for i in coll_as_vec.iter().rev() {
             count += 1;
-            retval = Cons(Rc::clone(i), Rc::new(retval), count);
+            retval = Cons(
+                Rc::clone(i),
+                Rc::new(retval),
+                count,
+                PersistentListMap::Empty,
+            );
         }
 
         retval
@@ -13,22 +13,40 @@
 //! a => {:a 1 :b 2}
 //! b => {:a 1 :b 3}
 
-use crate::maps::MapEntry;
+use crate::maps::{self, MapEntry};
 use crate::traits;
 use crate::value::Value;
 
 use std::collections::HashMap;
 use std::convert::From;
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::iter::FromIterator;
 use std::rc::Rc;
 
-#[derive(Debug, Clone, PartialEq, Hash)]
+#[derive(Debug, Clone)]
 pub enum PersistentListMap {
-    Map(Rc<PersistentListMap>, MapEntry),
+    // The meta of a map is kept on its latest entry
+    Map(
+        Rc<PersistentListMap>,
+        MapEntry,
+        Option<Rc<PersistentListMap>>,
+    ),
     Empty,
 }
+impl PartialEq for PersistentListMap {
+    // Remember; meta doesn't factor into equality, and neither does the order our entries
+    // were added in
+    fn eq(&self, other: &Self) -> bool {
+        maps::entries_equal(self.iter(), self.count(), other)
+    }
+}
 impl Eq for PersistentListMap {}
+impl Hash for PersistentListMap {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        maps::hash_entries(self.iter()).hash(state)
+    }
+}
 
 /// map_entry!("doc", "this is a docstring");
 #[macro_export]
@@ -103,12 +121,23 @@ pub trait IPersistentMap {
     fn get(&self, key: &Rc<Value>) -> Rc<Value>;
     fn assoc(&self, key: Rc<Value>, value: Rc<Value>) -> Self;
     fn contains_key(&self, key: &Rc<Value>) -> bool;
+    fn dissoc(&self, key: &Rc<Value>) -> Self;
+    fn count(&self) -> usize;
+}
+impl PersistentListMap {
+    /// Our entries, oldest first, with f applied to them, as a map with our meta
+    fn rebuild(&self, f: impl Fn(MapEntry) -> Option<MapEntry>) -> PersistentListMap {
+        let mut entries = self.iter().filter_map(f).collect::<Vec<MapEntry>>();
+        entries.reverse();
+        let meta = traits::IMeta::meta(self);
+        traits::IObj::with_meta(&entries.into_iter().collect::<PersistentListMap>(), meta)
+    }
 }
 impl IPersistentMap for PersistentListMap {
     // @TODO make fn of ILookup
     fn get(&self, key: &Rc<Value>) -> Rc<Value> {
         match self {
-            PersistentListMap::Map(parent, entry) => {
+            PersistentListMap::Map(parent, entry, _) => {
                 if entry.key == *key {
                     return Rc::clone(&entry.val);
                 }
@@ -118,11 +147,30 @@ impl IPersistentMap for PersistentListMap {
         }
     }
     fn assoc(&self, key: Rc<Value>, val: Rc<Value>) -> PersistentListMap {
-        PersistentListMap::Map(Rc::new(self.clone()), MapEntry { key, val })
+        // An existing key keeps its place, with its new val, rather than being shadowed
+        // by a new entry
+        if self.contains_key(&key) {
+            return self.rebuild(|entry| {
+                if entry.key == key {
+                    Some(MapEntry {
+                        key: Rc::clone(&key),
+                        val: Rc::clone(&val),
+                    })
+                } else {
+                    Some(entry)
+                }
+            });
+        }
+        // Like Clojure, we keep our meta through an assoc
+        let meta = match self {
+            PersistentListMap::Map(_, _, meta) => meta.clone(),
+            PersistentListMap::Empty => None,
+        };
+        PersistentListMap::Map(Rc::new(self.clone()), MapEntry { key, val }, meta)
     }
     fn contains_key(&self, key: &Rc<Value>) -> bool {
         match self {
-            PersistentListMap::Map(parent, entry) => {
+            PersistentListMap::Map(parent, entry, _) => {
                 if entry.key == *key {
                     return true;
                 }
@@ -131,37 +179,33 @@ impl IPersistentMap for PersistentListMap {
             PersistentListMap::Empty => false,
         }
     }
+    fn dissoc(&self, key: &Rc<Value>) -> PersistentListMap {
+        if !self.contains_key(key) {
+            return self.clone();
+        }
+        self.rebuild(|entry| if entry.key == *key { None } else { Some(entry) })
+    }
+    fn count(&self) -> usize {
+        self.iter().count()
+    }
 }
 
 impl IPersistentMap for Rc<PersistentListMap> {
     // @TODO make fn of ILookup
     fn get(&self, key: &Rc<Value>) -> Rc<Value> {
-        match &**self {
-            PersistentListMap::Map(parent, entry) => {
-                if entry.key == *key {
-                    return Rc::clone(&entry.val);
-                }
-                parent.get(key)
-            }
-            PersistentListMap::Empty => Rc::new(Value::Nil),
-        }
+        (**self).get(key)
     }
     fn assoc(&self, key: Rc<Value>, val: Rc<Value>) -> Rc<PersistentListMap> {
-        Rc::new(PersistentListMap::Map(
-            Rc::clone(self),
-            MapEntry { key, val },
-        ))
+        Rc::new((**self).assoc(key, val))
     }
     fn contains_key(&self, key: &Rc<Value>) -> bool {
-        match &**self {
-            PersistentListMap::Map(parent, entry) => {
-                if entry.key == *key {
-                    return true;
-                }
-                parent.contains_key(key)
-            }
-            PersistentListMap::Empty => false,
-        }
+        (**self).contains_key(key)
+    }
+    fn dissoc(&self, key: &Rc<Value>) -> Rc<PersistentListMap> {
+        Rc::new((**self).dissoc(key))
+    }
+    fn count(&self) -> usize {
+        (**self).count()
     }
 }
 
@@ -198,7 +242,7 @@ impl Iterator for PersistentListMapIter {
     type Item = MapEntry;
     fn next(&mut self) -> Option<Self::Item> {
         match &*(Rc::clone(&self.node)) {
-            PersistentListMap::Map(parent, mapentry) => {
+            PersistentListMap::Map(parent, mapentry, _) => {
                 self.node = Rc::clone(parent);
                 if self.seen.contains_key(&mapentry.key) {
                     return self.next();
@@ -230,7 +274,7 @@ impl FromIterator<MapEntry> for PersistentListMap {
         let mut map_so_far = PersistentListMap::Empty;
 
         for i in iter {
-            map_so_far = PersistentListMap::Map(Rc::new(map_so_far), i.clone());
+            map_so_far = PersistentListMap::Map(Rc::new(map_so_far), i.clone(), None);
         }
         map_so_far
     }
@@ -240,14 +284,21 @@ impl FromIterator<MapEntry> for PersistentListMap {
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 impl traits::IMeta for PersistentListMap {
     fn meta(&self) -> PersistentListMap {
-        // @TODO implement
-        PersistentListMap::Empty
+        match self {
+            PersistentListMap::Map(_, _, Some(meta)) => (**meta).clone(),
+            _ => PersistentListMap::Empty,
+        }
     }
 }
 impl traits::IObj for PersistentListMap {
     fn with_meta(&self, meta: PersistentListMap) -> PersistentListMap {
-        // @TODO implement
-        self.clone()
+        match self {
+            PersistentListMap::Map(parent, entry, _) => {
+                PersistentListMap::Map(Rc::clone(parent), entry.clone(), Some(Rc::new(meta)))
+            }
+            // @TODO give {} somewhere to keep meta
+            PersistentListMap::Empty => PersistentListMap::Empty,
+        }
     }
 }
 impl fmt::Display for PersistentListMap {
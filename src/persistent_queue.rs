@@ -0,0 +1,190 @@
+//! A persistent queue;  as in Clojure, a list we take values off the front of, and a
+//! vector we add values to the back of.  Once the front runs out, the back, in order,
+//! becomes the new front, so that conj, peek and pop are each O(1), or near enough
+//!
+//!   front        rear
+//!   (1 2)      [3 4 5]
+//! -------------------
+//! => #queue [1 2 3 4 5]
+use crate::persistent_list::{
+    PersistentList, PersistentListIter, ToPersistentList, ToPersistentListIter,
+};
+use crate::persistent_list_map::PersistentListMap;
+use crate::persistent_vector::{PersistentVector, PersistentVectorIter, ToPersistentVectorIter};
+use crate::protocols;
+use crate::traits;
+use crate::value::Value;
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::rc::Rc;
+
+#[derive(Debug, Clone)]
+pub struct PersistentQueue {
+    count: usize,
+    // Only ever empty when we are
+    front: Rc<PersistentList>,
+    rear: Rc<PersistentVector>,
+    meta: PersistentListMap,
+}
+impl PersistentQueue {
+    pub fn empty() -> PersistentQueue {
+        PersistentQueue {
+            count: 0,
+            front: Rc::new(PersistentList::Empty),
+            rear: Rc::new(PersistentVector::empty()),
+            meta: PersistentListMap::Empty,
+        }
+    }
+    pub fn count(&self) -> usize {
+        self.count
+    }
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+    /// Our first value, the next to be popped
+    pub fn peek(&self) -> Option<Rc<Value>> {
+        match &*self.front {
+            PersistentList::Cons(head, ..) => Some(Rc::clone(head)),
+            PersistentList::Empty => None,
+        }
+    }
+    /// Us, with val added to our back
+    pub fn conj(&self, val: Rc<Value>) -> PersistentQueue {
+        if self.is_empty() {
+            return PersistentQueue {
+                count: 1,
+                front: Rc::new(vec![val].into_list()),
+                ..self.clone()
+            };
+        }
+        PersistentQueue {
+            count: self.count + 1,
+            rear: Rc::new(self.rear.conj(val)),
+            ..self.clone()
+        }
+    }
+    /// Us, without our first value;  like Clojure, popping an empty queue gives it back
+    pub fn pop(&self) -> PersistentQueue {
+        let rest = match &*self.front {
+            PersistentList::Cons(_, rest, ..) => Rc::clone(rest),
+            PersistentList::Empty => return self.clone(),
+        };
+        if let PersistentList::Empty = &*rest {
+            return PersistentQueue {
+                count: self.count - 1,
+                front: Rc::new(self.rear.iter().collect::<PersistentList>()),
+                rear: Rc::new(PersistentVector::empty()),
+                meta: self.meta.clone(),
+            };
+        }
+        PersistentQueue {
+            count: self.count - 1,
+            front: rest,
+            ..self.clone()
+        }
+    }
+    pub fn iter(&self) -> PersistentQueueIter {
+        PersistentQueueIter {
+            front: self.front.iter(),
+            rear: self.rear.iter(),
+        }
+    }
+}
+impl PartialEq for PersistentQueue {
+    // Remember; meta doesn't factor into equality
+    fn eq(&self, other: &Self) -> bool {
+        self.count == other.count && self.iter().eq(other.iter())
+    }
+}
+impl Eq for PersistentQueue {}
+impl Hash for PersistentQueue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hashed as any list or vector of our values, which we're equal to
+        protocols::seq_hash(self.iter(), state)
+    }
+}
+impl FromIterator<Rc<Value>> for PersistentQueue {
+    fn from_iter<I: IntoIterator<Item = Rc<Value>>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(PersistentQueue::empty(), |queue, val| queue.conj(val))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Iterating
+//
+////////////////////////////////////////////////////////////////////////////////////////////////////
+pub struct PersistentQueueIter {
+    front: PersistentListIter,
+    rear: PersistentVectorIter,
+}
+impl Iterator for PersistentQueueIter {
+    type Item = Rc<Value>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.front.next().or_else(|| self.rear.next())
+    }
+}
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// End Iteration
+////////////////////////////////////////////////////////////////////////////////////////////////////
+impl traits::IMeta for PersistentQueue {
+    fn meta(&self) -> PersistentListMap {
+        self.meta.clone()
+    }
+}
+impl traits::IObj for PersistentQueue {
+    fn with_meta(&self, meta: PersistentListMap) -> PersistentQueue {
+        PersistentQueue {
+            meta,
+            ..self.clone()
+        }
+    }
+}
+impl fmt::Display for PersistentQueue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let str = self
+            .iter()
+            .map(|val| val.to_string_explicit())
+            .collect::<Vec<String>>()
+            .join(" ");
+        write!(f, "#queue [{}]", str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::persistent_queue::*;
+    use crate::value::ToValue;
+
+    fn val(i: i32) -> Rc<Value> {
+        i.to_rc_value()
+    }
+
+    #[test]
+    fn first_in_first_out() {
+        let mut queue = (0..100).map(val).collect::<PersistentQueue>();
+        let original = queue.clone();
+        for i in 0..100 {
+            assert_eq!(Some(val(i)), queue.peek());
+            queue = queue.pop();
+            // Adding as we go, between the front running out and the rear taking its place
+            if i % 10 == 0 {
+                queue = queue.conj(val(100 + i / 10));
+            }
+            assert!(queue.iter().eq((i + 1..100 + i / 10 + 1).map(val)));
+        }
+        assert_eq!(10, queue.count());
+        assert_eq!(100, original.count());
+        assert!(original.iter().eq((0..100).map(val)));
+    }
+
+    #[test]
+    fn popping_an_empty_queue() {
+        let queue = PersistentQueue::empty();
+        assert_eq!(None, queue.peek());
+        assert!(queue.pop().is_empty());
+        assert!(queue.conj(val(1)).pop().is_empty());
+    }
+}
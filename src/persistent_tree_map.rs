@@ -0,0 +1,575 @@
+//! A persistent sorted map;  a red-black tree, kept balanced as Okasaki does on insertion
+//! and Kahrs on deletion.  Each node is red or black, so that
+//!   - a red node never has a red child
+//!   - every path from the root down to a leaf passes through as many black nodes
+//!
+//! and so our longest path is at most twice our shortest.  Like our other persistent
+//! structures, an assoc or dissoc copies just the path down to its key, and shares the rest
+//!
+//!         (:b 2)B                   (:b 2)B
+//!         /     \        =>         /     \
+//!    (:a 1)R   (:c 3)R         (:a 1)R   (:c 4)R
+//!
+//!  (assoc m :c 4)
+use crate::compare::Comparator;
+use crate::maps::{self, MapEntry};
+use crate::persistent_list_map::PersistentListMap;
+use crate::traits;
+use crate::value::Value;
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Color {
+    Red,
+    Black,
+}
+use Color::*;
+
+#[derive(Debug)]
+struct Node {
+    color: Color,
+    left: Tree,
+    entry: MapEntry,
+    right: Tree,
+}
+// None for the empty tree, at our leaves
+type Tree = Option<Rc<Node>>;
+
+fn node(color: Color, left: Tree, entry: MapEntry, right: Tree) -> Tree {
+    Some(Rc::new(Node {
+        color,
+        left,
+        entry,
+        right,
+    }))
+}
+/// tree's root, if it's red
+fn red(tree: &Tree) -> Option<&Node> {
+    tree.as_deref().filter(|node| node.color == Red)
+}
+fn is_black(tree: &Tree) -> bool {
+    matches!(tree.as_deref(), Some(node) if node.color == Black)
+}
+/// tree, with its root black
+fn blacken(tree: Tree) -> Tree {
+    match tree.as_deref() {
+        Some(n) if n.color == Red => node(Black, n.left.clone(), n.entry.clone(), n.right.clone()),
+        _ => tree,
+    }
+}
+/// tree, a black node, made red;  a black path one shorter
+fn redden(tree: &Tree) -> Tree {
+    let n = tree.as_deref().expect("red-black tree lost its balance");
+    node(Red, n.left.clone(), n.entry.clone(), n.right.clone())
+}
+
+/// A black node of left, entry and right, with any red node that has a red child under
+/// it rotated up, and its children made black
+fn balance(left: Tree, entry: MapEntry, right: Tree) -> Tree {
+    if let (Some(l), Some(r)) = (red(&left), red(&right)) {
+        return node(
+            Red,
+            node(Black, l.left.clone(), l.entry.clone(), l.right.clone()),
+            entry,
+            node(Black, r.left.clone(), r.entry.clone(), r.right.clone()),
+        );
+    }
+    if let Some(l) = red(&left) {
+        if let Some(ll) = red(&l.left) {
+            return node(
+                Red,
+                node(Black, ll.left.clone(), ll.entry.clone(), ll.right.clone()),
+                l.entry.clone(),
+                node(Black, l.right.clone(), entry, right),
+            );
+        }
+        if let Some(lr) = red(&l.right) {
+            return node(
+                Red,
+                node(Black, l.left.clone(), l.entry.clone(), lr.left.clone()),
+                lr.entry.clone(),
+                node(Black, lr.right.clone(), entry, right),
+            );
+        }
+    }
+    if let Some(r) = red(&right) {
+        if let Some(rr) = red(&r.right) {
+            return node(
+                Red,
+                node(Black, left, entry, r.left.clone()),
+                r.entry.clone(),
+                node(Black, rr.left.clone(), rr.entry.clone(), rr.right.clone()),
+            );
+        }
+        if let Some(rl) = red(&r.left) {
+            return node(
+                Red,
+                node(Black, left, entry, rl.left.clone()),
+                rl.entry.clone(),
+                node(Black, rl.right.clone(), r.entry.clone(), r.right.clone()),
+            );
+        }
+    }
+    node(Black, left, entry, right)
+}
+/// A node of left, entry and right, where a delete has left left a black node short
+fn balance_left(left: Tree, entry: MapEntry, right: Tree) -> Tree {
+    if let Some(l) = red(&left) {
+        return node(
+            Red,
+            node(Black, l.left.clone(), l.entry.clone(), l.right.clone()),
+            entry,
+            right,
+        );
+    }
+    match right.as_deref() {
+        Some(r) if r.color == Black => balance(left, entry, redden(&right)),
+        Some(r) => {
+            let rl = r.left.as_deref().expect("red-black tree lost its balance");
+            node(
+                Red,
+                node(Black, left, entry, rl.left.clone()),
+                rl.entry.clone(),
+                balance(rl.right.clone(), r.entry.clone(), redden(&r.right)),
+            )
+        }
+        None => panic!("red-black tree lost its balance"),
+    }
+}
+/// A node of left, entry and right, where a delete has left right a black node short
+fn balance_right(left: Tree, entry: MapEntry, right: Tree) -> Tree {
+    if let Some(r) = red(&right) {
+        return node(
+            Red,
+            left,
+            entry,
+            node(Black, r.left.clone(), r.entry.clone(), r.right.clone()),
+        );
+    }
+    match left.as_deref() {
+        Some(l) if l.color == Black => balance(redden(&left), entry, right),
+        Some(l) => {
+            let lr = l.right.as_deref().expect("red-black tree lost its balance");
+            node(
+                Red,
+                balance(redden(&l.left), l.entry.clone(), lr.left.clone()),
+                lr.entry.clone(),
+                node(Black, lr.right.clone(), entry, right),
+            )
+        }
+        None => panic!("red-black tree lost its balance"),
+    }
+}
+/// left and right, everything in left coming before everything in right, as one tree;
+/// what's left of a node once its own entry is deleted
+fn append(left: &Tree, right: &Tree) -> Tree {
+    let (l, r) = match (left.as_deref(), right.as_deref()) {
+        (None, _) => return right.clone(),
+        (_, None) => return left.clone(),
+        (Some(l), Some(r)) => (l, r),
+    };
+    match (l.color, r.color) {
+        (Red, Red) => {
+            let middle = append(&l.right, &r.left);
+            match red(&middle) {
+                Some(m) => node(
+                    Red,
+                    node(Red, l.left.clone(), l.entry.clone(), m.left.clone()),
+                    m.entry.clone(),
+                    node(Red, m.right.clone(), r.entry.clone(), r.right.clone()),
+                ),
+                None => node(
+                    Red,
+                    l.left.clone(),
+                    l.entry.clone(),
+                    node(Red, middle, r.entry.clone(), r.right.clone()),
+                ),
+            }
+        }
+        (Black, Black) => {
+            let middle = append(&l.right, &r.left);
+            match red(&middle) {
+                Some(m) => node(
+                    Red,
+                    node(Black, l.left.clone(), l.entry.clone(), m.left.clone()),
+                    m.entry.clone(),
+                    node(Black, m.right.clone(), r.entry.clone(), r.right.clone()),
+                ),
+                None => balance_left(
+                    l.left.clone(),
+                    l.entry.clone(),
+                    node(Black, middle, r.entry.clone(), r.right.clone()),
+                ),
+            }
+        }
+        (_, Red) => node(Red, append(left, &r.left), r.entry.clone(), r.right.clone()),
+        (Red, _) => node(
+            Red,
+            l.left.clone(),
+            l.entry.clone(),
+            append(&l.right, right),
+        ),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PersistentTreeMap {
+    count: usize,
+    root: Tree,
+    comparator: Comparator,
+    meta: PersistentListMap,
+}
+impl PersistentTreeMap {
+    pub fn empty() -> PersistentTreeMap {
+        PersistentTreeMap::with_comparator(Comparator::Default)
+    }
+    pub fn with_comparator(comparator: Comparator) -> PersistentTreeMap {
+        PersistentTreeMap {
+            count: 0,
+            root: None,
+            comparator,
+            meta: PersistentListMap::Empty,
+        }
+    }
+    pub fn count(&self) -> usize {
+        self.count
+    }
+    /// How a compares to b, by our comparator;  that can fail, as keys of different sorts
+    /// don't compare, and a comparator fn can return an error
+    pub fn compare(&self, a: &Rc<Value>, b: &Rc<Value>) -> Result<Ordering, Rc<Value>> {
+        self.comparator.compare(a, b)
+    }
+    /// Our entry for key, if we have one
+    pub fn entry(&self, key: &Rc<Value>) -> Result<Option<MapEntry>, Rc<Value>> {
+        let mut tree = &self.root;
+        while let Some(node) = tree {
+            tree = match self.compare(key, &node.entry.key)? {
+                Ordering::Less => &node.left,
+                Ordering::Greater => &node.right,
+                Ordering::Equal => return Ok(Some(node.entry.clone())),
+            }
+        }
+        Ok(None)
+    }
+    pub fn get(&self, key: &Rc<Value>) -> Result<Option<Rc<Value>>, Rc<Value>> {
+        Ok(self.entry(key)?.map(|entry| entry.val))
+    }
+    pub fn contains_key(&self, key: &Rc<Value>) -> Result<bool, Rc<Value>> {
+        Ok(self.entry(key)?.is_some())
+    }
+    pub fn assoc(&self, key: Rc<Value>, val: Rc<Value>) -> Result<PersistentTreeMap, Rc<Value>> {
+        let mut added = false;
+        let root = self.insert(&self.root, key, val, &mut added)?;
+        Ok(PersistentTreeMap {
+            count: if added { self.count + 1 } else { self.count },
+            root: blacken(root),
+            // Like Clojure, we keep our meta through an assoc
+            ..self.clone()
+        })
+    }
+    fn insert(
+        &self,
+        tree: &Tree,
+        key: Rc<Value>,
+        val: Rc<Value>,
+        added: &mut bool,
+    ) -> Result<Tree, Rc<Value>> {
+        let n = match tree.as_deref() {
+            Some(n) => n,
+            None => {
+                *added = true;
+                return Ok(node(Red, None, MapEntry { key, val }, None));
+            }
+        };
+        Ok(match self.compare(&key, &n.entry.key)? {
+            Ordering::Less => {
+                let left = self.insert(&n.left, key, val, added)?;
+                match n.color {
+                    Black => balance(left, n.entry.clone(), n.right.clone()),
+                    Red => node(Red, left, n.entry.clone(), n.right.clone()),
+                }
+            }
+            Ordering::Greater => {
+                let right = self.insert(&n.right, key, val, added)?;
+                match n.color {
+                    Black => balance(n.left.clone(), n.entry.clone(), right),
+                    Red => node(Red, n.left.clone(), n.entry.clone(), right),
+                }
+            }
+            // Our key stays, with its new val
+            Ordering::Equal => node(
+                n.color,
+                n.left.clone(),
+                MapEntry {
+                    key: Rc::clone(&n.entry.key),
+                    val,
+                },
+                n.right.clone(),
+            ),
+        })
+    }
+    pub fn dissoc(&self, key: &Rc<Value>) -> Result<PersistentTreeMap, Rc<Value>> {
+        // Deleting assumes there's something to delete, or it would rebalance a path
+        // that was never short
+        if !self.contains_key(key)? {
+            return Ok(self.clone());
+        }
+        let root = self.delete(&self.root, key)?;
+        Ok(PersistentTreeMap {
+            count: self.count - 1,
+            root: blacken(root),
+            ..self.clone()
+        })
+    }
+    fn delete(&self, tree: &Tree, key: &Rc<Value>) -> Result<Tree, Rc<Value>> {
+        let n = match tree.as_deref() {
+            Some(n) => n,
+            None => return Ok(None),
+        };
+        Ok(match self.compare(key, &n.entry.key)? {
+            Ordering::Less => {
+                let left = self.delete(&n.left, key)?;
+                if is_black(&n.left) {
+                    balance_left(left, n.entry.clone(), n.right.clone())
+                } else {
+                    node(Red, left, n.entry.clone(), n.right.clone())
+                }
+            }
+            Ordering::Greater => {
+                let right = self.delete(&n.right, key)?;
+                if is_black(&n.right) {
+                    balance_right(n.left.clone(), n.entry.clone(), right)
+                } else {
+                    node(Red, n.left.clone(), n.entry.clone(), right)
+                }
+            }
+            Ordering::Equal => append(&n.left, &n.right),
+        })
+    }
+    /// Our first entry, by our order
+    pub fn first(&self) -> Option<MapEntry> {
+        self.iter().next()
+    }
+    /// Our last entry, by our order
+    pub fn last(&self) -> Option<MapEntry> {
+        self.rev_iter().next()
+    }
+    /// Our entries, in order
+    pub fn iter(&self) -> PersistentTreeMapIter {
+        let mut iter = PersistentTreeMapIter {
+            stack: vec![],
+            ascending: true,
+        };
+        iter.push_path(&self.root);
+        iter
+    }
+    /// Our entries, last first
+    pub fn rev_iter(&self) -> PersistentTreeMapIter {
+        let mut iter = PersistentTreeMapIter {
+            stack: vec![],
+            ascending: false,
+        };
+        iter.push_path(&self.root);
+        iter
+    }
+    /// Our entries from key on, in order if ascending, else in reverse;  including key
+    /// itself, if we have it
+    pub fn seek(
+        &self,
+        key: &Rc<Value>,
+        ascending: bool,
+    ) -> Result<PersistentTreeMapIter, Rc<Value>> {
+        let mut stack = vec![];
+        let mut tree = &self.root;
+        // Just as in push_path, we stack each node we step past on our way to key
+        while let Some(node) = tree {
+            let ordering = self.compare(&node.entry.key, key)?;
+            let before_key = if ascending {
+                ordering == Ordering::Less
+            } else {
+                ordering == Ordering::Greater
+            };
+            tree = match (before_key, ascending) {
+                (true, true) => &node.right,
+                (true, false) => &node.left,
+                (false, true) => {
+                    stack.push(Rc::clone(node));
+                    &node.left
+                }
+                (false, false) => {
+                    stack.push(Rc::clone(node));
+                    &node.right
+                }
+            };
+        }
+        Ok(PersistentTreeMapIter { stack, ascending })
+    }
+}
+impl PartialEq for PersistentTreeMap {
+    // Remember; meta doesn't factor into equality
+    fn eq(&self, other: &Self) -> bool {
+        self.count == other.count
+            && self
+                .iter()
+                .all(|entry| other.get(&entry.key) == Ok(Some(Rc::clone(&entry.val))))
+    }
+}
+impl Eq for PersistentTreeMap {}
+impl Hash for PersistentTreeMap {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        maps::hash_entries(self.iter()).hash(state)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Iterating
+//
+////////////////////////////////////////////////////////////////////////////////////////////////////
+pub struct PersistentTreeMapIter {
+    // The nodes whose entries are next, the next one on top;  each one's subtree on our
+    // side of it, we've already been through
+    stack: Vec<Rc<Node>>,
+    ascending: bool,
+}
+impl PersistentTreeMapIter {
+    /// Stacks tree's root, and each node down its side that comes first
+    fn push_path(&mut self, mut tree: &Tree) {
+        while let Some(node) = tree {
+            self.stack.push(Rc::clone(node));
+            tree = if self.ascending {
+                &node.left
+            } else {
+                &node.right
+            };
+        }
+    }
+}
+impl Iterator for PersistentTreeMapIter {
+    type Item = MapEntry;
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        if self.ascending {
+            self.push_path(&node.right);
+        } else {
+            self.push_path(&node.left);
+        }
+        Some(node.entry.clone())
+    }
+}
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// End Iteration
+////////////////////////////////////////////////////////////////////////////////////////////////////
+impl traits::IMeta for PersistentTreeMap {
+    fn meta(&self) -> PersistentListMap {
+        self.meta.clone()
+    }
+}
+impl traits::IObj for PersistentTreeMap {
+    fn with_meta(&self, meta: PersistentListMap) -> PersistentTreeMap {
+        PersistentTreeMap {
+            meta,
+            ..self.clone()
+        }
+    }
+}
+impl fmt::Display for PersistentTreeMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let entries = self
+            .iter()
+            .map(|mapentry| {
+                format!(
+                    "{} {}",
+                    mapentry.key.to_string_explicit(),
+                    mapentry.val.to_string_explicit()
+                )
+            })
+            .collect::<Vec<String>>();
+        write!(f, "{{{}}}", entries.join(", "))
+    }
+}
+#[cfg(test)]
+mod tests {
+    use crate::persistent_tree_map::*;
+    use crate::value::ToValue;
+
+    fn key(i: i32) -> Rc<Value> {
+        i.to_rc_value()
+    }
+
+    /// How many black nodes are on each path down tree, panicking should that differ,
+    /// or should a red node have a red child
+    fn black_height(tree: &Tree) -> usize {
+        match tree.as_deref() {
+            None => 1,
+            Some(node) => {
+                if node.color == Red {
+                    assert!(red(&node.left).is_none() && red(&node.right).is_none());
+                }
+                let height = black_height(&node.left);
+                assert_eq!(height, black_height(&node.right));
+                height + if node.color == Black { 1 } else { 0 }
+            }
+        }
+    }
+
+    #[test]
+    fn stays_balanced_and_in_order() {
+        let mut map = PersistentTreeMap::empty();
+        // Scattered, so that we insert on both sides of the tree
+        for i in 0..500 {
+            map = map.assoc(key((i * 37) % 500), key(i)).unwrap();
+            black_height(&map.root);
+        }
+        assert_eq!(500, map.count());
+        let keys = map
+            .iter()
+            .map(|entry| entry.key)
+            .collect::<Vec<Rc<Value>>>();
+        assert_eq!((0..500).map(key).collect::<Vec<Rc<Value>>>(), keys);
+
+        for i in (0..500).filter(|i| i % 3 != 0) {
+            map = map.dissoc(&key((i * 7) % 500)).unwrap();
+            black_height(&map.root);
+        }
+        assert_eq!((0..500).filter(|i| i % 3 == 0).count(), map.count());
+        assert_eq!(map.count(), map.iter().count());
+    }
+
+    #[test]
+    fn assoc_and_dissoc_leave_the_original_alone() {
+        let map1 = PersistentTreeMap::empty()
+            .assoc(key(1), key(1))
+            .unwrap()
+            .assoc(key(2), key(2))
+            .unwrap();
+        let map2 = map1.assoc(key(1), key(100)).unwrap();
+        let map3 = map1.dissoc(&key(2)).unwrap();
+        assert_eq!(Ok(Some(key(1))), map1.get(&key(1)));
+        assert_eq!(Ok(Some(key(100))), map2.get(&key(1)));
+        assert_eq!(2, map2.count());
+        assert_eq!(Ok(None), map3.get(&key(2)));
+        assert_eq!(Ok(Some(key(2))), map1.get(&key(2)));
+    }
+
+    #[test]
+    fn seek_starts_at_key() {
+        let map = (0..10).fold(PersistentTreeMap::empty(), |map, i| {
+            map.assoc(key(i * 2), key(i)).unwrap()
+        });
+        let from = |k: i32, ascending: bool| {
+            map.seek(&key(k), ascending)
+                .unwrap()
+                .map(|entry| entry.key)
+                .collect::<Vec<Rc<Value>>>()
+        };
+        assert_eq!(vec![key(14), key(16), key(18)], from(14, true));
+        assert_eq!(vec![key(16), key(18)], from(15, true));
+        assert_eq!(vec![key(2), key(0)], from(2, false));
+        assert_eq!(vec![key(2), key(0)], from(3, false));
+        assert!(from(19, true).is_empty());
+    }
+}
@@ -0,0 +1,133 @@
+//! A persistent sorted set;  like our PersistentHashSet over a PersistentHashMap,
+//! a PersistentTreeMap of each of our values to itself
+//!
+//!   (sorted-set 3 1 2) => #{1 2 3}
+use crate::compare::Comparator;
+use crate::maps::{self, MapEntry};
+use crate::persistent_list_map::PersistentListMap;
+use crate::persistent_tree_map::{PersistentTreeMap, PersistentTreeMapIter};
+use crate::traits;
+use crate::value::Value;
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersistentTreeSet {
+    map: PersistentTreeMap,
+}
+impl PersistentTreeSet {
+    pub fn empty() -> PersistentTreeSet {
+        PersistentTreeSet::with_comparator(Comparator::Default)
+    }
+    pub fn with_comparator(comparator: Comparator) -> PersistentTreeSet {
+        PersistentTreeSet {
+            map: PersistentTreeMap::with_comparator(comparator),
+        }
+    }
+    pub fn count(&self) -> usize {
+        self.map.count()
+    }
+    pub fn compare(&self, a: &Rc<Value>, b: &Rc<Value>) -> Result<Ordering, Rc<Value>> {
+        self.map.compare(a, b)
+    }
+    pub fn contains(&self, val: &Rc<Value>) -> Result<bool, Rc<Value>> {
+        self.map.contains_key(val)
+    }
+    /// The value of ours equal to val, if there is one
+    pub fn get(&self, val: &Rc<Value>) -> Result<Option<Rc<Value>>, Rc<Value>> {
+        Ok(self.map.entry(val)?.map(|entry| entry.key))
+    }
+    /// Us, with val
+    pub fn conj(&self, val: Rc<Value>) -> Result<PersistentTreeSet, Rc<Value>> {
+        if self.contains(&val)? {
+            return Ok(self.clone());
+        }
+        Ok(PersistentTreeSet {
+            map: self.map.assoc(Rc::clone(&val), val)?,
+        })
+    }
+    /// Us, without val
+    pub fn disj(&self, val: &Rc<Value>) -> Result<PersistentTreeSet, Rc<Value>> {
+        Ok(PersistentTreeSet {
+            map: self.map.dissoc(val)?,
+        })
+    }
+    pub fn first(&self) -> Option<Rc<Value>> {
+        self.map.first().map(|entry| entry.key)
+    }
+    pub fn last(&self) -> Option<Rc<Value>> {
+        self.map.last().map(|entry| entry.key)
+    }
+    /// Our values, in order
+    pub fn iter(&self) -> PersistentTreeSetIter {
+        PersistentTreeSetIter {
+            map_iter: self.map.iter(),
+        }
+    }
+    /// Our values, last first
+    pub fn rev_iter(&self) -> PersistentTreeSetIter {
+        PersistentTreeSetIter {
+            map_iter: self.map.rev_iter(),
+        }
+    }
+    /// Our values from val on, in order if ascending, else in reverse
+    pub fn seek(
+        &self,
+        val: &Rc<Value>,
+        ascending: bool,
+    ) -> Result<PersistentTreeSetIter, Rc<Value>> {
+        Ok(PersistentTreeSetIter {
+            map_iter: self.map.seek(val, ascending)?,
+        })
+    }
+}
+impl Eq for PersistentTreeSet {}
+impl Hash for PersistentTreeSet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // Hashed as our map, whose entries are just our values, twice;  so we hash as a
+        // PersistentHashSet of the same values does
+        maps::hash_entries(self.map.iter()).hash(state)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// Iterating
+//
+////////////////////////////////////////////////////////////////////////////////////////////////////
+pub struct PersistentTreeSetIter {
+    map_iter: PersistentTreeMapIter,
+}
+impl Iterator for PersistentTreeSetIter {
+    type Item = Rc<Value>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.map_iter.next().map(|MapEntry { key, .. }| key)
+    }
+}
+////////////////////////////////////////////////////////////////////////////////////////////////////
+// End Iteration
+////////////////////////////////////////////////////////////////////////////////////////////////////
+impl traits::IMeta for PersistentTreeSet {
+    fn meta(&self) -> PersistentListMap {
+        self.map.meta()
+    }
+}
+impl traits::IObj for PersistentTreeSet {
+    fn with_meta(&self, meta: PersistentListMap) -> PersistentTreeSet {
+        PersistentTreeSet {
+            map: self.map.with_meta(meta),
+        }
+    }
+}
+impl fmt::Display for PersistentTreeSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let str = self
+            .iter()
+            .map(|val| val.to_string_explicit())
+            .collect::<Vec<String>>()
+            .join(" ");
+        write!(f, "#{{{}}}", str)
+    }
+}
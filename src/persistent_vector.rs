@@ -1,33 +1,303 @@
+//! A persistent vector;  a 32-way bit-partitioned trie, as in Clojure.  The index of a
+//! value, 5 bits at a time, is the path to the leaf holding it, so finding it takes
+//! O(log32 n) steps, which for any vector that fits in memory is a handful.  The last
+//! (up to) 32 values are kept out of the trie, in a tail, so that conj and pop usually
+//! only copy the tail, and otherwise copy just the nodes on the way to it, sharing the
+//! rest with the vector they were made from
+//!
+//!               root                      tail
+//!           [ .    .  ]                [64 65 66]
+//!            /      \
+//!   [0 1 .. 31]   [32 33 .. 63]
+//! -------------------
+//! => [0 1 .. 66]
 use std::convert::From;
 use std::fmt;
 use std::fmt::Debug;
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
 use std::iter::FromIterator;
 use std::rc::Rc;
 
 use crate::persistent_list_map::PersistentListMap;
+use crate::protocols;
 use crate::traits;
 use crate::value::{ToValue, Value};
-#[derive(Debug, Clone, PartialEq, Hash)]
+
+/// How many bits of an index each level of the trie branches on
+const BITS: u32 = 5;
+const WIDTH: usize = 1 << BITS;
+const MASK: usize = WIDTH - 1;
+
+#[derive(Debug, Clone)]
+enum Node {
+    Branch(Vec<Rc<Node>>),
+    Leaf(Vec<Rc<Value>>),
+}
+impl Node {
+    fn children(&self) -> &Vec<Rc<Node>> {
+        match self {
+            Node::Branch(children) => children,
+            Node::Leaf(_) => panic!("A leaf of a PersistentVector was taken for a branch"),
+        }
+    }
+    fn children_mut(&mut self) -> &mut Vec<Rc<Node>> {
+        match self {
+            Node::Branch(children) => children,
+            Node::Leaf(_) => panic!("A leaf of a PersistentVector was taken for a branch"),
+        }
+    }
+    fn vals(&self) -> &Vec<Rc<Value>> {
+        match self {
+            Node::Leaf(vals) => vals,
+            Node::Branch(_) => panic!("A branch of a PersistentVector was taken for a leaf"),
+        }
+    }
+    /// node, shift bits up;  the chain of branches down to it
+    fn path(shift: u32, node: Rc<Node>) -> Rc<Node> {
+        if shift == 0 {
+            return node;
+        }
+        Rc::new(Node::Branch(vec![Node::path(shift - BITS, node)]))
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct PersistentVector {
-    pub vals: Vec<Rc<Value>>,
+    count: usize,
+    // How many bits of an index our root branches on, less BITS;  our leaves are at 0
+    shift: u32,
+    root: Rc<Node>,
+    tail: Rc<Vec<Rc<Value>>>,
+    pub meta: PersistentListMap,
+}
+impl PersistentVector {
+    pub fn empty() -> PersistentVector {
+        PersistentVector {
+            count: 0,
+            shift: BITS,
+            root: Rc::new(Node::Branch(vec![])),
+            tail: Rc::new(vec![]),
+            meta: PersistentListMap::Empty,
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.count
+    }
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+    /// The index of the first value in our tail;  every value before it is in our trie
+    fn tail_offset(&self) -> usize {
+        if self.count < WIDTH {
+            0
+        } else {
+            ((self.count - 1) >> BITS) << BITS
+        }
+    }
+    /// The values of the leaf, or tail, that holds ind
+    fn leaf_for(&self, ind: usize) -> &Vec<Rc<Value>> {
+        if ind >= self.tail_offset() {
+            return &self.tail;
+        }
+        let mut node = &self.root;
+        let mut shift = self.shift;
+        while shift > 0 {
+            node = &node.children()[(ind >> shift) & MASK];
+            shift -= BITS;
+        }
+        node.vals()
+    }
+    pub fn nth(&self, ind: usize) -> Option<&Rc<Value>> {
+        if ind >= self.count {
+            return None;
+        }
+        Some(&self.leaf_for(ind)[ind & MASK])
+    }
+    pub fn first(&self) -> Option<&Rc<Value>> {
+        self.nth(0)
+    }
+    pub fn last(&self) -> Option<&Rc<Value>> {
+        self.tail.last()
+    }
+    pub fn iter(&self) -> PersistentVectorIter {
+        Rc::new(self.clone()).iter()
+    }
+    pub fn to_vec(&self) -> Vec<Rc<Value>> {
+        self.iter().collect()
+    }
+    /// Us, with val added to our end
+    pub fn conj(&self, val: Rc<Value>) -> PersistentVector {
+        let mut pvector = self.clone();
+        pvector.conj_mut(val);
+        pvector
+    }
+    /// Adds val to our end, in place.  Like all our _mut fns, this edits a node in place
+    /// only if we're its one owner, as Rc::make_mut does, and otherwise edits a copy, so a
+    /// vector we share nodes with never sees the change;  our persistent fns are just these
+    /// on a clone of us, which shares every node, and so copies each one it touches
+    pub fn conj_mut(&mut self, val: Rc<Value>) {
+        // Room in our tail
+        if self.count - self.tail_offset() < WIDTH {
+            Rc::make_mut(&mut self.tail).push(val);
+            self.count += 1;
+            return;
+        }
+        // Otherwise our full tail goes into our trie, and val starts a new one
+        let tail = std::mem::replace(&mut self.tail, Rc::new(vec![val]));
+        let tail_node = Rc::new(Node::Leaf(
+            Rc::try_unwrap(tail).unwrap_or_else(|tail| (*tail).clone()),
+        ));
+        if (self.count >> BITS) > (1 << self.shift) {
+            // Our trie is full;  it gets a new root, one level up
+            self.root = Rc::new(Node::Branch(vec![
+                Rc::clone(&self.root),
+                Node::path(self.shift, tail_node),
+            ]));
+            self.shift += BITS;
+        } else {
+            PersistentVector::push_tail(self.count, self.shift, &mut self.root, tail_node);
+        }
+        self.count += 1;
+    }
+    /// Adds tail_node as the last leaf of node, shift bits up, in a vector of count values
+    fn push_tail(count: usize, shift: u32, node: &mut Rc<Node>, tail_node: Rc<Node>) {
+        let children = Rc::make_mut(node).children_mut();
+        let sub_ind = ((count - 1) >> shift) & MASK;
+        if shift == BITS {
+            children.push(tail_node);
+        } else if let Some(child) = children.get_mut(sub_ind) {
+            PersistentVector::push_tail(count, shift - BITS, child, tail_node);
+        } else {
+            children.push(Node::path(shift - BITS, tail_node));
+        }
+    }
+    /// Us, with the value at ind replaced by val;  ind can also be our count, in which
+    /// case val is added to our end.  None if ind is out of bounds
+    pub fn assoc_n(&self, ind: usize, val: Rc<Value>) -> Option<PersistentVector> {
+        let mut pvector = self.clone();
+        if pvector.assoc_n_mut(ind, val) {
+            Some(pvector)
+        } else {
+            None
+        }
+    }
+    /// Replaces the value at ind with val in place, or adds it to our end should ind be
+    /// our count;  false if ind is out of bounds
+    pub fn assoc_n_mut(&mut self, ind: usize, val: Rc<Value>) -> bool {
+        if ind == self.count {
+            self.conj_mut(val);
+            return true;
+        }
+        if ind > self.count {
+            return false;
+        }
+        if ind >= self.tail_offset() {
+            Rc::make_mut(&mut self.tail)[ind & MASK] = val;
+            return true;
+        }
+        PersistentVector::do_assoc(self.shift, &mut self.root, ind, val);
+        true
+    }
+    fn do_assoc(shift: u32, node: &mut Rc<Node>, ind: usize, val: Rc<Value>) {
+        match Rc::make_mut(node) {
+            Node::Leaf(vals) => vals[ind & MASK] = val,
+            Node::Branch(children) => PersistentVector::do_assoc(
+                shift - BITS,
+                &mut children[(ind >> shift) & MASK],
+                ind,
+                val,
+            ),
+        }
+    }
+    /// Us, without our last value;  None if we're empty
+    pub fn pop(&self) -> Option<PersistentVector> {
+        let mut pvector = self.clone();
+        if pvector.pop_mut() {
+            Some(pvector)
+        } else {
+            None
+        }
+    }
+    /// Removes our last value in place;  false if we're empty
+    pub fn pop_mut(&mut self) -> bool {
+        match self.count {
+            0 => return false,
+            1 => {
+                *self = PersistentVector {
+                    meta: self.meta.clone(),
+                    ..PersistentVector::empty()
+                };
+                return true;
+            }
+            _ => {}
+        }
+        // More than our last value in our tail
+        if self.count - self.tail_offset() > 1 {
+            Rc::make_mut(&mut self.tail).pop();
+            self.count -= 1;
+            return true;
+        }
+        // Otherwise our last leaf comes out of our trie, to be our new tail
+        self.tail = Rc::new(self.leaf_for(self.count - 2).clone());
+        if PersistentVector::pop_tail(self.count, self.shift, &mut self.root) {
+            self.root = Rc::new(Node::Branch(vec![]));
+        }
+        // A root with one branch is a level we no longer need
+        if self.shift > BITS && self.root.children().len() == 1 {
+            self.root = Rc::clone(&self.root.children()[0]);
+            self.shift -= BITS;
+        }
+        self.count -= 1;
+        true
+    }
+    /// Removes the last leaf of node, shift bits up, in a vector of count values;  true
+    /// should that leave it empty
+    fn pop_tail(count: usize, shift: u32, node: &mut Rc<Node>) -> bool {
+        let sub_ind = ((count - 2) >> shift) & MASK;
+        if shift > BITS {
+            let children = Rc::make_mut(node).children_mut();
+            if PersistentVector::pop_tail(count, shift - BITS, &mut children[sub_ind]) {
+                if sub_ind == 0 {
+                    return true;
+                }
+                children.truncate(sub_ind);
+            }
+            false
+        } else if sub_ind == 0 {
+            true
+        } else {
+            Rc::make_mut(node).children_mut().truncate(sub_ind);
+            false
+        }
+    }
+}
+impl PartialEq for PersistentVector {
+    // Remember; meta doesn't factor into equality
+    fn eq(&self, other: &Self) -> bool {
+        self.count == other.count && self.iter().eq(other.iter())
+    }
+}
+impl Hash for PersistentVector {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        protocols::seq_hash(self.iter(), state);
+    }
 }
 impl traits::IMeta for PersistentVector {
     fn meta(&self) -> PersistentListMap {
-        // @TODO implement
-        PersistentListMap::Empty
+        self.meta.clone()
     }
 }
 impl traits::IObj for PersistentVector {
     fn with_meta(&self, meta: PersistentListMap) -> PersistentVector {
-        // @TODO implement
-        self.clone()
+        PersistentVector {
+            meta,
+            ..self.clone()
+        }
     }
 }
 impl fmt::Display for PersistentVector {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let str = self
-            .vals
             .iter()
             .map(|rc_arg| rc_arg.to_string_explicit())
             .collect::<Vec<std::string::String>>()
@@ -41,12 +311,6 @@ impl From<Vec<Rc<Value>>> for PersistentVector {
         item.into_iter().collect::<PersistentVector>()
     }
 }
-// impl Hash for PersistentVector {
-//     fn hash<H: Hasher>(&self, state: &mut H) {
-// 	let as_vec = Rc::new(self.clone()).iter().collect::<Vec<Rc<Value>>>();
-// 	as_vec.hash(state)
-//     }
-// }
 //
 // Mostly to just make some code more concise
 // @TODO ~lookup proper rust conversion traits~
@@ -77,7 +341,6 @@ impl ToPersistentVectorIter for Rc<PersistentVector> {
             vector: Rc::clone(self),
             ind: 0,
         }
-        // self.vals.iter().map(|rc_ref| Rc::clone(rc_ref))
     }
 }
 pub struct PersistentVectorIter {
@@ -87,25 +350,93 @@ pub struct PersistentVectorIter {
 impl Iterator for PersistentVectorIter {
     type Item = Rc<Value>;
     fn next(&mut self) -> Option<Self::Item> {
-        let retval = (&*self.vector.vals)
-            .get(self.ind)
-            .map(|rc_val| Rc::clone(rc_val));
+        let retval = self.vector.nth(self.ind).map(Rc::clone);
         self.ind += 1;
         retval
     }
 }
 impl FromIterator<Rc<Value>> for PersistentVector {
-    //
-    // @TODO Surely we can just forward Vec's from_iter and wrap it
-    //
     fn from_iter<I: IntoIterator<Item = Rc<Value>>>(iter: I) -> Self {
-        // @TODO see if we can directly loop through our original iter backwards, and avoid
-        // dumping into this vector just to loop through again backwards
-        let mut coll_as_vec = vec![];
+        let mut pvector = PersistentVector::empty();
+        for val in iter {
+            pvector.conj_mut(val);
+        }
+        pvector
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::persistent_vector::*;
+
+    fn val(i: usize) -> Rc<Value> {
+        (i as i32).to_rc_value()
+    }
+
+    #[test]
+    fn conj_and_nth_across_levels() {
+        // Enough to need a trie three levels deep
+        let pvector = (0..40_000).map(val).collect::<PersistentVector>();
+        assert_eq!(40_000, pvector.len());
+        for i in (0..40_000).step_by(7) {
+            assert_eq!(Some(&val(i)), pvector.nth(i));
+        }
+        assert_eq!(None, pvector.nth(40_000));
+        assert_eq!(Some(&val(39_999)), pvector.last());
+        assert!(pvector.iter().eq((0..40_000).map(val)));
+    }
 
-        for i in iter {
-            coll_as_vec.push(i);
+    #[test]
+    fn assoc_n_shares_the_rest() {
+        let pvector = (0..2000).map(val).collect::<PersistentVector>();
+        let changed = pvector
+            .assoc_n(5, val(0))
+            .unwrap()
+            .assoc_n(1999, val(0))
+            .unwrap();
+        assert_eq!(Some(&val(0)), changed.nth(5));
+        assert_eq!(Some(&val(0)), changed.nth(1999));
+        assert_eq!(Some(&val(6)), changed.nth(6));
+        assert_eq!(Some(&val(5)), pvector.nth(5));
+        assert_eq!(
+            Some(&val(2000)),
+            pvector.assoc_n(2000, val(2000)).unwrap().nth(2000)
+        );
+        assert!(pvector.assoc_n(2001, val(0)).is_none());
+    }
+
+    #[test]
+    fn pop_back_down_to_empty() {
+        let mut pvector = (0..1100).map(val).collect::<PersistentVector>();
+        let original = pvector.clone();
+        for i in (0..1100).rev() {
+            assert_eq!(Some(&val(i)), pvector.last());
+            pvector = pvector.pop().unwrap();
+            assert_eq!(i, pvector.len());
+            assert!(pvector.iter().eq((0..i).map(val)));
+            // And we can still grow from wherever we've popped to
+            assert_eq!(Some(&val(7)), pvector.conj(val(7)).nth(i));
+        }
+        assert!(pvector.is_empty());
+        assert!(pvector.pop().is_none());
+        assert_eq!(1100, original.len());
+    }
+
+    #[test]
+    fn editing_in_place_leaves_sharers_alone() {
+        let original = (0..1100).map(val).collect::<PersistentVector>();
+        let mut pvector = original.clone();
+        for i in 0..1100 {
+            assert!(pvector.assoc_n_mut(i, val(i * 2)));
+        }
+        for i in 1100..1200 {
+            pvector.conj_mut(val(i * 2));
+        }
+        for _ in 0..150 {
+            assert!(pvector.pop_mut());
         }
-        PersistentVector { vals: coll_as_vec }
+        assert!(pvector.iter().eq((0..1050).map(|i| val(i * 2))));
+        assert!(original.iter().eq((0..1100).map(val)));
+        assert!(!pvector.assoc_n_mut(1051, val(0)));
     }
 }
@@ -0,0 +1,130 @@
+//! Where in its source a form was read;  the reader records this in the meta of the
+//! lists, vectors, maps and symbols it reads, as {:file "src/app/core.clj" :line 42 :column 7},
+//! so that an error can say where it happened
+use crate::keyword::Keyword;
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap};
+use crate::traits::IMeta;
+use crate::value::{ToValue, Value};
+use std::fmt;
+use std::rc::Rc;
+
+/// The file we say a form came from when it didn't come from one, as in Clojure
+pub const NO_SOURCE_FILE: &str = "NO_SOURCE_FILE";
+
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct Position {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+}
+impl Position {
+    pub fn new(file: &str, line: usize, column: usize) -> Position {
+        Position {
+            file: String::from(file),
+            line,
+            column,
+        }
+    }
+    /// {:file file :line line :column column}
+    pub fn to_meta(&self) -> PersistentListMap {
+        PersistentListMap::Empty
+            .assoc(
+                Keyword::intern("file").to_rc_value(),
+                Value::String(self.file.clone()).to_rc_value(),
+            )
+            .assoc(
+                Keyword::intern("line").to_rc_value(),
+                Value::I64(self.line as i64).to_rc_value(),
+            )
+            .assoc(
+                Keyword::intern("column").to_rc_value(),
+                Value::I64(self.column as i64).to_rc_value(),
+            )
+    }
+    /// The position recorded in meta, if there is one
+    pub fn from_meta(meta: &PersistentListMap) -> Option<Position> {
+        let get = |key: &str| meta.get(&Keyword::intern(key).to_rc_value());
+        let (line, column) = match (&*get("line"), &*get("column")) {
+            (Value::I64(line), Value::I64(column)) => (*line as usize, *column as usize),
+            _ => return None,
+        };
+        let file = match &*get("file") {
+            Value::String(file) => file.clone(),
+            _ => String::from(NO_SOURCE_FILE),
+        };
+        Some(Position { file, line, column })
+    }
+    /// Where form was read, if it's a form we record that for
+    pub fn of(form: &Value) -> Option<Position> {
+        let meta = match form {
+            Value::Symbol(sym) => sym.meta(),
+            Value::PersistentList(plist) => plist.meta(),
+            Value::PersistentVector(pvector) => pvector.meta(),
+            Value::PersistentListMap(plistmap) => plistmap.meta(),
+            Value::PersistentHashMap(phashmap) => phashmap.meta(),
+            Value::PersistentHashSet(phashset) => phashset.meta(),
+            _ => return None,
+        };
+        Position::from_meta(&meta)
+    }
+}
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+/// If result is an error that doesn't yet say where it happened, that error as having
+/// happened at form
+pub fn located(result: &Value, form: &Value) -> Option<Value> {
+    match result {
+        Value::Condition(error) if error.position.is_none() => {
+            Position::of(form).map(|position| Value::Condition(error.clone().at(position)))
+        }
+        _ => None,
+    }
+}
+
+/// result, or, should it be an error that doesn't yet say where it happened, that error as
+/// having happened at form.  Since we ask this on the way out of evaluating each form, an
+/// error ends up with the position of the innermost form that has one
+pub fn locate(result: Rc<Value>, form: &Value) -> Rc<Value> {
+    match located(&result, form) {
+        Some(located) => Rc::new(located),
+        None => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::reader::{read_source, Source};
+    use crate::repl::Repl;
+    use crate::value::Value;
+
+    fn eval_error(source: &str) -> String {
+        let repl = Repl::default();
+        let mut source_file = Source::new("src/app/core.clj");
+        let mut text = source.as_bytes();
+        let mut result = Value::Nil;
+        while !text.is_empty() {
+            result = repl.eval(&read_source(&mut text, &mut source_file));
+        }
+        match result {
+            Value::Condition(error) => error.to_string(),
+            _ => panic!("Expected an error, found {}", result),
+        }
+    }
+
+    #[test]
+    fn errors_say_where_they_happened() {
+        assert_eq!(
+            "src/app/core.clj:2:9: Undefined symbol foo",
+            eval_error("(let [a 1]\n  (+ a (foo a)))")
+        );
+        // In tail position, as here, the call is made after f has returned it
+        assert_eq!(
+            "src/app/core.clj:2:3: Index out of bounds: Index (5), Length: (1)",
+            eval_error("(defn f [x]\n  (nth [1] x))\n(f 5)")
+        );
+    }
+}
@@ -74,6 +74,30 @@ impl ProtocolCastable for Rc<Value> {
 // and having a clearer one like define_protocol(Iterable = A | B | C)
 #[macro_export]
 macro_rules! define_protocol {
+    // define_protocol!(Protocol = Nil | A | B), for a protocol nil also belongs to
+    ($protocol:ident = Nil | $($variant:ident) |*) => {
+        #[derive(Hash,PartialEq,Eq,Debug, Clone)]
+        pub struct $protocol {
+            value: Rc<Value>
+        }
+        impl $crate::protocol::Protocol for $protocol {
+            fn raw_wrap(val: &Rc<Value>) -> Self {
+                $protocol { value: Rc::clone(val) }
+            }
+            fn raw_unwrap(&self) -> Rc<Value> {
+                Rc::clone(&self.value)
+            }
+            fn instanceof(val: &Rc<Value>) -> bool {
+                match &**val {
+                    $crate::value::Value::Nil => true,
+                    $(
+                        $crate::value::Value::$variant(_) => true,
+                    )*
+                    _ => false
+                }
+            }
+        }
+    };
     // define_protocol!(Protocol = A | B)
     ($protocol:ident = $($variant:ident) |*) => {
         #[derive(Hash,PartialEq,Eq,Debug, Clone)]
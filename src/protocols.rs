@@ -9,3 +9,6 @@ pub use self::imeta::*;
 
 pub(crate) mod iobj;
 pub use self::iobj::*;
+
+pub(crate) mod iseq;
+pub use self::iseq::*;
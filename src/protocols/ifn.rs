@@ -1,15 +1,201 @@
+use crate::error_message;
 use crate::ifn;
+use crate::rust_core::get;
+use crate::type_tag::TypeTag;
 use crate::value::{ToValue, Value};
 use std::rc::Rc;
 
-// Let's keep it simple for now, but we will expand this
-define_protocol!(IFn, IFn);
+// Everything Clojure lets you call like a fn;  fns, but also keywords and symbols,
+// which look themselves up, collections, which look up what they're called with,
+// and vars, which call what they hold
+define_protocol!(
+    IFn = IFn
+        | Keyword
+        | Symbol
+        | PersistentListMap
+        | PersistentHashMap
+        | PersistentTreeMap
+        | PersistentHashSet
+        | PersistentTreeSet
+        | PersistentVector
+        | Var
+);
 
 impl ifn::IFn for IFn {
     fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
-        match &*self.value {
-            Value::IFn(ifn) => ifn.invoke(args),
-            _ => panic!("Called Iterable iter on non-iterable"),
+        invoke(&self.value, args)
+    }
+}
+
+/// Calls f on args, for any f of the IFn protocol;  (:a m) is (get m :a),  ({:a 1} :a)
+/// is (get {:a 1} :a),  ([:a :b] 1) is (nth [:a :b] 1), and so on
+pub fn invoke(f: &Value, args: Vec<Rc<Value>>) -> Value {
+    match f {
+        Value::IFn(ifn) => ifn.invoke(args),
+        // (:a m) or (:a m not-found)
+        Value::Keyword(_) | Value::Symbol(_) => {
+            if args.is_empty() || args.len() > 2 {
+                return error_message::wrong_varg_count(&[1, 2], args.len());
+            }
+            let key = Rc::new(f.clone());
+            lookup_or(&args[0], &key, args.get(1))
+        }
+        // ({:a 1} :a) or ({:a 1} :a not-found)
+        Value::PersistentListMap(_) | Value::PersistentHashMap(_) | Value::PersistentTreeMap(_) => {
+            if args.is_empty() || args.len() > 2 {
+                return error_message::wrong_varg_count(&[1, 2], args.len());
+            }
+            lookup_or(f, &args[0], args.get(1))
+        }
+        // (#{1 2} 1)
+        Value::PersistentHashSet(_) | Value::PersistentTreeSet(_) => {
+            if args.len() != 1 {
+                return error_message::wrong_arg_count(1, args.len());
+            }
+            lookup_or(f, &args[0], None)
+        }
+        // ([:a :b] 1)
+        Value::PersistentVector(pvector) => {
+            if args.len() != 1 {
+                return error_message::wrong_arg_count(1, args.len());
+            }
+            match &*args[0] {
+                Value::I64(ind) if *ind < 0 => error_message::index_cannot_be_negative(*ind),
+                Value::I64(ind) => match pvector.nth(*ind as usize) {
+                    Some(val) => val.to_value(),
+                    None => error_message::index_out_of_bounds(*ind as usize, pvector.len()),
+                },
+                _ => error_message::type_mismatch(TypeTag::Integer, &args[0]),
+            }
+        }
+        Value::Var(var) => {
+            let f = var.deref();
+            if f.short_circuits() {
+                return f.to_value();
+            }
+            invoke(&f, args)
+        }
+        _ => error_message::error(
+            "type-mismatch",
+            format!(
+                "Execution Error: {} cannot be cast to clojure.lang.IFn",
+                f.type_tag()
+            ),
+        ),
+    }
+}
+
+/// key in coll, as get finds it;  otherwise not_found, or nil
+fn lookup_or(coll: &Value, key: &Rc<Value>, not_found: Option<&Rc<Value>>) -> Value {
+    match get::lookup(coll, key) {
+        Ok(Some(val)) => val.to_value(),
+        Ok(None) => not_found.map_or(Value::Nil, |not_found| not_found.to_value()),
+        Err(condition) => condition.to_value(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod ifn_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn keywords_and_symbols_look_themselves_up() {
+            let repl = Repl::default();
+            assert_eq!(
+                "Cameron",
+                repl.eval_str("(:name {:name \"Cameron\"})").to_string()
+            );
+            assert_eq!(Value::Nil, repl.eval_str("(:age {:name \"Cameron\"})"));
+            assert_eq!(
+                Value::I64(30),
+                repl.eval_str("(:age {:name \"Cameron\"} 30)")
+            );
+            assert_eq!(Value::Nil, repl.eval_str("(:age {:age nil} 30)"));
+            assert_eq!(Value::I64(1), repl.eval_str("('a {'a 1})"));
+            assert_eq!(Value::Nil, repl.eval_str("(:a nil)"));
+            assert_eq!(
+                Value::Keyword(crate::keyword::Keyword::intern("a")),
+                repl.eval_str("(:a #{:a})")
+            );
+            assert!(matches!(repl.eval_str("(:a)"), Value::Condition(_)));
+        }
+
+        #[test]
+        fn collections_look_up_what_they_are_called_with() {
+            let repl = Repl::default();
+            assert_eq!(
+                "Cameron",
+                repl.eval_str("({:name \"Cameron\"} :name)").to_string()
+            );
+            assert_eq!(Value::I64(2), repl.eval_str("({:a 1} :b 2)"));
+            assert_eq!(Value::I64(1), repl.eval_str("((sorted-map :a 1) :a)"));
+            assert_eq!(Value::I64(1), repl.eval_str("(#{1 2} 1)"));
+            assert_eq!(Value::Nil, repl.eval_str("(#{1 2} 3)"));
+            assert_eq!(Value::I64(20), repl.eval_str("([10 20 30] 1)"));
+            assert_eq!(
+                "Index cannot be negative; Index (-1)",
+                match repl.eval_str("([1 2] -1)") {
+                    Value::Condition(condition) => condition.message.clone(),
+                    value => panic!("Expected an error, found {}", value),
+                }
+            );
+            for src in &["([10 20] 2)", "([10 20] -1)", "([10 20] :a)", "(#{1} 1 2)"] {
+                assert!(
+                    matches!(repl.eval_str(src), Value::Condition(_)),
+                    "Expected {} to be an error",
+                    src
+                );
+            }
+        }
+
+        #[test]
+        fn calling_them_matches_get() {
+            let repl = Repl::default();
+            for (called, get) in &[
+                ("(:a {:a 1})", "(get {:a 1} :a)"),
+                ("(:b {:a 1} 2)", "(get {:a 1} :b 2)"),
+                ("({:a 1} :b 2)", "(get {:a 1} :b 2)"),
+                ("({:a nil} :a 2)", "(get {:a nil} :a 2)"),
+                ("([1 2 3] 1)", "(get [1 2 3] 1)"),
+                ("(#{1} 2)", "(get #{1} 2)"),
+            ] {
+                assert_eq!(
+                    repl.eval_str(called),
+                    repl.eval_str(get),
+                    "{} vs {}",
+                    called,
+                    get
+                );
+            }
+            assert_eq!(Value::Nil, repl.eval_str("(get [1 2 3] 3)"));
+            assert_eq!(Value::I64(0), repl.eval_str("(get [1 2 3] -1 0)"));
+            assert!(matches!(repl.eval_str("(get {})"), Value::Condition(_)));
+        }
+
+        #[test]
+        fn vars_call_what_they_hold() {
+            let repl = Repl::default();
+            repl.eval_str("(defn twice [x] (* 2 x))");
+            assert_eq!(Value::I64(8), repl.eval_str("(#'twice 4)"));
+            assert_eq!(Value::I64(3), repl.eval_str("((var +) 1 2)"));
+        }
+
+        #[test]
+        fn passing_them_as_fns() {
+            let repl = Repl::default();
+            assert_eq!(
+                "(\"Cameron\" \"Ana\")",
+                repl.eval_str("(map :name [{:name \"Cameron\"} {:name \"Ana\"}])")
+                    .to_string_explicit()
+            );
+            assert_eq!("(:b :a)", repl.eval_str("(map [:a :b] [1 0])").to_string());
+            assert_eq!(
+                "(true nil)",
+                repl.eval_str("(map #{true} [true false])").to_string()
+            );
+            assert_eq!("(2 4)", repl.eval_str("(map #'inc [1 3])").to_string());
         }
     }
 }
@@ -13,6 +13,12 @@ define_protocol!(
             PersistentList     |
             PersistentVector   |
             PersistentListMap  |
+            PersistentHashMap  |
+            PersistentHashSet  |
+            PersistentTreeMap  |
+            PersistentTreeSet  |
+            PersistentQueue    |
+            Atom               |
             Symbol //             |
                    // IFn
 );
@@ -22,8 +28,14 @@ impl traits::IMeta for IMeta {
             Value::PersistentList(val) => val.meta(),
             Value::PersistentVector(val) => val.meta(),
             Value::PersistentListMap(val) => val.meta(),
+            Value::PersistentHashMap(val) => val.meta(),
+            Value::PersistentHashSet(val) => val.meta(),
+            Value::PersistentTreeMap(val) => val.meta(),
+            Value::PersistentTreeSet(val) => val.meta(),
+            Value::PersistentQueue(val) => val.meta(),
             Value::Symbol(val) => val.meta(),
             Value::Var(var) => var.meta(),
+            Value::Atom(atom) => atom.meta(),
             _ => panic!(
                 "protocols::IMeta was wrapping an invalid type {} when calling meta()",
                 self.value.type_tag()
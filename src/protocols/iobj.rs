@@ -6,8 +6,16 @@ use crate::value::{ToValue, Value};
 use std::rc::Rc;
 // TODO allow nullable protocols
 define_protocol!(
-    IObj = PersistentList | PersistentVector | PersistentListMap | Symbol //              |
-                                                                          // IFn
+    IObj = PersistentList
+        | PersistentVector
+        | PersistentListMap
+        | PersistentHashMap
+        | PersistentHashSet
+        | PersistentTreeMap
+        | PersistentTreeSet
+        | PersistentQueue
+        | Symbol //            |
+                 // IFn
 );
 impl traits::IMeta for IObj {
     fn meta(&self) -> PersistentListMap {
@@ -15,6 +23,11 @@ impl traits::IMeta for IObj {
             Value::PersistentList(val) => val.meta(),
             Value::PersistentVector(val) => val.meta(),
             Value::PersistentListMap(val) => val.meta(),
+            Value::PersistentHashMap(val) => val.meta(),
+            Value::PersistentHashSet(val) => val.meta(),
+            Value::PersistentTreeMap(val) => val.meta(),
+            Value::PersistentTreeSet(val) => val.meta(),
+            Value::PersistentQueue(val) => val.meta(),
             Value::Symbol(val) => val.meta(),
             _ => {
                 panic!(
@@ -36,6 +49,19 @@ impl traits::IObj for IObj {
             Value::PersistentListMap(val) => {
                 val.with_meta(meta).to_rc_value().as_protocol::<IObj>()
             }
+            Value::PersistentHashMap(val) => {
+                val.with_meta(meta).to_rc_value().as_protocol::<IObj>()
+            }
+            Value::PersistentHashSet(val) => {
+                val.with_meta(meta).to_rc_value().as_protocol::<IObj>()
+            }
+            Value::PersistentTreeMap(val) => {
+                val.with_meta(meta).to_rc_value().as_protocol::<IObj>()
+            }
+            Value::PersistentTreeSet(val) => {
+                val.with_meta(meta).to_rc_value().as_protocol::<IObj>()
+            }
+            Value::PersistentQueue(val) => val.with_meta(meta).to_rc_value().as_protocol::<IObj>(),
             Value::Symbol(val) => val.with_meta(meta).to_rc_value().as_protocol::<IObj>(),
             _ => {
                 panic!(
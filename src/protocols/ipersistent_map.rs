@@ -1,30 +1,96 @@
+use crate::maps::{self, MapEntry};
+use crate::persistent_hash_map::PersistentHashMapIter;
 use crate::persistent_list_map;
+use crate::persistent_list_map::{PersistentListMapIter, ToPersistentListMapIter};
 use crate::protocol::ProtocolCastable;
 use crate::value::{ToValue, Value};
 use std::rc::Rc;
 
-define_protocol!(IPersistentMap = PersistentListMap);
+define_protocol!(IPersistentMap = PersistentListMap | PersistentHashMap);
 
+impl IPersistentMap {
+    pub fn iter(&self) -> IPersistentMapIter {
+        match &*self.value {
+            Value::PersistentListMap(plist_map) => {
+                IPersistentMapIter::PersistentListMap(plist_map.iter())
+            }
+            Value::PersistentHashMap(phash_map) => {
+                IPersistentMapIter::PersistentHashMap(phash_map.iter())
+            }
+            _ => panic!("Called IPersistentMap iter on non-map"),
+        }
+    }
+}
 impl persistent_list_map::IPersistentMap for IPersistentMap {
     fn get(&self, key: &Rc<Value>) -> Rc<Value> {
         match &*self.value {
             Value::PersistentListMap(plist_map) => plist_map.get(key),
+            Value::PersistentHashMap(phash_map) => phash_map.get(key),
             _ => panic!("Called Iterable iter on non-iterable"),
         }
     }
     fn assoc(&self, key: Rc<Value>, value: Rc<Value>) -> IPersistentMap {
         match &*self.value {
+            // A list map that would grow past its threshold becomes a hash map
+            Value::PersistentListMap(plist_map)
+                if plist_map.count() >= maps::LIST_MAP_THRESHOLD
+                    && !plist_map.contains_key(&key) =>
+            {
+                maps::to_hash_map(plist_map)
+                    .assoc(key, value)
+                    .to_rc_value()
+                    .as_protocol::<IPersistentMap>()
+            }
             Value::PersistentListMap(plist_map) => plist_map
                 .assoc(key, value)
                 .to_rc_value()
                 .as_protocol::<IPersistentMap>(),
+            Value::PersistentHashMap(phash_map) => phash_map
+                .assoc(key, value)
+                .to_rc_value()
+                .as_protocol::<IPersistentMap>(),
             _ => panic!("Called Iterable iter on non-iterable"),
         }
     }
     fn contains_key(&self, key: &Rc<Value>) -> bool {
         match &*self.value {
             Value::PersistentListMap(plist_map) => plist_map.contains_key(key),
+            Value::PersistentHashMap(phash_map) => phash_map.contains_key(key),
             _ => panic!("Called Iterable iter on non-iterable"),
         }
     }
+    fn dissoc(&self, key: &Rc<Value>) -> IPersistentMap {
+        match &*self.value {
+            Value::PersistentListMap(plist_map) => plist_map
+                .dissoc(key)
+                .to_rc_value()
+                .as_protocol::<IPersistentMap>(),
+            Value::PersistentHashMap(phash_map) => phash_map
+                .dissoc(key)
+                .to_rc_value()
+                .as_protocol::<IPersistentMap>(),
+            _ => panic!("Called IPersistentMap dissoc on non-map"),
+        }
+    }
+    fn count(&self) -> usize {
+        match &*self.value {
+            Value::PersistentListMap(plist_map) => plist_map.count(),
+            Value::PersistentHashMap(phash_map) => phash_map.count(),
+            _ => panic!("Called IPersistentMap count on non-map"),
+        }
+    }
+}
+
+pub enum IPersistentMapIter {
+    PersistentListMap(PersistentListMapIter),
+    PersistentHashMap(PersistentHashMapIter),
+}
+impl Iterator for IPersistentMapIter {
+    type Item = MapEntry;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            IPersistentMapIter::PersistentListMap(plist_map_iter) => plist_map_iter.next(),
+            IPersistentMapIter::PersistentHashMap(phash_map_iter) => phash_map_iter.next(),
+        }
+    }
 }
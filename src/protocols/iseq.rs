@@ -0,0 +1,207 @@
+use crate::cons::Cons;
+use crate::iterable::Iterable;
+use crate::persistent_list::{cons_rc, PersistentList, ToPersistentList};
+use crate::persistent_vector::ToPersistentVector;
+use crate::protocol::ProtocolCastable;
+use crate::traits;
+use crate::traits::ISeq as _;
+use crate::value::{ToValue, Value};
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+define_protocol!(
+    ISeq = Nil
+        | PersistentList
+        | PersistentVector
+        | PersistentListMap
+        | PersistentHashMap
+        | PersistentHashSet
+        | PersistentTreeMap
+        | PersistentTreeSet
+        | PersistentQueue
+        | String
+        | LazySeq
+        | Cons
+);
+
+/// A list of vals, or nil if there are none
+fn seq_of(vals: Vec<Rc<Value>>) -> Rc<Value> {
+    if vals.is_empty() {
+        Rc::new(Value::Nil)
+    } else {
+        vals.into_list().to_rc_value()
+    }
+}
+
+impl traits::ISeq for ISeq {
+    fn seq(&self) -> Result<Rc<Value>, Rc<Value>> {
+        match &*self.value {
+            Value::Nil | Value::PersistentList(PersistentList::Empty) => Ok(Rc::new(Value::Nil)),
+            Value::PersistentList(_) | Value::Cons(_) => Ok(Rc::clone(&self.value)),
+            Value::PersistentVector(pvector) => Ok(seq_of(pvector.to_vec())),
+            // In Clojure, a map is a seq of [key val] entries
+            Value::PersistentListMap(_) | Value::PersistentHashMap(_) => Ok(seq_of(
+                self.value
+                    .as_protocol::<crate::protocols::IPersistentMap>()
+                    .iter()
+                    .map(|map_entry| vec![map_entry.key, map_entry.val].into_vector_value())
+                    .map(Rc::new)
+                    .collect(),
+            )),
+            Value::PersistentHashSet(phashset) => Ok(seq_of(phashset.iter().collect())),
+            // Sorted maps and sets are seqs in their order
+            Value::PersistentTreeMap(ptreemap) => Ok(seq_of(
+                ptreemap
+                    .iter()
+                    .map(|map_entry| vec![map_entry.key, map_entry.val].into_vector_value())
+                    .map(Rc::new)
+                    .collect(),
+            )),
+            Value::PersistentTreeSet(ptreeset) => Ok(seq_of(ptreeset.iter().collect())),
+            Value::PersistentQueue(pqueue) => Ok(seq_of(pqueue.iter().collect())),
+            Value::String(string) => Ok(seq_of(string.chars().map(|c| c.to_rc_value()).collect())),
+            Value::LazySeq(lazy_seq) => lazy_seq.seq(),
+            _ => panic!(
+                "protocols::ISeq was wrapping an invalid type {} when calling seq()",
+                self.value.type_tag()
+            ),
+        }
+    }
+    fn first(&self) -> Result<Rc<Value>, Rc<Value>> {
+        // No need to make a whole seq of a vector, just to get at its first
+        if let Value::PersistentVector(pvector) = &*self.value {
+            return Ok(pvector
+                .first()
+                .cloned()
+                .unwrap_or_else(|| Rc::new(Value::Nil)));
+        }
+        match &*self.seq()? {
+            Value::PersistentList(PersistentList::Cons(head, ..)) => Ok(Rc::clone(head)),
+            Value::Cons(cons) => Ok(cons.first()),
+            _ => Ok(Rc::new(Value::Nil)),
+        }
+    }
+    fn next(&self) -> Result<Rc<Value>, Rc<Value>> {
+        self.more()?.as_protocol::<ISeq>().seq()
+    }
+    fn more(&self) -> Result<Rc<Value>, Rc<Value>> {
+        match &*self.seq()? {
+            Value::PersistentList(PersistentList::Cons(_, tail, ..)) => {
+                Ok(Value::PersistentList((**tail).clone()).to_rc_value())
+            }
+            Value::Cons(cons) => match &*cons.more() {
+                Value::Nil => Ok(Value::PersistentList(PersistentList::Empty).to_rc_value()),
+                _ => Ok(cons.more()),
+            },
+            _ => Ok(Value::PersistentList(PersistentList::Empty).to_rc_value()),
+        }
+    }
+    fn cons(&self, x: Rc<Value>) -> Rc<Value> {
+        match &*self.value {
+            Value::Nil => {
+                Value::PersistentList(cons_rc(x, Rc::new(PersistentList::Empty))).to_rc_value()
+            }
+            Value::PersistentList(plist) => {
+                Value::PersistentList(cons_rc(x, Rc::new(plist.clone()))).to_rc_value()
+            }
+            _ => Value::Cons(Rc::new(Cons::new(x, Rc::clone(&self.value)))).to_rc_value(),
+        }
+    }
+}
+/// A seq's first value, and the seq after it
+type FirstAndMore = (Rc<Value>, Rc<Value>);
+
+impl ISeq {
+    /// Our first value and the seq after it, or None if we are empty
+    pub fn uncons(&self) -> Result<Option<FirstAndMore>, Rc<Value>> {
+        let seq = self.seq()?.as_protocol::<ISeq>();
+        if let Value::Nil = &*seq.value {
+            return Ok(None);
+        }
+        Ok(Some((seq.first()?, seq.more()?)))
+    }
+    /// Our values, one at a time.  Should realizing one fail, its error is the last thing
+    /// we give
+    pub fn iter(&self) -> SeqIter {
+        SeqIter {
+            rest: Some(Rc::clone(&self.value)),
+        }
+    }
+}
+
+pub struct SeqIter {
+    // What's left of the seq, or None once we're done
+    rest: Option<Rc<Value>>,
+}
+impl Iterator for SeqIter {
+    type Item = Result<Rc<Value>, Rc<Value>>;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.rest.take()?.as_protocol::<ISeq>().uncons() {
+            Ok(Some((first, more))) => {
+                self.rest = Some(more);
+                Some(Ok(first))
+            }
+            Ok(None) => None,
+            Err(error) => Some(Err(error)),
+        }
+    }
+}
+
+/// Whether two seqables have equal values, in the same order;  realizing, as far as
+/// they differ, any lazy seq among them.  An error doing so comes back out
+pub fn seq_equals(seq: &ISeq, seq2: &ISeq) -> Result<bool, Rc<Value>> {
+    let mut iter = seq.iter();
+    let mut iter2 = seq2.iter();
+    loop {
+        match (iter.next(), iter2.next()) {
+            (None, None) => return Ok(true),
+            (Some(val), Some(val2)) => {
+                if !equiv(&val?, &val2?)? {
+                    return Ok(false);
+                }
+            }
+            (Some(val), None) | (None, Some(val)) => {
+                val?;
+                return Ok(false);
+            }
+        }
+    }
+}
+
+/// (= a b);  a == b, but for an error realizing a lazy seq in either, which comes back
+/// out, rather than making them unequal
+pub fn equiv(a: &Rc<Value>, b: &Rc<Value>) -> Result<bool, Rc<Value>> {
+    if a.is_sequential() && b.is_sequential() {
+        return seq_equals(&a.as_protocol::<ISeq>(), &b.as_protocol::<ISeq>());
+    }
+    Ok(a == b)
+}
+
+/// Realizes every lazy seq in value, and in the collections within it;  first, as
+/// printing or hashing it would swallow any error in doing so, which we give back
+pub fn realize(value: &Rc<Value>) -> Result<(), Rc<Value>> {
+    match &**value {
+        Value::LazySeq(_) | Value::Cons(_) => {
+            for val in value.as_protocol::<ISeq>().iter() {
+                realize(&val?)?;
+            }
+            Ok(())
+        }
+        Value::String(_) => Ok(()),
+        _ => match value.try_as_protocol::<Iterable>() {
+            Some(coll) => coll.iter().try_for_each(|val| realize(&val)),
+            None => Ok(()),
+        },
+    }
+}
+
+/// Hashes the values of a sequential collection, in order;  the same for a list,
+/// vector, seq or queue of equal values, as any two of those are equal
+pub fn seq_hash<T: Hash, I: IntoIterator<Item = T>, H: Hasher>(vals: I, state: &mut H) {
+    let mut count = 0usize;
+    for val in vals {
+        val.hash(state);
+        count += 1;
+    }
+    count.hash(state);
+}
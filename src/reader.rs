@@ -1,14 +1,16 @@
 //! The reader.  The part that reads plain text and parses it into Clojure structures, which are
 //! themselves code.
 //!
-//! Right now there's no sort of data kept track by the reader at any point, so there's no real
-//! reader data structure here -- this is just a plain module, a bag of functions.  However,
-//! I believe this will change -- especially as, for instance, we define the idea of reader conditionals,
+//! Right now the only data kept track by the reader is where in its source it is, so that the
+//! forms it reads can say where they were read (see Source);  otherwise, this is
+//! just a plain module, a bag of functions.  However, I believe this will change -- especially as, for instance, we define the idea of reader conditionals,
 //! or even reader macros,  although the latter will likely be reserved for our interpreter here (but perhaps
 //! not;  since this is about being a 'free-er' Clojure, especially since it can't compete with it in raw
 //! power, neither speed or ecosystem,  it might be worth it to leave in reader macros.
 
 use nom::combinator::verify;
+use nom::error::ErrorKind;
+use nom::sequence::{terminated, tuple};
 use nom::{
     branch::alt, bytes::complete::tag, combinator::opt, map, sequence::preceded, take_until,
     Err::Incomplete, IResult,
@@ -16,17 +18,22 @@ use nom::{
 
 use crate::error_message;
 use crate::keyword::Keyword;
-use crate::maps::MapEntry;
+use crate::maps::{self, MapEntry};
+use crate::numbers::{self, Number};
+use crate::persistent_hash_set::PersistentHashSet;
 use crate::persistent_list::ToPersistentList;
-use crate::persistent_list_map::{PersistentListMap, ToPersistentListMap, ToPersistentListMapIter};
+use crate::persistent_list_map::{PersistentListMap, ToPersistentListMapIter};
+use crate::persistent_queue::PersistentQueue;
 use crate::persistent_vector::ToPersistentVector;
+use crate::position::{Position, NO_SOURCE_FILE};
 use crate::protocol::Protocol;
 use crate::protocol::ProtocolCastable;
 use crate::protocols;
 use crate::symbol::Symbol;
 use crate::traits::IMeta;
 use crate::traits::IObj;
-use crate::value::{ToValue, Value};
+use crate::value::{ToValue, Value, CHAR_NAMES};
+use std::cell::RefCell;
 use std::io::BufRead;
 use std::rc::Rc;
 //
@@ -35,7 +42,7 @@ use std::rc::Rc;
 //   symbol_parser
 //   integer_parser
 // And our 'try readers'
-//   try_read_i32
+//   try_read_number
 //   try_read_string
 //   try_read_map
 //   try_read_list
@@ -104,8 +111,10 @@ fn cons_str(head: char, tail: &str) -> String {
 ///   - `$`,
 ///   - `*`,
 ///   - `!`,
+///   - `#`, (but not at the start, so x# but not #x)
+///   - `'`, (but not at the start, so x' but not 'x)
 fn is_identifier_char(ch: char) -> bool {
-    ch.is_alphanumeric() || "|?<>+-_=^%&$*!.".contains(ch)
+    ch.is_alphanumeric() || "|?<>+-_=^%&$*!.#'".contains(ch)
 }
 
 /// Returns true if a character is an acceptable (non numeric) identifier char
@@ -331,11 +340,10 @@ fn integer_tail(input: &str) -> IResult<&str, &str> {
     nom::bytes::complete::take_while1(|c: char| c.is_digit(10))(input)
 }
 
-/// Parses valid integers
-/// Example Successes: 1, 2, 4153,  -12421
-///
-///
-pub fn integer_parser(input: &str) -> IResult<&str, i32> {
+/// Parses the digits of an integer, sign and all, as a string;  there may be too many of
+/// them for any one integer type
+/// Example Successes: 1, 2, 4153,  -12421, 1423152621625226126431525
+fn integer_digits_parser(input: &str) -> IResult<&str, String> {
     named!(integer_sign<&str, &str>,
        map!(
            opt!(take_while_m_n!(1, 1, is_minus_char)),
@@ -344,32 +352,85 @@ pub fn integer_parser(input: &str) -> IResult<&str, i32> {
     );
     // integer_tail<&str,&str> above function
 
-    named!(integer_parser <&str, String>,
+    named!(integer_digits_parser <&str, String>,
          do_parse!(
              sign: integer_sign >>
              rest_input: integer_tail >>
              (format!("{}{}",sign,rest_input))
          )
     );
-    integer_parser(input).map(|(rest, digits)| (rest, digits.parse().unwrap()))
+    integer_digits_parser(input)
 }
 
-/// Parses valid doubles
-/// Example Successes: -1.0, 0.023, 1234.3223423
+/// Parses valid integers, that fit in an i64
+/// Example Successes: 1, 2, 4153,  -12421
 ///
 ///
-pub fn double_parser(input: &str) -> IResult<&str, f64> {
+pub fn integer_parser(input: &str) -> IResult<&str, i64> {
+    let (rest_input, digits) = integer_digits_parser(input)?;
+    match digits.parse() {
+        Ok(integer) => Ok((rest_input, integer)),
+        Err(_) => Err(nom::Err::Error((input, ErrorKind::Digit))),
+    }
+}
+
+/// Parses the digits of a double, sign, decimal point and all, as a string
+/// Example Successes: -1.0, 0.023, 1234.3223423
+fn double_digits_parser(input: &str) -> IResult<&str, String> {
     named!(decimal_point<&str, &str>, take_while_m_n!(1, 1, is_period_char));
 
-    named!(double_parser <&str, String>,
+    named!(double_digits_parser <&str, String>,
          do_parse!(
-             integer: integer_parser >> //integer_part >>
+             integer: integer_digits_parser >> //integer_part >>
              point: complete!(decimal_point) >>
              decimal: integer_tail >> //decimal_part >>
              (format!("{}{}{}",integer, point, decimal))
          )
     );
-    double_parser(input).map(|(rest, digits)| (rest, digits.parse().unwrap()))
+    double_digits_parser(input)
+}
+
+/// Parses valid doubles
+/// Example Successes: -1.0, 0.023, 1234.3223423
+///
+///
+pub fn double_parser(input: &str) -> IResult<&str, f64> {
+    double_digits_parser(input).map(|(rest, digits)| (rest, digits.parse().unwrap()))
+}
+
+/// Parses any of our numbers;  an integer, as an i64 unless it's too big for one or ends
+/// in N, a ratio, a double, or a decimal ending in M
+/// Example Successes: 1, -12, 9223372036854775808, 1N, 1/3, -1.5, 1.5M, 2M
+pub fn number_parser(input: &str) -> IResult<&str, Number> {
+    fn ratio_parser(input: &str) -> IResult<&str, Number> {
+        let (rest_input, (numerator, _, denominator)) =
+            tuple((integer_digits_parser, tag("/"), integer_tail))(input)?;
+        let numerator = Number::integer(numerator.parse().unwrap());
+        let denominator = Number::integer(denominator.parse().unwrap());
+        match numbers::divide(&numerator, &denominator) {
+            Ok(ratio) => Ok((rest_input, ratio)),
+            // 1/0
+            Err(_) => Err(nom::Err::Error((input, ErrorKind::Verify))),
+        }
+    }
+    fn big_decimal_parser(input: &str) -> IResult<&str, Number> {
+        let (rest_input, digits) =
+            terminated(alt((double_digits_parser, integer_digits_parser)), tag("M"))(input)?;
+        Ok((rest_input, Number::BigDecimal(digits.parse().unwrap())))
+    }
+    fn big_int_parser(input: &str) -> IResult<&str, Number> {
+        let (rest_input, digits) = integer_digits_parser(input)?;
+        Ok((rest_input, Number::BigInt(digits.parse().unwrap())))
+    }
+    alt((
+        ratio_parser,
+        big_decimal_parser,
+        nom::combinator::map(double_parser, Number::F64),
+        terminated(big_int_parser, tag("N")),
+        nom::combinator::map(integer_parser, Number::I64),
+        // Too big to be an i64
+        big_int_parser,
+    ))(input)
 }
 // Currently used to create 'try_readers', which are readers (or
 // reader functions, at least) that are basically composable InputType
@@ -393,20 +454,21 @@ pub fn to_value_parser<I, O: ToValue>(
 //
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 
-// @TODO make sure whitespace or 'nothing' is at the end, fail for
-// float like numbers
-/// Tries to parse &str into Value::I32
+// @TODO make sure whitespace or 'nothing' is at the end
+/// Tries to parse &str into one of our numbers
 /// Expects:
-///   Integers
+///   Integers, ratios, doubles and decimals
 /// Example Successes:
-///    1 => Value::I32(1),
-///    5 => Value::I32(5),
-///    1231415 => Value::I32(1231415)
-///    -2 => Value::I32(-2)
-/// Example Failures:
-///    1.5,  7.1321 , 1423152621625226126431525
-pub fn try_read_i32(input: &str) -> IResult<&str, Value> {
-    to_value_parser(integer_parser)(input)
+///    1 => Value::I64(1),
+///    -2 => Value::I64(-2)
+///    9223372036854775808 => Value::BigInt(9223372036854775808)
+///    1N => Value::BigInt(1)
+///    1/3 => Value::Ratio(1/3)
+///    4/2 => Value::I64(2)
+///    1.5 => Value::F64(1.5)
+///    1.5M => Value::BigDecimal(1.5)
+pub fn try_read_number(input: &str) -> IResult<&str, Value> {
+    to_value_parser(number_parser)(input)
 }
 
 /// Tries to parse &str into Value::Boolean
@@ -421,12 +483,6 @@ pub fn try_read_bool(input: &str) -> IResult<&str, Value> {
     Ok((rest_input, Value::Boolean(bool.parse().unwrap())))
 }
 
-/// Tries to parse &str into Value::double
-///
-pub fn try_read_f64(input: &str) -> IResult<&str, Value> {
-    to_value_parser(double_parser)(input)
-}
-
 // Perhaps generalize this into reader macros
 /// Tries to parse &str into Value::Keyword
 /// Example Successes:
@@ -471,6 +527,44 @@ pub fn try_read_string(input: &str) -> IResult<&str, Value> {
     to_value_parser(string_parser)(input)
 }
 
+/// Tries to parse &str into Value::Char;  a backslash, and then the char itself, its
+/// name, or its unicode code point
+/// Example Successes:
+///    \a        => Value::Char('a')
+///    \é        => Value::Char('é')
+///    \(        => Value::Char('(')
+///    \newline  => Value::Char('\n')
+///    \space    => Value::Char(' ')
+///    \u00e9    => Value::Char('é')
+/// Example Failures:
+///    \ab,  \u12
+pub fn try_read_char(input: &str) -> IResult<&str, Value> {
+    let (rest_input, _) = tag("\\")(input)?;
+    // Whatever comes first is the char, even if it's something like ( or ;, but
+    // anything after is part of a name, as with \newline
+    let first = match rest_input.chars().next() {
+        Some(first) => first,
+        None => return Err(nom::Err::Error((input, ErrorKind::Char))),
+    };
+    let (rest_input, name) = identifier_tail(&rest_input[first.len_utf8()..])?;
+    let c = if name.is_empty() {
+        Some(first)
+    } else {
+        let name = cons_str(first, name);
+        match CHAR_NAMES.iter().find(|(named, _)| *named == name) {
+            Some((_, c)) => Some(*c),
+            None if first == 'u' && name.len() == 5 => u32::from_str_radix(&name[1..], 16)
+                .ok()
+                .and_then(std::char::from_u32),
+            None => None,
+        }
+    };
+    match c {
+        Some(c) => Ok((rest_input, Value::Char(c))),
+        None => Err(nom::Err::Error((input, ErrorKind::Char))),
+    }
+}
+
 pub fn try_read_pattern(input: &str) -> IResult<&str, Value> {
     named!(hash_parser<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("#")));
 
@@ -492,6 +586,16 @@ pub fn try_read_var(input: &str) -> IResult<&str, Value> {
     Ok((rest_input, list_val!(sym!("var") val)))
 }
 
+// Reads the @
+pub fn try_read_deref(input: &str) -> IResult<&str, Value> {
+    named!(deref_parser<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("@")));
+
+    let (rest_input, _) = deref_parser(input)?;
+    let (rest_input, val) = try_read(rest_input)?;
+    // @x is (deref x)
+    Ok((rest_input, list_val!(sym!("deref") val)))
+}
+
 // @TODO Perhaps generalize this, or even generalize it as a reader macro
 /// Tries to parse &str into Value::PersistentListMap, or some other Value::..Map
 /// Example Successes:
@@ -505,7 +609,7 @@ pub fn try_read_map(input: &str) -> IResult<&str, Value> {
     loop {
         let right_brace = rbracep(rest_input);
         if let Ok((after_map_input, _)) = right_brace {
-            return Ok((after_map_input, map_as_vec.into_list_map().to_value()));
+            return Ok((after_map_input, maps::map_of(map_as_vec)));
         }
         let (_rest_input, next_key) = try_read(rest_input)?;
         let (_rest_input, next_val) = try_read(_rest_input)?;
@@ -517,6 +621,56 @@ pub fn try_read_map(input: &str) -> IResult<&str, Value> {
     }
 }
 
+/// Tries to parse &str into Value::PersistentQueue
+/// Example Successes:
+///    #queue [1 2] => Value::PersistentQueue(PersistentQueue { .. })
+pub fn try_read_queue(input: &str) -> IResult<&str, Value> {
+    named!(queue_parser<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("#queue")));
+
+    let (rest_input, _) = queue_parser(input)?;
+    let (rest_input, vals) = try_read_vector(rest_input)?;
+    let queue = match vals {
+        Value::PersistentVector(pvector) => pvector
+            .to_vec()
+            .into_iter()
+            .collect::<PersistentQueue>()
+            .to_value(),
+        _ => unreachable!("try_read_vector read something other than a vector"),
+    };
+    Ok((rest_input, queue))
+}
+
+/// Tries to parse &str into Value::PersistentHashSet
+/// Example Successes:
+///    #{1 2} => Value::PersistentHashSet(PersistentHashSet { .. })
+/// A value given twice, as in #{1 1}, is an error, as it surely wasn't meant
+pub fn try_read_set(input: &str) -> IResult<&str, Value> {
+    named!(lsetp<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("#{")));
+    named!(rbracep<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("}")));
+    let (set_inner_input, _) = lsetp(input)?;
+    let mut set = PersistentHashSet::empty();
+    let mut duplicate = None;
+    let mut rest_input = set_inner_input;
+    loop {
+        if let Ok((after_set_input, _)) = rbracep(rest_input) {
+            let set = match duplicate {
+                Some(val) => {
+                    error_message::error("reader/duplicate-key", format!("Duplicate key: {}", val))
+                }
+                None => set.to_value(),
+            };
+            return Ok((after_set_input, set));
+        }
+        let (_rest_input, form) = try_read(rest_input)?;
+        let form = form.to_rc_value();
+        if set.contains(&form) && duplicate.is_none() {
+            duplicate = Some(Rc::clone(&form));
+        }
+        set = set.conj(form);
+        rest_input = _rest_input;
+    }
+}
+
 pub fn try_read_meta(input: &str) -> IResult<&str, Value> {
     named!(meta_start<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("^")));
     let (rest_input, _) = meta_start(input)?;
@@ -542,6 +696,9 @@ pub fn try_read_meta(input: &str) -> IResult<&str, Value> {
             meta = plist_map.clone();
             // Then we're already set
         }
+        Value::PersistentHashMap(phash_map) => {
+            meta = phash_map.iter().collect::<PersistentListMap>();
+        }
         _ => {
             // @TODO check instanceof IPersistentMap here instead
             // @TODO Clojure has basically this one off error here, but another thing we wish to do
@@ -561,12 +718,8 @@ pub fn try_read_meta(input: &str) -> IResult<&str, Value> {
         .to_rc_value()
         .try_as_protocol::<protocols::IObj>()
     {
-        // @TODO get actual line and column info
-        let line = 1;
-        let column = 1;
-        // @TODO merge the meta iobj_value *already* has
+        // Its position, as try_read gave it, is among the meta iobj_value already has
         // @TODO define some better macros and / or functions for map handling
-        meta = conj!(meta, map_entry!("line", line), map_entry!("column", column));
         meta = merge!(meta, iobj_value.meta());
         Ok((rest_input, iobj_value.with_meta(meta).unwrap().to_value()))
     } else {
@@ -576,9 +729,9 @@ pub fn try_read_meta(input: &str) -> IResult<&str, Value> {
 // @TODO use nom functions in place of macro
 /// Tries to parse &str into Value::PersistentVector
 /// Example Successes:
-///    [1 2 3] => Value::PersistentVector(PersistentVector { vals: [Rc(Value::I32(1) ... ]})
+///    [1 2 3] => Value::PersistentVector(PersistentVector { vals: [Rc(Value::I64(1) ... ]})
 ///    [1 2 [5 10 15] 3]
-///      => Value::PersistentVector(PersistentVector { vals: [Rc(Value::I32(1) .. Rc(Value::PersistentVector..)]})
+///      => Value::PersistentVector(PersistentVector { vals: [Rc(Value::I64(1) .. Rc(Value::PersistentVector..)]})
 pub fn try_read_vector(input: &str) -> IResult<&str, Value> {
     named!(lbracketp<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("[")));
     named!(rbracketp<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("]")));
@@ -628,27 +781,151 @@ pub fn try_read_quoted(input: &str) -> IResult<&str, Value> {
     Ok((rest_input, list_val!(sym!("quote") quoted_form_value)))
 }
 
+pub fn try_read_syntax_quoted(input: &str) -> IResult<&str, Value> {
+    named!(syntax_quote<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("`")));
+
+    let (form, _) = syntax_quote(input)?;
+
+    let (rest_input, quoted_form_value) = try_read(form)?;
+
+    // (syntax-quote value)
+    Ok((
+        rest_input,
+        list_val!(sym!("syntax-quote") quoted_form_value),
+    ))
+}
+
+pub fn try_read_unquoted(input: &str) -> IResult<&str, Value> {
+    named!(unquote<&str, &str>, preceded!(consume_clojure_whitespaces_parser, tag!("~")));
+    named!(splice<&str, &str>, tag!("@"));
+
+    let (form, _) = unquote(input)?;
+
+    // ~@value is (unquote-splicing value)
+    let (form, unquote_sym) = match splice(form) {
+        Ok((form, _)) => (form, Symbol::intern("unquote-splicing")),
+        Err(_) => (form, Symbol::intern("unquote")),
+    };
+
+    let (rest_input, unquoted_form_value) = try_read(form)?;
+
+    // (unquote value)
+    Ok((rest_input, list_val!(unquote_sym unquoted_form_value)))
+}
+
 pub fn try_read(input: &str) -> IResult<&str, Value> {
-    preceded(
-        consume_clojure_whitespaces_parser,
-        alt((
-            try_read_meta,
-            try_read_quoted,
-            try_read_nil,
-            try_read_map,
-            try_read_string,
-            try_read_f64,
-            try_read_i32,
-            try_read_bool,
-            try_read_nil,
-            try_read_symbol,
-            try_read_keyword,
-            try_read_list,
-            try_read_vector,
-            try_read_pattern,
-            try_read_var,
-        )),
-    )(input)
+    let (input, _) = consume_clojure_whitespaces_parser(input)?;
+    let (rest_input, form) = alt((
+        try_read_meta,
+        try_read_quoted,
+        try_read_syntax_quoted,
+        try_read_unquoted,
+        try_read_deref,
+        try_read_nil,
+        try_read_map,
+        try_read_string,
+        try_read_char,
+        try_read_number,
+        try_read_bool,
+        try_read_nil,
+        try_read_symbol,
+        try_read_keyword,
+        try_read_list,
+        try_read_vector,
+        try_read_set,
+        try_read_queue,
+        try_read_pattern,
+        try_read_var,
+    ))(input)?;
+    Ok((rest_input, with_position(form, input)))
+}
+
+/// form, with the position it was read at, input, in its meta;  if it's a form we record
+/// that for, we know where we're reading, and it doesn't already have one (as ^{:line 1} x does)
+fn with_position(form: Value, input: &str) -> Value {
+    let meta = match &form {
+        Value::Symbol(sym) => sym.meta(),
+        Value::PersistentList(plist) => plist.meta(),
+        Value::PersistentVector(pvector) => pvector.meta(),
+        Value::PersistentListMap(plistmap) => plistmap.meta(),
+        Value::PersistentHashMap(phashmap) => phashmap.meta(),
+        Value::PersistentHashSet(phashset) => phashset.meta(),
+        _ => return form,
+    };
+    if Position::from_meta(&meta).is_some() {
+        return form;
+    }
+    let position = match position_of(input) {
+        Some(position) => position,
+        None => return form,
+    };
+    let meta = merge!(position.to_meta(), meta);
+    match &form {
+        Value::Symbol(sym) => sym.with_meta(meta).to_value(),
+        Value::PersistentList(plist) => plist.with_meta(meta).to_value(),
+        Value::PersistentVector(pvector) => pvector.with_meta(meta).to_value(),
+        Value::PersistentListMap(plistmap) => plistmap.with_meta(meta).to_value(),
+        Value::PersistentHashMap(phashmap) => phashmap.with_meta(meta).to_value(),
+        Value::PersistentHashSet(phashset) => phashset.with_meta(meta).to_value(),
+        _ => form,
+    }
+}
+
+/// Where the text we read comes from, and the line we've read up to in it
+pub struct Source {
+    pub file: String,
+    /// The line the next form we read starts on
+    pub line: usize,
+}
+impl Source {
+    pub fn new(file: &str) -> Source {
+        Source {
+            file: String::from(file),
+            line: 1,
+        }
+    }
+}
+
+// The text we are in the middle of reading, if we know where it comes from
+struct SourceText {
+    file: String,
+    text: String,
+    first_line: usize,
+}
+thread_local! {
+    static SOURCE_TEXT: RefCell<Option<SourceText>> = const { RefCell::new(None) };
+}
+
+/// The position of input, which is what's left of the text we're reading
+fn position_of(input: &str) -> Option<Position> {
+    SOURCE_TEXT.with(|source_text| {
+        let source_text = source_text.borrow();
+        let source_text = source_text.as_ref()?;
+        // Our parsers only ever consume from the front, so input is a suffix of the text
+        let offset = source_text.text.len().checked_sub(input.len())?;
+        let read = source_text.text.get(..offset)?;
+        let line_start = read.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        Some(Position::new(
+            &source_text.file,
+            source_text.first_line + read.matches('\n').count(),
+            read[line_start..].chars().count() + 1,
+        ))
+    })
+}
+
+/// try_read, giving what we read the positions they were read at in source, where text
+/// starts at source.line
+fn try_read_source<'a>(text: &'a str, source: &Source) -> IResult<&'a str, Value> {
+    SOURCE_TEXT.with(|source_text| {
+        *source_text.borrow_mut() = Some(SourceText {
+            file: source.file.clone(),
+            text: String::from(text),
+            first_line: source.line,
+        })
+    });
+    let read = try_read(text);
+    SOURCE_TEXT.with(|source_text| *source_text.borrow_mut() = None);
+    read
 }
 ////////////////////////////////////////////////////////////////////////////////////////////////////
 //      End Try-Readers
@@ -662,6 +939,12 @@ pub fn try_read(input: &str) -> IResult<&str, Value> {
 
 // This is the high level read function that Clojure RS wraps
 pub fn read<R: BufRead>(reader: &mut R) -> Value {
+    read_source(reader, &mut Source::new(NO_SOURCE_FILE))
+}
+
+/// Reads the next form from reader, which reads source;  what we read says where in source
+/// it was read, and source is kept up to date with the line we've read up to
+pub fn read_source<R: BufRead>(reader: &mut R, source: &mut Source) -> Value {
     // This is a buffer that will accumulate if a read requires more
     // text to make sense, such as trying to read (+ 1
     let mut input_buffer = String::new();
@@ -672,28 +955,32 @@ pub fn read<R: BufRead>(reader: &mut R) -> Value {
         let maybe_line = reader.by_ref().lines().next();
 
         match maybe_line {
-            Some(Err(e)) => return Value::Condition(format!("Reader error: {}", e)),
+            Some(Err(e)) => {
+                return error_message::error("reader/error", format!("Reader error: {}", e))
+            }
             // `lines` does not include \n,  but \n is part of the whitespace given to the reader
             // (and is important for reading comments) so we will push a newline as well
             Some(Ok(line)) => {
                 input_buffer.push_str(&line);
                 input_buffer.push_str("\n");
             }
-            None => {
-                return Value::Condition(String::from("Tried to read empty stream; unexpected EOF"))
-            }
+            None => return error_message::reader_eof(),
         }
 
-        let line_read = try_read(&input_buffer);
+        let line_read = try_read_source(&input_buffer, source);
         match line_read {
-            Ok((_, value)) => return value,
+            Ok((_, value)) => {
+                // What's left of the last line is dropped, so the next form starts on the next line
+                source.line += input_buffer.matches('\n').count();
+                return value;
+            }
             // Continue accumulating more input
             Err(Incomplete(_)) => continue,
             Err(err) => {
-                return Value::Condition(format!(
-                    "Reader Error: could not read next form; {:?}",
-                    err
-                ))
+                return error_message::error(
+                    "reader/error",
+                    format!("Reader Error: could not read next form; {:?}", err),
+                )
             }
         }
     }
@@ -884,6 +1171,7 @@ mod tests {
     mod try_read_tests {
         use crate::keyword::Keyword;
         use crate::persistent_list;
+        use crate::persistent_list::ToPersistentList;
         use crate::persistent_list_map;
         use crate::persistent_list_map::IPersistentMap;
         use crate::persistent_vector;
@@ -934,17 +1222,60 @@ mod tests {
 
         #[test]
         fn try_read_int_test() {
-            assert_eq!(Value::I32(1), try_read("1 ").ok().unwrap().1);
+            assert_eq!(Value::I64(1), try_read("1 ").ok().unwrap().1);
         }
 
         #[test]
         fn try_read_negative_int_test() {
-            assert_eq!(Value::I32(-1), try_read("-1 ").ok().unwrap().1);
+            assert_eq!(Value::I64(-1), try_read("-1 ").ok().unwrap().1);
         }
 
         #[test]
         fn try_read_negative_int_with_second_dash_test() {
-            assert_eq!(Value::I32(-1), try_read("-1-2 ").ok().unwrap().1);
+            assert_eq!(Value::I64(-1), try_read("-1-2 ").ok().unwrap().1);
+        }
+
+        #[test]
+        fn try_read_char_test() {
+            assert_eq!(Value::Char('a'), try_read("\\a ").ok().unwrap().1);
+            assert_eq!(Value::Char('\n'), try_read("\\newline)").ok().unwrap().1);
+            assert_eq!(Value::Char(')'), try_read("\\))").ok().unwrap().1);
+            assert_eq!((")", Value::Char('é')), try_read("\\u00e9)").ok().unwrap());
+            assert!(try_read("\\ab ").is_err());
+            assert!(try_read("\\u12 ").is_err());
+        }
+
+        #[test]
+        fn try_read_i64_test() {
+            assert_eq!(
+                Value::I64(i64::MIN),
+                try_read("-9223372036854775808 ").ok().unwrap().1
+            );
+        }
+
+        #[test]
+        fn try_read_bigger_than_an_i64_test() {
+            let read = try_read("9223372036854775808 ").ok().unwrap().1;
+            assert!(matches!(read, Value::BigInt(_)));
+            assert_eq!("9223372036854775808N", read.to_string());
+        }
+
+        #[test]
+        fn try_read_other_numbers_test() {
+            for (src, printed) in &[
+                ("1N ", "1N"),
+                ("-12N ", "-12N"),
+                ("1/3 ", "1/3"),
+                ("-2/6 ", "-1/3"),
+                ("4/2 ", "2"),
+                ("1.5M ", "1.5M"),
+                ("-0.10M ", "-0.10M"),
+                ("2M ", "2M"),
+                ("1.5 ", "1.5"),
+            ] {
+                assert_eq!(*printed, try_read(src).ok().unwrap().1.to_string());
+            }
+            assert!(matches!(try_read("4/2 ").ok().unwrap().1, Value::I64(2)));
         }
 
         #[test]
@@ -982,7 +1313,7 @@ mod tests {
         #[test]
         fn try_read_empty_vector_test() {
             assert_eq!(
-                PersistentVector(persistent_vector::PersistentVector { vals: [].to_vec() }),
+                PersistentVector(persistent_vector::PersistentVector::from(vec![])),
                 try_read("[] ").ok().unwrap().1
             );
         }
@@ -996,6 +1327,23 @@ mod tests {
         fn try_read_bool_false_test() {
             assert_eq!(Value::Boolean(false), try_read("false ").ok().unwrap().1)
         }
+
+        #[test]
+        fn try_read_syntax_quoted_test() {
+            let unquoted = list_val!(sym!("unquote") sym!("b"));
+            let spliced = list_val!(sym!("unquote-splicing") sym!("c"));
+            let template = vec![
+                sym!("a").to_rc_value(),
+                unquoted.to_rc_value(),
+                spliced.to_rc_value(),
+                sym!("d#").to_rc_value(),
+            ]
+            .into_list();
+            assert_eq!(
+                list_val!(sym!("syntax-quote") template),
+                try_read("`(a ~b ~@c d#) ").ok().unwrap().1
+            );
+        }
         #[test]
         fn try_read_meta_symbol() {
             let with_meta = "^cat a";
@@ -1038,14 +1386,14 @@ mod tests {
                         .meta()
                         .contains_key(&Keyword::intern("cat").to_rc_value()));
                     assert_eq!(
-                        Value::I32(1),
+                        Value::I64(1),
                         *symbol.meta().get(&Keyword::intern("cat").to_rc_value())
                     );
                     assert!(symbol
                         .meta()
                         .contains_key(&Keyword::intern("dog").to_rc_value()));
                     assert_eq!(
-                        Value::I32(2),
+                        Value::I64(2),
                         *symbol.meta().get(&Keyword::intern("dog").to_rc_value())
                     );
                     assert!(!symbol
@@ -1249,4 +1597,50 @@ mod tests {
             assert_eq!(false, is_clojure_whitespace('a'));
         }
     }
+
+    mod read_source_tests {
+        use crate::keyword::Keyword;
+        use crate::persistent_list::ToPersistentListIter;
+        use crate::persistent_list_map::IPersistentMap;
+        use crate::position::Position;
+        use crate::reader::{read_source, Source};
+        use crate::value::{ToValue, Value};
+        use std::rc::Rc;
+
+        #[test]
+        fn read_source_records_positions() {
+            let mut source = Source::new("core.clj");
+            let mut text = "(foo\n  [a {:b c}])\n\n  bar".as_bytes();
+
+            let form = read_source(&mut text, &mut source);
+            assert_eq!(Some(Position::new("core.clj", 1, 1)), Position::of(&form));
+            let items = match &form {
+                Value::PersistentList(plist) => Rc::new(plist.clone()).iter().collect::<Vec<_>>(),
+                _ => panic!("Expected a list, found {}", form),
+            };
+            assert_eq!(
+                Some(Position::new("core.clj", 1, 2)),
+                Position::of(&items[0])
+            );
+            assert_eq!(
+                Some(Position::new("core.clj", 2, 3)),
+                Position::of(&items[1])
+            );
+            let map = match &*items[1] {
+                Value::PersistentVector(pvector) => Rc::clone(pvector.nth(1).unwrap()),
+                _ => panic!("Expected a vector, found {}", items[1]),
+            };
+            assert_eq!(Some(Position::new("core.clj", 2, 6)), Position::of(&map));
+            let c = match &*map {
+                Value::PersistentListMap(plistmap) => {
+                    plistmap.get(&Keyword::intern("b").to_rc_value())
+                }
+                _ => panic!("Expected a map, found {}", map),
+            };
+            assert_eq!(Some(Position::new("core.clj", 2, 10)), Position::of(&c));
+
+            let form = read_source(&mut text, &mut source);
+            assert_eq!(Some(Position::new("core.clj", 4, 3)), Position::of(&form));
+        }
+    }
 }
@@ -4,8 +4,13 @@ use std::io::BufRead;
 use std::io::BufReader;
 use std::io::Write;
 
+use crate::call_stack;
+use crate::condition;
 use crate::environment::Environment;
+use crate::error_message;
+use crate::protocols;
 use crate::reader;
+use crate::symbol::Symbol;
 use crate::value::{Evaluable, ToValue, Value};
 use std::rc::Rc;
 
@@ -21,7 +26,33 @@ impl Repl {
     // to frame eval as "environment.eval(value)", and then likewise define a
     // 'repl.eval(value)', rather than 'value.eval(environment)'
     pub fn eval(&self, value: &Value) -> Value {
-        value.eval(Rc::clone(&self.environment))
+        // Printing a lazy seq realizes it, which can fail;  so we realize it first, and
+        // any error doing so is our result, rather than being printed as one of its values
+        let result = Rc::new(value.eval(Rc::clone(&self.environment)));
+        let result = match protocols::realize(&result) {
+            Ok(()) => result.to_value(),
+            Err(condition) => condition.to_value(),
+        };
+        let result = match result {
+            // A (recur ..) that made it all the way up here had no loop or fn to return to
+            Value::Recur(_) => error_message::recur_not_in_tail_position(),
+            // Nor anything an Unwind could, but to here;  as when we abort at the restart prompt
+            Value::Unwind(_, args) => match args.first() {
+                Some(arg) => arg.to_value(),
+                None => Value::Nil,
+            },
+            evaled_value => evaled_value,
+        };
+        // The last error, along with its trace, is kept in *e;  as the map a catch would
+        // be given, so that it can be looked at without being raised again
+        if let Value::Condition(error) = &result {
+            self.environment.insert_into_namespace(
+                &Symbol::intern("clojure.core"),
+                Symbol::intern("*e"),
+                condition::from_error(error),
+            );
+        }
+        result
     }
 
     // Just wraps reader's read
@@ -32,8 +63,15 @@ impl Repl {
     pub fn read_string(string: &str) -> Value {
         Repl::read(&mut string.as_bytes())
     }
+    /// Reads and evaluates string, for tests
+    #[cfg(test)]
+    pub fn eval_str(&self, string: &str) -> Value {
+        self.eval(&Repl::read_string(string))
+    }
     pub fn run(&self) {
         let stdin = io::stdin();
+        self.environment.conditions().enable_restart_prompt();
+        let mut source = reader::Source::new("REPL");
 
         loop {
             print!("{}=> ", self.environment.get_current_namespace_name());
@@ -42,17 +80,91 @@ impl Repl {
             let next = {
                 let mut stdin_reader = stdin.lock();
                 // Read
-                Repl::read(&mut stdin_reader)
+                reader::read_source(&mut stdin_reader, &mut source)
                 // Release stdin.lock
             };
+            // Out of input, as on Ctrl-D
+            if let Value::Condition(error) = &next {
+                if error.is("reader/eof") {
+                    println!();
+                    return;
+                }
+            }
 
             // Eval
             let evaled_next = self.eval(&next);
             // Print
             println!("{}", evaled_next);
+            if let Value::Condition(error) = &evaled_next {
+                for frame in error.trace.iter() {
+                    println!("  at {}", frame);
+                }
+            }
             // Loop
         }
     }
+    /// Asks the user which of the available restarts to take, for a condition nothing
+    /// handled, and returns the Unwind to it.  Or to the top level, to abort
+    ///
+    /// ```text
+    ///   Unhandled condition :divide-by-zero: Tried to divide by zero
+    ///   Restarts:
+    ///     0: [return-zero []]
+    ///     1: [return-value [r]]
+    ///     2: [abort] Return to the REPL
+    ///   Restart number: 1
+    ///   r: 10
+    /// ```
+    pub fn restart_prompt(environment: &Rc<Environment>, condition: &Rc<Value>) -> Value {
+        let stdin = io::stdin();
+        let restarts = environment.conditions().restarts();
+        println!("{}", condition::describe(condition));
+        println!("Restarts:");
+        for (i, restart) in restarts.iter().enumerate() {
+            println!("  {}: [{} {}]", i, restart.name, restart.params);
+        }
+        println!("  {}: [abort] Return to the REPL", restarts.len());
+
+        let read = || {
+            let _ = io::stdout().flush();
+            let mut stdin_reader = stdin.lock();
+            Repl::read(&mut stdin_reader)
+        };
+        let abort = || {
+            Value::Unwind(
+                condition::TOP_LEVEL,
+                vec![Rc::new(condition::unhandled(condition))],
+            )
+        };
+        // Out of input, as on Ctrl-D, there's no asking again;  we abort
+        let is_eof =
+            |read: &Value| matches!(read, Value::Condition(error) if error.is("reader/eof"));
+        loop {
+            print!("Restart number: ");
+            let restart = match read() {
+                Value::I64(i) if i >= 0 && (i as usize) < restarts.len() => &restarts[i as usize],
+                Value::I64(i) if i as usize == restarts.len() => return abort(),
+                read if is_eof(&read) => {
+                    println!();
+                    return abort();
+                }
+                _ => continue,
+            };
+            let mut args = vec![];
+            if let Value::PersistentVector(params) = &*restart.params {
+                for param in params.iter() {
+                    print!("{}: ", param);
+                    let arg = read();
+                    if is_eof(&arg) {
+                        println!();
+                        return abort();
+                    }
+                    args.push(arg.eval_to_rc(Rc::clone(environment)));
+                }
+            }
+            return Value::Unwind(restart.id, args);
+        }
+    }
     //
     // Will possibly just add this to our environment, or turn this into a parallel of clojure.lang.RT
     //
@@ -60,14 +172,12 @@ impl Repl {
     pub fn try_eval_file(&self, filepath: &str) -> Result<Value, std::io::Error> {
         let core = File::open(filepath)?;
         let mut reader = BufReader::new(core);
+        let mut source = reader::Source::new(filepath);
 
-        let mut last_val = Repl::read(&mut reader);
+        let mut last_val = reader::read_source(&mut reader, &mut source);
         loop {
-            // @TODO this is hardcoded until we refactor Conditions to have keys, so that
-            //       we can properly identify them
-            // @FIXME
             if let Value::Condition(cond) = &last_val {
-                if cond != "Tried to read empty stream; unexpected EOF" {
+                if !cond.is("reader/eof") {
                     println!("Error reading file: {}", cond);
                 }
 
@@ -77,10 +187,10 @@ impl Repl {
             let evaled_last_val = self.eval(&last_val);
 
             if let Value::Condition(cond) = evaled_last_val {
-                println!("{}", cond);
+                println!("{}", call_stack::describe(&cond));
             }
 
-            last_val = Repl::read(&mut reader);
+            last_val = reader::read_source(&mut reader, &mut source);
         }
     }
     pub fn eval_file(&self, filepath: &str) -> Value {
@@ -105,8 +215,8 @@ mod tests {
     fn read_string() {
         let num = Repl::read_string("1");
         match num {
-            Value::I32(_) => {}
-            _ => panic!("Reading of integer should have returned Value::I32"),
+            Value::I64(_) => {}
+            _ => panic!("Reading of integer should have returned Value::I64"),
         }
         let list = Repl::read_string("(+ 1 2)");
         match list {
@@ -1,10 +1,6 @@
 // This module will hold core function and macro primitives that aren't special cases
 // (like the quote macro, or let), and can't be implemented in clojure itself
 
-// special forms
-pub(crate) mod special_form;
-pub use self::special_form::*;
-
 // language core functions
 pub(crate) mod eval;
 pub use self::eval::*;
@@ -13,6 +9,52 @@ pub(crate) mod meta;
 pub use self::meta::*;
 pub(crate) mod with_meta;
 pub use self::with_meta::*;
+pub(crate) mod trampoline;
+pub use self::trampoline::*;
+pub(crate) mod macroexpand;
+pub use self::macroexpand::*;
+pub(crate) mod gensym;
+pub use self::gensym::*;
+
+// vars and dynamic binding
+pub(crate) mod vars;
+pub use self::vars::*;
+pub(crate) mod binding;
+pub use self::binding::*;
+
+// reference types
+pub(crate) mod deref;
+pub use self::deref::*;
+pub(crate) mod atom;
+pub use self::atom::*;
+pub(crate) mod volatile;
+pub use self::volatile::*;
+pub(crate) mod delay;
+pub use self::delay::*;
+
+// seqs
+pub(crate) mod seq;
+pub use self::seq::*;
+pub(crate) mod iterate;
+pub use self::iterate::*;
+pub(crate) mod range;
+pub use self::range::*;
+pub(crate) mod take;
+pub use self::take::*;
+
+// conditions and restarts
+pub(crate) mod signal;
+pub use self::signal::*;
+pub(crate) mod handler_bind;
+pub use self::handler_bind::*;
+pub(crate) mod restart_case;
+pub use self::restart_case::*;
+pub(crate) mod throw;
+pub use self::throw::*;
+pub(crate) mod try_catch;
+pub use self::try_catch::*;
+pub(crate) mod ex_info;
+pub use self::ex_info::*;
 // macros
 pub(crate) mod do_macro;
 pub use self::do_macro::*;
@@ -40,6 +82,12 @@ pub use self::_multiply_::*;
 pub(crate) mod rem;
 pub use self::rem::*;
 
+pub(crate) mod unchecked;
+pub use self::unchecked::*;
+
+pub(crate) mod bignum;
+pub use self::bignum::*;
+
 pub(crate) mod rand;
 pub use self::rand::*;
 
@@ -61,6 +109,8 @@ pub use self::gte::*;
 // string
 pub(crate) mod str;
 pub use self::str::*;
+pub(crate) mod char;
+pub use self::char::*;
 
 // operations on collections
 pub(crate) mod nth;
@@ -69,8 +119,30 @@ pub(crate) mod concat;
 pub use self::concat::*;
 pub(crate) mod assoc;
 pub use self::assoc::*;
+pub(crate) mod dissoc;
+pub use self::dissoc::*;
+pub(crate) mod conj;
+pub use self::conj::*;
+pub(crate) mod pop;
+pub use self::pop::*;
 pub(crate) mod get;
 pub use self::get::*;
+pub(crate) mod contains;
+pub use self::contains::*;
+pub(crate) mod set;
+pub use self::set::*;
+pub(crate) mod sorted;
+pub use self::sorted::*;
+pub(crate) mod subseq;
+pub use self::subseq::*;
+pub(crate) mod compare;
+pub use self::compare::*;
+pub(crate) mod transient;
+pub use self::transient::*;
+pub(crate) mod into;
+pub use self::into::*;
+pub(crate) mod queue;
+pub use self::queue::*;
 pub(crate) mod map;
 pub use self::map::*;
 
@@ -80,6 +152,8 @@ pub(crate) mod first;
 pub use self::first::*;
 pub(crate) mod second;
 pub use self::second::*;
+pub(crate) mod last;
+pub use self::last::*;
 pub(crate) mod count;
 pub use self::count::*;
 
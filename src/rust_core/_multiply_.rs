@@ -1,9 +1,11 @@
 use crate::ifn::IFn;
+use crate::numbers::{self, Number, Overflow};
 use crate::value::{ToValue, Value};
 use std::rc::Rc;
 
 /// (* x y & xys)
 ///
+/// Their product;  an error, should it overflow an i64
 #[derive(Debug, Clone)]
 pub struct MultiplyFn {}
 impl ToValue for MultiplyFn {
@@ -13,30 +15,26 @@ impl ToValue for MultiplyFn {
 }
 impl IFn for MultiplyFn {
     fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
-        args.into_iter().fold(1_i32.to_value(), |a, b| match a {
-            Value::I32(a_) => match *b {
-                Value::I32(b_) => Value::I32(a_ * b_),
-                Value::F64(b_) => Value::F64(a_ as f64 * b_),
-                _ => Value::Condition(format!(
-                    // TODO: what error message should be returned regarding using typetags?
-                    "Type mismatch; Expecting: (i32 | i64 | f32 | f64), Found: {}",
-                    b.type_tag()
-                )),
-            },
-            Value::F64(a_) => match *b {
-                Value::I32(b_) => Value::F64(a_ * b_ as f64),
-                Value::F64(b_) => Value::F64(a_ * b_),
-                _ => Value::Condition(format!(
-                    // TODO: what error message should be returned regarding using typetags?
-                    "Type mismatch; Expecting: (i32 | i64 | f32 | f64), Found: {}",
-                    b.type_tag()
-                )),
-            },
-            _ => Value::Condition(format!(
-                // TODO: what error message should be returned regarding using typetags?
-                "Type mismatch: Expecting: (i32 | i64 | f32 | f64), Found: {}",
-                a.type_tag()
-            )),
+        numbers::fold(&args, Number::I64(1), |a, b| {
+            numbers::multiply(a, b, Overflow::Error)
+        })
+    }
+}
+
+/// (*' x y & xys)
+///
+/// Their product;  a BigInt, should it overflow an i64
+#[derive(Debug, Clone)]
+pub struct MultiplyPromotingFn {}
+impl ToValue for MultiplyPromotingFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for MultiplyPromotingFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        numbers::fold(&args, Number::I64(1), |a, b| {
+            numbers::multiply(a, b, Overflow::Promote)
         })
     }
 }
@@ -53,21 +51,21 @@ mod tests {
         fn multiply_without_arguments_returns_one() {
             let multiply = MultiplyFn {};
             let args = vec![];
-            assert_eq!(Value::I32(1), multiply.invoke(args));
+            assert_eq!(Value::I64(1), multiply.invoke(args));
         }
 
         #[test]
         fn multiply_with_one_argument_returns_identity() {
             let multiply = MultiplyFn {};
-            let args = vec![Rc::new(Value::I32(5))];
-            assert_eq!(Value::I32(5), multiply.invoke(args));
+            let args = vec![Rc::new(Value::I64(5))];
+            assert_eq!(Value::I64(5), multiply.invoke(args));
         }
 
         #[test]
         fn multiply_with_two_argument_returns_product() {
             let multiply = MultiplyFn {};
-            let args = vec![Rc::new(Value::I32(5)), Rc::new(Value::I32(6))];
-            assert_eq!(Value::I32(30), multiply.invoke(args));
+            let args = vec![Rc::new(Value::I64(5)), Rc::new(Value::I64(6))];
+            assert_eq!(Value::I64(30), multiply.invoke(args));
         }
     }
 }
@@ -1,9 +1,11 @@
 use crate::ifn::IFn;
+use crate::numbers::{self, Number, Overflow};
 use crate::value::{ToValue, Value};
 use std::rc::Rc;
 
 /// (+ x y & xys)
 ///
+/// Their sum;  an error, should it overflow an i64
 #[derive(Debug, Clone)]
 pub struct AddFn {}
 impl ToValue for AddFn {
@@ -13,30 +15,26 @@ impl ToValue for AddFn {
 }
 impl IFn for AddFn {
     fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
-        args.into_iter().fold(0_i32.to_value(), |a, b| match a {
-            Value::I32(a_) => match *b {
-                Value::I32(b_) => Value::I32(a_ + b_),
-                Value::F64(b_) => Value::F64(a_ as f64 + b_),
-                _ => Value::Condition(format!(
-                    // TODO: what error message should be returned regarding using typetags?
-                    "Type mismatch; Expecting: (i32 | i64 | f32 | f64), Found: {}",
-                    b.type_tag()
-                )),
-            },
-            Value::F64(a_) => match *b {
-                Value::I32(b_) => Value::F64(a_ + b_ as f64),
-                Value::F64(b_) => Value::F64(a_ + b_),
-                _ => Value::Condition(format!(
-                    // TODO: what error message should be returned regarding using typetags?
-                    "Type mismatch; Expecting: (i32 | i64 | f32 | f64), Found: {}",
-                    b.type_tag()
-                )),
-            },
-            _ => Value::Condition(format!(
-                // TODO: what error message should be returned regarding using typetags?
-                "Type mismatch: Expecting: (i32 | i64 | f32 | f64), Found: {}",
-                a.type_tag()
-            )),
+        numbers::fold(&args, Number::I64(0), |a, b| {
+            numbers::add(a, b, Overflow::Error)
+        })
+    }
+}
+
+/// (+' x y & xys)
+///
+/// Their sum;  a BigInt, should it overflow an i64
+#[derive(Debug, Clone)]
+pub struct AddPromotingFn {}
+impl ToValue for AddPromotingFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for AddPromotingFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        numbers::fold(&args, Number::I64(0), |a, b| {
+            numbers::add(a, b, Overflow::Promote)
         })
     }
 }
@@ -53,21 +51,79 @@ mod tests {
         fn plus_without_arguments_returns_zero() {
             let addition = AddFn {};
             let args = vec![];
-            assert_eq!(Value::I32(0), addition.invoke(args));
+            assert_eq!(Value::I64(0), addition.invoke(args));
         }
 
         #[test]
         fn plus_with_one_argument_returns_identity() {
             let addition = AddFn {};
-            let args = vec![Rc::new(Value::I32(5))];
-            assert_eq!(Value::I32(5), addition.invoke(args));
+            let args = vec![Rc::new(Value::I64(5))];
+            assert_eq!(Value::I64(5), addition.invoke(args));
         }
 
         #[test]
         fn plus_with_two_argument_returns_product() {
             let addition = AddFn {};
-            let args = vec![Rc::new(Value::I32(5)), Rc::new(Value::I32(6))];
-            assert_eq!(Value::I32(11), addition.invoke(args));
+            let args = vec![Rc::new(Value::I64(5)), Rc::new(Value::I64(6))];
+            assert_eq!(Value::I64(11), addition.invoke(args));
+        }
+    }
+
+    mod plus_overflow_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn plus_errors_on_overflow() {
+            let repl = Repl::default();
+            for src in &[
+                "(+ 9223372036854775807 1)",
+                "(- -9223372036854775808 1)",
+                "(* 9223372036854775807 2)",
+                "(inc 9223372036854775807)",
+                "(- -9223372036854775808)",
+            ] {
+                match repl.eval_str(src) {
+                    Value::Condition(error) => {
+                        assert!(error.is("arithmetic"));
+                        assert_eq!("integer overflow", error.message);
+                    }
+                    result => panic!("Expected {} to overflow, found {}", src, result),
+                }
+            }
+        }
+
+        #[test]
+        fn plus_promoting_gives_a_bigint() {
+            let repl = Repl::default();
+            assert_eq!(
+                "9223372036854775808N",
+                repl.eval_str("(+' 9223372036854775807 1)").to_string()
+            );
+            assert_eq!(
+                "-9223372036854775809N",
+                repl.eval_str("(-' -9223372036854775808 1)").to_string()
+            );
+            assert_eq!(
+                "18446744073709551614N",
+                repl.eval_str("(*' 9223372036854775807 2)").to_string()
+            );
+            assert_eq!(
+                "9223372036854775808N",
+                repl.eval_str("(inc' 9223372036854775807)").to_string()
+            );
+            assert_eq!(Value::I64(3), repl.eval_str("(+' 1 2)"));
+            assert_eq!("3N", repl.eval_str("(+ 1N 2)").to_string());
+        }
+
+        #[test]
+        fn plus_in_the_widest_category() {
+            let repl = Repl::default();
+            assert_eq!("3/2", repl.eval_str("(+ 1 1/2)").to_string());
+            assert_eq!(Value::F64(1.0), repl.eval_str("(+ 1/2 0.5)"));
+            assert_eq!("0.3M", repl.eval_str("(+ 0.1M 0.2M)").to_string());
+            assert_eq!("3.30M", repl.eval_str("(* 1.10M 3)").to_string());
+            assert_eq!("1.5M", repl.eval_str("(+ 1M 1/2)").to_string());
         }
     }
 }
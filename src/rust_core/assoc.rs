@@ -1,5 +1,9 @@
+use crate::error_message;
 use crate::ifn::IFn;
-use crate::persistent_list_map::IPersistentMap;
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap};
+use crate::protocol::{Protocol, ProtocolCastable};
+use crate::protocols;
+use crate::type_tag::TypeTag;
 use crate::util::IsEven;
 use crate::value::{ToValue, Value};
 use itertools::Itertools;
@@ -8,7 +12,7 @@ use std::rc::Rc;
 /// (assoc map key val & kvs)
 ///
 // General assoc fn; however,  currently just implemented
-// for our one map type, PersistentListMap
+// for our maps, sorted maps and vectors
 #[derive(Debug, Clone)]
 pub struct AssocFn {}
 impl ToValue for AssocFn {
@@ -23,23 +27,64 @@ impl IFn for AssocFn {
         // (assoc {} :a 1 :b 2) ;; 5 args
         // (assoc {} :a 1 :b 2 :c 3) ;; 7 args ...
         if args.len() < 3 || args.len().is_even() {
-            return Value::Condition(format!(
+            return error_message::error(
+                "arity",
+                format!(
                 "Wrong number of arguments given to function (Given: {}, Expected: 3 | 5 | 7 | ..)",
                 args.len()
-            ));
+            ),
+            );
         }
 
-        if let Value::PersistentListMap(pmap) = &*(args.get(0).unwrap().clone()) {
-            let mut retval = pmap.clone();
-            for (key_value, val_value) in args.into_iter().skip(1).tuples() {
-                let key = key_value.to_rc_value();
-                let val = val_value.to_rc_value();
-                println!("key: {:?}, val: {:?}", key, val);
-                retval = pmap.assoc(key, val);
+        // (assoc [1 2] 0 :a) replaces the value at an index, which can also be one past the
+        // end, to add to it
+        if let Value::PersistentVector(pvector) = &*args[0] {
+            let mut pvector = pvector.clone();
+            for (ind, val) in args.into_iter().skip(1).tuples() {
+                let ind = match &*ind {
+                    Value::I64(ind) if *ind >= 0 => *ind as usize,
+                    Value::I64(ind) => return error_message::index_cannot_be_negative(*ind),
+                    _ => return error_message::type_mismatch(TypeTag::Integer, &ind),
+                };
+                pvector = match pvector.assoc_n(ind, val) {
+                    Some(pvector) => pvector,
+                    None => return error_message::index_out_of_bounds(ind, pvector.len()),
+                };
             }
-            return Value::PersistentListMap(retval);
+            return pvector.to_value();
         }
-
-        Value::Nil
+        // A sorted map can fail to assoc a key its comparator can't order
+        if let Value::PersistentTreeMap(ptreemap) = &*args[0] {
+            let mut ptreemap = ptreemap.clone();
+            for (key, val) in args.into_iter().skip(1).tuples() {
+                ptreemap = match ptreemap.assoc(key, val) {
+                    Ok(ptreemap) => ptreemap,
+                    Err(condition) => return condition.to_value(),
+                };
+            }
+            return ptreemap.to_value();
+        }
+        // Hashing a lazy seq realizes it, which can fail
+        for (key, _) in args.iter().skip(1).tuples() {
+            if let Err(condition) = protocols::realize(key) {
+                return condition.to_value();
+            }
+        }
+        // (assoc nil :a 1) gives a new map, {:a 1}
+        let pmap = match &*args[0] {
+            Value::Nil => PersistentListMap::Empty
+                .to_rc_value()
+                .as_protocol::<protocols::IPersistentMap>(),
+            _ => match args[0].try_as_protocol::<protocols::IPersistentMap>() {
+                Some(pmap) => pmap,
+                None => return error_message::type_mismatch(TypeTag::PersistentListMap, &args[0]),
+            },
+        };
+        args.into_iter()
+            .skip(1)
+            .tuples()
+            .fold(pmap, |pmap, (key, val)| pmap.assoc(key, val))
+            .unwrap()
+            .to_value()
     }
 }
@@ -0,0 +1,358 @@
+use crate::atom::Atom;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::persistent_vector::ToPersistentVector;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+fn atom(atom: &Rc<Value>) -> Result<Rc<Atom>, Rc<Value>> {
+    match &**atom {
+        Value::Atom(atom) => Ok(Rc::clone(atom)),
+        _ => Err(Rc::new(error_message::type_mismatch(TypeTag::Atom, atom))),
+    }
+}
+
+fn ifn(ifn: &Rc<Value>) -> Result<Rc<dyn IFn>, Rc<Value>> {
+    match &**ifn {
+        Value::IFn(ifn) => Ok(Rc::clone(ifn)),
+        _ => Err(Rc::new(error_message::type_mismatch(TypeTag::IFn, ifn))),
+    }
+}
+
+/// A validator fn, or nil for none
+fn validator(validator: &Rc<Value>) -> Result<Option<Rc<dyn IFn>>, Rc<Value>> {
+    match &**validator {
+        Value::Nil => Ok(None),
+        _ => ifn(validator).map(Some),
+    }
+}
+
+/// (atom x & options)
+///
+/// An atom, starting out as x.  Its options are :meta, its meta map, and :validator,
+/// a fn every state it takes on must pass
+#[derive(Debug, Clone)]
+pub struct AtomFn {}
+impl ToValue for AtomFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for AtomFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1, 3, 5], args.len());
+        }
+        let atom = Atom::new(Rc::clone(&args[0]));
+        for option in args[1..].chunks(2) {
+            let (key, val) = match option {
+                [key, val] => (key, val),
+                _ => return error_message::wrong_varg_count(&[1, 3, 5], args.len()),
+            };
+            match &**key {
+                Value::Keyword(keyword) if *keyword == Keyword::intern("meta") => match &**val {
+                    Value::PersistentListMap(meta) => atom.reset_meta(meta.clone()),
+                    Value::PersistentHashMap(meta) => atom.reset_meta(meta.iter().collect()),
+                    _ => return error_message::type_mismatch(TypeTag::PersistentListMap, val),
+                },
+                Value::Keyword(keyword) if *keyword == Keyword::intern("validator") => {
+                    let result = match validator(val) {
+                        Ok(validator) => atom.set_validator(validator),
+                        Err(condition) => condition.to_value(),
+                    };
+                    if result.short_circuits() {
+                        return result;
+                    }
+                }
+                _ => {
+                    return error_message::error(
+                        "syntax",
+                        format!("atom takes the options :meta and :validator, not {}", key),
+                    )
+                }
+            }
+        }
+        Value::Atom(Rc::new(atom))
+    }
+}
+
+/// (swap! atom f & args)
+///
+/// Gives atom the state (apply f state args), and returns it
+#[derive(Debug, Clone)]
+pub struct SwapFn {}
+impl ToValue for SwapFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for SwapFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() < 2 {
+            return error_message::wrong_varg_count(&[2], args.len());
+        }
+        let (atom, f) = match (atom(&args[0]), ifn(&args[1])) {
+            (Ok(atom), Ok(f)) => (atom, f),
+            (Err(condition), _) | (_, Err(condition)) => return condition.to_value(),
+        };
+        match atom.swap_vals(&f, args[2..].to_vec()) {
+            Ok((_, new_state)) => new_state.to_value(),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// (swap-vals! atom f & args)
+///
+/// Like swap!, but returns [old new], the states atom went from and to
+#[derive(Debug, Clone)]
+pub struct SwapValsFn {}
+impl ToValue for SwapValsFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for SwapValsFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() < 2 {
+            return error_message::wrong_varg_count(&[2], args.len());
+        }
+        let (atom, f) = match (atom(&args[0]), ifn(&args[1])) {
+            (Ok(atom), Ok(f)) => (atom, f),
+            (Err(condition), _) | (_, Err(condition)) => return condition.to_value(),
+        };
+        match atom.swap_vals(&f, args[2..].to_vec()) {
+            Ok((old_state, new_state)) => vec![old_state, new_state].into_vector_value(),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// (reset! atom new-state)
+///
+/// Gives atom the state new-state, whatever it was before, and returns it
+#[derive(Debug, Clone)]
+pub struct ResetFn {}
+impl ToValue for ResetFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for ResetFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match atom(&args[0]) {
+            Ok(atom) => atom.reset(Rc::clone(&args[1])),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// (compare-and-set! atom old-state new-state)
+///
+/// Gives atom the state new-state only if its state is old-state;  returns whether it did
+#[derive(Debug, Clone)]
+pub struct CompareAndSetFn {}
+impl ToValue for CompareAndSetFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for CompareAndSetFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 3 {
+            return error_message::wrong_arg_count(3, args.len());
+        }
+        match atom(&args[0]) {
+            Ok(atom) => atom.compare_and_set(&args[1], Rc::clone(&args[2])),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// (set-validator! atom validator-fn)
+///
+/// Gives atom a new validator, or none, for nil;  its current state must pass it
+#[derive(Debug, Clone)]
+pub struct SetValidatorFn {}
+impl ToValue for SetValidatorFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for SetValidatorFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match (atom(&args[0]), validator(&args[1])) {
+            (Ok(atom), Ok(validator)) => atom.set_validator(validator),
+            (Err(condition), _) | (_, Err(condition)) => condition.to_value(),
+        }
+    }
+}
+
+/// (get-validator atom)
+#[derive(Debug, Clone)]
+pub struct GetValidatorFn {}
+impl ToValue for GetValidatorFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for GetValidatorFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match atom(&args[0]) {
+            Ok(atom) => match atom.validator() {
+                Some(validator) => Value::IFn(validator),
+                None => Value::Nil,
+            },
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// (add-watch atom key watch-fn)
+///
+/// Has (watch-fn key atom old-state new-state) called each time atom's state changes;
+/// returns atom
+#[derive(Debug, Clone)]
+pub struct AddWatchFn {}
+impl ToValue for AddWatchFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for AddWatchFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 3 {
+            return error_message::wrong_arg_count(3, args.len());
+        }
+        match (atom(&args[0]), ifn(&args[2])) {
+            (Ok(atom), Ok(watch)) => {
+                atom.add_watch(Rc::clone(&args[1]), watch);
+                Value::Atom(atom)
+            }
+            (Err(condition), _) | (_, Err(condition)) => condition.to_value(),
+        }
+    }
+}
+
+/// (remove-watch atom key)
+///
+/// Removes the watch added under key;  returns atom
+#[derive(Debug, Clone)]
+pub struct RemoveWatchFn {}
+impl ToValue for RemoveWatchFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for RemoveWatchFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match atom(&args[0]) {
+            Ok(atom) => {
+                atom.remove_watch(&args[1]);
+                Value::Atom(atom)
+            }
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod atom_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn atoms_keep_state_across_calls() {
+            let repl = Repl::default();
+            repl.eval_str("(def counter (atom 0))");
+            repl.eval_str("(defn tick [] (swap! counter + 1))");
+            repl.eval_str("(tick)");
+            assert_eq!(Value::I64(2), repl.eval_str("(tick)"));
+            assert_eq!(Value::I64(2), repl.eval_str("@counter"));
+            assert_eq!(Value::I64(10), repl.eval_str("(reset! counter 10)"));
+            assert_eq!(Value::I64(10), repl.eval_str("(deref counter)"));
+            assert_eq!("#atom[10]", repl.eval_str("counter").to_string());
+        }
+
+        #[test]
+        fn swap_vals_returns_old_and_new() {
+            let repl = Repl::default();
+            repl.eval_str("(def a (atom 1))");
+            assert_eq!("[1 5]", repl.eval_str("(swap-vals! a + 1 3)").to_string());
+        }
+
+        #[test]
+        fn compare_and_set_only_sets_from_the_expected_state() {
+            let repl = Repl::default();
+            repl.eval_str("(def a (atom 1))");
+            assert_eq!(
+                Value::Boolean(false),
+                repl.eval_str("(compare-and-set! a 2 3)")
+            );
+            assert_eq!(Value::I64(1), repl.eval_str("@a"));
+            assert_eq!(
+                Value::Boolean(true),
+                repl.eval_str("(compare-and-set! a 1 3)")
+            );
+            assert_eq!(Value::I64(3), repl.eval_str("@a"));
+        }
+
+        #[test]
+        fn validators_reject_invalid_states() {
+            let repl = Repl::default();
+            repl.eval_str("(def a (atom 1 :validator (fn [n] (gt n 0))))");
+            match repl.eval_str("(swap! a - 5)") {
+                Value::Condition(error) => assert!(error.is("illegal-state")),
+                result => panic!("Expected an error, found {}", result),
+            }
+            assert_eq!(Value::I64(1), repl.eval_str("@a"));
+            match repl.eval_str("(atom 0 :validator (fn [n] (gt n 0)))") {
+                Value::Condition(error) => assert!(error.is("illegal-state")),
+                result => panic!("Expected an error, found {}", result),
+            }
+            repl.eval_str("(set-validator! a nil)");
+            assert_eq!(Value::I64(-4), repl.eval_str("(swap! a - 5)"));
+        }
+
+        #[test]
+        fn watches_see_each_change() {
+            let repl = Repl::default();
+            repl.eval_str("(def a (atom 1))");
+            repl.eval_str("(def log (atom []))");
+            repl.eval_str(
+                "(add-watch a :log (fn [key ref old new] (swap! log concat [key old new])))",
+            );
+            repl.eval_str("(swap! a + 1)");
+            repl.eval_str("(reset! a 5)");
+            assert_eq!("(:log 1 2 :log 2 5)", repl.eval_str("@log").to_string());
+            repl.eval_str("(remove-watch a :log)");
+            repl.eval_str("(reset! a 6)");
+            assert_eq!("(:log 1 2 :log 2 5)", repl.eval_str("@log").to_string());
+        }
+
+        #[test]
+        fn atoms_carry_meta() {
+            let repl = Repl::default();
+            repl.eval_str("(def a (atom 1 :meta {:doc \"A counter\"}))");
+            assert_eq!(
+                Value::String(String::from("A counter")),
+                repl.eval_str("(get (meta a) :doc)")
+            );
+        }
+    }
+}
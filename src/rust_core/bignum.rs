@@ -0,0 +1,125 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::numbers::Number;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+fn number(args: &[Rc<Value>]) -> Result<Number, Rc<Value>> {
+    if args.len() != 1 {
+        return Err(Rc::new(error_message::wrong_arg_count(1, args.len())));
+    }
+    match &*args[0] {
+        Value::F64(n) if !n.is_finite() => Err(Rc::new(error_message::error(
+            "illegal-argument",
+            format!("{} has no exact value", n),
+        ))),
+        x => Number::of(x),
+    }
+}
+
+/// (bigint x)
+///
+/// The number x as a BigInt, truncated should it have a fractional part
+#[derive(Debug, Clone)]
+pub struct BigintFn {}
+impl ToValue for BigintFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for BigintFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        match number(&args) {
+            Ok(n) => Value::BigInt(n.to_big_int()),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// (bigdec x)
+///
+/// The number x as a BigDecimal;  an f64 as it prints, so that (bigdec 0.1) is 0.1M
+#[derive(Debug, Clone)]
+pub struct BigdecFn {}
+impl ToValue for BigdecFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for BigdecFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        match number(&args) {
+            Ok(n) => Value::BigDecimal(n.to_big_decimal()),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// (numerator r)
+///
+/// The numerator of the Ratio r, in its lowest terms
+#[derive(Debug, Clone)]
+pub struct NumeratorFn {}
+impl ToValue for NumeratorFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for NumeratorFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::Ratio(r) => Number::integer(r.numer().clone()).to_value(),
+            _ => error_message::type_mismatch(TypeTag::Ratio, &args[0]),
+        }
+    }
+}
+
+/// (denominator r)
+///
+/// The denominator of the Ratio r, in its lowest terms
+#[derive(Debug, Clone)]
+pub struct DenominatorFn {}
+impl ToValue for DenominatorFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for DenominatorFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::Ratio(r) => Number::integer(r.denom().clone()).to_value(),
+            _ => error_message::type_mismatch(TypeTag::Ratio, &args[0]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod bignum_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn converting() {
+            let repl = Repl::default();
+            assert_eq!("3N", repl.eval_str("(bigint 3.7)").to_string());
+            assert_eq!("2N", repl.eval_str("(bigint 7/3)").to_string());
+            assert_eq!("0.1M", repl.eval_str("(bigdec 0.1)").to_string());
+            assert_eq!("0.25M", repl.eval_str("(bigdec 1/4)").to_string());
+            assert_eq!("12M", repl.eval_str("(bigdec 12)").to_string());
+            assert_eq!(Value::I64(2), repl.eval_str("(numerator 4/6)"));
+            assert_eq!(Value::I64(3), repl.eval_str("(denominator 4/6)"));
+            assert!(matches!(
+                repl.eval_str("(numerator 2)"),
+                Value::Condition(_)
+            ));
+        }
+    }
+}
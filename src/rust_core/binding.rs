@@ -0,0 +1,229 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::protocol::ProtocolCastable;
+use crate::protocols;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use crate::var::{ThreadBindings, Var};
+use std::rc::Rc;
+
+fn var(var: &Rc<Value>) -> Result<Rc<Var>, Rc<Value>> {
+    match &**var {
+        Value::Var(var) => Ok(Rc::clone(var)),
+        _ => Err(Rc::new(error_message::type_mismatch(TypeTag::Var, var))),
+    }
+}
+
+type VarBindings = Vec<(Rc<Var>, Rc<Value>)>;
+
+/// The {var val ..} given to with-bindings*, or the [var val ..] that binding gives it
+fn bindings(bindings: &Rc<Value>) -> Result<VarBindings, Rc<Value>> {
+    let pairs = match &**bindings {
+        Value::PersistentListMap(_) | Value::PersistentHashMap(_) => bindings
+            .as_protocol::<protocols::IPersistentMap>()
+            .iter()
+            .map(|map_entry| (map_entry.key, map_entry.val))
+            .collect::<Vec<(Rc<Value>, Rc<Value>)>>(),
+        Value::PersistentVector(pvector) if pvector.len() % 2 == 0 => pvector
+            .to_vec()
+            .chunks(2)
+            .map(|pair| (Rc::clone(&pair[0]), Rc::clone(&pair[1])))
+            .collect::<Vec<(Rc<Value>, Rc<Value>)>>(),
+        Value::PersistentVector(_) => {
+            return Err(Rc::new(error_message::error(
+                "syntax",
+                String::from("Bindings should be given as pairs of a var and a value"),
+            )))
+        }
+        _ => {
+            return Err(Rc::new(error_message::type_mismatch(
+                TypeTag::PersistentListMap,
+                bindings,
+            )))
+        }
+    };
+    pairs
+        .into_iter()
+        .map(|(key, val)| var(&key).map(|var| (var, val)))
+        .collect()
+}
+
+fn thunk(thunk: &Rc<Value>) -> Result<Rc<dyn IFn>, Rc<Value>> {
+    match &**thunk {
+        Value::IFn(ifn) => Ok(Rc::clone(ifn)),
+        _ => Err(Rc::new(error_message::type_mismatch(TypeTag::IFn, thunk))),
+    }
+}
+
+/// (with-bindings* {var val ..} body-fn)
+///
+/// Calls body-fn with each (dynamic) var bound to its val on this thread.  What binding
+/// and with-bindings expand to
+#[derive(Debug, Clone)]
+pub struct WithBindingsFn {}
+impl ToValue for WithBindingsFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for WithBindingsFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let (bindings, body) = match (bindings(&args[0]), thunk(&args[1])) {
+            (Ok(bindings), Ok(body)) => (bindings, body),
+            (Err(condition), _) | (_, Err(condition)) => return condition.to_value(),
+        };
+        Var::with_bindings(bindings, || body.invoke(vec![]))
+    }
+}
+
+/// (var-set var val)
+///
+/// Sets var's binding on this thread to val;  what set! on a var expands to
+#[derive(Debug, Clone)]
+pub struct VarSetFn {}
+impl ToValue for VarSetFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for VarSetFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match var(&args[0]) {
+            Ok(var) => var.set(Rc::clone(&args[1])),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// A fn that runs with the bindings that were in effect when it was made, wherever
+/// it's called from
+#[derive(Debug, Clone)]
+pub struct BoundFn {
+    ifn: Rc<dyn IFn>,
+    bindings: Rc<ThreadBindings>,
+}
+impl IFn for BoundFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        Var::with_thread_bindings(Rc::clone(&self.bindings), || self.ifn.invoke(args))
+    }
+    fn name(&self) -> Option<Symbol> {
+        self.ifn.name()
+    }
+}
+
+/// (bound-fn* f)
+///
+/// f, as a fn that will run with the bindings in effect now.  What bound-fn expands to
+#[derive(Debug, Clone)]
+pub struct BoundFnFn {}
+impl ToValue for BoundFnFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for BoundFnFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match thunk(&args[0]) {
+            Ok(ifn) => Value::IFn(Rc::new(BoundFn {
+                ifn,
+                bindings: Var::get_thread_bindings(),
+            })),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod binding_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn binding_rebinds_dynamic_vars_for_its_extent() {
+            let repl = Repl::default();
+            repl.eval_str("(def ^:dynamic *x* 1)");
+            repl.eval_str("(defn x [] *x*)");
+            assert_eq!(Value::I64(2), repl.eval_str("(binding [*x* 2] (x))"));
+            assert_eq!(
+                Value::I64(5),
+                repl.eval_str("(binding [*x* 2] (binding [*x* 3] (+ *x* (x) -1)))")
+            );
+            assert_eq!(Value::I64(1), repl.eval_str("(x)"));
+            assert_eq!(
+                Value::I64(4),
+                repl.eval_str("(with-bindings {(var *x*) 4} (x))")
+            );
+        }
+
+        #[test]
+        fn only_dynamic_vars_can_be_bound() {
+            let repl = Repl::default();
+            repl.eval_str("(def y 1)");
+            match repl.eval_str("(binding [y 2] y)") {
+                Value::Condition(error) => {
+                    assert!(error.is("illegal-state"));
+                    assert_eq!(
+                        "Can't dynamically bind non-dynamic var: clojure.core/y",
+                        error.message
+                    );
+                }
+                result => panic!("Expected an error, found {}", result),
+            }
+        }
+
+        #[test]
+        fn set_changes_a_binding_but_not_a_root() {
+            let repl = Repl::default();
+            repl.eval_str("(def ^:dynamic *x* 1)");
+            assert_eq!(
+                Value::I64(3),
+                repl.eval_str("(binding [*x* 2] (set! *x* 3) *x*)")
+            );
+            assert_eq!(Value::I64(1), repl.eval_str("*x*"));
+            match repl.eval_str("(set! *x* 3)") {
+                Value::Condition(error) => assert!(error.is("illegal-state")),
+                result => panic!("Expected an error, found {}", result),
+            }
+        }
+
+        #[test]
+        fn bound_fn_keeps_the_bindings_it_was_made_under() {
+            let repl = Repl::default();
+            repl.eval_str("(def ^:dynamic *x* 1)");
+            repl.eval_str("(def f (binding [*x* 2] (bound-fn [y] (+ *x* y))))");
+            assert_eq!(Value::I64(12), repl.eval_str("(f 10)"));
+            assert_eq!(Value::I64(12), repl.eval_str("(binding [*x* 5] (f 10))"));
+        }
+
+        #[test]
+        fn recur_cannot_cross_a_binding() {
+            let repl = Repl::default();
+            repl.eval_str("(def ^:dynamic *x* 1)");
+            for (src, form) in &[
+                ("(loop [x 0] (binding [*x* 2] (recur 1)))", "binding"),
+                (
+                    "(loop [x 0] (with-bindings {#'*x* 2} (recur 1)))",
+                    "with-bindings",
+                ),
+            ] {
+                match repl.eval_str(src) {
+                    Value::Condition(error) => {
+                        assert_eq!(format!("Cannot recur across {}", form), error.message)
+                    }
+                    result => panic!("Expected {} to be an error, found {}", src, result),
+                }
+            }
+        }
+    }
+}
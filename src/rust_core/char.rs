@@ -0,0 +1,158 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::numbers::Number;
+use crate::value::{ToValue, Value};
+use num_traits::ToPrimitive;
+use std::convert::TryFrom;
+use std::rc::Rc;
+
+/// The number x, truncated to an integer;  None, should it not fit in an i64
+fn integer(x: &Value) -> Result<Option<i64>, Rc<Value>> {
+    Ok(Number::of(x)?.to_big_int().to_i64())
+}
+
+/// (char x)
+///
+/// The char with the code point x;  or x, if it's a char already
+#[derive(Debug, Clone)]
+pub struct CharFn {}
+impl ToValue for CharFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for CharFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        if let Value::Char(c) = &*args[0] {
+            return Value::Char(*c);
+        }
+        match integer(&args[0]) {
+            Ok(code_point) => match code_point
+                .and_then(|code_point| u32::try_from(code_point).ok())
+                .and_then(std::char::from_u32)
+            {
+                Some(c) => Value::Char(c),
+                None => error_message::error(
+                    "illegal-argument",
+                    format!("Value out of range for char: {}", args[0]),
+                ),
+            },
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// (int x)
+///
+/// The code point of the char x, or the number x truncated to an integer
+#[derive(Debug, Clone)]
+pub struct IntFn {}
+impl ToValue for IntFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for IntFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        if let Value::Char(c) = &*args[0] {
+            return Value::I64(i64::from(u32::from(*c)));
+        }
+        match integer(&args[0]) {
+            Ok(Some(n)) => Value::I64(n),
+            Ok(None) => error_message::error(
+                "illegal-argument",
+                format!("Value out of range for int: {}", args[0]),
+            ),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// (char? x)
+///
+/// Whether x is a char
+#[derive(Debug, Clone)]
+pub struct IsCharFn {}
+impl ToValue for IsCharFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for IsCharFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        Value::Boolean(matches!(&*args[0], Value::Char(_)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod char_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn reading_and_printing_chars() {
+            let repl = Repl::default();
+            for (src, c) in &[
+                ("\\a", 'a'),
+                ("\\é", 'é'),
+                ("\\(", '('),
+                ("\\newline", '\n'),
+                ("\\space", ' '),
+                ("\\tab", '\t'),
+                ("\\u00e9", 'é'),
+            ] {
+                assert_eq!(Value::Char(*c), repl.eval_str(src));
+            }
+            assert_eq!(
+                "[\\a \\newline \\space \\é \\u0000]",
+                repl.eval_str("[\\a \\newline \\space \\é (char 0)]")
+                    .to_string()
+            );
+            assert_eq!("a b", repl.eval_str("(str \\a \\space \\b)").to_string());
+        }
+
+        #[test]
+        fn strings_are_seqs_of_chars() {
+            let repl = Repl::default();
+            assert_eq!(Value::Char('a'), repl.eval_str("(first \"abc\")"));
+            assert_eq!("(\\b \\c)", repl.eval_str("(rest \"abc\")").to_string());
+            assert_eq!(
+                "(98 99 100)",
+                repl.eval_str("(map (fn [c] (inc (int c))) \"abc\")")
+                    .to_string()
+            );
+            assert_eq!("[\\h \\i]", repl.eval_str("(into [] \"hi\")").to_string());
+        }
+
+        #[test]
+        fn converting_chars() {
+            let repl = Repl::default();
+            assert_eq!(Value::I64(97), repl.eval_str("(int \\a)"));
+            assert_eq!(Value::I64(233), repl.eval_str("(int \\é)"));
+            assert_eq!(Value::I64(3), repl.eval_str("(int 3.7)"));
+            assert_eq!(Value::Char('a'), repl.eval_str("(char 97)"));
+            assert_eq!(Value::Char('a'), repl.eval_str("(char \\a)"));
+            assert_eq!(Value::Boolean(true), repl.eval_str("(char? \\a)"));
+            assert_eq!(Value::Boolean(false), repl.eval_str("(char? \"a\")"));
+            assert_eq!(Value::Boolean(true), repl.eval_str("(= \\a (char 97))"));
+            assert_eq!(Value::I64(-1), repl.eval_str("(compare \\a \\b)"));
+            for src in &["(char -1)", "(char 55296)", "(char \"a\")", "(int :a)"] {
+                assert!(
+                    matches!(repl.eval_str(src), Value::Condition(_)),
+                    "Expected {} to be an error",
+                    src
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::compare;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// (compare x y)
+///
+/// -1, 0 or 1, as x comes before, is equal to, or comes after y;  numbers, strings,
+/// keywords, symbols and vectors compare, and nil comes before everything
+#[derive(Debug, Clone)]
+pub struct CompareFn {}
+impl ToValue for CompareFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for CompareFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match compare::compare(&args[0], &args[1]) {
+            Ok(ordering) => Value::I64(ordering as i64),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod compare_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn compare_orders_alike_values() {
+            let repl = Repl::default();
+            assert_eq!(Value::I64(-1), repl.eval_str("(compare 1 2)"));
+            assert_eq!(Value::I64(0), repl.eval_str("(compare 2 2.0)"));
+            assert_eq!(Value::I64(1), repl.eval_str("(compare \"b\" \"a\")"));
+            assert_eq!(Value::I64(-1), repl.eval_str("(compare :a :b)"));
+            assert_eq!(Value::I64(-1), repl.eval_str("(compare :b :a/a)"));
+            assert_eq!(Value::I64(1), repl.eval_str("(compare 'b 'a)"));
+            assert_eq!(Value::I64(-1), repl.eval_str("(compare nil 1)"));
+            // A shorter vector comes first, whatever's in it
+            assert_eq!(Value::I64(-1), repl.eval_str("(compare [9] [1 2])"));
+            assert_eq!(Value::I64(1), repl.eval_str("(compare [1 3] [1 2])"));
+        }
+
+        #[test]
+        fn compare_errors_on_unalike_values() {
+            let repl = Repl::default();
+            match repl.eval_str("(compare 1 :a)") {
+                Value::Condition(error) => assert!(error.is("type-mismatch")),
+                result => panic!("Expected an error, found {}", result),
+            }
+        }
+    }
+}
@@ -1,12 +1,16 @@
+use crate::cons::Cons;
+use crate::error_message;
 use crate::ifn::IFn;
+use crate::lazy_seq::LazySeq;
+use crate::protocol::ProtocolCastable;
+use crate::protocols;
+use crate::type_tag::TypeTag;
 use crate::value::{ToValue, Value};
 use std::rc::Rc;
 
-use crate::persistent_list::{PersistentList, ToPersistentListIter};
-use crate::persistent_vector::ToPersistentVectorIter;
-
 /// (concat x y & zs)
 ///
+/// A lazy seq of the values of each coll given, one after the other
 #[derive(Debug, Clone)]
 pub struct ConcatFn {}
 impl ToValue for ConcatFn {
@@ -16,20 +20,38 @@ impl ToValue for ConcatFn {
 }
 impl IFn for ConcatFn {
     fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
-        let concatted_vec = args.iter().fold(Vec::new(), |mut sum, coll| {
-            let coll_vec = match &**coll {
-                Value::PersistentList(plist) => {
-                    Rc::new(plist.clone()).iter().collect::<Vec<Rc<Value>>>()
-                }
-                Value::PersistentVector(pvector) => {
-                    Rc::new(pvector.clone()).iter().collect::<Vec<Rc<Value>>>()
-                }
-                _ => vec![],
-            };
+        if let Some(coll) = args
+            .iter()
+            .find(|coll| !coll.instanceof::<protocols::ISeq>())
+        {
+            return error_message::type_mismatch(TypeTag::ISeq, coll);
+        }
+        Value::LazySeq(Rc::new(LazySeq::new(Rc::new(ConcatStep { colls: args }))))
+    }
+}
 
-            sum.extend(coll_vec);
-            sum
-        });
-        Value::PersistentList(concatted_vec.into_iter().collect::<PersistentList>())
+/// What's left of a concat;  the values of each of colls
+#[derive(Debug, Clone)]
+struct ConcatStep {
+    colls: Vec<Rc<Value>>,
+}
+impl IFn for ConcatStep {
+    fn invoke(&self, _args: Vec<Rc<Value>>) -> Value {
+        for (i, coll) in self.colls.iter().enumerate() {
+            match coll.as_protocol::<protocols::ISeq>().uncons() {
+                Ok(Some((first, more))) => {
+                    let mut colls = vec![more];
+                    colls.extend_from_slice(&self.colls[i + 1..]);
+                    let rest = LazySeq::new(Rc::new(ConcatStep { colls }));
+                    return Value::Cons(Rc::new(Cons::new(
+                        first,
+                        Value::LazySeq(Rc::new(rest)).to_rc_value(),
+                    )));
+                }
+                Ok(None) => {}
+                Err(condition) => return condition.to_value(),
+            }
+        }
+        Value::Nil
     }
 }
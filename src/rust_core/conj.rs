@@ -0,0 +1,136 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list_map::IPersistentMap;
+use crate::persistent_vector::PersistentVector;
+use crate::protocol::{Protocol, ProtocolCastable};
+use crate::protocols;
+use crate::traits::ISeq;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// coll with x added, wherever coll adds things most cheaply;  to the end of a vector or
+/// queue, the front of a list, as an entry of a map, or into a set
+pub(crate) fn conj(coll: &Rc<Value>, x: Rc<Value>) -> Result<Rc<Value>, Rc<Value>> {
+    match &**coll {
+        Value::PersistentVector(pvector) => Ok(pvector.conj(x).to_rc_value()),
+        Value::PersistentListMap(_) | Value::PersistentHashMap(_) => {
+            // Hashing a lazy seq realizes it, which can fail
+            protocols::realize(&x)?;
+            let pmap = coll.as_protocol::<protocols::IPersistentMap>();
+            match &*x {
+                // (conj {} [:a 1])
+                Value::PersistentVector(entry) if entry.len() == 2 => Ok(pmap
+                    .assoc(
+                        Rc::clone(entry.nth(0).unwrap()),
+                        Rc::clone(entry.nth(1).unwrap()),
+                    )
+                    .unwrap()),
+                // (conj {} {:a 1 :b 2})
+                Value::PersistentListMap(_) | Value::PersistentHashMap(_) => Ok(x
+                    .as_protocol::<protocols::IPersistentMap>()
+                    .iter()
+                    .fold(pmap, |pmap, entry| pmap.assoc(entry.key, entry.val))
+                    .unwrap()),
+                _ => Err(Rc::new(error_message::error(
+                    "illegal-argument",
+                    String::from("Vector arg to map conj must be a pair"),
+                ))),
+            }
+        }
+        Value::PersistentTreeMap(ptreemap) => match &*x {
+            // (conj (sorted-map) [:a 1])
+            Value::PersistentVector(entry) if entry.len() == 2 => Ok(ptreemap
+                .assoc(
+                    Rc::clone(entry.nth(0).unwrap()),
+                    Rc::clone(entry.nth(1).unwrap()),
+                )?
+                .to_rc_value()),
+            // (conj (sorted-map) {:a 1 :b 2})
+            Value::PersistentListMap(_) | Value::PersistentHashMap(_) => {
+                let mut ptreemap = ptreemap.clone();
+                for entry in x.as_protocol::<protocols::IPersistentMap>().iter() {
+                    ptreemap = ptreemap.assoc(entry.key, entry.val)?;
+                }
+                Ok(ptreemap.to_rc_value())
+            }
+            _ => Err(Rc::new(error_message::error(
+                "illegal-argument",
+                String::from("Vector arg to map conj must be a pair"),
+            ))),
+        },
+        Value::PersistentHashSet(phashset) => {
+            protocols::realize(&x)?;
+            Ok(phashset.conj(x).to_rc_value())
+        }
+        Value::PersistentTreeSet(ptreeset) => Ok(ptreeset.conj(x)?.to_rc_value()),
+        Value::PersistentQueue(pqueue) => Ok(pqueue.conj(x).to_rc_value()),
+        Value::Nil | Value::PersistentList(_) | Value::LazySeq(_) | Value::Cons(_) => {
+            Ok(coll.as_protocol::<protocols::ISeq>().cons(x))
+        }
+        _ => Err(Rc::new(error_message::type_mismatch(TypeTag::ISeq, coll))),
+    }
+}
+
+/// (conj coll & xs)
+///
+/// coll with each of xs added;  (conj) is [], and (conj nil x) is the list (x)
+#[derive(Debug, Clone)]
+pub struct ConjFn {}
+impl ToValue for ConjFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for ConjFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        let mut args = args.into_iter();
+        let mut coll = match args.next() {
+            Some(coll) => coll,
+            None => return PersistentVector::empty().to_value(),
+        };
+        for x in args {
+            coll = match conj(&coll, x) {
+                Ok(coll) => coll,
+                Err(condition) => return condition.to_value(),
+            };
+        }
+        coll.to_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod conj_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn conj_adds_where_each_coll_adds_cheaply() {
+            let repl = Repl::default();
+            assert_eq!("[1 2 3 4]", repl.eval_str("(conj [1 2] 3 4)").to_string());
+            assert_eq!("(4 3 1 2)", repl.eval_str("(conj '(1 2) 3 4)").to_string());
+            assert_eq!("(1)", repl.eval_str("(conj nil 1)").to_string());
+            assert_eq!("[]", repl.eval_str("(conj)").to_string());
+            assert_eq!(
+                repl.eval_str("{:a 1 :b 2 :c 3}"),
+                repl.eval_str("(conj {:a 0} [:a 1] {:b 2 :c 3})")
+            );
+        }
+
+        #[test]
+        fn building_a_big_vector() {
+            let repl = Repl::default();
+            repl.eval_str(
+                "(def v (loop [i 0 v []] (if (= i 20000) v (recur (inc i) (conj v i)))))",
+            );
+            assert_eq!(Value::I64(20000), repl.eval_str("(count v)"));
+            assert_eq!(Value::I64(12345), repl.eval_str("(nth v 12345)"));
+            assert_eq!(
+                Value::I64(-1),
+                repl.eval_str("(nth (assoc v 19999 -1) 19999)")
+            );
+            assert_eq!(Value::I64(19999), repl.eval_str("(nth v 19999)"));
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list_map::IPersistentMap;
+use crate::protocol::ProtocolCastable;
+use crate::protocols;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// (contains? coll key)
+///
+/// Whether key is in coll;  a key of a map, a value of a set, or an index of a
+/// vector or string.  This is not whether coll has some value, for that see some
+#[derive(Debug, Clone)]
+pub struct ContainsFn {}
+impl ToValue for ContainsFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for ContainsFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let key = &args[1];
+        // Hashing a lazy seq realizes it, which can fail
+        if let Err(condition) = protocols::realize(key) {
+            return condition.to_value();
+        }
+        let contains = match &*args[0] {
+            Value::Nil => false,
+            Value::PersistentListMap(_) | Value::PersistentHashMap(_) => args[0]
+                .as_protocol::<protocols::IPersistentMap>()
+                .contains_key(key),
+            Value::PersistentHashSet(phashset) => phashset.contains(key),
+            Value::PersistentTreeMap(ptreemap) => match ptreemap.contains_key(key) {
+                Ok(contains) => contains,
+                Err(condition) => return condition.to_value(),
+            },
+            Value::PersistentTreeSet(ptreeset) => match ptreeset.contains(key) {
+                Ok(contains) => contains,
+                Err(condition) => return condition.to_value(),
+            },
+            Value::PersistentVector(pvector) => match &**key {
+                Value::I64(ind) => *ind >= 0 && (*ind as usize) < pvector.len(),
+                _ => false,
+            },
+            Value::String(string) => match &**key {
+                Value::I64(ind) => *ind >= 0 && (*ind as usize) < string.chars().count(),
+                _ => false,
+            },
+            _ => {
+                return error_message::error(
+                    "illegal-argument",
+                    format!("contains? not supported on type: {}", args[0].type_tag()),
+                )
+            }
+        };
+        Value::Boolean(contains)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod contains_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn contains_looks_up_keys_not_values() {
+            let repl = Repl::default();
+            assert_eq!(Value::Boolean(true), repl.eval_str("(contains? {:a 1} :a)"));
+            assert_eq!(Value::Boolean(false), repl.eval_str("(contains? {:a 1} 1)"));
+            assert_eq!(
+                Value::Boolean(false),
+                repl.eval_str("(contains? [:a :b] :a)")
+            );
+            assert_eq!(
+                Value::Boolean(false),
+                repl.eval_str("(contains? [:a :b] 2)")
+            );
+            assert_eq!(Value::Boolean(true), repl.eval_str("(contains? \"ab\" 0)"));
+            assert_eq!(Value::Boolean(false), repl.eval_str("(contains? nil :a)"));
+        }
+    }
+}
@@ -5,7 +5,9 @@ use std::rc::Rc;
 use crate::error_message;
 use crate::iterable::Iterable;
 use crate::persistent_list::ToPersistentList;
+use crate::persistent_list_map::IPersistentMap;
 use crate::protocol::ProtocolCastable;
+use crate::protocols;
 use crate::type_tag::TypeTag;
 
 /// (count coll)
@@ -23,24 +25,51 @@ impl IFn for CountFn {
             return error_message::wrong_arg_count(1, args.len());
         }
 
-        let coll_size = match args.get(0).unwrap().try_as_protocol::<Iterable>() {
-            Some(iterable) => iterable.iter().count(),
-            None => match args.get(0).unwrap().to_value() {
-                Value::Nil => 0,
-                _unsupported => return error_message::type_mismatch(TypeTag::ISeq, &_unsupported),
+        let coll_size = match &*args[0] {
+            // Counting a lazy seq realizes it, which can fail
+            Value::LazySeq(_) | Value::Cons(_) => match args[0]
+                .as_protocol::<protocols::ISeq>()
+                .iter()
+                .collect::<Result<Vec<Rc<Value>>, Rc<Value>>>()
+            {
+                Ok(vals) => vals.len(),
+                Err(condition) => return condition.to_value(),
+            },
+            Value::PersistentListMap(_) | Value::PersistentHashMap(_) => {
+                args[0].as_protocol::<protocols::IPersistentMap>().count()
+            }
+            Value::PersistentVector(pvector) => pvector.len(),
+            Value::PersistentHashSet(phashset) => phashset.count(),
+            Value::PersistentTreeMap(ptreemap) => ptreemap.count(),
+            Value::PersistentTreeSet(ptreeset) => ptreeset.count(),
+            Value::PersistentQueue(pqueue) => pqueue.count(),
+            Value::Transient(transient) => match transient.count() {
+                Ok(count) => count,
+                Err(condition) => return condition.to_value(),
+            },
+            _ => match args[0].try_as_protocol::<Iterable>() {
+                Some(iterable) => iterable.iter().count(),
+                None => match args[0].to_value() {
+                    Value::Nil => 0,
+                    _unsupported => {
+                        return error_message::type_mismatch(TypeTag::ISeq, &_unsupported)
+                    }
+                },
             },
         };
 
-        return Value::I32(coll_size as i32);
+        Value::I64(coll_size as i64)
     }
 }
 
 #[cfg(test)]
 mod tests {
     mod count_tests {
+        use crate::error_message;
         use crate::ifn::IFn;
         use crate::persistent_vector::PersistentVector;
         use crate::rust_core::CountFn;
+        use crate::type_tag::TypeTag;
         use crate::value::Value;
         use std::rc::Rc;
 
@@ -48,7 +77,7 @@ mod tests {
         fn count_nil() {
             let count = CountFn {};
             let args = vec![Rc::new(Value::Nil)];
-            assert_eq!(Value::I32(0), count.invoke(args));
+            assert_eq!(Value::I64(0), count.invoke(args));
         }
 
         #[test]
@@ -62,7 +91,7 @@ mod tests {
                 .into_iter()
                 .collect::<PersistentVector>(),
             ))];
-            assert_eq!(Value::I32(2), count.invoke(args));
+            assert_eq!(Value::I64(2), count.invoke(args));
         }
 
         #[test]
@@ -70,10 +99,7 @@ mod tests {
             let count = CountFn {};
             let args = vec![Rc::new(Value::Boolean(true))];
             assert_eq!(
-                Value::Condition(
-                    "Type mismatch; Expected instance of clojure.lang.ISeq,  Recieved type true"
-                        .to_string()
-                ),
+                error_message::type_mismatch(TypeTag::ISeq, &Value::Boolean(true)),
                 count.invoke(args)
             );
         }
@@ -0,0 +1,98 @@
+use crate::delay::Delay;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// (delay* body-fn)
+///
+/// A delay of whatever body-fn returns, calling it only the first time that's asked for.
+/// What delay expands to
+#[derive(Debug, Clone)]
+pub struct DelayFn {}
+impl ToValue for DelayFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for DelayFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::IFn(thunk) => Value::Delay(Rc::new(Delay::new(Rc::clone(thunk)))),
+            _ => error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        }
+    }
+}
+
+/// (force x)
+///
+/// x's value, if it's a delay, or else just x
+#[derive(Debug, Clone)]
+pub struct ForceFn {}
+impl ToValue for ForceFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for ForceFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::Delay(delay) => delay.deref(),
+            x => x.clone(),
+        }
+    }
+}
+
+/// (realized? x)
+///
+/// Whether the delay or lazy seq x has had its value worked out yet
+#[derive(Debug, Clone)]
+pub struct IsRealizedFn {}
+impl ToValue for IsRealizedFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for IsRealizedFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::Delay(delay) => Value::Boolean(delay.is_realized()),
+            Value::LazySeq(lazy_seq) => Value::Boolean(lazy_seq.is_realized()),
+            _ => error_message::type_mismatch(TypeTag::Delay, &args[0]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod delay_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn delays_are_worked_out_once() {
+            let repl = Repl::default();
+            repl.eval_str("(def calls (atom 0))");
+            repl.eval_str("(def d (delay (swap! calls inc) :done))");
+            assert_eq!(Value::Boolean(false), repl.eval_str("(realized? d)"));
+            assert_eq!("#delay[:pending]", repl.eval_str("d").to_string());
+            assert_eq!("#delay[:pending]", repl.eval_str("d").to_string());
+            assert_eq!(":done", repl.eval_str("(force d)").to_string());
+            assert_eq!(":done", repl.eval_str("@d").to_string());
+            assert_eq!(Value::I64(1), repl.eval_str("@calls"));
+            assert_eq!(Value::Boolean(true), repl.eval_str("(realized? d)"));
+            assert_eq!("#delay[:done]", repl.eval_str("d").to_string());
+            assert_eq!(Value::I64(5), repl.eval_str("(force 5)"));
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// (deref ref), or @ref
+///
+/// The value a reference holds;  an atom or volatile's state, a var's value, or a
+/// delay's value, worked out should it not be yet
+#[derive(Debug, Clone)]
+pub struct DerefFn {}
+impl ToValue for DerefFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for DerefFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::Atom(atom) => atom.deref().to_value(),
+            Value::Volatile(volatile) => volatile.deref().to_value(),
+            Value::Var(var) => var.deref().to_value(),
+            Value::Delay(delay) => delay.deref(),
+            _ => error_message::error(
+                "type-mismatch",
+                format!(
+                    "In (deref ..), .. must be a reference, as an atom, volatile, var or delay, and {} is of type {}, which is not",
+                    args[0],
+                    args[0].type_tag()
+                ),
+            ),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list_map::IPersistentMap;
+use crate::protocol::{Protocol, ProtocolCastable};
+use crate::protocols;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// (dissoc map & keys)
+///
+/// map, without the entries for keys;  (dissoc nil :a) is nil
+#[derive(Debug, Clone)]
+pub struct DissocFn {}
+impl ToValue for DissocFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for DissocFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], args.len());
+        }
+        if let Value::Nil = &*args[0] {
+            return Value::Nil;
+        }
+        if let Value::PersistentTreeMap(ptreemap) = &*args[0] {
+            let mut ptreemap = ptreemap.clone();
+            for key in args[1..].iter() {
+                ptreemap = match ptreemap.dissoc(key) {
+                    Ok(ptreemap) => ptreemap,
+                    Err(condition) => return condition.to_value(),
+                };
+            }
+            return ptreemap.to_value();
+        }
+        // Hashing a lazy seq realizes it, which can fail
+        if let Err(condition) = args[1..].iter().try_for_each(protocols::realize) {
+            return condition.to_value();
+        }
+        match args[0].try_as_protocol::<protocols::IPersistentMap>() {
+            Some(pmap) => args[1..]
+                .iter()
+                .fold(pmap, |pmap, key| pmap.dissoc(key))
+                .unwrap()
+                .to_value(),
+            None => error_message::type_mismatch(TypeTag::PersistentListMap, &args[0]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod dissoc_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn dissoc_removes_keys() {
+            let repl = Repl::default();
+            assert_eq!(
+                repl.eval_str("{:b 2}"),
+                repl.eval_str("(dissoc {:a 1 :b 2 :c 3} :a :c :d)")
+            );
+            assert_eq!(Value::I64(0), repl.eval_str("(count (dissoc {:a 1} :a))"));
+            assert_eq!(Value::Nil, repl.eval_str("(dissoc nil :a)"));
+        }
+
+        #[test]
+        fn dissoc_leaves_the_original_map_alone() {
+            let repl = Repl::default();
+            repl.eval_str("(def m {:a 1 :b 2})");
+            repl.eval_str("(dissoc m :a)");
+            assert_eq!(Value::I64(1), repl.eval_str("(get m :a)"));
+            assert_eq!(Value::I64(2), repl.eval_str("(count m)"));
+        }
+    }
+}
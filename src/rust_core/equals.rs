@@ -1,4 +1,6 @@
+use crate::error_message;
 use crate::ifn::IFn;
+use crate::protocols;
 use crate::value::{ToValue, Value};
 use std::rc::Rc;
 
@@ -17,17 +19,20 @@ impl IFn for EqualsFn {
     fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
         if args.is_empty() {
             //@TODO use proper error function
-            return Value::Condition(format!(
-                "Wrong number of arguments given to function (Given: {}, Expected: > 0)",
-                args.len()
-            ));
+            return error_message::error(
+                "arity",
+                format!(
+                    "Wrong number of arguments given to function (Given: {}, Expected: > 0)",
+                    args.len()
+                ),
+            );
         }
 
         for pair in args.windows(2) {
-            let a = &pair[0];
-            let b = &pair[1];
-            if a != b {
-                return Value::Boolean(false);
+            match protocols::equiv(&pair[0], &pair[1]) {
+                Ok(true) => {}
+                Ok(false) => return Value::Boolean(false),
+                Err(condition) => return condition.to_value(),
             }
         }
         Value::Boolean(true)
@@ -48,16 +53,16 @@ mod tests {
     #[test]
     fn equals_basic() {
         let equals = EqualsFn {};
-        let _i32 = Value::I32(1).to_rc_value();
+        let _i64 = Value::I64(1).to_rc_value();
         // To test that we're not getting some sort of 'memory equality'
-        let i32_copy = Value::I32(1).to_rc_value();
+        let i64_copy = Value::I64(1).to_rc_value();
         assert!(equals
-            .invoke(vec![i32_copy.clone(), _i32.clone()])
+            .invoke(vec![i64_copy.clone(), _i64.clone()])
             .is_truthy());
-        assert!(equals.invoke(vec![_i32.clone(), _i32.clone()]).is_truthy());
+        assert!(equals.invoke(vec![_i64.clone(), _i64.clone()]).is_truthy());
 
-        let i32_2 = Value::I32(5).to_rc_value();
-        assert!(!equals.invoke(vec![_i32.clone(), i32_2.clone()]).is_truthy());
+        let i64_2 = Value::I64(5).to_rc_value();
+        assert!(!equals.invoke(vec![_i64.clone(), i64_2.clone()]).is_truthy());
 
         let keyword = Keyword::intern("cat").to_rc_value();
         let keyword2 = Keyword::intern("cat").to_rc_value();
@@ -67,6 +72,22 @@ mod tests {
             .invoke(vec![keyword.clone(), keyword2.clone()])
             .is_truthy());
         assert!(!equals.invoke(vec![keyword2, keyword3]).is_truthy());
-        assert!(!equals.invoke(vec![keyword, _i32]).is_truthy());
+        assert!(!equals.invoke(vec![keyword, _i64]).is_truthy());
+    }
+
+    #[test]
+    fn equals_across_numbers() {
+        use crate::repl::Repl;
+        let repl = Repl::default();
+        let eval = |src: &str| repl.eval(&Repl::read_string(src));
+        assert_eq!(Value::Boolean(true), eval("(= 1 1N)"));
+        assert_eq!(Value::Boolean(true), eval("(= 1/2 (/ 2 4))"));
+        assert_eq!(Value::Boolean(true), eval("(= 1.50M 1.5M)"));
+        assert_eq!(Value::Boolean(false), eval("(= 1 1.0)"));
+        assert_eq!(Value::Boolean(false), eval("(= 1 1M)"));
+        assert_eq!(Value::Boolean(false), eval("(= 1/2 0.5)"));
+        // Equal numbers hash alike
+        assert_eq!(Value::I64(1), eval("(count (hash-set 1 1N))"));
+        assert_eq!(Value::I64(1), eval("(count (hash-set 1.5M 1.50M))"));
     }
 }
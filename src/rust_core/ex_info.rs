@@ -0,0 +1,152 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap};
+use crate::protocol::ProtocolCastable;
+use crate::protocols;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// The value of key in e, if e is a condition
+fn condition_get(e: &Rc<Value>, key: &str) -> Value {
+    match e.try_as_protocol::<protocols::IPersistentMap>() {
+        Some(pmap) => pmap.get(&Keyword::intern(key).to_rc_value()).to_value(),
+        None => Value::Nil,
+    }
+}
+
+/// (ex-info msg map) (ex-info msg map cause)
+///
+/// Makes an error, a condition of type :ex-info, carrying a message and a map of data
+#[derive(Debug, Clone)]
+pub struct ExInfoFn {}
+impl ToValue for ExInfoFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for ExInfoFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 && args.len() != 3 {
+            return error_message::wrong_varg_count(&[2, 3], args.len());
+        }
+        if !matches!(&*args[0], Value::String(_)) {
+            return error_message::type_mismatch(TypeTag::String, &args[0]);
+        }
+        if !args[1].instanceof::<protocols::IPersistentMap>() {
+            return error_message::type_mismatch(TypeTag::PersistentListMap, &args[1]);
+        }
+        let mut ex_info = PersistentListMap::Empty
+            .assoc(
+                Keyword::intern("type").to_rc_value(),
+                Keyword::intern("ex-info").to_rc_value(),
+            )
+            .assoc(
+                Keyword::intern("message").to_rc_value(),
+                Rc::clone(&args[0]),
+            )
+            .assoc(Keyword::intern("data").to_rc_value(), Rc::clone(&args[1]));
+        if let Some(cause) = args.get(2) {
+            ex_info = ex_info.assoc(Keyword::intern("cause").to_rc_value(), Rc::clone(cause));
+        }
+        ex_info.to_value()
+    }
+}
+
+/// (ex-data ex)
+///
+/// The map of data of an ex-info;  nil for anything else
+#[derive(Debug, Clone)]
+pub struct ExDataFn {}
+impl ToValue for ExDataFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for ExDataFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        condition_get(args.first().unwrap(), "data")
+    }
+}
+
+/// (ex-message ex)
+///
+/// The message of an error;  nil if it has none
+#[derive(Debug, Clone)]
+pub struct ExMessageFn {}
+impl ToValue for ExMessageFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for ExMessageFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        condition_get(args.first().unwrap(), "message")
+    }
+}
+
+/// (ex-cause ex)
+///
+/// The error that caused an ex-info, if it was given one;  otherwise nil
+#[derive(Debug, Clone)]
+pub struct ExCauseFn {}
+impl ToValue for ExCauseFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for ExCauseFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        condition_get(args.first().unwrap(), "cause")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod ex_info_tests {
+        use crate::ifn::IFn;
+        use crate::keyword::Keyword;
+        use crate::persistent_list_map::{IPersistentMap, PersistentListMap};
+        use crate::rust_core::ex_info::{ExCauseFn, ExDataFn, ExInfoFn, ExMessageFn};
+        use crate::value::{ToValue, Value};
+        use std::rc::Rc;
+
+        #[test]
+        fn ex_info_carries_message_data_and_cause() {
+            let data = PersistentListMap::Empty
+                .assoc(Keyword::intern("a").to_rc_value(), Rc::new(Value::I64(1)))
+                .to_rc_value();
+            let cause = ExInfoFn {}
+                .invoke(vec![
+                    Rc::new(Value::String(String::from("underlying"))),
+                    Rc::clone(&data),
+                ])
+                .to_rc_value();
+            let ex_info = ExInfoFn {}
+                .invoke(vec![
+                    Rc::new(Value::String(String::from("boom"))),
+                    Rc::clone(&data),
+                    Rc::clone(&cause),
+                ])
+                .to_rc_value();
+
+            assert_eq!(
+                Value::String(String::from("boom")),
+                ExMessageFn {}.invoke(vec![Rc::clone(&ex_info)])
+            );
+            assert_eq!(*data, ExDataFn {}.invoke(vec![Rc::clone(&ex_info)]));
+            assert_eq!(*cause, ExCauseFn {}.invoke(vec![Rc::clone(&ex_info)]));
+            assert_eq!(Value::Nil, ExCauseFn {}.invoke(vec![cause]));
+        }
+    }
+}
@@ -1,15 +1,14 @@
 use crate::error_message;
 use crate::ifn::IFn;
-use crate::iterable::Iterable;
-use crate::persistent_list::PersistentList;
-use crate::protocol::{Protocol, ProtocolCastable};
+use crate::protocol::ProtocolCastable;
+use crate::protocols;
+use crate::traits::ISeq;
 use crate::type_tag::TypeTag;
 use crate::value::{ToValue, Value};
 use std::rc::Rc;
 
 /// (first x)
 /// returns first element or nil
-/// TODO: support for strings
 #[derive(Debug, Clone)]
 pub struct FirstFn {}
 impl ToValue for FirstFn {
@@ -22,10 +21,10 @@ impl IFn for FirstFn {
         if args.len() != 1 {
             return error_message::wrong_arg_count(1, args.len());
         }
-        match args.get(0).unwrap().try_as_protocol::<Iterable>() {
-            Some(iterable) => match iterable.iter().next() {
-                Some(val) => val.to_value(),
-                _ => Value::Nil,
+        match args[0].try_as_protocol::<protocols::ISeq>() {
+            Some(iseq) => match iseq.first() {
+                Ok(val) => val.to_value(),
+                Err(condition) => condition.to_value(),
             },
             _ => error_message::type_mismatch(TypeTag::ISeq, args.get(0).unwrap()),
         }
@@ -62,11 +61,18 @@ mod tests {
             assert_eq!(Value::Boolean(true), first.invoke(args));
         }
 
+        #[test]
+        fn first_on_nil() {
+            let first = FirstFn {};
+            let args = vec![Rc::new(Value::Nil)];
+            assert_eq!(Value::Nil, first.invoke(args));
+        }
+
         #[test]
         #[should_panic]
         fn first_on_non_iterable_value() {
             let first = FirstFn {};
-            let args = vec![Rc::new(Value::Nil)];
+            let args = vec![Rc::new(Value::Boolean(true))];
             assert_eq!(Value::Nil, first.invoke(args));
         }
     }
@@ -0,0 +1,52 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// (gensym) (gensym prefix-string)
+///
+/// Returns a new symbol, with a unique name, like G__12
+#[derive(Debug, Clone)]
+pub struct GensymFn {}
+impl ToValue for GensymFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for GensymFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        match args.len() {
+            0 => Symbol::gensym("G__").to_value(),
+            1 => match &**args.first().unwrap() {
+                Value::String(prefix) => Symbol::gensym(prefix).to_value(),
+                _ => error_message::type_mismatch(TypeTag::String, args.first().unwrap()),
+            },
+            _ => error_message::wrong_varg_count(&[0, 1], args.len()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod gensym_tests {
+        use crate::ifn::IFn;
+        use crate::rust_core::gensym::GensymFn;
+        use crate::value::Value;
+        use std::rc::Rc;
+
+        #[test]
+        fn gensym_is_unique() {
+            let gensym = GensymFn {};
+            assert_ne!(gensym.invoke(vec![]), gensym.invoke(vec![]));
+        }
+
+        #[test]
+        fn gensym_with_prefix() {
+            let gensym = GensymFn {};
+            let args = vec![Rc::new(Value::String(String::from("foo")))];
+            assert!(gensym.invoke(args).to_string().starts_with("foo"));
+        }
+    }
+}
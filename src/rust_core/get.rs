@@ -1,10 +1,14 @@
+use crate::error_message;
 use crate::ifn::IFn;
 use crate::persistent_list_map::IPersistentMap;
+use crate::protocols;
 use crate::value::{ToValue, Value};
 use std::rc::Rc;
 
+/// (get coll key)  (get coll key not-found)
+///
 // General get fn; however,  currently just implemented
-// for our one map type, PersistentListMap
+// for our maps and sets, sorted or not, and vectors
 #[derive(Debug, Clone)]
 pub struct GetFn {}
 impl ToValue for GetFn {
@@ -14,19 +18,41 @@ impl ToValue for GetFn {
 }
 impl IFn for GetFn {
     fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
-        if args.len() != 2 {
-            return Value::Condition(format!(
-                "Wrong number of arguments given to function (Given: {}, Expected: 2)",
-                args.len()
-            ));
+        if args.len() != 2 && args.len() != 3 {
+            return error_message::wrong_varg_count(&[2, 3], args.len());
         }
 
-        if let Value::PersistentListMap(pmap) = &*(args.get(0).unwrap().clone()) {
-            let key = args.get(1).unwrap();
-            return pmap.get(key).to_value();
+        match lookup(&args[0], &args[1]) {
+            Ok(Some(val)) => val.to_value(),
+            Ok(None) => args
+                .get(2)
+                .map_or(Value::Nil, |not_found| not_found.to_value()),
+            // @TODO add error in here with erkk's new error tools
+            Err(condition) => condition.to_value(),
         }
-        // @TODO add error in here with erkk's new error tools
+    }
+}
 
-        Value::Nil
+/// What get finds of key in coll;  None, should key not be there, or coll be
+/// nothing get looks in.  Shared with the maps, sets and keywords we call as fns,
+/// so that (:k m default) is (get m :k default)
+pub(crate) fn lookup(coll: &Value, key: &Rc<Value>) -> Result<Option<Rc<Value>>, Rc<Value>> {
+    // Hashing a lazy seq realizes it, which can fail
+    protocols::realize(key)?;
+    match coll {
+        Value::PersistentListMap(plistmap) if plistmap.contains_key(key) => {
+            Ok(Some(plistmap.get(key)))
+        }
+        Value::PersistentHashMap(phashmap) if phashmap.contains_key(key) => {
+            Ok(Some(phashmap.get(key)))
+        }
+        Value::PersistentHashSet(phashset) => Ok(phashset.get(key)),
+        Value::PersistentTreeMap(ptreemap) => ptreemap.get(key),
+        Value::PersistentTreeSet(ptreeset) => ptreeset.get(key),
+        Value::PersistentVector(pvector) => Ok(match &**key {
+            Value::I64(ind) if *ind >= 0 => pvector.nth(*ind as usize).cloned(),
+            _ => None,
+        }),
+        _ => Ok(None),
     }
 }
@@ -0,0 +1,137 @@
+use crate::condition::{Handler, HandlerAction};
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// The handlers of the [condition-type f ..] given to handler-bind* and handler-case*,
+/// each calling its f
+fn handlers(bindings: &Rc<Value>) -> Result<Vec<Handler>, Rc<Value>> {
+    let vals = match &**bindings {
+        Value::PersistentVector(pvector) => pvector.to_vec(),
+        _ => {
+            return Err(Rc::new(error_message::type_mismatch(
+                TypeTag::PersistentVector,
+                bindings,
+            )))
+        }
+    };
+    if vals.len() % 2 != 0 {
+        return Err(Rc::new(error_message::error(
+            "syntax",
+            String::from("Handlers should be given as pairs of a condition type and a fn"),
+        )));
+    }
+    let mut handlers = vec![];
+    for pair in vals.chunks(2) {
+        match &*pair[1] {
+            Value::IFn(ifn) => handlers.push(Handler {
+                condition_type: Rc::clone(&pair[0]),
+                action: HandlerAction::Call(Rc::clone(ifn)),
+            }),
+            _ => {
+                return Err(Rc::new(error_message::type_mismatch(
+                    TypeTag::IFn,
+                    &pair[1],
+                )))
+            }
+        }
+    }
+    Ok(handlers)
+}
+
+fn thunk(thunk: &Rc<Value>) -> Result<Rc<dyn IFn>, Rc<Value>> {
+    match &**thunk {
+        Value::IFn(ifn) => Ok(Rc::clone(ifn)),
+        _ => Err(Rc::new(error_message::type_mismatch(TypeTag::IFn, thunk))),
+    }
+}
+
+/// (handler-bind* [condition-type handler ..] body-fn)
+///
+/// Calls body-fn with handlers in effect, which are called on the conditions of their type
+/// signalled within, where they are signalled.  What handler-bind expands to
+#[derive(Debug, Clone)]
+pub struct HandlerBindFn {
+    enclosing_environment: Rc<Environment>,
+}
+impl HandlerBindFn {
+    pub fn new(enclosing_environment: Rc<Environment>) -> HandlerBindFn {
+        HandlerBindFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for HandlerBindFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for HandlerBindFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let (handlers, body) = match (handlers(&args[0]), thunk(&args[1])) {
+            (Ok(handlers), Ok(body)) => (handlers, body),
+            (Err(condition), _) | (_, Err(condition)) => return condition.to_value(),
+        };
+        self.enclosing_environment
+            .conditions()
+            .with_handlers(handlers, || body.invoke(vec![]))
+    }
+}
+
+/// (handler-case* body-fn [condition-type clause-fn ..])
+///
+/// Calls body-fn;  should a condition of one of our types be signalled within, unwinds
+/// back out of it and returns the clause-fn for that type called on the condition instead.
+/// What handler-case expands to
+#[derive(Debug, Clone)]
+pub struct HandlerCaseFn {
+    enclosing_environment: Rc<Environment>,
+}
+impl HandlerCaseFn {
+    pub fn new(enclosing_environment: Rc<Environment>) -> HandlerCaseFn {
+        HandlerCaseFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for HandlerCaseFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for HandlerCaseFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let (body, mut handlers) = match (thunk(&args[0]), handlers(&args[1])) {
+            (Ok(body), Ok(handlers)) => (body, handlers),
+            (Err(condition), _) | (_, Err(condition)) => return condition.to_value(),
+        };
+        // Rather than call its clause, each handler unwinds back to us first
+        let conditions = self.enclosing_environment.conditions();
+        let mut clauses = vec![];
+        for handler in handlers.iter_mut() {
+            if let HandlerAction::Call(clause) = &handler.action {
+                let id = conditions.new_id();
+                clauses.push((id, Rc::clone(clause)));
+                handler.action = HandlerAction::Unwind(id);
+            }
+        }
+        match conditions.with_handlers(handlers, || body.invoke(vec![])) {
+            Value::Unwind(id, unwind_args) => {
+                match clauses.iter().find(|(clause_id, _)| *clause_id == id) {
+                    Some((_, clause)) => clause.invoke(unwind_args),
+                    None => Value::Unwind(id, unwind_args),
+                }
+            }
+            result => result,
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_vector::PersistentVector;
+use crate::protocol::ProtocolCastable;
+use crate::protocols;
+use crate::rust_core::conj::conj;
+use crate::transient::Transient;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// (into to from)
+///
+/// to, with each value of from conj'd on.  A vector, map or set is built up as a
+/// transient, so that each value is added in place;  anything else, like a list, a
+/// value at a time with conj
+#[derive(Debug, Clone)]
+pub struct IntoFn {}
+impl ToValue for IntoFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for IntoFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        let (to, from) = match args.as_slice() {
+            [] => return PersistentVector::empty().to_value(),
+            [to] => return to.to_value(),
+            [to, from] => (to, from),
+            _ => return error_message::wrong_varg_count(&[0, 1, 2], args.len()),
+        };
+        let from = match from.try_as_protocol::<protocols::ISeq>() {
+            Some(from) => from,
+            None => return error_message::type_mismatch(TypeTag::ISeq, from),
+        };
+        let result = match Transient::new(to) {
+            Some(transient) => from
+                .iter()
+                .try_for_each(|x| transient.conj(x?))
+                .and_then(|_| transient.persistent()),
+            None => from
+                .iter()
+                .try_fold(Rc::clone(to), |coll, x| conj(&coll, x?))
+                .map(|coll| coll.to_value()),
+        };
+        match result {
+            Ok(coll) => coll,
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod into_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn into_each_sort_of_collection() {
+            let repl = Repl::default();
+            assert_eq!(
+                "[1 2 3 4]",
+                repl.eval_str("(into [1 2] '(3 4))").to_string()
+            );
+            assert_eq!(
+                "(4 3 1 2)",
+                repl.eval_str("(into '(1 2) [3 4])").to_string()
+            );
+            assert_eq!(
+                repl.eval_str("{:a 1 :b 2}"),
+                repl.eval_str("(into {:a 0} [[:a 1] [:b 2]])")
+            );
+            assert_eq!(
+                repl.eval_str("{:a 1 :b 2}"),
+                repl.eval_str("(into {} {:a 1 :b 2})")
+            );
+            assert_eq!(
+                repl.eval_str("#{1 2 3}"),
+                repl.eval_str("(into #{1} [2 3 2])")
+            );
+            assert_eq!(
+                "#{1 2 3}",
+                repl.eval_str("(into (sorted-set 3) [2 1])").to_string()
+            );
+            assert_eq!(
+                "[0 1]",
+                repl.eval_str("(into [] (take 2 (range)))").to_string()
+            );
+            assert_eq!("[1]", repl.eval_str("(into [1] nil)").to_string());
+            assert_eq!("[]", repl.eval_str("(into)").to_string());
+        }
+
+        #[test]
+        fn into_keeps_meta() {
+            let repl = Repl::default();
+            assert_eq!(
+                repl.eval_str("{:tag 1}"),
+                repl.eval_str("(meta (into (with-meta [] {:tag 1}) [1 2]))")
+            );
+        }
+
+        #[test]
+        fn into_a_big_map() {
+            let repl = Repl::default();
+            repl.eval_str("(def m (into {} (map (fn [i] [i (inc i)]) (take 3000 (range)))))");
+            assert_eq!(Value::I64(3000), repl.eval_str("(count m)"));
+            assert_eq!(Value::I64(2000), repl.eval_str("(get m 1999)"));
+        }
+    }
+}
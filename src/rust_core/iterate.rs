@@ -0,0 +1,57 @@
+use crate::cons::Cons;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq::LazySeq;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// x, followed by the lazy rest of (iterate f x)
+fn iterate(f: Rc<dyn IFn>, x: Rc<Value>) -> Value {
+    let rest = LazySeq::new(Rc::new(IterateStep {
+        f,
+        x: Rc::clone(&x),
+    }));
+    Value::Cons(Rc::new(Cons::new(
+        x,
+        Value::LazySeq(Rc::new(rest)).to_rc_value(),
+    )))
+}
+
+/// (iterate f x)
+///
+/// The infinite lazy seq x, (f x), (f (f x)) ..
+#[derive(Debug, Clone)]
+pub struct IterateFn {}
+impl ToValue for IterateFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for IterateFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match &*args[0] {
+            Value::IFn(f) => iterate(Rc::clone(f), Rc::clone(&args[1])),
+            _ => error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        }
+    }
+}
+
+/// What's left of an iterate, after x
+#[derive(Debug, Clone)]
+struct IterateStep {
+    f: Rc<dyn IFn>,
+    x: Rc<Value>,
+}
+impl IFn for IterateStep {
+    fn invoke(&self, _args: Vec<Rc<Value>>) -> Value {
+        let next_x = self.f.invoke(vec![Rc::clone(&self.x)]);
+        if next_x.short_circuits() {
+            return next_x;
+        }
+        iterate(Rc::clone(&self.f), next_x.to_rc_value())
+    }
+}
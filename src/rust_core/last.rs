@@ -0,0 +1,65 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_vector::ToPersistentVector;
+use crate::protocol::ProtocolCastable;
+use crate::protocols;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// (last coll)
+///
+/// The last value of coll, or nil if it's empty;  straight there for vectors and sorted
+/// maps and sets, else by walking through all of coll
+#[derive(Debug, Clone)]
+pub struct LastFn {}
+impl ToValue for LastFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for LastFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let last = match &*args[0] {
+            Value::PersistentVector(pvector) => pvector.last().cloned(),
+            Value::PersistentTreeMap(ptreemap) => ptreemap
+                .last()
+                .map(|entry| vec![entry.key, entry.val].into_vector().to_rc_value()),
+            Value::PersistentTreeSet(ptreeset) => ptreeset.last(),
+            _ => match args[0].try_as_protocol::<protocols::ISeq>() {
+                Some(seq) => match seq.iter().last() {
+                    Some(Ok(last)) => Some(last),
+                    Some(Err(condition)) => return condition.to_value(),
+                    None => None,
+                },
+                None => return error_message::type_mismatch(TypeTag::ISeq, &args[0]),
+            },
+        };
+        last.map_or(Value::Nil, |last| last.to_value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod last_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn last_of_colls() {
+            let repl = Repl::default();
+            assert_eq!(Value::I64(3), repl.eval_str("(last [1 2 3])"));
+            assert_eq!(Value::I64(3), repl.eval_str("(last '(1 2 3))"));
+            assert_eq!(Value::I64(4), repl.eval_str("(last (take 5 (range)))"));
+            assert_eq!(
+                repl.eval_str("[:b 2]"),
+                repl.eval_str("(last (sorted-map :b 2 :a 1))")
+            );
+            assert_eq!(Value::Nil, repl.eval_str("(last [])"));
+            assert_eq!(Value::Nil, repl.eval_str("(last nil)"));
+        }
+    }
+}
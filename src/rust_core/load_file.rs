@@ -1,4 +1,5 @@
 use crate::environment::Environment;
+use crate::error_message;
 use crate::ifn::IFn;
 use crate::repl::Repl;
 use crate::type_tag::TypeTag;
@@ -24,21 +25,27 @@ impl ToValue for LoadFileFn {
 impl IFn for LoadFileFn {
     fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
         if args.len() != 1 {
-            Value::Condition(format!(
-                "Wrong number of arguments given to function (Given: {}, Expected: 1)",
-                args.len()
-            ))
+            error_message::error(
+                "arity",
+                format!(
+                    "Wrong number of arguments given to function (Given: {}, Expected: 1)",
+                    args.len()
+                ),
+            )
         } else if let Value::String(file) = &**args.get(0).unwrap() {
             // @TODO clean this
             Repl::new(Rc::clone(&self.enclosing_environment)).try_eval_file(file);
             //@TODO remove this placeholder value, return last value evaluated in try_eval_file
             Value::Nil
         } else {
-            Value::Condition(format!(
-                "Type mismatch; Expected instance of {}, Recieved type {}",
-                TypeTag::String,
-                args.len()
-            ))
+            error_message::error(
+                "type-mismatch",
+                format!(
+                    "Type mismatch; Expected instance of {}, Recieved type {}",
+                    TypeTag::String,
+                    args.len()
+                ),
+            )
         }
     }
 }
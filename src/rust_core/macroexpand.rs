@@ -0,0 +1,65 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// (macroexpand-1 form)
+///
+/// If form is a call to a macro, expands it once;  otherwise returns it as is
+#[derive(Debug, Clone)]
+pub struct Macroexpand1Fn {
+    enclosing_environment: Rc<Environment>,
+}
+impl Macroexpand1Fn {
+    pub fn new(enclosing_environment: Rc<Environment>) -> Macroexpand1Fn {
+        Macroexpand1Fn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for Macroexpand1Fn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for Macroexpand1Fn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        let form = args.first().unwrap();
+        match Value::macroexpand_1(form, &self.enclosing_environment) {
+            Some(expansion) => expansion.to_value(),
+            None => form.to_value(),
+        }
+    }
+}
+
+/// (macroexpand form)
+///
+/// Expands form until it is no longer a call to a macro
+#[derive(Debug, Clone)]
+pub struct MacroexpandFn {
+    enclosing_environment: Rc<Environment>,
+}
+impl MacroexpandFn {
+    pub fn new(enclosing_environment: Rc<Environment>) -> MacroexpandFn {
+        MacroexpandFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for MacroexpandFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for MacroexpandFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        Value::macroexpand(args.first().unwrap(), &self.enclosing_environment).to_value()
+    }
+}
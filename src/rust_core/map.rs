@@ -1,8 +1,9 @@
+use crate::cons::Cons;
 use crate::error_message;
 use crate::ifn::IFn;
-use crate::iterable::Iterable;
-use crate::persistent_list::PersistentList;
+use crate::lazy_seq::LazySeq;
 use crate::protocol::ProtocolCastable;
+use crate::protocols;
 use crate::type_tag::TypeTag;
 use crate::value::{ToValue, Value};
 use std::rc::Rc;
@@ -10,6 +11,9 @@ use std::rc::Rc;
 // This is a tide me over rust wrapper, as map is implemented in lower level primitives
 // in pure Clojure
 // // That being said, I have not decided as to whether or not there is value to having both
+/// (map f coll)
+///
+/// A lazy seq of f called on each value of coll
 #[derive(Debug, Clone)]
 pub struct MapFn {}
 impl ToValue for MapFn {
@@ -19,22 +23,50 @@ impl ToValue for MapFn {
 }
 impl IFn for MapFn {
     fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
-        if args.is_empty() {
-            return error_message::wrong_arg_count(1, args.len());
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
         }
-        if let Value::IFn(ifn) = &**args.get(0).unwrap() {
-            if let Some(iterable) = args.get(1).unwrap().try_as_protocol::<Iterable>() {
-                return iterable
-                    .iter()
-                    .map(|rc_val| Rc::new(ifn.invoke(vec![rc_val])))
-                    .collect::<PersistentList>()
-                    .to_value();
-            }
+        let f: Rc<dyn IFn> = match &*args[0] {
+            Value::IFn(f) => Rc::clone(f),
+            _ => match args[0].try_as_protocol::<protocols::IFn>() {
+                Some(f) => Rc::new(f),
+                None => return error_message::type_mismatch(TypeTag::IFn, &args[0]),
+            },
+        };
+        if !args[1].instanceof::<protocols::ISeq>() {
+            return error_message::type_mismatch(TypeTag::ISeq, &args[1]);
         }
-        Value::Condition(format!(
-            "Type mismatch; Expected instance of {}, Recieved type {}",
-            TypeTag::IFn,
-            args.len()
-        ))
+        Value::LazySeq(Rc::new(LazySeq::new(Rc::new(MapStep {
+            f,
+            coll: Rc::clone(&args[1]),
+        }))))
+    }
+}
+
+/// What's left of a map;  f called on each value of coll
+#[derive(Debug, Clone)]
+struct MapStep {
+    f: Rc<dyn IFn>,
+    coll: Rc<Value>,
+}
+impl IFn for MapStep {
+    fn invoke(&self, _args: Vec<Rc<Value>>) -> Value {
+        let (first, more) = match self.coll.as_protocol::<protocols::ISeq>().uncons() {
+            Ok(Some(first_and_more)) => first_and_more,
+            Ok(None) => return Value::Nil,
+            Err(condition) => return condition.to_value(),
+        };
+        let mapped = self.f.invoke(vec![first]);
+        if mapped.short_circuits() {
+            return mapped;
+        }
+        let rest = LazySeq::new(Rc::new(MapStep {
+            f: Rc::clone(&self.f),
+            coll: more,
+        }));
+        Value::Cons(Rc::new(Cons::new(
+            mapped.to_rc_value(),
+            Value::LazySeq(Rc::new(rest)).to_rc_value(),
+        )))
     }
 }
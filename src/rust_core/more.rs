@@ -1,15 +1,14 @@
 use crate::error_message;
 use crate::ifn::IFn;
-use crate::iterable::Iterable;
-use crate::persistent_list::PersistentList;
-use crate::protocol::{Protocol, ProtocolCastable};
+use crate::protocol::ProtocolCastable;
+use crate::protocols;
+use crate::traits::ISeq;
 use crate::type_tag::TypeTag;
 use crate::value::{ToValue, Value};
 use std::rc::Rc;
 
 /// (more x)
 /// for rest, returns possibly empty sequence after the first
-/// TODO: support for strings
 #[derive(Debug, Clone)]
 pub struct MoreFn {}
 impl ToValue for MoreFn {
@@ -22,12 +21,10 @@ impl IFn for MoreFn {
         if args.len() != 1 {
             return error_message::wrong_arg_count(1, args.len());
         }
-        match args.get(0).unwrap().try_as_protocol::<Iterable>() {
-            Some(iterable) => match iterable.iter().collect::<Vec<Rc<Value>>>().split_first() {
-                Some((_, more)) => {
-                    Value::PersistentList(more.to_vec().into_iter().collect::<PersistentList>())
-                }
-                _ => Value::PersistentList(vec![].into_iter().collect::<PersistentList>()),
+        match args[0].try_as_protocol::<protocols::ISeq>() {
+            Some(iseq) => match iseq.more() {
+                Ok(more) => more.to_value(),
+                Err(condition) => condition.to_value(),
             },
             _ => error_message::type_mismatch(TypeTag::ISeq, args.get(0).unwrap()),
         }
@@ -75,16 +72,16 @@ mod tests {
             let more = MoreFn {};
             let args = vec![Rc::new(Value::PersistentList(
                 vec![
-                    Rc::new(Value::I32(1)),
-                    Rc::new(Value::I32(2)),
-                    Rc::new(Value::I32(3)),
+                    Rc::new(Value::I64(1)),
+                    Rc::new(Value::I64(2)),
+                    Rc::new(Value::I64(3)),
                 ]
                 .into_iter()
                 .collect::<PersistentList>(),
             ))];
             assert_eq!(
                 Value::PersistentList(
-                    vec![Rc::new(Value::I32(2)), Rc::new(Value::I32(3))]
+                    vec![Rc::new(Value::I64(2)), Rc::new(Value::I64(3))]
                         .into_iter()
                         .collect::<PersistentList>()
                 ),
@@ -96,8 +93,18 @@ mod tests {
         #[should_panic]
         fn more_on_non_iterable_value() {
             let more = MoreFn {};
-            let args = vec![Rc::new(Value::Nil)];
+            let args = vec![Rc::new(Value::Boolean(true))];
             assert_eq!(Value::Nil, more.invoke(args));
         }
+
+        #[test]
+        fn more_on_nil() {
+            let more = MoreFn {};
+            let args = vec![Rc::new(Value::Nil)];
+            assert_eq!(
+                Value::PersistentList(vec![].into_iter().collect::<PersistentList>()),
+                more.invoke(args)
+            );
+        }
     }
 }
@@ -24,6 +24,8 @@ impl ToValue for NsMacro {
 }
 impl IFn for NsMacro {
     fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        // Like any macro, we're first given &form and &env, which we have no use for
+        let args = args.get(2..).unwrap_or(&[]);
         if args.len() != 1 {
             return error_message::wrong_arg_count(1, args.len());
         }
@@ -6,7 +6,8 @@ use std::rc::Rc;
 use crate::error_message;
 use crate::persistent_list::PersistentList::{Cons, Empty};
 use crate::persistent_list::ToPersistentListIter;
-use crate::persistent_vector::PersistentVector;
+use crate::protocol::ProtocolCastable;
+use crate::protocols;
 
 /// (nth coll index)
 ///
@@ -25,14 +26,14 @@ impl IFn for NthFn {
         }
         // @TODO change iteration to work with Value references, or even change invoke to work on Rc<..>
         //       as we do everything else; surely we don't want to clone just to read from a collection
-        if let Value::I32(ind) = **args.get(1).unwrap() {
+        if let Value::I64(ind) = **args.get(1).unwrap() {
             if ind < 0 {
-                return error_message::index_cannot_be_negative(ind as usize);
+                return error_message::index_cannot_be_negative(ind);
             }
             let ind = ind as usize;
 
             match &**args.get(0).unwrap() {
-                Value::PersistentList(Cons(head, tail, count)) => {
+                Value::PersistentList(Cons(head, tail, count, _)) => {
                     let count = *count as usize;
                     if ind >= count {
                         error_message::index_out_of_bounds(ind, count)
@@ -43,12 +44,21 @@ impl IFn for NthFn {
                     }
                 }
                 Value::PersistentList(Empty) => error_message::index_out_of_bounds(ind, 0),
-                Value::PersistentVector(PersistentVector { vals }) => {
-                    if ind >= vals.len() {
-                        error_message::index_out_of_bounds(ind, vals.len())
-                    } else {
-                        vals.get(ind).unwrap().to_value()
+                Value::PersistentVector(pvector) => match pvector.nth(ind) {
+                    Some(val) => val.to_value(),
+                    None => error_message::index_out_of_bounds(ind, pvector.len()),
+                },
+                // Only realized as far as ind
+                Value::LazySeq(_) | Value::Cons(_) => {
+                    let mut count = 0;
+                    for val in args[0].as_protocol::<protocols::ISeq>().iter() {
+                        match val {
+                            Ok(val) if count == ind => return val.to_value(),
+                            Ok(_) => count += 1,
+                            Err(condition) => return condition.to_value(),
+                        }
                     }
+                    error_message::index_out_of_bounds(ind, count)
                 }
                 _ => error_message::type_mismatch(TypeTag::ISeq, &**args.get(0).unwrap()),
             }
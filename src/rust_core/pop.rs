@@ -0,0 +1,101 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::PersistentList;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// (pop coll)
+///
+/// coll without the value peek would give;  a vector without its last, or a list or
+/// queue without its first.  (pop nil) is nil
+#[derive(Debug, Clone)]
+pub struct PopFn {}
+impl ToValue for PopFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for PopFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::PersistentVector(pvector) => match pvector.pop() {
+                Some(pvector) => pvector.to_value(),
+                None => {
+                    error_message::error("illegal-state", String::from("Can't pop empty vector"))
+                }
+            },
+            Value::PersistentList(PersistentList::Cons(_, rest, ..)) => rest.to_value(),
+            Value::PersistentList(PersistentList::Empty) => {
+                error_message::error("illegal-state", String::from("Can't pop empty list"))
+            }
+            // Like Clojure, popping an empty queue just gives it back
+            Value::PersistentQueue(pqueue) => pqueue.pop().to_value(),
+            Value::Nil => Value::Nil,
+            _ => error_message::type_mismatch(TypeTag::PersistentVector, &args[0]),
+        }
+    }
+}
+
+/// (peek coll)
+///
+/// The value of coll that's cheapest to get at;  the last of a vector, or the first of a
+/// list or queue.  nil if coll is empty
+#[derive(Debug, Clone)]
+pub struct PeekFn {}
+impl ToValue for PeekFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for PeekFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::PersistentVector(pvector) => match pvector.last() {
+                Some(val) => val.to_value(),
+                None => Value::Nil,
+            },
+            Value::PersistentList(PersistentList::Cons(first, ..)) => first.to_value(),
+            Value::PersistentQueue(pqueue) => match pqueue.peek() {
+                Some(val) => val.to_value(),
+                None => Value::Nil,
+            },
+            Value::PersistentList(PersistentList::Empty) | Value::Nil => Value::Nil,
+            _ => error_message::type_mismatch(TypeTag::PersistentVector, &args[0]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod pop_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn pop_and_peek_work_at_the_cheap_end() {
+            let repl = Repl::default();
+            assert_eq!("[1 2]", repl.eval_str("(pop [1 2 3])").to_string());
+            assert_eq!("(2 3)", repl.eval_str("(pop '(1 2 3))").to_string());
+            assert_eq!(Value::I64(3), repl.eval_str("(peek [1 2 3])"));
+            assert_eq!(Value::I64(1), repl.eval_str("(peek '(1 2 3))"));
+            assert_eq!(Value::Nil, repl.eval_str("(peek [])"));
+            assert_eq!(Value::Nil, repl.eval_str("(pop nil)"));
+        }
+
+        #[test]
+        fn popping_an_empty_vector_is_an_error() {
+            let repl = Repl::default();
+            match repl.eval_str("(pop [])") {
+                Value::Condition(error) => assert!(error.is("illegal-state")),
+                result => panic!("Expected an error, found {}", result),
+            }
+        }
+    }
+}
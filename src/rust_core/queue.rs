@@ -0,0 +1,117 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_queue::PersistentQueue;
+use crate::protocol::ProtocolCastable;
+use crate::protocols;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// (queue) (queue coll)
+///
+/// A queue of the values of coll, first to last;  or an empty queue, as is
+/// clojure.lang.PersistentQueue/EMPTY
+#[derive(Debug, Clone)]
+pub struct QueueFn {}
+impl ToValue for QueueFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for QueueFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        match args.as_slice() {
+            [] => PersistentQueue::empty().to_value(),
+            [coll] => match coll.try_as_protocol::<protocols::ISeq>() {
+                // Realizing a lazy seq can fail
+                Some(seq) => match seq.iter().collect::<Result<PersistentQueue, Rc<Value>>>() {
+                    Ok(pqueue) => pqueue.to_value(),
+                    Err(condition) => condition.to_value(),
+                },
+                None => error_message::type_mismatch(TypeTag::ISeq, coll),
+            },
+            _ => error_message::wrong_varg_count(&[0, 1], args.len()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod queue_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn conj_to_the_back_and_pop_from_the_front() {
+            let repl = Repl::default();
+            repl.eval_str("(def q (conj clojure.lang.PersistentQueue/EMPTY 1 2 3))");
+            assert_eq!(Value::I64(1), repl.eval_str("(peek q)"));
+            assert_eq!("#queue [2 3]", repl.eval_str("(pop q)").to_string());
+            assert_eq!(
+                "#queue [2 3 4]",
+                repl.eval_str("(conj (pop q) 4)").to_string()
+            );
+            assert_eq!(Value::I64(3), repl.eval_str("(count q)"));
+            assert_eq!("(1 2 3)", repl.eval_str("(seq q)").to_string());
+            assert_eq!(Value::Nil, repl.eval_str("(peek (queue))"));
+            assert_eq!(Value::Nil, repl.eval_str("(seq (pop (queue [1])))"));
+            assert_eq!("#queue []", repl.eval_str("(pop (queue))").to_string());
+        }
+
+        #[test]
+        fn queues_print_and_read_back() {
+            let repl = Repl::default();
+            assert_eq!(
+                "#queue [1 2 3]",
+                repl.eval_str("(queue '(1 2 3))").to_string()
+            );
+            assert_eq!(
+                repl.eval_str("(queue [1 2 3])"),
+                repl.eval_str("#queue [1 2 3]")
+            );
+            assert_eq!(
+                repl.eval_str("(queue [2 :a])"),
+                repl.eval_str("#queue [(inc 1) :a]")
+            );
+            assert_eq!("#queue []", repl.eval_str("'#queue []").to_string());
+        }
+
+        #[test]
+        fn queues_are_equal_to_sequences_of_the_same_values() {
+            let repl = Repl::default();
+            assert_eq!(
+                repl.eval_str("true"),
+                repl.eval_str("(= (queue [1 2]) [1 2])")
+            );
+            assert_eq!(
+                repl.eval_str("true"),
+                repl.eval_str("(= '(1 2) (queue [1 2]))")
+            );
+            assert_eq!(
+                repl.eval_str("true"),
+                repl.eval_str("(= (pop (queue [0 1 2])) (conj (queue [1]) 2))")
+            );
+            assert_eq!(
+                repl.eval_str("false"),
+                repl.eval_str("(= (queue [1 2]) [2 1])")
+            );
+            assert_eq!(
+                repl.eval_str("false"),
+                repl.eval_str("(= (queue [1 2]) #{1 2})")
+            );
+            assert_eq!(
+                Value::I64(1),
+                repl.eval_str("(count (hash-set (queue [1 2]) [1 2]))")
+            );
+        }
+
+        #[test]
+        fn a_breadth_first_traversal() {
+            let repl = Repl::default();
+            repl.eval_str("(def tree {:a [:b :c] :b [:d] :c [:e] :d [] :e []})");
+            repl.eval_str("(def order (loop [q (queue [:a]) seen []] (if (peek q) (recur (into (pop q) (get tree (peek q))) (conj seen (peek q))) seen)))",
+            );
+            assert_eq!("[:a :b :c :d :e]", repl.eval_str("order").to_string());
+        }
+    }
+}
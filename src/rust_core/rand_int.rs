@@ -19,13 +19,16 @@ impl IFn for RandIntFn {
             1 => {
                 let arg = args.get(0).unwrap().to_value();
                 match arg {
-                    Value::I32(i_) => Value::I32(thread_rng().gen_range(0, i_)),
-                    Value::F64(f_) => Value::I32(thread_rng().gen_range(0, f_ as i32)),
-                    _ => Value::Condition(format!(
-                        // TODO: what error message should be returned regarding using typetags?
-                        "Type mismatch; Expecting: (i32 | i64 | f32 | f64), Found: {}",
-                        arg.type_tag()
-                    )),
+                    Value::I64(i_) => Value::I64(thread_rng().gen_range(0, i_)),
+                    Value::F64(f_) => Value::I64(thread_rng().gen_range(0, f_ as i64)),
+                    _ => error_message::error(
+                        "type-mismatch",
+                        format!(
+                            // TODO: what error message should be returned regarding using typetags?
+                            "Type mismatch; Expecting: (i32 | i64 | f32 | f64), Found: {}",
+                            arg.type_tag()
+                        ),
+                    ),
                 }
             }
             _ => error_message::wrong_arg_count(1, args.len()),
@@ -0,0 +1,98 @@
+use crate::cons::Cons;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq::LazySeq;
+use crate::rust_core::AddFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+fn number(n: &Rc<Value>) -> Result<f64, Rc<Value>> {
+    match &**n {
+        Value::I64(n) => Ok(*n as f64),
+        Value::F64(n) => Ok(*n),
+        _ => Err(Rc::new(error_message::type_mismatch(TypeTag::Integer, n))),
+    }
+}
+
+/// (range)
+/// (range end)
+/// (range start end)
+/// (range start end step)
+///
+/// The lazy seq start, start + step, start + 2 * step .. up to, but not including, end.
+/// start defaults to 0, step to 1, and end to never ending
+#[derive(Debug, Clone)]
+pub struct RangeFn {}
+impl ToValue for RangeFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for RangeFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        let zero = Rc::new(Value::I64(0));
+        let one = Rc::new(Value::I64(1));
+        let (start, end, step) = match args.as_slice() {
+            [] => (zero, None, one),
+            [end] => (zero, Some(end), one),
+            [start, end] => (Rc::clone(start), Some(end), one),
+            [start, end, step] => (Rc::clone(start), Some(end), Rc::clone(step)),
+            _ => return error_message::wrong_varg_count(&[0, 1, 2, 3], args.len()),
+        };
+        let numbers = match (number(&start), end.map(number).transpose(), number(&step)) {
+            (Ok(_), Ok(end), Ok(step_size)) => (end, step_size),
+            (Err(condition), ..) | (_, Err(condition), _) | (.., Err(condition)) => {
+                return condition.to_value()
+            }
+        };
+        Value::LazySeq(Rc::new(LazySeq::new(Rc::new(RangeStep {
+            start,
+            end: numbers.0,
+            step,
+            step_size: numbers.1,
+        }))))
+    }
+}
+
+/// What's left of a range, from start on
+#[derive(Debug, Clone)]
+struct RangeStep {
+    start: Rc<Value>,
+    end: Option<f64>,
+    step: Rc<Value>,
+    // step, for comparing against end
+    step_size: f64,
+}
+impl IFn for RangeStep {
+    fn invoke(&self, _args: Vec<Rc<Value>>) -> Value {
+        if let Some(end) = self.end {
+            let start = match number(&self.start) {
+                Ok(start) => start,
+                Err(condition) => return condition.to_value(),
+            };
+            let done = if self.step_size > 0.0 {
+                start >= end
+            } else if self.step_size < 0.0 {
+                start <= end
+            } else {
+                start == end
+            };
+            if done {
+                return Value::Nil;
+            }
+        }
+        let next_start = AddFn {}.invoke(vec![Rc::clone(&self.start), Rc::clone(&self.step)]);
+        if next_start.short_circuits() {
+            return next_start;
+        }
+        let rest = LazySeq::new(Rc::new(RangeStep {
+            start: next_start.to_rc_value(),
+            ..self.clone()
+        }));
+        Value::Cons(Rc::new(Cons::new(
+            Rc::clone(&self.start),
+            Value::LazySeq(Rc::new(rest)).to_rc_value(),
+        )))
+    }
+}
@@ -0,0 +1,139 @@
+use crate::condition::Restart;
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// A restart is named by a symbol or keyword, as invoke-restart takes either
+fn restart_name(name: &Value) -> Option<Symbol> {
+    match name {
+        Value::Symbol(sym) => Some(sym.clone()),
+        Value::Keyword(kw) => Some(Symbol::intern(kw.name())),
+        _ => None,
+    }
+}
+
+/// (restart-case* body-fn [[name params restart-fn] ..])
+///
+/// Calls body-fn with our restarts available;  should one be invoked within, unwinds
+/// back out of it and returns its restart-fn called on the arguments it was invoked with.
+/// What restart-case expands to
+#[derive(Debug, Clone)]
+pub struct RestartCaseFn {
+    enclosing_environment: Rc<Environment>,
+}
+impl RestartCaseFn {
+    pub fn new(enclosing_environment: Rc<Environment>) -> RestartCaseFn {
+        RestartCaseFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for RestartCaseFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for RestartCaseFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let body = match &*args[0] {
+            Value::IFn(ifn) => Rc::clone(ifn),
+            _ => return error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        };
+        let clause_vals = match &*args[1] {
+            Value::PersistentVector(pvector) => pvector.to_vec(),
+            _ => return error_message::type_mismatch(TypeTag::PersistentVector, &args[1]),
+        };
+        let conditions = self.enclosing_environment.conditions();
+        let mut restarts = vec![];
+        let mut clauses = vec![];
+        for clause in clause_vals.iter() {
+            let clause_vals = match &**clause {
+                Value::PersistentVector(pvector) => pvector.to_vec(),
+                _ => return error_message::type_mismatch(TypeTag::PersistentVector, clause),
+            };
+            match clause_vals.as_slice() {
+                [name, params, restart_fn] => match (restart_name(name), &**restart_fn) {
+                    (Some(name), Value::IFn(restart_fn)) => {
+                        let id = conditions.new_id();
+                        restarts.push(Restart {
+                            name,
+                            id,
+                            params: Rc::clone(params),
+                        });
+                        clauses.push((id, Rc::clone(restart_fn)));
+                    }
+                    _ => {
+                        return error_message::error(
+                            "syntax",
+                            format!(
+                                "Restarts should be given as [name params fn], not {}",
+                                clause
+                            ),
+                        )
+                    }
+                },
+                _ => {
+                    return error_message::error(
+                        "syntax",
+                        format!(
+                            "Restarts should be given as [name params fn], not {}",
+                            clause
+                        ),
+                    )
+                }
+            }
+        }
+        match conditions.with_restarts(restarts, || body.invoke(vec![])) {
+            Value::Unwind(id, unwind_args) => {
+                match clauses.iter().find(|(clause_id, _)| *clause_id == id) {
+                    Some((_, restart_fn)) => restart_fn.invoke(unwind_args),
+                    None => Value::Unwind(id, unwind_args),
+                }
+            }
+            result => result,
+        }
+    }
+}
+
+/// (invoke-restart name & args)
+///
+/// Unwinds to the innermost restart of that name, a symbol or keyword, and calls it on args
+#[derive(Debug, Clone)]
+pub struct InvokeRestartFn {
+    enclosing_environment: Rc<Environment>,
+}
+impl InvokeRestartFn {
+    pub fn new(enclosing_environment: Rc<Environment>) -> InvokeRestartFn {
+        InvokeRestartFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for InvokeRestartFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for InvokeRestartFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        let name = match args.first().map(|name| &**name) {
+            Some(Value::Symbol(sym)) => sym.name.clone(),
+            Some(Value::Keyword(kw)) => kw.name().to_string(),
+            Some(name) => return error_message::type_mismatch(TypeTag::Symbol, name),
+            None => return error_message::zero_arg_count(args.len()),
+        };
+        match self.enclosing_environment.conditions().find_restart(&name) {
+            Some(restart) => Value::Unwind(restart.id, args[1..].to_vec()),
+            None => {
+                error_message::error("no-restart", format!("No restart named {} is active", name))
+            }
+        }
+    }
+}
@@ -55,11 +55,11 @@ mod tests {
         fn second_on_iterable_with_two_value_list() {
             let second = SecondFn {};
             let args = vec![Rc::new(Value::PersistentList(
-                vec![Rc::new(Value::I32(1)), Rc::new(Value::I32(2))]
+                vec![Rc::new(Value::I64(1)), Rc::new(Value::I64(2))]
                     .into_iter()
                     .collect::<PersistentList>(),
             ))];
-            assert_eq!(Value::I32(2), second.invoke(args));
+            assert_eq!(Value::I64(2), second.invoke(args));
         }
 
         #[test]
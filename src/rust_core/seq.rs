@@ -0,0 +1,283 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq::LazySeq;
+use crate::protocol::ProtocolCastable;
+use crate::protocols;
+use crate::traits::ISeq;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+fn iseq(coll: &Rc<Value>) -> Result<protocols::ISeq, Rc<Value>> {
+    coll.try_as_protocol::<protocols::ISeq>()
+        .ok_or_else(|| Rc::new(error_message::type_mismatch(TypeTag::ISeq, coll)))
+}
+
+/// (seq coll)
+///
+/// nil, if coll is empty, or else a seq of its values
+#[derive(Debug, Clone)]
+pub struct SeqFn {}
+impl ToValue for SeqFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for SeqFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match iseq(&args[0]).map(|iseq| iseq.seq()) {
+            Ok(Ok(seq)) => seq.to_value(),
+            Ok(Err(condition)) | Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// (next coll)
+///
+/// The values of coll after its first, or nil if there are none
+#[derive(Debug, Clone)]
+pub struct NextFn {}
+impl ToValue for NextFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for NextFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match iseq(&args[0]).map(|iseq| iseq.next()) {
+            Ok(Ok(next)) => next.to_value(),
+            Ok(Err(condition)) | Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// (cons x coll)
+///
+/// A seq of x, followed by the values of coll
+#[derive(Debug, Clone)]
+pub struct ConsFn {}
+impl ToValue for ConsFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for ConsFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match iseq(&args[1]) {
+            Ok(iseq) => iseq.cons(Rc::clone(&args[0])).to_value(),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// (lazy-seq* body-fn)
+///
+/// A seq of whatever body-fn returns, calling it only once that's asked for.  What
+/// lazy-seq expands to
+#[derive(Debug, Clone)]
+pub struct LazySeqFn {}
+impl ToValue for LazySeqFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for LazySeqFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match &*args[0] {
+            Value::IFn(thunk) => Value::LazySeq(Rc::new(LazySeq::new(Rc::clone(thunk)))),
+            _ => error_message::type_mismatch(TypeTag::IFn, &args[0]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod seq_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn seq_covers_every_collection() {
+            let repl = Repl::default();
+            assert_eq!(Value::Nil, repl.eval_str("(seq nil)"));
+            assert_eq!(Value::Nil, repl.eval_str("(seq [])"));
+            assert_eq!(Value::Nil, repl.eval_str("(seq \"\")"));
+            assert_eq!("(1 2)", repl.eval_str("(seq [1 2])").to_string());
+            assert_eq!("([:a 1])", repl.eval_str("(seq {:a 1})").to_string());
+            assert_eq!("(\\a \\b)", repl.eval_str("(seq \"ab\")").to_string());
+            assert_eq!(Value::Nil, repl.eval_str("(next '(1))"));
+            assert_eq!("(2)", repl.eval_str("(next [1 2])").to_string());
+            assert_eq!("(0 1 2)", repl.eval_str("(cons 0 [1 2])").to_string());
+        }
+
+        #[test]
+        fn lazy_seqs_are_only_realized_as_far_as_they_are_used() {
+            let repl = Repl::default();
+            repl.eval_str("(def realized (atom 0))");
+            repl.eval_str(
+                "(defn numbers [n] (lazy-seq (swap! realized inc) (cons n (numbers (inc n)))))",
+            );
+            repl.eval_str("(def xs (numbers 0))");
+            assert_eq!(Value::I64(0), repl.eval_str("@realized"));
+            assert_eq!(Value::Boolean(false), repl.eval_str("(realized? xs)"));
+            assert_eq!(Value::I64(2), repl.eval_str("(first (next (next xs)))"));
+            assert_eq!(Value::I64(3), repl.eval_str("@realized"));
+            // Realized values are kept, rather than worked out again
+            assert_eq!(Value::I64(2), repl.eval_str("(first (rest (rest xs)))"));
+            assert_eq!(Value::I64(3), repl.eval_str("@realized"));
+            assert_eq!(Value::Boolean(true), repl.eval_str("(realized? xs)"));
+        }
+
+        #[test]
+        fn lazy_seqs_equal_lists_and_vectors_with_the_same_values() {
+            let repl = Repl::default();
+            assert_eq!(
+                Value::Boolean(true),
+                repl.eval_str("(= '(2 3) (map inc [1 2]))")
+            );
+            assert_eq!(
+                Value::Boolean(true),
+                repl.eval_str("(= (map inc [1 2]) [2 3])")
+            );
+            assert_eq!(
+                Value::Boolean(true),
+                repl.eval_str("(= '() (lazy-seq nil))")
+            );
+            assert_eq!(
+                Value::Boolean(false),
+                repl.eval_str("(= nil (lazy-seq nil))")
+            );
+            // As they're equal, they hash alike, and so are the one key in a set
+            assert_eq!(
+                Value::I64(1),
+                repl.eval_str("(count (hash-set (range 3) [0 1 2]))")
+            );
+            assert_eq!(
+                Value::I64(1),
+                repl.eval_str("(count (hash-set (map inc [0 1]) '(1 2)))")
+            );
+        }
+
+        #[test]
+        fn sequential_collections_all_equal_one_another() {
+            let repl = Repl::default();
+            for src in &[
+                "(= [0 1] '(0 1))",
+                "(= '(0 1) [0 1])",
+                "(= [] '())",
+                "(= #queue [0 1] '(0 1) [0 1] (range 2))",
+            ] {
+                assert_eq!(Value::Boolean(true), repl.eval_str(src), "{}", src);
+            }
+            assert_eq!(Value::Boolean(false), repl.eval_str("(= [0 1] '(1 0))"));
+            assert_eq!(Value::Boolean(false), repl.eval_str("(= [0 1] '(0 1 2))"));
+            // So a set holds one of them, whichever order they go in
+            assert_eq!(
+                Value::I64(1),
+                repl.eval_str("(count (hash-set [0 1] '(0 1) (range 2)))")
+            );
+            assert_eq!(
+                Value::I64(1),
+                repl.eval_str("(count (hash-set (range 2) [0 1] '(0 1)))")
+            );
+        }
+
+        #[test]
+        fn infinite_seqs_can_be_taken_from() {
+            let repl = Repl::default();
+            assert_eq!(
+                "(0 1 2)",
+                repl.eval_str("(take 3 (iterate inc 0))").to_string()
+            );
+            assert_eq!("(0 1 2 3)", repl.eval_str("(take 4 (range))").to_string());
+            assert_eq!(Value::I64(10), repl.eval_str("(nth (range) 10)"));
+            assert_eq!(
+                "(1 2 3)",
+                repl.eval_str("(take 3 (map inc (range)))").to_string()
+            );
+            assert_eq!(
+                "(0 1 2 0 1)",
+                repl.eval_str("(take 5 (concat (range 3) (range)))")
+                    .to_string()
+            );
+            assert_eq!("(1 3 5 7)", repl.eval_str("(range 1 9 2)").to_string());
+            assert_eq!("(3 2 1)", repl.eval_str("(range 3 0 -1)").to_string());
+            assert_eq!(Value::I64(5), repl.eval_str("(count (range 5))"));
+        }
+
+        #[test]
+        fn errors_realizing_a_lazy_seq_come_back_out() {
+            let repl = Repl::default();
+            match repl.eval_str("(first (lazy-seq (nth [] 0)))") {
+                Value::Condition(error) => assert!(error.is("index-out-of-bounds")),
+                result => panic!("Expected an error, found {}", result),
+            }
+            match repl.eval_str("(count (map (fn [n] (nth [] n)) [0]))") {
+                Value::Condition(error) => assert!(error.is("index-out-of-bounds")),
+                result => panic!("Expected an error, found {}", result),
+            }
+            // Even one only realized to be printed, and inside a vector at that
+            match repl.eval_str("[(map (fn [x] (/ 1 x)) [1 0])]") {
+                Value::Condition(error) => assert!(error.is("divide-by-zero")),
+                result => panic!("Expected an error, found {}", result),
+            }
+            assert_eq!(
+                Repl::read_string(":divide-by-zero"),
+                repl.eval_str("(:type *e)")
+            );
+        }
+
+        #[test]
+        fn errors_realizing_a_lazy_seq_are_not_printed_compared_or_hashed_away() {
+            let repl = Repl::default();
+            for src in &[
+                "(str (lazy-seq (foo)))",
+                "(str [(lazy-seq (foo))])",
+                "(= (map (fn [x] (foo)) [1]) [1])",
+                "(= [1] (map (fn [x] (foo)) [1]))",
+                "(= [[1]] [(map (fn [x] (foo)) [1])])",
+                "(= (map (fn [x] (foo)) [1]) [])",
+                "(hash-set (lazy-seq (foo)))",
+                "(set [(lazy-seq (foo))])",
+                "(conj #{} (lazy-seq (foo)))",
+                "(assoc {} (lazy-seq (foo)) 1)",
+                "(get {} (lazy-seq (foo)))",
+                "(contains? #{} (lazy-seq (foo)))",
+            ] {
+                match repl.eval_str(src) {
+                    Value::Condition(error) => assert!(error.is("undefined-symbol"), "{}", src),
+                    result => panic!("Expected {} to be an error, found {}", src, result),
+                }
+            }
+            // Nor is an unwind to a restart
+            assert_eq!(
+                Value::I64(1),
+                repl.eval_str(
+                    "(restart-case (str (map (fn [x] (invoke-restart :r x)) [1 2])) (:r [v] v))"
+                )
+            );
+            // = only realizes as far as its arguments differ
+            assert_eq!(Value::Boolean(false), repl.eval_str("(= (range) [0 1])"));
+        }
+
+        #[test]
+        fn macros_can_build_their_expansions_lazily() {
+            let repl = Repl::default();
+            repl.eval_str("(defmacro plus [& xs] (concat '(+) xs))");
+            assert_eq!(Value::I64(6), repl.eval_str("(plus 1 2 3)"));
+            assert_eq!(Value::I64(6), repl.eval_str("(apply + [1 2 3])"));
+        }
+    }
+}
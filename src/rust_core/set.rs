@@ -0,0 +1,108 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::protocol::ProtocolCastable;
+use crate::protocols;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// (set coll)
+///
+/// A set of the distinct values of coll
+#[derive(Debug, Clone)]
+pub struct SetFn {}
+impl ToValue for SetFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for SetFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        if let Value::PersistentHashSet(_) = &*args[0] {
+            return args[0].to_value();
+        }
+        match args[0].try_as_protocol::<protocols::ISeq>() {
+            // Realizing a lazy seq can fail
+            Some(seq) => match seq
+                .iter()
+                .map(|key| {
+                    // As can hashing one
+                    let key = key?;
+                    protocols::realize(&key)?;
+                    Ok(key)
+                })
+                .collect::<Result<PersistentHashSet, Rc<Value>>>()
+            {
+                Ok(set) => set.to_value(),
+                Err(condition) => condition.to_value(),
+            },
+            None => error_message::type_mismatch(TypeTag::ISeq, &args[0]),
+        }
+    }
+}
+
+/// (hash-set & keys)
+///
+/// A set of keys
+#[derive(Debug, Clone)]
+pub struct HashSetFn {}
+impl ToValue for HashSetFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for HashSetFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        // Hashing a lazy seq realizes it, which can fail
+        if let Err(condition) = args.iter().try_for_each(protocols::realize) {
+            return condition.to_value();
+        }
+        args.into_iter().collect::<PersistentHashSet>().to_value()
+    }
+}
+
+/// (disj set & keys)
+///
+/// set, without keys;  (disj nil :a) is nil
+#[derive(Debug, Clone)]
+pub struct DisjFn {}
+impl ToValue for DisjFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for DisjFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], args.len());
+        }
+        match &*args[0] {
+            Value::Nil => Value::Nil,
+            Value::PersistentHashSet(phashset) => {
+                // Hashing a lazy seq realizes it, which can fail
+                if let Err(condition) = args[1..].iter().try_for_each(protocols::realize) {
+                    return condition.to_value();
+                }
+                args[1..]
+                    .iter()
+                    .fold(phashset.clone(), |phashset, key| phashset.disj(key))
+                    .to_value()
+            }
+            Value::PersistentTreeSet(ptreeset) => {
+                let mut ptreeset = ptreeset.clone();
+                for key in args[1..].iter() {
+                    ptreeset = match ptreeset.disj(key) {
+                        Ok(ptreeset) => ptreeset,
+                        Err(condition) => return condition.to_value(),
+                    };
+                }
+                ptreeset.to_value()
+            }
+            _ => error_message::type_mismatch(TypeTag::PersistentHashSet, &args[0]),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::condition;
+use crate::environment::Environment;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// (signal condition-type) (signal condition-type data)
+///
+/// Offers a condition to the handlers for it;  returns nil if they all decline
+#[derive(Debug, Clone)]
+pub struct SignalFn {
+    enclosing_environment: Rc<Environment>,
+}
+impl SignalFn {
+    pub fn new(enclosing_environment: Rc<Environment>) -> SignalFn {
+        SignalFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for SignalFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for SignalFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        let condition = match condition::make_condition(&args) {
+            Ok(condition) => condition,
+            Err(condition) => return condition.to_value(),
+        };
+        self.enclosing_environment
+            .conditions()
+            .signal(&condition)
+            .unwrap_or(Value::Nil)
+    }
+}
+
+/// (error message) (error condition-type) (error condition-type data)
+///
+/// Signals a condition that must be handled;  if nothing does, at the REPL we ask
+/// which restart to take, and otherwise just return the error
+#[derive(Debug, Clone)]
+pub struct ErrorFn {
+    enclosing_environment: Rc<Environment>,
+}
+impl ErrorFn {
+    pub fn new(enclosing_environment: Rc<Environment>) -> ErrorFn {
+        ErrorFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for ErrorFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for ErrorFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        let condition = match condition::make_condition(&args) {
+            Ok(condition) => condition,
+            Err(condition) => return condition.to_value(),
+        };
+        condition::raise(&self.enclosing_environment, &condition)
+    }
+}
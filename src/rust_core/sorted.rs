@@ -0,0 +1,201 @@
+use crate::compare::Comparator;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_tree_map::PersistentTreeMap;
+use crate::persistent_tree_set::PersistentTreeSet;
+use crate::type_tag::TypeTag;
+use crate::util::IsEven;
+use crate::value::{ToValue, Value};
+use itertools::Itertools;
+use std::rc::Rc;
+
+/// The comparator fn given to sorted-map-by or sorted-set-by
+fn comparator_of(f: &Rc<Value>) -> Result<Comparator, Rc<Value>> {
+    match &**f {
+        Value::IFn(f) => Ok(Comparator::Fn(Rc::clone(f))),
+        _ => Err(Rc::new(error_message::type_mismatch(TypeTag::IFn, f))),
+    }
+}
+
+fn sorted_map(comparator: Comparator, keyvals: &[Rc<Value>]) -> Value {
+    if !keyvals.len().is_even() {
+        return error_message::error(
+            "illegal-argument",
+            format!("No value supplied for key: {}", keyvals.last().unwrap()),
+        );
+    }
+    let mut ptreemap = PersistentTreeMap::with_comparator(comparator);
+    for (key, val) in keyvals.iter().tuples() {
+        ptreemap = match ptreemap.assoc(Rc::clone(key), Rc::clone(val)) {
+            Ok(ptreemap) => ptreemap,
+            Err(condition) => return condition.to_value(),
+        };
+    }
+    ptreemap.to_value()
+}
+
+fn sorted_set(comparator: Comparator, keys: &[Rc<Value>]) -> Value {
+    let mut ptreeset = PersistentTreeSet::with_comparator(comparator);
+    for key in keys.iter() {
+        ptreeset = match ptreeset.conj(Rc::clone(key)) {
+            Ok(ptreeset) => ptreeset,
+            Err(condition) => return condition.to_value(),
+        };
+    }
+    ptreeset.to_value()
+}
+
+/// (sorted-map & keyvals)
+///
+/// A map of keyvals, kept in the order of its keys, by compare
+#[derive(Debug, Clone)]
+pub struct SortedMapFn {}
+impl ToValue for SortedMapFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for SortedMapFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        sorted_map(Comparator::Default, &args)
+    }
+}
+
+/// (sorted-map-by comparator & keyvals)
+///
+/// A map of keyvals, kept in the order of its keys, by comparator
+#[derive(Debug, Clone)]
+pub struct SortedMapByFn {}
+impl ToValue for SortedMapByFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for SortedMapByFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], args.len());
+        }
+        match comparator_of(&args[0]) {
+            Ok(comparator) => sorted_map(comparator, &args[1..]),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// (sorted-set & keys)
+///
+/// A set of keys, kept in order, by compare
+#[derive(Debug, Clone)]
+pub struct SortedSetFn {}
+impl ToValue for SortedSetFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for SortedSetFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        sorted_set(Comparator::Default, &args)
+    }
+}
+
+/// (sorted-set-by comparator & keys)
+///
+/// A set of keys, kept in order, by comparator
+#[derive(Debug, Clone)]
+pub struct SortedSetByFn {}
+impl ToValue for SortedSetByFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for SortedSetByFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], args.len());
+        }
+        match comparator_of(&args[0]) {
+            Ok(comparator) => sorted_set(comparator, &args[1..]),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod sorted_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn sorted_maps_keep_their_keys_in_order() {
+            let repl = Repl::default();
+            assert_eq!(
+                "{1 :a, 2 :b, 3 :c}",
+                repl.eval_str("(sorted-map 3 :c 1 :a 2 :b)").to_string()
+            );
+            assert_eq!(
+                "{:a 1, :b 3}",
+                repl.eval_str("(assoc (sorted-map :b 2) :a 1 :b 3)")
+                    .to_string()
+            );
+            assert_eq!(
+                "{:c 3}",
+                repl.eval_str("(dissoc (sorted-map :a 1 :c 3) :a)")
+                    .to_string()
+            );
+            assert_eq!(
+                Value::I64(2),
+                repl.eval_str("(get (sorted-map :a 1 :b 2) :b)")
+            );
+            assert_eq!(
+                repl.eval_str("[1 :a]"),
+                repl.eval_str("(first (sorted-map 2 :b 1 :a))")
+            );
+            assert_eq!(
+                repl.eval_str("{:a 1 :b 2}"),
+                repl.eval_str("(sorted-map :b 2 :a 1)")
+            );
+        }
+
+        #[test]
+        fn sorted_sets_keep_their_values_in_order() {
+            let repl = Repl::default();
+            assert_eq!(
+                "#{1 2 3}",
+                repl.eval_str("(conj (sorted-set 3 1) 2 1)").to_string()
+            );
+            assert_eq!(
+                "#{1}",
+                repl.eval_str("(disj (sorted-set 1 2) 2)").to_string()
+            );
+            assert_eq!(Value::I64(1), repl.eval_str("((sorted-set 1 2) 1)"));
+            assert_eq!(repl.eval_str("#{1 2}"), repl.eval_str("(sorted-set 2 1)"));
+            assert_eq!(Value::I64(3), repl.eval_str("(last (sorted-set 3 1 2))"));
+        }
+
+        #[test]
+        fn sorted_by_a_comparator() {
+            let repl = Repl::default();
+            assert_eq!(
+                "{3 :c, 2 :b, 1 :a}",
+                repl.eval_str("(sorted-map-by gt 1 :a 3 :c 2 :b)")
+                    .to_string()
+            );
+            assert_eq!(
+                "#{\"ccc\" \"bb\" \"a\"}",
+                repl.eval_str("(sorted-set-by (fn [a b] (compare b a)) \"a\" \"ccc\" \"bb\")")
+                    .to_string()
+            );
+        }
+
+        #[test]
+        fn keys_that_dont_compare_are_an_error() {
+            let repl = Repl::default();
+            match repl.eval_str("(sorted-map 1 :a :b 2)") {
+                Value::Condition(error) => assert!(error.is("type-mismatch")),
+                result => panic!("Expected an error, found {}", result),
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::ifn::IFn;
+use crate::protocols;
 use crate::value::{ToValue, Value};
 use std::rc::Rc;
 
@@ -13,6 +14,10 @@ impl ToValue for StrFn {
 }
 impl IFn for StrFn {
     fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        // Printing a lazy seq realizes it, which can fail
+        if let Err(condition) = args.iter().try_for_each(protocols::realize) {
+            return condition.to_value();
+        }
         Value::String(
             args.into_iter()
                 .map(|arg| arg.to_string())
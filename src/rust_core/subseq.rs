@@ -0,0 +1,241 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_list::ToPersistentList;
+use crate::persistent_vector::ToPersistentVector;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+/// A bound on the keys of a subseq, like the gt 1 of (subseq sc gt 1);  just as in
+/// Clojure, a key is within it should (test (compare key 1) 0) be true
+struct Bound {
+    test: Rc<dyn IFn>,
+    key: Rc<Value>,
+}
+impl Bound {
+    fn new(test: &Rc<Value>, key: &Rc<Value>) -> Result<Bound, Rc<Value>> {
+        match &**test {
+            Value::IFn(test) => Ok(Bound {
+                test: Rc::clone(test),
+                key: Rc::clone(key),
+            }),
+            _ => Err(Rc::new(error_message::type_mismatch(TypeTag::IFn, test))),
+        }
+    }
+    fn includes_ordering(&self, ordering: Ordering) -> Result<bool, Rc<Value>> {
+        let included = self.test.invoke(vec![
+            Rc::new(Value::I64(ordering as i64)),
+            Rc::new(Value::I64(0)),
+        ]);
+        if included.short_circuits() {
+            return Err(Rc::new(included));
+        }
+        Ok(included.is_truthy())
+    }
+    fn includes(&self, sc: &Rc<Value>, key: &Rc<Value>) -> Result<bool, Rc<Value>> {
+        self.includes_ordering(compare(sc, key, &self.key)?)
+    }
+    /// Whether we bound keys from below, as gt and gte do
+    fn is_lower(&self) -> Result<bool, Rc<Value>> {
+        self.includes_ordering(Ordering::Greater)
+    }
+}
+
+/// How a compares to b, by sc's comparator
+fn compare(sc: &Rc<Value>, a: &Rc<Value>, b: &Rc<Value>) -> Result<Ordering, Rc<Value>> {
+    match &**sc {
+        Value::PersistentTreeMap(ptreemap) => ptreemap.compare(a, b),
+        Value::PersistentTreeSet(ptreeset) => ptreeset.compare(a, b),
+        _ => Err(Rc::new(error_message::type_mismatch(
+            TypeTag::PersistentTreeMap,
+            sc,
+        ))),
+    }
+}
+
+/// Each key of a sorted map or set, along with what its seq has for it
+type Items = Box<dyn Iterator<Item = (Rc<Value>, Rc<Value>)>>;
+
+/// The items of sc, a sorted map or set;  from start on if given, in order if ascending,
+/// else in reverse
+fn items(sc: &Rc<Value>, start: Option<&Rc<Value>>, ascending: bool) -> Result<Items, Rc<Value>> {
+    match &**sc {
+        Value::PersistentTreeMap(ptreemap) => {
+            let entries = match start {
+                Some(start) => ptreemap.seek(start, ascending)?,
+                None if ascending => ptreemap.iter(),
+                None => ptreemap.rev_iter(),
+            };
+            Ok(Box::new(entries.map(|entry| {
+                (
+                    Rc::clone(&entry.key),
+                    vec![entry.key, entry.val].into_vector().to_rc_value(),
+                )
+            })))
+        }
+        Value::PersistentTreeSet(ptreeset) => {
+            let vals = match start {
+                Some(start) => ptreeset.seek(start, ascending)?,
+                None if ascending => ptreeset.iter(),
+                None => ptreeset.rev_iter(),
+            };
+            Ok(Box::new(vals.map(|val| (Rc::clone(&val), val))))
+        }
+        _ => Err(Rc::new(error_message::type_mismatch(
+            TypeTag::PersistentTreeMap,
+            sc,
+        ))),
+    }
+}
+
+/// What of sc is within the bounds of args, as a seq in order if ascending, else in reverse
+fn subseq(args: &[Rc<Value>], ascending: bool) -> Result<Rc<Value>, Rc<Value>> {
+    // Whichever bound is on the side we start from, we seek straight to;  the other
+    // is where we stop
+    let (start, end) = match args.len() {
+        3 => {
+            let bound = Bound::new(&args[1], &args[2])?;
+            if bound.is_lower()? == ascending {
+                (Some(bound), None)
+            } else {
+                (None, Some(bound))
+            }
+        }
+        5 => {
+            let lower = Bound::new(&args[1], &args[2])?;
+            let upper = Bound::new(&args[3], &args[4])?;
+            if ascending {
+                (Some(lower), Some(upper))
+            } else {
+                (Some(upper), Some(lower))
+            }
+        }
+        _ => {
+            return Err(Rc::new(error_message::wrong_varg_count(
+                &[3, 5],
+                args.len(),
+            )))
+        }
+    };
+    let sc = &args[0];
+    let mut items = items(sc, start.as_ref().map(|start| &start.key), ascending)?.peekable();
+    // Seeking leaves us at the start bound's key itself, should sc have it, which the
+    // bound may leave out
+    if let (Some(start), Some((key, _))) = (&start, items.peek()) {
+        if !start.includes(sc, key)? {
+            items.next();
+        }
+    }
+    let mut vals = vec![];
+    for (key, val) in items {
+        if let Some(end) = &end {
+            if !end.includes(sc, &key)? {
+                break;
+            }
+        }
+        vals.push(val);
+    }
+    if vals.is_empty() {
+        Ok(Rc::new(Value::Nil))
+    } else {
+        Ok(vals.into_list().to_rc_value())
+    }
+}
+
+/// (subseq sc test key)
+/// (subseq sc start-test start-key end-test end-key)
+///
+/// A seq of what of sc, a sorted map or set, has keys within the bounds given, in order;
+/// a key is within test and key should (test (compare k key) 0) be true, as in
+///   (subseq (sorted-set 1 2 3 4) gt 1 lte 3) => (2 3)
+#[derive(Debug, Clone)]
+pub struct SubseqFn {}
+impl ToValue for SubseqFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for SubseqFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        match subseq(&args, true) {
+            Ok(seq) => seq.to_value(),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// (rsubseq sc test key)
+/// (rsubseq sc start-test start-key end-test end-key)
+///
+/// Like subseq, but in reverse order
+#[derive(Debug, Clone)]
+pub struct RsubseqFn {}
+impl ToValue for RsubseqFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for RsubseqFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        match subseq(&args, false) {
+            Ok(seq) => seq.to_value(),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod subseq_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn subseq_of_one_bound() {
+            let repl = Repl::default();
+            repl.eval_str("(def s (sorted-set 5 1 4 2 3))");
+            assert_eq!(repl.eval_str("'(4 5)"), repl.eval_str("(subseq s gt 3)"));
+            assert_eq!(repl.eval_str("'(3 4 5)"), repl.eval_str("(subseq s gte 3)"));
+            assert_eq!(repl.eval_str("'(1 2)"), repl.eval_str("(subseq s lt 3)"));
+            assert_eq!(repl.eval_str("'(2 1)"), repl.eval_str("(rsubseq s lt 3)"));
+            assert_eq!(
+                repl.eval_str("'(5 4 3)"),
+                repl.eval_str("(rsubseq s gte 3)")
+            );
+            assert_eq!(Value::Nil, repl.eval_str("(subseq s gt 5)"));
+        }
+
+        #[test]
+        fn subseq_between_two_bounds() {
+            let repl = Repl::default();
+            repl.eval_str("(def m (sorted-map 1 :a 2 :b 3 :c 4 :d))");
+            assert_eq!(
+                repl.eval_str("'([2 :b] [3 :c])"),
+                repl.eval_str("(subseq m gt 1 lte 3)")
+            );
+            assert_eq!(
+                repl.eval_str("'([3 :c] [2 :b])"),
+                repl.eval_str("(rsubseq m gte 2 lt 4)")
+            );
+            // Bounds that aren't keys of m themselves
+            repl.eval_str("(def s (sorted-set 10 20 30))");
+            assert_eq!(
+                repl.eval_str("'(20)"),
+                repl.eval_str("(subseq s gt 15 lt 25)")
+            );
+        }
+
+        #[test]
+        fn subseq_with_the_wrong_arg_count() {
+            let repl = Repl::default();
+            for src in &["(subseq)", "(rsubseq)", "(subseq (sorted-set 1) gt)"] {
+                assert!(
+                    matches!(repl.eval_str(src), Value::Condition(_)),
+                    "Expected {} to be an error",
+                    src
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::cons::Cons;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::lazy_seq::LazySeq;
+use crate::protocol::ProtocolCastable;
+use crate::protocols;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// (take n coll)
+///
+/// A lazy seq of the first n values of coll, or all of them, should there be fewer
+#[derive(Debug, Clone)]
+pub struct TakeFn {}
+impl ToValue for TakeFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for TakeFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        let n = match &*args[0] {
+            Value::I64(n) => *n,
+            _ => return error_message::type_mismatch(TypeTag::Integer, &args[0]),
+        };
+        if !args[1].instanceof::<protocols::ISeq>() {
+            return error_message::type_mismatch(TypeTag::ISeq, &args[1]);
+        }
+        Value::LazySeq(Rc::new(LazySeq::new(Rc::new(TakeStep {
+            n,
+            coll: Rc::clone(&args[1]),
+        }))))
+    }
+}
+
+/// What's left of a take;  the first n values of coll
+#[derive(Debug, Clone)]
+struct TakeStep {
+    n: i64,
+    coll: Rc<Value>,
+}
+impl IFn for TakeStep {
+    fn invoke(&self, _args: Vec<Rc<Value>>) -> Value {
+        // Checked first, so we realize no more of coll than we take
+        if self.n <= 0 {
+            return Value::Nil;
+        }
+        match self.coll.as_protocol::<protocols::ISeq>().uncons() {
+            Ok(Some((first, more))) => {
+                let rest = LazySeq::new(Rc::new(TakeStep {
+                    n: self.n - 1,
+                    coll: more,
+                }));
+                Value::Cons(Rc::new(Cons::new(
+                    first,
+                    Value::LazySeq(Rc::new(rest)).to_rc_value(),
+                )))
+            }
+            Ok(None) => Value::Nil,
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
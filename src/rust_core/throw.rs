@@ -0,0 +1,34 @@
+use crate::condition;
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// (throw e)
+///
+/// Raises e, usually an ex-info, as an error;  to be caught by a try, or any handler for it
+#[derive(Debug, Clone)]
+pub struct ThrowFn {
+    enclosing_environment: Rc<Environment>,
+}
+impl ThrowFn {
+    pub fn new(enclosing_environment: Rc<Environment>) -> ThrowFn {
+        ThrowFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for ThrowFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for ThrowFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        condition::raise(&self.enclosing_environment, args.first().unwrap())
+    }
+}
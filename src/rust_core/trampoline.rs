@@ -0,0 +1,54 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// (trampoline f & args)
+///
+/// Calls f on args; so long as what comes back is itself a fn, calls that
+/// with no arguments, and so on, returning the first value that isn't a fn
+#[derive(Debug, Clone)]
+pub struct TrampolineFn {}
+impl ToValue for TrampolineFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for TrampolineFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], args.len());
+        }
+        let mut result = match &**args.first().unwrap() {
+            Value::IFn(ifn) => ifn.invoke(args.get(1..).unwrap().to_vec()),
+            _ => return error_message::type_mismatch(TypeTag::IFn, args.first().unwrap()),
+        };
+        while let Value::IFn(ifn) = result {
+            result = ifn.invoke(vec![]);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod trampoline_tests {
+        use crate::error_message;
+        use crate::ifn::IFn;
+        use crate::rust_core::trampoline::TrampolineFn;
+        use crate::type_tag::TypeTag;
+        use crate::value::Value;
+        use std::rc::Rc;
+
+        #[test]
+        fn trampoline_on_non_fn() {
+            let trampoline = TrampolineFn {};
+            let args = vec![Rc::new(Value::I64(1))];
+            assert_eq!(
+                error_message::type_mismatch(TypeTag::IFn, &Value::I64(1)),
+                trampoline.invoke(args)
+            );
+        }
+    }
+}
@@ -0,0 +1,289 @@
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::persistent_vector::PersistentVector;
+use crate::transient::Transient;
+use crate::type_tag::TypeTag;
+use crate::util::IsEven;
+use crate::value::{ToValue, Value};
+use itertools::Itertools;
+use std::rc::Rc;
+
+fn transient(coll: &Rc<Value>) -> Result<Rc<Transient>, Rc<Value>> {
+    match &**coll {
+        Value::Transient(transient) => Ok(Rc::clone(transient)),
+        _ => Err(Rc::new(error_message::type_mismatch(
+            TypeTag::Transient,
+            coll,
+        ))),
+    }
+}
+
+/// The transient a fn of them was given, once each of its args has been put to it by
+/// edit;  or the first error
+fn edit_each<F>(args: Vec<Rc<Value>>, step: usize, edit: F) -> Value
+where
+    F: Fn(&Transient, &[Rc<Value>]) -> Result<(), Rc<Value>>,
+{
+    let transient = match transient(&args[0]) {
+        Ok(transient) => transient,
+        Err(condition) => return condition.to_value(),
+    };
+    for chunk in args[1..].chunks(step) {
+        if let Err(condition) = edit(&transient, chunk) {
+            return condition.to_value();
+        }
+    }
+    args[0].to_value()
+}
+
+/// (transient coll)
+///
+/// A transient of the vector, map or set coll;  an editable version of it, cheap to add
+/// to, that persistent! turns back into a persistent collection
+#[derive(Debug, Clone)]
+pub struct TransientFn {}
+impl ToValue for TransientFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for TransientFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match Transient::new(&args[0]) {
+            Some(transient) => Value::Transient(Rc::new(transient)),
+            None => error_message::error(
+                "type-mismatch",
+                format!("Cannot make a transient of {}", args[0].type_tag()),
+            ),
+        }
+    }
+}
+
+/// (persistent! coll)
+///
+/// The transient coll, made persistent again;  coll can't be used after this
+#[derive(Debug, Clone)]
+pub struct PersistentFn {}
+impl ToValue for PersistentFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for PersistentFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match transient(&args[0]).and_then(|transient| transient.persistent()) {
+            Ok(coll) => coll,
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// (conj! coll x)
+///
+/// Adds x to the transient coll, as conj would, and returns coll.  (conj!) is a new
+/// transient vector
+#[derive(Debug, Clone)]
+pub struct TransientConjFn {}
+impl ToValue for TransientConjFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for TransientConjFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        match args.len() {
+            0 => Value::Transient(Rc::new(
+                Transient::new(&PersistentVector::empty().to_value()).unwrap(),
+            )),
+            1 | 2 => edit_each(args, 1, |transient, x| transient.conj(Rc::clone(&x[0]))),
+            _ => error_message::wrong_varg_count(&[0, 1, 2], args.len()),
+        }
+    }
+}
+
+/// (assoc! coll key val & kvs)
+///
+/// Associates each key with its val in the transient map coll, or replaces the value at
+/// each index key of the transient vector coll, and returns coll
+#[derive(Debug, Clone)]
+pub struct TransientAssocFn {}
+impl ToValue for TransientAssocFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for TransientAssocFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() < 3 || args.len().is_even() {
+            return error_message::error(
+                "arity",
+                format!(
+                "Wrong number of arguments given to function (Given: {}, Expected: 3 | 5 | 7 | ..)",
+                args.len()
+            ),
+            );
+        }
+        edit_each(args, 2, |transient, kv| {
+            match kv.iter().cloned().collect_tuple() {
+                Some((key, val)) => transient.assoc(key, val),
+                None => Ok(()),
+            }
+        })
+    }
+}
+
+/// (dissoc! coll & keys)
+///
+/// Takes keys out of the transient map coll, and returns coll
+#[derive(Debug, Clone)]
+pub struct TransientDissocFn {}
+impl ToValue for TransientDissocFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for TransientDissocFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], args.len());
+        }
+        edit_each(args, 1, |transient, key| transient.dissoc(&key[0]))
+    }
+}
+
+/// (pop! coll)
+///
+/// Takes the last value off the transient vector coll, and returns coll
+#[derive(Debug, Clone)]
+pub struct TransientPopFn {}
+impl ToValue for TransientPopFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for TransientPopFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match transient(&args[0]).and_then(|transient| transient.pop()) {
+            Ok(()) => args[0].to_value(),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// (disj! coll & keys)
+///
+/// Takes keys out of the transient set coll, and returns coll
+#[derive(Debug, Clone)]
+pub struct TransientDisjFn {}
+impl ToValue for TransientDisjFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for TransientDisjFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.is_empty() {
+            return error_message::wrong_varg_count(&[1], args.len());
+        }
+        edit_each(args, 1, |transient, key| transient.disj(&key[0]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod transient_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn building_in_place() {
+            let repl = Repl::default();
+            assert_eq!(
+                "[1 2 3]",
+                repl.eval_str("(persistent! (conj! (conj! (conj! (transient []) 1) 2) 3))")
+                    .to_string()
+            );
+            assert_eq!(
+                "[:a 2]",
+                repl.eval_str("(persistent! (pop! (assoc! (transient [1 2 3]) 0 :a)))")
+                    .to_string()
+            );
+            assert_eq!(
+                repl.eval_str("{:b 2 :c 3}"),
+                repl.eval_str("(persistent! (dissoc! (assoc! (transient {:a 1}) :b 2 :c 3) :a))")
+            );
+            assert_eq!(
+                repl.eval_str("#{1 3}"),
+                repl.eval_str("(persistent! (disj! (conj! (transient #{1 2}) 3) 2))")
+            );
+            assert_eq!(
+                Value::I64(2),
+                repl.eval_str("(count (conj! (transient [1]) 2))")
+            );
+        }
+
+        #[test]
+        fn building_a_big_map_in_a_loop() {
+            let repl = Repl::default();
+            repl.eval_str("(def m (loop [i 0 t (transient {})] (if (= i 5000) (persistent! t) (recur (inc i) (assoc! t i (inc i))))))",
+            );
+            assert_eq!(Value::I64(5000), repl.eval_str("(count m)"));
+            assert_eq!(Value::I64(4000), repl.eval_str("(get m 3999)"));
+        }
+
+        #[test]
+        fn the_original_is_untouched() {
+            let repl = Repl::default();
+            repl.eval_str("(def v [1 2 3])");
+            repl.eval_str("(def t (transient v))");
+            repl.eval_str("(assoc! t 0 :a)");
+            repl.eval_str("(conj! t 4)");
+            assert_eq!("[:a 2 3 4]", repl.eval_str("(persistent! t)").to_string());
+            assert_eq!("[1 2 3]", repl.eval_str("v").to_string());
+        }
+
+        #[test]
+        fn a_transient_is_done_with_after_persistent() {
+            let repl = Repl::default();
+            repl.eval_str("(def t (transient []))");
+            repl.eval_str("(persistent! t)");
+            for src in &["(conj! t 1)", "(persistent! t)", "(count t)"] {
+                match repl.eval_str(src) {
+                    Value::Condition(error) => {
+                        assert!(error.is("illegal-access"));
+                        assert_eq!("Transient used after persistent! call", error.message);
+                    }
+                    result => panic!("Expected an error, found {}", result),
+                }
+            }
+        }
+
+        #[test]
+        fn only_the_right_ops_for_each_collection() {
+            let repl = Repl::default();
+            for src in &[
+                "(transient '(1 2))",
+                "(pop! (transient []))",
+                "(assoc! (transient [1]) 5 :a)",
+                "(disj! (transient []) 1)",
+                "(dissoc! (transient #{}) 1)",
+                "(conj! [] 1)",
+            ] {
+                assert!(
+                    matches!(repl.eval_str(src), Value::Condition(_)),
+                    "Expected {} to be an error",
+                    src
+                );
+            }
+        }
+    }
+}
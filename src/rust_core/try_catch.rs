@@ -0,0 +1,355 @@
+use crate::condition;
+use crate::condition::{Handler, HandlerAction};
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::keyword::Keyword;
+use crate::persistent_list::{PersistentList, ToPersistentList, ToPersistentListIter};
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap};
+use crate::persistent_vector::ToPersistentVector;
+use crate::symbol::Symbol;
+use crate::traits::IObj;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+/// (try expr* (catch type e expr*)* (finally expr*)?)
+///
+/// Expands to (try* (fn [] expr*) ['type (fn [e] expr*) ..] (fn [] expr*))
+#[derive(Debug, Clone)]
+pub struct TryMacro {}
+impl ToValue for TryMacro {
+    fn to_value(&self) -> Value {
+        Value::Macro(Rc::new(self.clone()))
+    }
+}
+impl TryMacro {
+    /// (fn ^{:recur-across clause} params body*);  a fn of a clause's body, which a recur
+    /// in it can't be made to, rather than crossing it
+    fn fn_form(params: Vec<Rc<Value>>, body: &[Rc<Value>], clause: &str) -> Rc<Value> {
+        let meta = PersistentListMap::Empty.assoc(
+            Keyword::intern("recur-across").to_rc_value(),
+            Value::String(String::from(clause)).to_rc_value(),
+        );
+        let mut fn_form = vec![
+            Symbol::intern_with_ns("clojure.core", "fn").to_rc_value(),
+            params.into_vector().with_meta(meta).to_rc_value(),
+        ];
+        fn_form.extend_from_slice(body);
+        fn_form.into_list().to_rc_value()
+    }
+    /// If form is (clause-name ..), its contents, minus the clause-name
+    fn clause(form: &Rc<Value>, clause_name: &str) -> Option<Vec<Rc<Value>>> {
+        if let Value::PersistentList(PersistentList::Cons(head, tail, ..)) = &**form {
+            if **head == Symbol::intern(clause_name).to_value() {
+                return Some(tail.iter().collect());
+            }
+        }
+        None
+    }
+}
+impl IFn for TryMacro {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        // Like any macro, we're first given &form and &env, which we have no use for
+        let args = args.get(2..).unwrap_or(&[]);
+
+        let mut body = vec![];
+        let mut catches = vec![];
+        let mut finally = Rc::new(Value::Nil);
+        for (i, form) in args.iter().enumerate() {
+            if let Some(catch) = TryMacro::clause(form, "catch") {
+                // (catch type e expr*)
+                let binding = match catch.get(1).map(|binding| &**binding) {
+                    Some(Value::Symbol(_)) => Rc::clone(&catch[1]),
+                    _ => {
+                        return error_message::error(
+                            "syntax",
+                            format!(
+                            "catch takes a type and a symbol, as in (catch Exception e ..), not {}",
+                            form
+                        ),
+                        )
+                    }
+                };
+                catches.push(
+                    vec![Symbol::intern("quote").to_rc_value(), Rc::clone(&catch[0])]
+                        .into_list()
+                        .to_rc_value(),
+                );
+                catches.push(TryMacro::fn_form(vec![binding], &catch[2..], "catch"));
+            } else if let Some(finally_body) = TryMacro::clause(form, "finally") {
+                if i != args.len() - 1 {
+                    return error_message::error(
+                        "syntax",
+                        String::from("finally clause must be the last thing in a try"),
+                    );
+                }
+                finally = TryMacro::fn_form(vec![], &finally_body, "finally");
+            } else if catches.is_empty() {
+                body.push(Rc::clone(form));
+            } else {
+                return error_message::error(
+                    "syntax",
+                    String::from("Only catch or finally clauses can follow catch in a try"),
+                );
+            }
+        }
+        vec![
+            Symbol::intern_with_ns("clojure.core", "try*").to_rc_value(),
+            TryMacro::fn_form(vec![], &body, "try"),
+            catches.into_vector().to_rc_value(),
+            finally,
+        ]
+        .into_list()
+        .to_value()
+    }
+}
+
+/// Which conditions a (catch type ..) catches, as the condition type a handler for them
+/// would have;  Exception and friends, or :default, catch anything, ExceptionInfo catches
+/// ex-infos, and a keyword catches conditions of that :type
+fn catch_condition_type(catch_type: &Rc<Value>) -> Result<Rc<Value>, Rc<Value>> {
+    match &**catch_type {
+        Value::Keyword(kw) if kw.name() == "default" => {
+            Ok(Keyword::intern("condition").to_rc_value())
+        }
+        Value::Keyword(_) => Ok(Rc::clone(catch_type)),
+        Value::Symbol(sym) => match sym.name.as_str() {
+            "Throwable" | "Exception" | "RuntimeException" | "Error" => {
+                Ok(Keyword::intern("condition").to_rc_value())
+            }
+            "ExceptionInfo" | "clojure.lang.ExceptionInfo" => {
+                Ok(Keyword::intern("ex-info").to_rc_value())
+            }
+            _ => Err(Rc::new(error_message::error(
+                "syntax",
+                format!("Unable to resolve classname: {}", sym),
+            ))),
+        },
+        _ => Err(Rc::new(error_message::type_mismatch(
+            TypeTag::Keyword,
+            catch_type,
+        ))),
+    }
+}
+
+/// (try* body-fn [catch-type catch-fn ..] finally-fn)
+///
+/// Calls body-fn;  should it throw, or return, an error that one of our catch-types
+/// catches, returns the first such catch-fn called on it instead.  Either way, calls
+/// finally-fn last, if given one.  What try expands to
+#[derive(Debug, Clone)]
+pub struct TryFn {
+    enclosing_environment: Rc<Environment>,
+}
+impl TryFn {
+    pub fn new(enclosing_environment: Rc<Environment>) -> TryFn {
+        TryFn {
+            enclosing_environment,
+        }
+    }
+    fn try_catch(&self, body: &Rc<Value>, catches: &Rc<Value>) -> Value {
+        let body = match &**body {
+            Value::IFn(ifn) => Rc::clone(ifn),
+            _ => return error_message::type_mismatch(TypeTag::IFn, body),
+        };
+        let catch_vals = match &**catches {
+            Value::PersistentVector(pvector) => pvector.to_vec(),
+            _ => return error_message::type_mismatch(TypeTag::PersistentVector, catches),
+        };
+        // As with handler-case, each catch is a handler that unwinds back to us
+        let conditions = self.enclosing_environment.conditions();
+        let mut handlers = vec![];
+        let mut clauses = vec![];
+        for pair in catch_vals.chunks(2) {
+            let condition_type = match catch_condition_type(&pair[0]) {
+                Ok(condition_type) => condition_type,
+                Err(condition) => return condition.to_value(),
+            };
+            let clause = match pair.get(1).map(|clause| &**clause) {
+                Some(Value::IFn(clause)) => Rc::clone(clause),
+                _ => {
+                    return error_message::error(
+                        "syntax",
+                        String::from("catch is missing its body"),
+                    )
+                }
+            };
+            let id = conditions.new_id();
+            handlers.push(Handler {
+                condition_type,
+                action: HandlerAction::Unwind(id),
+            });
+            clauses.push((id, clause));
+        }
+        match conditions.with_handlers(handlers.clone(), || body.invoke(vec![])) {
+            Value::Unwind(id, unwind_args) => {
+                match clauses.iter().find(|(clause_id, _)| *clause_id == id) {
+                    Some((_, clause)) => clause.invoke(unwind_args),
+                    None => Value::Unwind(id, unwind_args),
+                }
+            }
+            // An error that came back to us as a value, rather than being thrown
+            Value::Condition(error) => {
+                let condition = condition::from_error(&error);
+                let error_type = condition::condition_type(&condition);
+                let clause = handlers
+                    .iter()
+                    .zip(clauses.iter())
+                    .find(|(handler, _)| handler.handles(&error_type));
+                match clause {
+                    Some((_, (_, clause))) => clause.invoke(vec![condition]),
+                    None => Value::Condition(error),
+                }
+            }
+            result => result,
+        }
+    }
+}
+impl ToValue for TryFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for TryFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 3 {
+            return error_message::wrong_arg_count(3, args.len());
+        }
+        let result = self.try_catch(&args[0], &args[1]);
+        match &*args[2] {
+            Value::IFn(finally) => {
+                // What finally returns is thrown away, unless it's on its way out itself
+                let finally_result = finally.invoke(vec![]);
+                if finally_result.short_circuits() {
+                    return finally_result;
+                }
+                result
+            }
+            Value::Nil => result,
+            _ => error_message::type_mismatch(TypeTag::IFn, &args[2]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod try_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn try_catches_thrown_ex_info() {
+            let repl = Repl::default();
+            assert_eq!(
+                Repl::read_string("[\"boom\" {:a 1}]"),
+                repl.eval_str(
+                    "(try
+                       (+ 1 (throw (ex-info \"boom\" {:a 1})))
+                       (catch :other e :other)
+                       (catch ExceptionInfo e [(ex-message e) (ex-data e)]))"
+                )
+            );
+        }
+
+        #[test]
+        fn try_catches_errors_returned_as_values() {
+            let repl = Repl::default();
+            assert_eq!(
+                Repl::read_string("{:index 5 :count 2}"),
+                repl.eval_str("(try (nth [1 2] 5) (catch Exception e (ex-data e)))")
+            );
+        }
+
+        #[test]
+        fn try_catches_errors_by_kind() {
+            let repl = Repl::default();
+            assert_eq!(
+                Repl::read_string(":arity"),
+                repl.eval_str(
+                    "(try ((fn [x] x))
+                       (catch :type-mismatch e :type-mismatch)
+                       (catch :arity e (get e :type)))"
+                )
+            );
+        }
+
+        #[test]
+        fn try_without_error_returns_body() {
+            let repl = Repl::default();
+            assert_eq!(
+                Value::I64(3),
+                repl.eval_str("(try 1 (+ 1 2) (catch Exception e 0))")
+            );
+        }
+
+        #[test]
+        fn finally_always_runs() {
+            let repl = Repl::default();
+            repl.eval_str(
+                "(defn f [x]
+                   (restart-case
+                     (try x (finally (invoke-restart :ran-finally x)))
+                     (ran-finally [x] [:finally x])))",
+            );
+            assert_eq!(Repl::read_string("[:finally 1]"), repl.eval_str("(f 1)"));
+            assert_eq!(
+                Repl::read_string(":caught"),
+                repl.eval_str(
+                    "(try (try (throw (ex-info \"inner\" {})) (finally 1))
+                          (catch ExceptionInfo e :caught))"
+                )
+            );
+        }
+
+        #[test]
+        fn uncaught_throw_passes_through() {
+            let repl = Repl::default();
+            match repl.eval_str("(try (throw (ex-info \"boom\" {})) (catch :other e :other))") {
+                Value::Condition(error) => {
+                    assert!(error.is("ex-info"));
+                    assert_eq!("Unhandled condition :ex-info: boom", error.message);
+                }
+                result => panic!("Expected an error, found {}", result),
+            }
+        }
+
+        #[test]
+        fn recur_cannot_cross_a_try() {
+            let repl = Repl::default();
+            for (src, message) in &[
+                ("(loop [] (try (recur)))", "Cannot recur across try"),
+                ("((fn [] (try (recur))))", "Cannot recur across try"),
+                (
+                    "(loop [x 0] (if (lt x 3) (try (recur (inc x))) x))",
+                    "Cannot recur across try",
+                ),
+                (
+                    "(loop [x 0] (try (throw (ex-info \"x\" {})) (catch :default e (recur 1))))",
+                    "Cannot recur across catch",
+                ),
+                (
+                    "(loop [x 0] (try 1 (finally (recur 1))))",
+                    "Cannot recur across finally",
+                ),
+            ] {
+                match repl.eval_str(src) {
+                    Value::Condition(error) => {
+                        assert!(error.is("syntax"), "{}", src);
+                        assert_eq!(*message, error.message, "{}", src);
+                    }
+                    result => panic!("Expected {} to be an error, found {}", src, result),
+                }
+            }
+            // Though a loop or fn inside one is still somewhere to recur to
+            assert_eq!(
+                Value::I64(3),
+                repl.eval_str("(try (loop [x 0] (if (lt x 3) (recur (inc x)) x)))")
+            );
+            assert_eq!(
+                Value::I64(3),
+                repl.eval_str("(loop [x 0] (if (lt x 3) (recur (try (inc x))) x))")
+            );
+        }
+    }
+}
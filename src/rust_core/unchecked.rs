@@ -0,0 +1,165 @@
+//! The unchecked- fns;  arithmetic that wraps around on i64 overflow, rather than being
+//! an error, as it is in Java.  On anything but two i64s, they're the same as + - and *
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::numbers::{self, Number, Overflow};
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+fn unary<F>(args: &[Rc<Value>], op: F) -> Value
+where
+    F: Fn(&Number) -> Result<Number, Rc<Value>>,
+{
+    if args.len() != 1 {
+        return error_message::wrong_arg_count(1, args.len());
+    }
+    match Number::of(&args[0]).and_then(|x| op(&x)) {
+        Ok(n) => n.to_value(),
+        Err(condition) => condition.to_value(),
+    }
+}
+
+fn binary<F>(args: &[Rc<Value>], op: F) -> Value
+where
+    F: Fn(&Number, &Number) -> Result<Number, Rc<Value>>,
+{
+    if args.len() != 2 {
+        return error_message::wrong_arg_count(2, args.len());
+    }
+    match Number::of(&args[0]).and_then(|x| op(&x, &Number::of(&args[1])?)) {
+        Ok(n) => n.to_value(),
+        Err(condition) => condition.to_value(),
+    }
+}
+
+/// (unchecked-add x y)
+#[derive(Debug, Clone)]
+pub struct UncheckedAddFn {}
+impl ToValue for UncheckedAddFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for UncheckedAddFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        binary(&args, |x, y| numbers::add(x, y, Overflow::Wrap))
+    }
+}
+
+/// (unchecked-subtract x y)
+#[derive(Debug, Clone)]
+pub struct UncheckedSubtractFn {}
+impl ToValue for UncheckedSubtractFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for UncheckedSubtractFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        binary(&args, |x, y| numbers::subtract(x, y, Overflow::Wrap))
+    }
+}
+
+/// (unchecked-multiply x y)
+#[derive(Debug, Clone)]
+pub struct UncheckedMultiplyFn {}
+impl ToValue for UncheckedMultiplyFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for UncheckedMultiplyFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        binary(&args, |x, y| numbers::multiply(x, y, Overflow::Wrap))
+    }
+}
+
+/// (unchecked-inc x)
+#[derive(Debug, Clone)]
+pub struct UncheckedIncFn {}
+impl ToValue for UncheckedIncFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for UncheckedIncFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        unary(&args, |x| numbers::add(x, &Number::I64(1), Overflow::Wrap))
+    }
+}
+
+/// (unchecked-dec x)
+#[derive(Debug, Clone)]
+pub struct UncheckedDecFn {}
+impl ToValue for UncheckedDecFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for UncheckedDecFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        unary(&args, |x| {
+            numbers::subtract(x, &Number::I64(1), Overflow::Wrap)
+        })
+    }
+}
+
+/// (unchecked-negate x)
+#[derive(Debug, Clone)]
+pub struct UncheckedNegateFn {}
+impl ToValue for UncheckedNegateFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for UncheckedNegateFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        unary(&args, |x| {
+            numbers::subtract(&Number::I64(0), x, Overflow::Wrap)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod unchecked_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn wrapping_around() {
+            let repl = Repl::default();
+            assert_eq!(
+                Value::I64(i64::MIN),
+                repl.eval_str("(unchecked-add 9223372036854775807 1)")
+            );
+            assert_eq!(
+                Value::I64(i64::MAX),
+                repl.eval_str("(unchecked-dec -9223372036854775808)")
+            );
+            assert_eq!(
+                Value::I64(i64::MIN),
+                repl.eval_str("(unchecked-inc 9223372036854775807)")
+            );
+            assert_eq!(
+                Value::I64(i64::MIN),
+                repl.eval_str("(unchecked-negate -9223372036854775808)")
+            );
+            assert_eq!(
+                Value::I64(-2),
+                repl.eval_str("(unchecked-multiply 9223372036854775807 2)")
+            );
+            assert_eq!(
+                Value::I64(i64::MAX),
+                repl.eval_str("(unchecked-subtract -9223372036854775808 1)")
+            );
+        }
+
+        #[test]
+        fn anything_else_as_usual() {
+            let repl = Repl::default();
+            assert_eq!("5/6", repl.eval_str("(unchecked-add 1/2 1/3)").to_string());
+            assert_eq!(Value::F64(2.5), repl.eval_str("(unchecked-inc 1.5)"));
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use crate::environment::Environment;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::symbol::Symbol;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use crate::var::Var;
+use std::rc::Rc;
+
+fn var(var: &Rc<Value>) -> Result<Rc<Var>, Rc<Value>> {
+    match &**var {
+        Value::Var(var) => Ok(Rc::clone(var)),
+        _ => Err(Rc::new(error_message::type_mismatch(TypeTag::Var, var))),
+    }
+}
+
+/// (var-get var)
+///
+/// var's value;  its thread binding, if it has one, or else its root
+#[derive(Debug, Clone)]
+pub struct VarGetFn {}
+impl ToValue for VarGetFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for VarGetFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        match var(&args[0]) {
+            Ok(var) => var.deref().to_value(),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+/// (alter-var-root var f & args)
+///
+/// Gives var the root (apply f root args), and returns it
+#[derive(Debug, Clone)]
+pub struct AlterVarRootFn {}
+impl ToValue for AlterVarRootFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for AlterVarRootFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() < 2 {
+            return error_message::wrong_varg_count(&[2], args.len());
+        }
+        let var = match var(&args[0]) {
+            Ok(var) => var,
+            Err(condition) => return condition.to_value(),
+        };
+        match &*args[1] {
+            Value::IFn(f) => var.alter_root(f, args[2..].to_vec()),
+            _ => error_message::type_mismatch(TypeTag::IFn, &args[1]),
+        }
+    }
+}
+
+/// (bound? & vars)
+///
+/// Whether every one of vars has a value, as one that has only been declared doesn't
+#[derive(Debug, Clone)]
+pub struct IsBoundFn {}
+impl ToValue for IsBoundFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for IsBoundFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        for arg in args.iter() {
+            match var(arg) {
+                Ok(var) if !var.is_bound() => return Value::Boolean(false),
+                Ok(_) => {}
+                Err(condition) => return condition.to_value(),
+            }
+        }
+        Value::Boolean(true)
+    }
+}
+
+/// (intern ns name)
+/// (intern ns name val)
+///
+/// The var name names in the namespace ns, made should there not be one yet, and given
+/// the root val, if there is one
+#[derive(Debug, Clone)]
+pub struct InternFn {
+    enclosing_environment: Rc<Environment>,
+}
+impl InternFn {
+    pub fn new(enclosing_environment: Rc<Environment>) -> InternFn {
+        InternFn {
+            enclosing_environment,
+        }
+    }
+}
+impl ToValue for InternFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for InternFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 && args.len() != 3 {
+            return error_message::wrong_varg_count(&[2, 3], args.len());
+        }
+        let (ns, name) = match (&*args[0], &*args[1]) {
+            (Value::Symbol(ns), Value::Symbol(name)) => (ns, name),
+            (Value::Symbol(_), _) => {
+                return error_message::type_mismatch(TypeTag::Symbol, &args[1])
+            }
+            _ => return error_message::type_mismatch(TypeTag::Symbol, &args[0]),
+        };
+        let var = self
+            .enclosing_environment
+            .intern(&Symbol::intern_with_ns(&ns.name, &name.name));
+        if let Some(val) = args.get(2) {
+            var.bind_root(Rc::clone(val));
+            var.set_meta(name.meta());
+        }
+        Value::Var(var)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod vars_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn def_returns_the_var() {
+            let repl = Repl::default();
+            assert_eq!("#'clojure.core/x", repl.eval_str("(def x 1)").to_string());
+            assert_eq!(repl.eval_str("#'x"), repl.eval_str("(def x 2)"));
+            assert_eq!(Value::I64(2), repl.eval_str("(var-get #'x)"));
+        }
+
+        #[test]
+        fn redefining_a_fn_is_seen_by_its_callers() {
+            let repl = Repl::default();
+            repl.eval_str("(defn greeting [] \"hello\")");
+            repl.eval_str("(defn greet [] (greeting))");
+            repl.eval_str("(def greeting-var #'greeting)");
+            repl.eval_str("(defn greeting [] \"hi\")");
+            assert_eq!(Value::String(String::from("hi")), repl.eval_str("(greet)"));
+            assert_eq!(
+                Value::String(String::from("hi")),
+                repl.eval_str("((var-get greeting-var))")
+            );
+        }
+
+        #[test]
+        fn alter_var_root_applies_f_to_the_root() {
+            let repl = Repl::default();
+            repl.eval_str("(def counter 1)");
+            assert_eq!(
+                Value::I64(11),
+                repl.eval_str("(alter-var-root #'counter + 4 6)")
+            );
+            assert_eq!(Value::I64(11), repl.eval_str("counter"));
+        }
+
+        #[test]
+        fn declare_allows_forward_references() {
+            let repl = Repl::default();
+            repl.eval_str("(declare is-odd?)");
+            assert_eq!(Value::Boolean(false), repl.eval_str("(bound? #'is-odd?)"));
+            match repl.eval_str("is-odd?") {
+                Value::Condition(error) => assert!(error.is("illegal-state")),
+                result => panic!("Expected an error, found {}", result),
+            }
+            repl.eval_str("(defn is-even? [n] (if (= n 0) true (is-odd? (- n 1))))");
+            repl.eval_str("(defn is-odd? [n] (if (= n 0) false (is-even? (- n 1))))");
+            assert_eq!(Value::Boolean(true), repl.eval_str("(is-even? 10)"));
+        }
+
+        #[test]
+        fn defonce_only_defines_once() {
+            let repl = Repl::default();
+            repl.eval_str("(defonce config 1)");
+            repl.eval_str("(defonce config 2)");
+            assert_eq!(Value::I64(1), repl.eval_str("config"));
+        }
+
+        #[test]
+        fn intern_defines_into_any_namespace() {
+            let repl = Repl::default();
+            repl.eval_str("(intern 'app.config 'port 8080)");
+            assert_eq!(Value::I64(8080), repl.eval_str("app.config/port"));
+            assert_eq!(
+                "#'app.config/port",
+                repl.eval_str("(intern 'app.config 'port)").to_string()
+            );
+        }
+    }
+}
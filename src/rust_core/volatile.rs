@@ -0,0 +1,108 @@
+use crate::atom::Volatile;
+use crate::error_message;
+use crate::ifn::IFn;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::rc::Rc;
+
+fn volatile(volatile: &Rc<Value>) -> Result<Rc<Volatile>, Rc<Value>> {
+    match &**volatile {
+        Value::Volatile(volatile) => Ok(Rc::clone(volatile)),
+        _ => Err(Rc::new(error_message::type_mismatch(
+            TypeTag::Volatile,
+            volatile,
+        ))),
+    }
+}
+
+/// (volatile! x)
+///
+/// A volatile, starting out as x
+#[derive(Debug, Clone)]
+pub struct VolatileFn {}
+impl ToValue for VolatileFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for VolatileFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 1 {
+            return error_message::wrong_arg_count(1, args.len());
+        }
+        Value::Volatile(Rc::new(Volatile::new(Rc::clone(&args[0]))))
+    }
+}
+
+/// (vswap! volatile f & args)
+///
+/// Gives volatile the state (apply f state args), and returns it
+#[derive(Debug, Clone)]
+pub struct VswapFn {}
+impl ToValue for VswapFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for VswapFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() < 2 {
+            return error_message::wrong_varg_count(&[2], args.len());
+        }
+        let volatile = match volatile(&args[0]) {
+            Ok(volatile) => volatile,
+            Err(condition) => return condition.to_value(),
+        };
+        let f = match &*args[1] {
+            Value::IFn(f) => f,
+            _ => return error_message::type_mismatch(TypeTag::IFn, &args[1]),
+        };
+        let mut f_args = vec![volatile.deref()];
+        f_args.extend_from_slice(&args[2..]);
+        let new_state = f.invoke(f_args);
+        if new_state.short_circuits() {
+            return new_state;
+        }
+        volatile.reset(new_state.to_rc_value())
+    }
+}
+
+/// (vreset! volatile new-state)
+///
+/// Gives volatile the state new-state, and returns it
+#[derive(Debug, Clone)]
+pub struct VresetFn {}
+impl ToValue for VresetFn {
+    fn to_value(&self) -> Value {
+        Value::IFn(Rc::new(self.clone()))
+    }
+}
+impl IFn for VresetFn {
+    fn invoke(&self, args: Vec<Rc<Value>>) -> Value {
+        if args.len() != 2 {
+            return error_message::wrong_arg_count(2, args.len());
+        }
+        match volatile(&args[0]) {
+            Ok(volatile) => volatile.reset(Rc::clone(&args[1])),
+            Err(condition) => condition.to_value(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod volatile_tests {
+        use crate::repl::Repl;
+        use crate::value::Value;
+
+        #[test]
+        fn volatiles_hold_state() {
+            let repl = Repl::default();
+            repl.eval_str("(def v (volatile! 1))");
+            assert_eq!(Value::I64(4), repl.eval_str("(vswap! v + 1 2)"));
+            assert_eq!(Value::I64(7), repl.eval_str("(vreset! v 7)"));
+            assert_eq!(Value::I64(7), repl.eval_str("@v"));
+            assert_eq!("#volatile[7]", repl.eval_str("v").to_string());
+        }
+    }
+}
@@ -39,6 +39,9 @@ impl IFn for WithMetaFn {
                 Value::PersistentListMap(plistmap) => {
                     obj.with_meta(plistmap).unwrap().to_value()
                 }
+                Value::PersistentHashMap(phashmap) => {
+                    obj.with_meta(phashmap.iter().collect()).unwrap().to_value()
+                }
                 _ => error_message::type_mismatch(
                     TypeTag::PersistentListMap,
                     args.get(0).unwrap(),
@@ -2,6 +2,9 @@ use crate::persistent_list_map::PersistentListMap;
 use crate::traits;
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static GENSYM_COUNTER: AtomicUsize = AtomicUsize::new(1);
 
 #[derive(Eq, Clone, Debug)]
 pub struct Symbol {
@@ -57,6 +60,11 @@ impl Symbol {
             meta: PersistentListMap::Empty,
         }
     }
+    /// A symbol guaranteed to be unlike any other we've made, like p__12
+    pub fn gensym(prefix: &str) -> Symbol {
+        let id = GENSYM_COUNTER.fetch_add(1, Ordering::SeqCst);
+        Symbol::intern(&format!("{}{}", prefix, id))
+    }
     pub fn unqualified(&self) -> Symbol {
         // So we can keep the same meta
         let mut retval = self.clone();
@@ -0,0 +1,317 @@
+//! Syntax-quote;  the templating behind most macros, as in
+//!
+//! ```clojure
+//!   `(if ~test (do ~@body))
+//! ```
+//!
+//! The reader reads `x, ~x and ~@x as (syntax-quote x), (unquote x) and
+//! (unquote-splicing x), just as it reads 'x as (quote x).  Evaluating
+//! (syntax-quote x) then builds x, with
+//!   - symbols qualified by the namespace they resolve to, println -> clojure.core/println
+//!   - symbols like x# replaced by a gensym, the same one throughout the template
+//!   - (unquote x) replaced by the value of x
+//!   - (unquote-splicing x) replaced by the elements of x, spliced into the collection around it
+//!
+//! Since that resolution would otherwise happen as a macro expands, in whatever namespace
+//! it's called from, defmacro resolves the templates in its body ahead of time, so its symbols
+//! mean what they meant where it was defined
+use crate::environment::Environment;
+use crate::error_message;
+use crate::iterable::Iterable;
+use crate::maps::{self, MapEntry};
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::persistent_list::{PersistentList, ToPersistentList, ToPersistentListIter};
+use crate::persistent_vector::PersistentVector;
+use crate::protocol::ProtocolCastable;
+use crate::protocols;
+use crate::symbol::Symbol;
+use crate::value::{Evaluable, ToValue, Value};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+// Left as is, rather than qualified
+const SPECIAL_FORMS: &[&str] = &[
+    "def", "if", "do", "quote", "var", "recur", "loop*", "let*", "fn*", "letfn*", "set!", "try",
+    "catch", "finally", "throw", "&", ".", "new",
+];
+
+/// Builds the template of a (syntax-quote template), evaluating what's unquoted in environment
+pub fn expand(template: &Rc<Value>, environment: &Rc<Environment>) -> Rc<Value> {
+    let mut gensyms = HashMap::new();
+    match expand_form(template, environment, &mut gensyms) {
+        Ok(form) => form,
+        Err(condition) => condition,
+    }
+}
+
+/// Qualifies the symbols of every (syntax-quote ..) template within form, ahead of time
+pub fn resolve_templates(form: &Rc<Value>, environment: &Rc<Environment>) -> Rc<Value> {
+    match &**form {
+        Value::PersistentList(plist) => match special_form_arg(plist, "syntax-quote") {
+            Some(template) => vec![
+                Symbol::intern("syntax-quote").to_rc_value(),
+                resolve_template(&template, environment),
+            ]
+            .into_list()
+            .to_rc_value(),
+            None => match special_form_arg(plist, "quote") {
+                Some(_) => Rc::clone(form),
+                None => map_list(plist, |form| resolve_templates(form, environment)),
+            },
+        },
+        Value::PersistentVector(pvector) => {
+            map_vector(pvector, |form| resolve_templates(form, environment))
+        }
+        Value::PersistentListMap(_) | Value::PersistentHashMap(_) => {
+            map_map(&form.as_protocol::<protocols::IPersistentMap>(), |form| {
+                resolve_templates(form, environment)
+            })
+        }
+        Value::PersistentHashSet(phashset) => {
+            map_set(phashset, |form| resolve_templates(form, environment))
+        }
+        _ => Rc::clone(form),
+    }
+}
+
+fn resolve_template(template: &Rc<Value>, environment: &Rc<Environment>) -> Rc<Value> {
+    match &**template {
+        Value::Symbol(sym) => qualify(sym, environment).to_rc_value(),
+        Value::PersistentList(plist) => {
+            // What's unquoted is code again, which may have templates of its own
+            for unquote in ["unquote", "unquote-splicing"].iter() {
+                if let Some(form) = special_form_arg(plist, unquote) {
+                    return vec![
+                        Symbol::intern(unquote).to_rc_value(),
+                        resolve_templates(&form, environment),
+                    ]
+                    .into_list()
+                    .to_rc_value();
+                }
+            }
+            map_list(plist, |form| resolve_template(form, environment))
+        }
+        Value::PersistentVector(pvector) => {
+            map_vector(pvector, |form| resolve_template(form, environment))
+        }
+        Value::PersistentListMap(_) | Value::PersistentHashMap(_) => map_map(
+            &template.as_protocol::<protocols::IPersistentMap>(),
+            |form| resolve_template(form, environment),
+        ),
+        Value::PersistentHashSet(phashset) => {
+            map_set(phashset, |form| resolve_template(form, environment))
+        }
+        _ => Rc::clone(template),
+    }
+}
+
+fn is_auto_gensym(sym: &Symbol) -> bool {
+    !sym.has_ns() && sym.name.len() > 1 && sym.name.ends_with('#')
+}
+
+fn qualify(sym: &Symbol, environment: &Rc<Environment>) -> Symbol {
+    // Interop like Foo. and .method also stay as they are
+    if is_auto_gensym(sym) || SPECIAL_FORMS.contains(&sym.name()) || sym.name.contains('.') {
+        return sym.clone();
+    }
+    environment.resolve_symbol(sym)
+}
+
+/// If plist is (name arg), returns arg
+fn special_form_arg(plist: &PersistentList, name: &str) -> Option<Rc<Value>> {
+    if let PersistentList::Cons(head, tail, 2, _) = plist {
+        if **head == Symbol::intern(name).to_value() {
+            if let PersistentList::Cons(arg, ..) = &**tail {
+                return Some(Rc::clone(arg));
+            }
+        }
+    }
+    None
+}
+
+fn expand_form(
+    template: &Rc<Value>,
+    environment: &Rc<Environment>,
+    gensyms: &mut HashMap<String, Symbol>,
+) -> Result<Rc<Value>, Rc<Value>> {
+    match &**template {
+        Value::Symbol(sym) if is_auto_gensym(sym) => {
+            let gensym = gensyms.entry(sym.name.clone()).or_insert_with(|| {
+                let base = &sym.name[..sym.name.len() - 1];
+                let gensym = Symbol::gensym(&format!("{}__", base));
+                Symbol::intern(&format!("{}__auto__", gensym.name))
+            });
+            Ok(gensym.to_rc_value())
+        }
+        Value::Symbol(sym) => Ok(qualify(sym, environment).to_rc_value()),
+        Value::PersistentList(plist) => {
+            if let Some(form) = special_form_arg(plist, "unquote") {
+                return Ok(form.eval_to_rc(Rc::clone(environment)));
+            }
+            if special_form_arg(plist, "unquote-splicing").is_some() {
+                return Err(Rc::new(error_message::error(
+                    "syntax",
+                    String::from("unquote-splicing used outside of a list, vector or map"),
+                )));
+            }
+            let items = Rc::new(plist.clone()).iter();
+            Ok(expand_items(items, environment, gensyms)?
+                .into_list()
+                .to_rc_value())
+        }
+        Value::PersistentVector(pvector) => {
+            let items = pvector.iter();
+            Ok(expand_items(items, environment, gensyms)?
+                .into_iter()
+                .collect::<PersistentVector>()
+                .to_rc_value())
+        }
+        Value::PersistentListMap(_) | Value::PersistentHashMap(_) => {
+            let mut expanded_map = vec![];
+            for map_entry in template.as_protocol::<protocols::IPersistentMap>().iter() {
+                expanded_map.push(MapEntry {
+                    key: expand_form(&map_entry.key, environment, gensyms)?,
+                    val: expand_form(&map_entry.val, environment, gensyms)?,
+                });
+            }
+            Ok(maps::map_of(expanded_map).to_rc_value())
+        }
+        Value::PersistentHashSet(phashset) => {
+            let mut expanded_set = vec![];
+            for form in phashset.iter() {
+                expanded_set.push(expand_form(&form, environment, gensyms)?);
+            }
+            Ok(expanded_set
+                .into_iter()
+                .collect::<PersistentHashSet>()
+                .to_rc_value())
+        }
+        _ => Ok(Rc::clone(template)),
+    }
+}
+
+/// Expands the elements of a list or vector template, splicing in any (unquote-splicing ..)
+fn expand_items(
+    items: impl Iterator<Item = Rc<Value>>,
+    environment: &Rc<Environment>,
+    gensyms: &mut HashMap<String, Symbol>,
+) -> Result<Vec<Rc<Value>>, Rc<Value>> {
+    let mut expanded_items = vec![];
+    for item in items {
+        let spliced_form = match &*item {
+            Value::PersistentList(plist) => special_form_arg(plist, "unquote-splicing"),
+            _ => None,
+        };
+        match spliced_form {
+            Some(form) => {
+                let spliced = form.eval_to_rc(Rc::clone(environment));
+                match &*spliced {
+                    Value::Nil => {}
+                    Value::Condition(_) => return Err(spliced),
+                    _ => match spliced.try_as_protocol::<Iterable>() {
+                        Some(iterable) => expanded_items.extend(iterable.iter()),
+                        None => {
+                            return Err(Rc::new(error_message::error(
+                                "type-mismatch",
+                                format!(
+                                    "Can't splice {} with ~@, as it is not a seq",
+                                    spliced.type_tag()
+                                ),
+                            )))
+                        }
+                    },
+                }
+            }
+            None => expanded_items.push(expand_form(&item, environment, gensyms)?),
+        }
+    }
+    Ok(expanded_items)
+}
+
+fn map_list(plist: &PersistentList, f: impl Fn(&Rc<Value>) -> Rc<Value>) -> Rc<Value> {
+    Rc::new(plist.clone())
+        .iter()
+        .map(|form| f(&form))
+        .collect::<PersistentList>()
+        .to_rc_value()
+}
+
+fn map_vector(pvector: &PersistentVector, f: impl Fn(&Rc<Value>) -> Rc<Value>) -> Rc<Value> {
+    pvector
+        .iter()
+        .map(|form| f(&form))
+        .collect::<PersistentVector>()
+        .to_rc_value()
+}
+
+fn map_set(phashset: &PersistentHashSet, f: impl Fn(&Rc<Value>) -> Rc<Value>) -> Rc<Value> {
+    phashset
+        .iter()
+        .map(|form| f(&form))
+        .collect::<PersistentHashSet>()
+        .to_rc_value()
+}
+
+fn map_map(pmap: &protocols::IPersistentMap, f: impl Fn(&Rc<Value>) -> Rc<Value>) -> Rc<Value> {
+    maps::map_of(pmap.iter().map(|map_entry| MapEntry {
+        key: f(&map_entry.key),
+        val: f(&map_entry.val),
+    }))
+    .to_rc_value()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::repl::Repl;
+    use crate::value::Value;
+
+    #[test]
+    fn syntax_quote_qualifies_symbols() {
+        let repl = Repl::default();
+        repl.eval_str("(ns user)");
+        assert_eq!(
+            Repl::read_string("(clojure.core/println user/undefined-thing if x/y)"),
+            repl.eval_str("`(println undefined-thing if x/y)")
+        );
+    }
+
+    #[test]
+    fn syntax_quote_unquotes() {
+        let repl = Repl::default();
+        repl.eval_str("(def xs '(1 2))");
+        assert_eq!(
+            Repl::read_string("(1 (1 2) [1 2 3] {:a (1 2)})"),
+            repl.eval_str("(let [x 1] `(~x ~xs [~@xs 3] {:a ~xs}))")
+        );
+        assert_eq!(Repl::read_string("(1 2)"), repl.eval_str("`(~@xs ~@nil)"));
+    }
+
+    #[test]
+    fn syntax_quote_auto_gensyms() {
+        let repl = Repl::default();
+        let result = repl.eval_str("`[x# x# y#]");
+        match result {
+            Value::PersistentVector(pvector) => {
+                assert_eq!(pvector.nth(0).unwrap(), pvector.nth(1).unwrap());
+                assert_ne!(pvector.nth(0).unwrap(), pvector.nth(2).unwrap());
+                assert!(pvector.nth(0).unwrap().to_string().starts_with("x__"));
+                assert!(pvector.nth(0).unwrap().to_string().ends_with("__auto__"));
+            }
+            _ => panic!("Expected a vector, found {}", result),
+        }
+    }
+
+    #[test]
+    fn macro_templates_resolve_where_defined() {
+        let repl = Repl::default();
+        repl.eval_str("(ns one)");
+        repl.eval_str("(def helper (fn [] :one))");
+        repl.eval_str("(defmacro call-helper [] `(helper))");
+        repl.eval_str("(ns two)");
+        repl.eval_str("(clojure.core/def helper (fn [] :two))");
+        assert_eq!(
+            Repl::read_string(":one"),
+            repl.eval_str("(one/call-helper)")
+        );
+    }
+}
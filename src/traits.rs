@@ -6,3 +6,6 @@ pub use self::ifn::*;
 
 pub(crate) mod imeta;
 pub use self::imeta::*;
+
+pub(crate) mod iseq;
+pub use self::iseq::*;
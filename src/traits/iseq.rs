@@ -0,0 +1,18 @@
+use crate::value::Value;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// A sequence;  a first value, and a seq of the values after it.  As realizing a lazy
+/// seq can fail, so can anything that might need to realize one
+pub trait ISeq: Debug {
+    /// nil, if we are empty, or else a seq with at least one value in it
+    fn seq(&self) -> Result<Rc<Value>, Rc<Value>>;
+    /// Our first value, or nil if we are empty
+    fn first(&self) -> Result<Rc<Value>, Rc<Value>>;
+    /// The values after our first, or nil if there are none
+    fn next(&self) -> Result<Rc<Value>, Rc<Value>>;
+    /// The values after our first, or () if there are none
+    fn more(&self) -> Result<Rc<Value>, Rc<Value>>;
+    /// A seq of x, followed by our values
+    fn cons(&self, x: Rc<Value>) -> Rc<Value>;
+}
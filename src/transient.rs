@@ -0,0 +1,273 @@
+//! Transients;  a vector, map or set made editable in place, so that it can be built up a
+//! value at a time without copying the path to each one, and then made persistent again
+//!
+//! ```clojure
+//!   (def t (transient []))
+//!   (conj! t 1)
+//!   (conj! t 2)
+//!   (persistent! t)
+//!   ;; => [1 2]
+//!   (conj! t 3)
+//!   ;; => #Condition["Transient used after persistent! call"]
+//! ```
+//!
+//! A transient edits a node of its collection in place only when it's that node's one
+//! owner;  the nodes it starts out sharing with the collection it was made from are
+//! copied the first time they're edited, so that collection never sees a change.  Once
+//! persistent! has handed its collection back, a transient is done with, and any further
+//! use of it is an error
+use crate::error_message;
+use crate::maps;
+use crate::persistent_hash_map::PersistentHashMap;
+use crate::persistent_hash_set::PersistentHashSet;
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap, ToPersistentListMapIter};
+use crate::persistent_vector::PersistentVector;
+use crate::type_tag::TypeTag;
+use crate::value::{ToValue, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Debug)]
+enum Editable {
+    Vector(PersistentVector),
+    // A small map stays a list map until it grows past its threshold, as it would
+    // through assoc
+    ListMap(PersistentListMap),
+    HashMap(PersistentHashMap),
+    HashSet(PersistentHashSet),
+}
+impl Editable {
+    fn type_tag(&self) -> TypeTag {
+        match self {
+            Editable::Vector(_) => TypeTag::PersistentVector,
+            Editable::ListMap(_) => TypeTag::PersistentListMap,
+            Editable::HashMap(_) => TypeTag::PersistentHashMap,
+            Editable::HashSet(_) => TypeTag::PersistentHashSet,
+        }
+    }
+    fn assoc(&mut self, key: Rc<Value>, val: Rc<Value>) -> Result<(), Rc<Value>> {
+        match self {
+            Editable::Vector(pvector) => {
+                let ind = match &*key {
+                    Value::I64(ind) if *ind >= 0 => *ind as usize,
+                    Value::I64(ind) => {
+                        return Err(Rc::new(error_message::index_cannot_be_negative(*ind)))
+                    }
+                    _ => {
+                        return Err(Rc::new(error_message::type_mismatch(
+                            TypeTag::Integer,
+                            &key,
+                        )))
+                    }
+                };
+                if !pvector.assoc_n_mut(ind, val) {
+                    return Err(Rc::new(error_message::index_out_of_bounds(
+                        ind,
+                        pvector.len(),
+                    )));
+                }
+            }
+            Editable::ListMap(plist_map)
+                if plist_map.count() >= maps::LIST_MAP_THRESHOLD
+                    && !plist_map.contains_key(&key) =>
+            {
+                let mut phash_map = maps::to_hash_map(plist_map);
+                phash_map.assoc_mut(key, val);
+                *self = Editable::HashMap(phash_map);
+            }
+            Editable::ListMap(plist_map) => *plist_map = plist_map.assoc(key, val),
+            Editable::HashMap(phash_map) => phash_map.assoc_mut(key, val),
+            Editable::HashSet(_) => return Err(self.unsupported("assoc!")),
+        }
+        Ok(())
+    }
+    fn unsupported(&self, op: &str) -> Rc<Value> {
+        Rc::new(error_message::error(
+            "type-mismatch",
+            format!("Cannot {} a transient {}", op, self.type_tag()),
+        ))
+    }
+}
+
+#[derive(Debug)]
+pub struct Transient {
+    // None once persistent! has been called
+    coll: RefCell<Option<Editable>>,
+}
+impl Transient {
+    /// A transient of coll;  None, should it be anything but a vector, map or set
+    pub fn new(coll: &Value) -> Option<Transient> {
+        let coll = match coll {
+            Value::PersistentVector(pvector) => Editable::Vector(pvector.clone()),
+            Value::PersistentListMap(plist_map) => Editable::ListMap(plist_map.clone()),
+            Value::PersistentHashMap(phash_map) => Editable::HashMap(phash_map.clone()),
+            Value::PersistentHashSet(phash_set) => Editable::HashSet(phash_set.clone()),
+            _ => return None,
+        };
+        Some(Transient {
+            coll: RefCell::new(Some(coll)),
+        })
+    }
+    fn edit<T>(
+        &self,
+        f: impl FnOnce(&mut Editable) -> Result<T, Rc<Value>>,
+    ) -> Result<T, Rc<Value>> {
+        match self.coll.borrow_mut().as_mut() {
+            Some(coll) => f(coll),
+            None => Err(Rc::new(error_message::error(
+                "illegal-access",
+                String::from("Transient used after persistent! call"),
+            ))),
+        }
+    }
+    /// The sort of collection we're editing;  None, once we've been made persistent
+    pub fn coll_type_tag(&self) -> Option<TypeTag> {
+        self.coll.borrow().as_ref().map(Editable::type_tag)
+    }
+    /// Our collection, made persistent;  we're done with after this
+    pub fn persistent(&self) -> Result<Value, Rc<Value>> {
+        self.edit(|_| Ok(()))?;
+        Ok(match self.coll.borrow_mut().take() {
+            Some(Editable::Vector(pvector)) => pvector.to_value(),
+            Some(Editable::ListMap(plist_map)) => plist_map.to_value(),
+            Some(Editable::HashMap(phash_map)) => phash_map.to_value(),
+            Some(Editable::HashSet(phash_set)) => phash_set.to_value(),
+            None => Value::Nil,
+        })
+    }
+    pub fn count(&self) -> Result<usize, Rc<Value>> {
+        self.edit(|coll| {
+            Ok(match coll {
+                Editable::Vector(pvector) => pvector.len(),
+                Editable::ListMap(plist_map) => plist_map.count(),
+                Editable::HashMap(phash_map) => phash_map.count(),
+                Editable::HashSet(phash_set) => phash_set.count(),
+            })
+        })
+    }
+    /// Adds val as conj would;  to the end of a vector, as an entry of a map, or to a set
+    pub fn conj(&self, val: Rc<Value>) -> Result<(), Rc<Value>> {
+        self.edit(|coll| match coll {
+            Editable::Vector(pvector) => {
+                pvector.conj_mut(val);
+                Ok(())
+            }
+            Editable::HashSet(phash_set) => {
+                phash_set.conj_mut(val);
+                Ok(())
+            }
+            Editable::ListMap(_) | Editable::HashMap(_) => match &*val {
+                // (conj! (transient {}) [:a 1])
+                Value::PersistentVector(entry) if entry.len() == 2 => coll.assoc(
+                    Rc::clone(entry.nth(0).unwrap()),
+                    Rc::clone(entry.nth(1).unwrap()),
+                ),
+                // (conj! (transient {}) {:a 1 :b 2})
+                Value::PersistentListMap(plist_map) => plist_map
+                    .iter()
+                    .try_for_each(|entry| coll.assoc(entry.key, entry.val)),
+                Value::PersistentHashMap(phash_map) => phash_map
+                    .iter()
+                    .try_for_each(|entry| coll.assoc(entry.key, entry.val)),
+                _ => Err(Rc::new(error_message::error(
+                    "illegal-argument",
+                    String::from("Vector arg to map conj must be a pair"),
+                ))),
+            },
+        })
+    }
+    /// Associates key with val in a map, or replaces the value at index key of a vector
+    pub fn assoc(&self, key: Rc<Value>, val: Rc<Value>) -> Result<(), Rc<Value>> {
+        self.edit(|coll| coll.assoc(key, val))
+    }
+    pub fn dissoc(&self, key: &Rc<Value>) -> Result<(), Rc<Value>> {
+        self.edit(|coll| {
+            match coll {
+                Editable::ListMap(plist_map) => *plist_map = plist_map.dissoc(key),
+                Editable::HashMap(phash_map) => phash_map.dissoc_mut(key),
+                _ => return Err(coll.unsupported("dissoc!")),
+            }
+            Ok(())
+        })
+    }
+    /// Takes the last value off a vector
+    pub fn pop(&self) -> Result<(), Rc<Value>> {
+        self.edit(|coll| match coll {
+            Editable::Vector(pvector) => {
+                if pvector.pop_mut() {
+                    Ok(())
+                } else {
+                    Err(Rc::new(error_message::error(
+                        "illegal-state",
+                        String::from("Can't pop empty vector"),
+                    )))
+                }
+            }
+            _ => Err(coll.unsupported("pop!")),
+        })
+    }
+    pub fn disj(&self, val: &Rc<Value>) -> Result<(), Rc<Value>> {
+        self.edit(|coll| match coll {
+            Editable::HashSet(phash_set) => {
+                phash_set.disj_mut(val);
+                Ok(())
+            }
+            _ => Err(coll.unsupported("disj!")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::persistent_vector::PersistentVector;
+    use crate::transient::*;
+
+    fn val(i: i32) -> Rc<Value> {
+        i.to_rc_value()
+    }
+
+    #[test]
+    fn the_collection_made_from_is_untouched() {
+        let pvector = (0..100).map(val).collect::<PersistentVector>();
+        let transient = Transient::new(&Value::PersistentVector(pvector.clone())).unwrap();
+        for i in 0..100 {
+            transient.assoc(val(i), val(-i)).unwrap();
+        }
+        transient.conj(val(100)).unwrap();
+        transient.pop().unwrap();
+        transient.pop().unwrap();
+        assert_eq!(Ok(99), transient.count());
+        match transient.persistent() {
+            Ok(Value::PersistentVector(edited)) => {
+                assert!(edited.iter().eq((0..99).map(|i| val(-i))))
+            }
+            result => panic!("Expected a vector, found {:?}", result),
+        }
+        assert!(pvector.iter().eq((0..100).map(val)));
+    }
+
+    #[test]
+    fn a_map_grows_into_a_hash_map() {
+        let transient =
+            Transient::new(&Value::PersistentListMap(PersistentListMap::Empty)).unwrap();
+        for i in 0..20 {
+            transient.assoc(val(i), val(i)).unwrap();
+        }
+        transient.dissoc(&val(0)).unwrap();
+        match transient.persistent() {
+            Ok(Value::PersistentHashMap(phash_map)) => assert_eq!(19, phash_map.count()),
+            result => panic!("Expected a hash map, found {:?}", result),
+        }
+    }
+
+    #[test]
+    fn nothing_is_allowed_after_persistent() {
+        let transient =
+            Transient::new(&Value::PersistentHashSet(PersistentHashSet::empty())).unwrap();
+        transient.conj(val(1)).unwrap();
+        assert!(transient.persistent().is_ok());
+        assert!(transient.conj(val(2)).is_err());
+        assert!(transient.count().is_err());
+        assert!(transient.persistent().is_err());
+    }
+}
@@ -2,8 +2,12 @@ use std::fmt;
 
 #[derive(Debug, Clone)]
 pub enum TypeTag {
-    I32,
+    I64,
     F64,
+    BigInt,
+    Ratio,
+    BigDecimal,
+    Number,
     Boolean,
     Symbol,
     Var,
@@ -13,13 +17,28 @@ pub enum TypeTag {
     PersistentList,
     PersistentVector,
     PersistentListMap,
+    PersistentHashMap,
+    PersistentHashSet,
+    PersistentTreeMap,
+    PersistentTreeSet,
+    PersistentQueue,
+    Atom,
+    Volatile,
+    Delay,
+    Transient,
+    LazySeq,
+    Cons,
     // Experimental; may make no sense at runtime, as we will likely be unable to take the value of a macro
     Macro,
     String,
+    Char,
     Integer,
     ISeq,
     Nil,
     Pattern,
+    Recur,
+    TailCall,
+    Unwind,
 }
 
 use TypeTag::*;
@@ -27,9 +46,13 @@ impl fmt::Display for TypeTag {
     // This trait requires `fmt` with this exact signature.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let str = match self {
-            I32 => std::string::String::from("rust.std.i32"),
+            I64 => std::string::String::from("rust.std.i64"),
             Boolean => std::string::String::from("rust.std.bool"),
             F64 => std::string::String::from("rust.std.f64"),
+            BigInt => std::string::String::from("clojure.lang.BigInt"),
+            Ratio => std::string::String::from("clojure.lang.Ratio"),
+            BigDecimal => std::string::String::from("clojure.lang.BigDecimal"),
+            Number => std::string::String::from("clojure.lang.Number"),
             Symbol => std::string::String::from("clojure.lang.Symbol"),
             Var => std::string::String::from("clojure.lang.Var"),
             Keyword => std::string::String::from("clojure.lang.Keyword"),
@@ -38,12 +61,27 @@ impl fmt::Display for TypeTag {
             PersistentList => std::string::String::from("clojure.lang.PersistentList"),
             PersistentVector => std::string::String::from("clojure.lang.PersistentVector"),
             PersistentListMap => std::string::String::from("clojure.lang.PersistentListMap"),
+            PersistentHashMap => std::string::String::from("clojure.lang.PersistentHashMap"),
+            PersistentHashSet => std::string::String::from("clojure.lang.PersistentHashSet"),
+            PersistentTreeMap => std::string::String::from("clojure.lang.PersistentTreeMap"),
+            PersistentTreeSet => std::string::String::from("clojure.lang.PersistentTreeSet"),
+            PersistentQueue => std::string::String::from("clojure.lang.PersistentQueue"),
+            Atom => std::string::String::from("clojure.lang.Atom"),
+            Volatile => std::string::String::from("clojure.lang.Volatile"),
+            Delay => std::string::String::from("clojure.lang.Delay"),
+            Transient => std::string::String::from("clojure.lang.ITransientCollection"),
+            LazySeq => std::string::String::from("clojure.lang.LazySeq"),
+            Cons => std::string::String::from("clojure.lang.Cons"),
             Macro => std::string::String::from("clojure.lang.Macro"),
             TypeTag::String => std::string::String::from("rust.std.string.String"),
+            Char => std::string::String::from("rust.std.char"),
             TypeTag::Integer => std::string::String::from("clojure.lang.Integer"),
             ISeq => std::string::String::from("clojure.lang.ISeq"),
             Nil => std::string::String::from("clojure.lang.Nil"),
             Pattern => std::string::String::from("rust.regex"),
+            Recur => std::string::String::from("clojure.lang.Recur"),
+            TailCall => std::string::String::from("clojure.lang.TailCall"),
+            Unwind => std::string::String::from("clojure.lang.Unwind"),
         };
         write!(f, "{}", str)
     }
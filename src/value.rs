@@ -1,39 +1,76 @@
+use crate::atom::{Atom, Volatile};
+use crate::call_stack::Frame;
+use crate::condition::Condition;
+use crate::cons;
+use crate::delay::Delay;
+use crate::destructure;
 use crate::environment::Environment;
+use crate::error_message;
 use crate::ifn::IFn;
 use crate::keyword::Keyword;
 use crate::lambda;
-use crate::maps::MapEntry;
+use crate::lazy_seq::LazySeq;
+use crate::maps::{self, MapEntry};
+use crate::numbers::{self, Number};
+use crate::persistent_hash_map::PersistentHashMap;
+use crate::persistent_hash_set::PersistentHashSet;
 use crate::persistent_list::PersistentList::Cons;
 use crate::persistent_list::{PersistentList, ToPersistentList, ToPersistentListIter};
-use crate::persistent_list_map::{PersistentListMap, ToPersistentListMapIter};
-use crate::persistent_vector::PersistentVector;
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap, ToPersistentListMapIter};
+use crate::persistent_queue::PersistentQueue;
+use crate::persistent_tree_map::PersistentTreeMap;
+use crate::persistent_tree_set::PersistentTreeSet;
+use crate::persistent_vector::{PersistentVector, ToPersistentVector};
+use crate::position;
+use crate::protocol::ProtocolCastable;
+use crate::protocols;
 use crate::symbol::Symbol;
+use crate::syntax_quote;
+use crate::traits::IMeta;
+use crate::transient::Transient;
 use crate::type_tag::TypeTag;
 use crate::var::Var;
+use bigdecimal::BigDecimal;
 use core::fmt::Display;
 
 extern crate rand;
 use rand::Rng;
 
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::ToPrimitive;
 use std::cmp::{Ord, Ordering};
 use std::fmt;
 use std::fmt::Debug;
 use std::hash::{Hash, Hasher};
 use std::rc::Rc;
 
+/// The chars read and printed by name, as \newline is, rather than as themselves
+pub const CHAR_NAMES: [(&str, char); 6] = [
+    ("newline", '\n'),
+    ("space", ' '),
+    ("tab", '\t'),
+    ("backspace", '\u{8}'),
+    ("formfeed", '\u{c}'),
+    ("return", '\r'),
+];
+
 // @TODO Change IFn's name -- IFn is a function, not an IFn.
 //       The body it executes just happens to be an the IFn.
 /// Represents any Value known to ClojureRS, by wrapping any Value known to ClojureRS;
 /// an int, a symbol, a fn, and so on.  Some Values here are more specific than others;
-/// I32 wraps any I32, but QuoteMacro specifically wraps the value for the quote macro, which
+/// I64 wraps any I64, but QuoteMacro specifically wraps the value for the quote macro, which
 /// is a special case macro that has hardcoded behavior.
 #[derive(Debug, Clone)]
 pub enum Value {
-    I32(i32),
+    I64(i64),
     F64(f64),
+    BigInt(BigInt),
+    Ratio(BigRational),
+    BigDecimal(BigDecimal),
     Boolean(bool),
     Symbol(Symbol),
-    Var(Var),
+    Var(Rc<Var>),
     Keyword(Keyword),
     IFn(Rc<dyn IFn>),
     //
@@ -47,21 +84,52 @@ pub enum Value {
     PersistentList(PersistentList),
     PersistentVector(PersistentVector),
     PersistentListMap(PersistentListMap),
+    PersistentHashMap(PersistentHashMap),
+    PersistentHashSet(PersistentHashSet),
+    PersistentTreeMap(PersistentTreeMap),
+    PersistentTreeSet(PersistentTreeSet),
+    PersistentQueue(PersistentQueue),
+    LazySeq(Rc<LazySeq>),
+    Cons(Rc<cons::Cons>),
+
+    Atom(Rc<Atom>),
+    Volatile(Rc<Volatile>),
+    Delay(Rc<Delay>),
+    Transient(Rc<Transient>),
 
-    Condition(std::string::String),
+    Condition(Condition),
     // Macro body is still a function, that will be applied to our unevaled arguments
     Macro(Rc<dyn IFn>),
     //
     // Special case macros
     //
     QuoteMacro,
+    SyntaxQuoteMacro,
     DefmacroMacro,
     DefMacro,
+    VarMacro,
     FnMacro,
     LetMacro,
+    LetfnMacro,
     IfMacro,
+    DoMacro,
+    LoopMacro,
+    RecurMacro,
+    // Not something you can write yourself; this is what evaluating (recur ..)
+    // produces, carrying its arguments back up through our tail positions to
+    // the nearest loop or fn, which will rebind them and go again
+    Recur(Vec<Rc<Value>>),
+    // Likewise internal; a call to a fn found in tail position, handed back up
+    // uncalled so that whoever is trampolining us can make it without growing the stack.
+    // Along with it goes the (f ..) form it came from, for saying where an error in it happened
+    TailCall(Rc<dyn IFn>, Vec<Rc<Value>>, Rc<Value>),
+    // Likewise internal; a way out of everything evaluating between here and the
+    // handler-case clause or restart established with this id, carrying the arguments
+    // it is to be called on.  See condition.rs
+    Unwind(usize, Vec<Rc<Value>>),
 
     String(std::string::String),
+    Char(char),
     Nil,
     Pattern(regex::Regex),
 }
@@ -73,15 +141,20 @@ impl PartialEq for Value {
     fn eq(&self, other: &Value) -> bool {
         //
         match (self, other) {
-            (Value::I32(i), Value::I32(i2)) => i == i2,
+            (Value::I64(i), Value::I64(i2)) => i == i2,
             (Value::F64(d), Value::F64(d2)) => d == d2,
+            (a, b) if numbers::is_number(a) && numbers::is_number(b) => {
+                match (Number::of(a), Number::of(b)) {
+                    (Ok(a), Ok(b)) => numbers::equiv(&a, &b),
+                    _ => false,
+                }
+            }
             (Value::Boolean(b), Value::Boolean(b2)) => b == b2,
             (Value::Symbol(sym), Value::Symbol(sym2)) => sym == sym2,
             (Value::Var(var), Value::Var(var2)) => var == var2,
             (Value::Keyword(kw), Value::Keyword(kw2)) => kw == kw2,
-            // Equality not defined on functions, similar to Clojure
-            // Change this perhaps? Diverge?
-            (Value::IFn(_), Value::IFn(_)) => false,
+            // A fn is only ever equal to itself, as in Clojure
+            (Value::IFn(ifn), Value::IFn(ifn2)) => Rc::ptr_eq(ifn, ifn2),
             // Is it misleading for equality to sometimes work?
             (Value::LexicalEvalFn, Value::LexicalEvalFn) => true,
             (Value::PersistentList(plist), Value::PersistentList(plist2)) => plist == plist2,
@@ -91,12 +164,90 @@ impl PartialEq for Value {
             (Value::PersistentListMap(plistmap), Value::PersistentListMap(plistmap2)) => {
                 *plistmap == *plistmap2
             }
-            (Value::Condition(msg), Value::Condition(msg2)) => msg == msg2,
+            (Value::PersistentHashMap(phashmap), Value::PersistentHashMap(phashmap2)) => {
+                *phashmap == *phashmap2
+            }
+            (Value::PersistentHashSet(phashset), Value::PersistentHashSet(phashset2)) => {
+                *phashset == *phashset2
+            }
+            (Value::PersistentTreeMap(ptreemap), Value::PersistentTreeMap(ptreemap2)) => {
+                *ptreemap == *ptreemap2
+            }
+            (Value::PersistentTreeSet(ptreeset), Value::PersistentTreeSet(ptreeset2)) => {
+                *ptreeset == *ptreeset2
+            }
+            (Value::PersistentQueue(pqueue), Value::PersistentQueue(pqueue2)) => {
+                *pqueue == *pqueue2
+            }
+            // A sorted map is equal to any other map with the same entries, and a sorted
+            // set to any other set with the same values
+            (Value::PersistentTreeMap(ptreemap), Value::PersistentListMap(plistmap))
+            | (Value::PersistentListMap(plistmap), Value::PersistentTreeMap(ptreemap)) => {
+                maps::entries_equal(ptreemap.iter(), ptreemap.count(), plistmap)
+            }
+            (Value::PersistentTreeMap(ptreemap), Value::PersistentHashMap(phashmap))
+            | (Value::PersistentHashMap(phashmap), Value::PersistentTreeMap(ptreemap)) => {
+                maps::entries_equal(ptreemap.iter(), ptreemap.count(), phashmap)
+            }
+            (Value::PersistentTreeSet(ptreeset), Value::PersistentHashSet(phashset))
+            | (Value::PersistentHashSet(phashset), Value::PersistentTreeSet(ptreeset)) => {
+                ptreeset.count() == phashset.count()
+                    && ptreeset.iter().all(|val| phashset.contains(&val))
+            }
+            // A map is equal to any other map with the same entries
+            (Value::PersistentListMap(plistmap), Value::PersistentHashMap(phashmap))
+            | (Value::PersistentHashMap(phashmap), Value::PersistentListMap(plistmap)) => {
+                maps::entries_equal(plistmap.iter(), plistmap.count(), phashmap)
+            }
+            // A list is equal to a vector with the same values, in the same order
+            (Value::PersistentList(plist), Value::PersistentVector(pvector))
+            | (Value::PersistentVector(pvector), Value::PersistentList(plist)) => {
+                plist.len() as usize == pvector.len()
+                    && Rc::new(plist.clone())
+                        .iter()
+                        .zip(pvector.iter())
+                        .all(|(val, val2)| val == val2)
+            }
+            // A seq, or queue, is equal to any list or vector with the same values;  (= ..)
+            // compares with protocols::equiv instead, to give back any error realizing one
+            (Value::LazySeq(_), _)
+            | (Value::Cons(_), _)
+            | (Value::PersistentQueue(_), _)
+            | (_, Value::LazySeq(_))
+            | (_, Value::Cons(_))
+            | (_, Value::PersistentQueue(_))
+                if self.is_sequential() && other.is_sequential() =>
+            {
+                protocols::seq_equals(
+                    &self.to_rc_value().as_protocol::<protocols::ISeq>(),
+                    &other.to_rc_value().as_protocol::<protocols::ISeq>(),
+                )
+                .unwrap_or(false)
+            }
+            // A reference is only ever equal to itself
+            (Value::Atom(atom), Value::Atom(atom2)) => Rc::ptr_eq(atom, atom2),
+            (Value::Volatile(volatile), Value::Volatile(volatile2)) => {
+                Rc::ptr_eq(volatile, volatile2)
+            }
+            (Value::Delay(delay), Value::Delay(delay2)) => Rc::ptr_eq(delay, delay2),
+            (Value::Transient(transient), Value::Transient(transient2)) => {
+                Rc::ptr_eq(transient, transient2)
+            }
+            (Value::Condition(error), Value::Condition(error2)) => error == error2,
             (Value::QuoteMacro, Value::QuoteMacro) => true,
+            (Value::SyntaxQuoteMacro, Value::SyntaxQuoteMacro) => true,
             (Value::DefmacroMacro, Value::DefmacroMacro) => true,
             (Value::DefMacro, Value::DefMacro) => true,
+            (Value::VarMacro, Value::VarMacro) => true,
             (Value::LetMacro, Value::LetMacro) => true,
+            (Value::LetfnMacro, Value::LetfnMacro) => true,
+            (Value::DoMacro, Value::DoMacro) => true,
+            (Value::LoopMacro, Value::LoopMacro) => true,
+            (Value::RecurMacro, Value::RecurMacro) => true,
+            (Value::Recur(args), Value::Recur(args2)) => args == args2,
+            (Value::Unwind(id, args), Value::Unwind(id2, args2)) => id == id2 && args == args2,
             (Value::String(string), Value::String(string2)) => string == string2,
+            (Value::Char(c), Value::Char(c2)) => c == c2,
             (Value::Nil, Value::Nil) => true,
             (Value::Pattern(p1), Value::Pattern(p2)) => p1.as_str() == p2.as_str(),
             _ => false,
@@ -110,33 +261,62 @@ impl PartialEq for Value {
 enum ValueHash {
     LexicalEvalFn,
     QuoteMacro,
+    SyntaxQuoteMacro,
     DefmacroMacro,
     DefMacro,
+    VarMacro,
     FnMacro,
     IfMacro,
     LetMacro,
+    LetfnMacro,
+    DoMacro,
+    LoopMacro,
+    RecurMacro,
     Nil,
 }
 impl Eq for Value {}
 impl Hash for Value {
     fn hash<H: Hasher>(&self, state: &mut H) {
         match self {
-            Value::I32(i) => i.hash(state),
-            Value::F64(d) => d.to_value().hash(state),
+            Value::I64(i) => i.hash(state),
+            Value::F64(d) => d.to_bits().hash(state),
+            // Hashed as the i64 it's equal to, should it fit in one
+            Value::BigInt(n) => match n.to_i64() {
+                Some(i) => i.hash(state),
+                None => n.hash(state),
+            },
+            Value::Ratio(n) => n.hash(state),
+            // 1.0M and 1.00M are equal, so their scale can't factor in
+            Value::BigDecimal(n) => n.normalized().hash(state),
             Value::Boolean(b) => b.hash(state),
             Value::Symbol(sym) => sym.hash(state),
             Value::Var(var) => var.hash(state),
             Value::Keyword(kw) => kw.hash(state),
-            Value::IFn(_) => {
-                let mut rng = rand::thread_rng();
-                let n2: u16 = rng.gen();
-                n2.hash(state)
-            }
+            // Hashed by address alone, as a fn is equal to itself alone
+            Value::IFn(ifn) => (Rc::as_ptr(ifn) as *const ()).hash(state),
             Value::LexicalEvalFn => (ValueHash::LexicalEvalFn).hash(state),
             Value::PersistentList(plist) => plist.hash(state),
             Value::PersistentVector(pvector) => pvector.hash(state),
+            // Hashed as any list or vector it is equal to;  what hashes one realizes it first,
+            // with protocols::realize, to give back any error in doing so
+            Value::LazySeq(_) | Value::Cons(_) => protocols::seq_hash(
+                self.to_rc_value()
+                    .as_protocol::<protocols::ISeq>()
+                    .iter()
+                    .map_while(Result::ok),
+                state,
+            ),
+            Value::Atom(atom) => Rc::as_ptr(atom).hash(state),
+            Value::Volatile(volatile) => Rc::as_ptr(volatile).hash(state),
+            Value::Delay(delay) => Rc::as_ptr(delay).hash(state),
+            Value::Transient(transient) => Rc::as_ptr(transient).hash(state),
             Value::PersistentListMap(plistmap) => plistmap.hash(state),
-            Value::Condition(msg) => msg.hash(state),
+            Value::PersistentHashMap(phashmap) => phashmap.hash(state),
+            Value::PersistentHashSet(phashset) => phashset.hash(state),
+            Value::PersistentTreeMap(ptreemap) => ptreemap.hash(state),
+            Value::PersistentTreeSet(ptreeset) => ptreeset.hash(state),
+            Value::PersistentQueue(pqueue) => pqueue.hash(state),
+            Value::Condition(error) => error.hash(state),
             // Random hash is temporary;
             // @TODO implement hashing for functions / macros
             Value::Macro(_) => {
@@ -145,13 +325,23 @@ impl Hash for Value {
                 n2.hash(state)
             }
             Value::QuoteMacro => ValueHash::QuoteMacro.hash(state),
+            Value::SyntaxQuoteMacro => ValueHash::SyntaxQuoteMacro.hash(state),
             Value::DefmacroMacro => ValueHash::DefmacroMacro.hash(state),
             Value::DefMacro => ValueHash::DefMacro.hash(state),
+            Value::VarMacro => ValueHash::VarMacro.hash(state),
             Value::FnMacro => ValueHash::FnMacro.hash(state),
             Value::LetMacro => ValueHash::LetMacro.hash(state),
+            Value::LetfnMacro => ValueHash::LetfnMacro.hash(state),
             Value::IfMacro => ValueHash::IfMacro.hash(state),
+            Value::DoMacro => ValueHash::DoMacro.hash(state),
+            Value::LoopMacro => ValueHash::LoopMacro.hash(state),
+            Value::RecurMacro => ValueHash::RecurMacro.hash(state),
+            Value::Recur(args) => args.hash(state),
+            Value::TailCall(_, args, _) => args.hash(state),
+            Value::Unwind(id, args) => (id, args).hash(state),
 
             Value::String(string) => string.hash(state),
+            Value::Char(c) => c.hash(state),
             Value::Pattern(p) => p.as_str().hash(state),
             Value::Nil => ValueHash::Nil.hash(state),
         }
@@ -162,26 +352,91 @@ impl Hash for Value {
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let str = match self {
-            Value::I32(val) => val.to_string(),
+            Value::I64(val) => val.to_string(),
             Value::F64(val) => val.to_string(),
+            Value::BigInt(val) => format!("{}N", val),
+            Value::Ratio(val) => val.to_string(),
+            Value::BigDecimal(val) => format!("{}M", val),
             Value::Boolean(val) => val.to_string(),
             Value::Symbol(sym) => sym.to_string(),
             Value::Var(var) => var.to_string(),
             Value::Keyword(kw) => kw.to_string(),
-            Value::IFn(_) => std::string::String::from("#function[]"),
+            Value::IFn(ifn) => match ifn.name() {
+                Some(name) => format!("#function[{}]", name),
+                None => std::string::String::from("#function[]"),
+            },
             Value::LexicalEvalFn => std::string::String::from("#function[lexical-eval*]"),
             Value::PersistentList(plist) => plist.to_string(),
             Value::PersistentVector(pvector) => pvector.to_string(),
             Value::PersistentListMap(plistmap) => plistmap.to_string(),
-            Value::Condition(msg) => format!("#Condition[\"{}\"]", msg),
+            Value::PersistentHashMap(phashmap) => phashmap.to_string(),
+            Value::PersistentHashSet(phashset) => phashset.to_string(),
+            Value::PersistentTreeMap(ptreemap) => ptreemap.to_string(),
+            Value::PersistentTreeSet(ptreeset) => ptreeset.to_string(),
+            Value::PersistentQueue(pqueue) => pqueue.to_string(),
+            // Printed like a list, realizing it all;  what prints one, like str, realizes it
+            // first, with protocols::realize, to give back any error rather than print it
+            Value::LazySeq(_) | Value::Cons(_) => format!(
+                "({})",
+                self.to_rc_value()
+                    .as_protocol::<protocols::ISeq>()
+                    .iter()
+                    .map(|val| match val {
+                        Ok(val) | Err(val) => val.to_string_explicit(),
+                    })
+                    .collect::<Vec<std::string::String>>()
+                    .join(" ")
+            ),
+            Value::Atom(atom) => format!("#atom[{}]", atom.deref().to_string_explicit()),
+            Value::Volatile(volatile) => {
+                format!("#volatile[{}]", volatile.deref().to_string_explicit())
+            }
+            Value::Delay(delay) => match delay.value() {
+                Some(value) => format!("#delay[{}]", value.to_string_explicit()),
+                None => std::string::String::from("#delay[:pending]"),
+            },
+            Value::Transient(transient) => match transient.coll_type_tag() {
+                Some(type_tag) => format!("#transient[{}]", type_tag),
+                None => std::string::String::from("#transient[:persistent]"),
+            },
+            Value::Condition(error) => format!("#Condition[\"{}\"]", error),
             Value::Macro(_) => std::string::String::from("#macro[]"),
             Value::QuoteMacro => std::string::String::from("#macro[quote*]"),
+            Value::SyntaxQuoteMacro => std::string::String::from("#macro[syntax-quote*]"),
             Value::DefMacro => std::string::String::from("#macro[def*]"),
+            Value::VarMacro => std::string::String::from("#macro[var*]"),
             Value::DefmacroMacro => std::string::String::from("#macro[defmacro*]"),
             Value::FnMacro => std::string::String::from("#macro[fn*]"),
             Value::IfMacro => std::string::String::from("#macro[if*]"),
             Value::LetMacro => std::string::String::from("#macro[let*]"),
+            Value::LetfnMacro => std::string::String::from("#macro[letfn*]"),
+            Value::DoMacro => std::string::String::from("#macro[do*]"),
+            Value::LoopMacro => std::string::String::from("#macro[loop*]"),
+            Value::RecurMacro => std::string::String::from("#macro[recur*]"),
+            Value::Recur(args) => format!(
+                "#recur[{}]",
+                args.iter()
+                    .map(|arg| arg.to_string_explicit())
+                    .collect::<Vec<std::string::String>>()
+                    .join(" ")
+            ),
+            Value::TailCall(_, args, _) => format!(
+                "#tail-call[{}]",
+                args.iter()
+                    .map(|arg| arg.to_string_explicit())
+                    .collect::<Vec<std::string::String>>()
+                    .join(" ")
+            ),
+            Value::Unwind(id, args) => format!(
+                "#unwind[{} {}]",
+                id,
+                args.iter()
+                    .map(|arg| arg.to_string_explicit())
+                    .collect::<Vec<std::string::String>>()
+                    .join(" ")
+            ),
             Value::String(string) => string.clone(),
+            Value::Char(c) => c.to_string(),
             Value::Pattern(pattern) => std::string::String::from(
                 "#\"".to_owned() + &pattern.as_str().escape_default().to_string().clone() + "\"",
             ),
@@ -196,19 +451,27 @@ impl Value {
     // I cannot remember for the life of me whether or not there's a function like this normally
     // and what its called
     // Regardless, when we have, say, a string inside a list, we want to print the string explicitly
-    // with a \"\" and all.
+    // with a \"\" and all, and a char as it's read, as \a or \newline.
     // Everything else we print as is.
     //
     pub fn to_string_explicit(&self) -> std::string::String {
         match self {
             Value::String(string) => format!("\"{}\"", string),
+            Value::Char(c) => match CHAR_NAMES.iter().find(|(_, named)| named == c) {
+                Some((name, _)) => format!("\\{}", name),
+                None if c.is_control() => format!("\\u{:04x}", *c as u32),
+                None => format!("\\{}", c),
+            },
             _ => self.to_string(),
         }
     }
     pub fn type_tag(&self) -> TypeTag {
         match self {
-            Value::I32(_) => TypeTag::I32,
+            Value::I64(_) => TypeTag::I64,
             Value::F64(_) => TypeTag::F64,
+            Value::BigInt(_) => TypeTag::BigInt,
+            Value::Ratio(_) => TypeTag::Ratio,
+            Value::BigDecimal(_) => TypeTag::BigDecimal,
             Value::Boolean(_) => TypeTag::Boolean,
             Value::Symbol(_) => TypeTag::Symbol,
             Value::Var(_) => TypeTag::Var,
@@ -218,17 +481,38 @@ impl Value {
             Value::PersistentList(_) => TypeTag::PersistentList,
             Value::PersistentVector(_) => TypeTag::PersistentVector,
             Value::PersistentListMap(_) => TypeTag::PersistentListMap,
+            Value::PersistentHashMap(_) => TypeTag::PersistentHashMap,
+            Value::PersistentHashSet(_) => TypeTag::PersistentHashSet,
+            Value::PersistentTreeMap(_) => TypeTag::PersistentTreeMap,
+            Value::PersistentTreeSet(_) => TypeTag::PersistentTreeSet,
+            Value::PersistentQueue(_) => TypeTag::PersistentQueue,
+            Value::Atom(_) => TypeTag::Atom,
+            Value::Volatile(_) => TypeTag::Volatile,
+            Value::Delay(_) => TypeTag::Delay,
+            Value::Transient(_) => TypeTag::Transient,
+            Value::LazySeq(_) => TypeTag::LazySeq,
+            Value::Cons(_) => TypeTag::Cons,
             Value::Condition(_) => TypeTag::Condition,
             // Note; normal Clojure cannot take the value of a macro, so I don't imagine this
             // having significance in the long run, but we will see
             Value::Macro(_) => TypeTag::Macro,
             Value::QuoteMacro => TypeTag::Macro,
+            Value::SyntaxQuoteMacro => TypeTag::Macro,
             Value::DefMacro => TypeTag::Macro,
+            Value::VarMacro => TypeTag::Macro,
             Value::DefmacroMacro => TypeTag::Macro,
             Value::LetMacro => TypeTag::Macro,
+            Value::LetfnMacro => TypeTag::Macro,
             Value::FnMacro => TypeTag::Macro,
             Value::IfMacro => TypeTag::Macro,
+            Value::DoMacro => TypeTag::Macro,
+            Value::LoopMacro => TypeTag::Macro,
+            Value::RecurMacro => TypeTag::Macro,
+            Value::Recur(_) => TypeTag::Recur,
+            Value::TailCall(..) => TypeTag::TailCall,
+            Value::Unwind(..) => TypeTag::Unwind,
             Value::String(_) => TypeTag::String,
+            Value::Char(_) => TypeTag::Char,
             Value::Nil => TypeTag::Nil,
             Value::Pattern(_) => TypeTag::Pattern,
         }
@@ -255,51 +539,81 @@ impl Value {
     // hunt around for each individual implementation.
     //
     /// Applies any valid function-like Value to a PersistentList, or returns None if our Value can't be applied
+    ///
+    /// If we are in tail position, a call to a fn is not made but returned as a Value::TailCall,
+    /// and the same goes for whatever is in tail position of an if, do, let, etc. in turn
     fn apply_to_persistent_list(
         &self,
         environment: &Rc<Environment>,
+        form: &Rc<Value>,
         args: &Rc<PersistentList>,
+        in_tail_position: bool,
     ) -> Option<Rc<Value>> {
         match self {
             Value::IFn(ifn) => {
                 // Eval arguments
-                let evaled_arg_refs = PersistentList::iter(args)
-                    .map(|rc_arg| rc_arg.eval_to_rc(Rc::clone(environment)))
-                    .collect::<Vec<Rc<Value>>>();
+                let evaled_arg_refs = match Value::eval_non_tail_forms(args.iter(), environment) {
+                    Ok(evaled_arg_refs) => evaled_arg_refs,
+                    Err(condition) => return Some(condition),
+                };
 
+                if in_tail_position {
+                    return Some(Rc::new(Value::TailCall(
+                        Rc::clone(ifn),
+                        evaled_arg_refs,
+                        Rc::clone(form),
+                    )));
+                }
                 // Invoke fn on arguments
-                Some(Rc::new(ifn.invoke(evaled_arg_refs)))
+                Some(Rc::new(
+                    environment
+                        .call_stack()
+                        .call(Frame::new(ifn, form), || ifn.invoke(evaled_arg_refs)),
+                ))
             }
             Value::LexicalEvalFn => {
                 if args.len() != 1 {
-                    return Some(Rc::new(Value::Condition(format!(
-                        "Wrong number of arguments (Given: {}, Expected: 1)",
-                        args.len()
-                    ))));
+                    return Some(Rc::new(error_message::error(
+                        "arity",
+                        format!(
+                            "Wrong number of arguments (Given: {}, Expected: 1)",
+                            args.len()
+                        ),
+                    )));
                 }
                 // This should only be one value
-                let evaled_arg_values = PersistentList::iter(args)
-                    .map(|rc_arg| rc_arg.eval_to_rc(Rc::clone(environment)))
-                    .collect::<Vec<Rc<Value>>>();
+                let evaled_arg_values = match Value::eval_non_tail_forms(args.iter(), environment) {
+                    Ok(evaled_arg_values) => evaled_arg_values,
+                    Err(condition) => return Some(condition),
+                };
 
                 let evaled_arg = evaled_arg_values.get(0).unwrap();
 
                 Some(evaled_arg.eval_to_rc(Rc::clone(environment)))
             }
-            //
-            // Unless I'm mistaken, this is incorrect; instead of having a phase where
-            // the macro expands, and then another phase where the whole expanded form
-            // is evaluated, it all happens at once.  I will have to look further into
-            // whether or not this will cause any problems; you'd think I'd know more
-            // about this particular step by now, but this is an implementation detail
-            // that's never interested me all that much
-            //
-            Value::Macro(ifn) => {
-                let arg_refs = PersistentList::iter(args).collect::<Vec<Rc<Value>>>();
-
-                let macroexpansion = Rc::new(ifn.invoke(arg_refs));
-
-                Some(macroexpansion.eval_to_rc(Rc::clone(environment)))
+            // (#{1 2} 1) is a lookup of 1 in our set;  1 if it's there, else nil.  Likewise
+            // ({:a 1} :a) and (:a {:a 1}) are both lookups of :a, and ([:a :b] 1) is :b
+            Value::Keyword(_)
+            | Value::Symbol(_)
+            | Value::PersistentListMap(_)
+            | Value::PersistentHashMap(_)
+            | Value::PersistentTreeMap(_)
+            | Value::PersistentHashSet(_)
+            | Value::PersistentTreeSet(_)
+            | Value::PersistentVector(_) => {
+                let evaled_args = match Value::eval_non_tail_forms(args.iter(), environment) {
+                    Ok(evaled_args) => evaled_args,
+                    Err(condition) => return Some(condition),
+                };
+                Some(Rc::new(protocols::invoke(self, evaled_args)))
+            }
+            // (#'f 1) is (f 1)
+            Value::Var(var) => {
+                let f = var.deref();
+                if f.short_circuits() {
+                    return Some(f);
+                }
+                f.apply_to_persistent_list(environment, form, args, in_tail_position)
             }
             //
             // Special case macros
@@ -327,19 +641,29 @@ impl Value {
                     .collect::<Vec<Rc<Value>>>();
 
                 if arg_rc_values.len() > 3 || arg_rc_values.is_empty() {
-                    return Some(Rc::new(Value::Condition(format!(
-                        "Wrong number of arguments (Given: {}, Expected: 1-3)",
-                        arg_rc_values.len()
-                    ))));
+                    return Some(Rc::new(error_message::error(
+                        "arity",
+                        format!(
+                            "Wrong number of arguments (Given: {}, Expected: 1-3)",
+                            arg_rc_values.len()
+                        ),
+                    )));
                 }
 
                 let defname = arg_rc_values.get(0).unwrap();
 
+                // (def x) interns x, but leaves whatever it's bound to, if anything, be
                 let defval = arg_rc_values
                     .get(if arg_rc_values.len() == 2 { 1 } else { 2 })
-                    .or(Some(&Rc::new(Value::Nil)))
-                    .unwrap()
-                    .eval_to_rc(Rc::clone(&environment));
+                    .map(|init| init.eval_to_rc(Rc::clone(environment)));
+                if let Some(defval) = &defval {
+                    if defval.is_recur() {
+                        return Some(Rc::new(error_message::recur_not_in_tail_position()));
+                    }
+                    if defval.short_circuits() {
+                        return Some(Rc::clone(defval));
+                    }
+                }
 
                 let doc_string = if arg_rc_values.len() == 3 {
                     match arg_rc_values.get(1).unwrap().to_value() {
@@ -358,14 +682,36 @@ impl Value {
                             meta = conj!(meta, map_entry!("doc", doc_string));
                         }
 
-                        let sym = sym.with_meta(meta);
-                        environment.insert(sym.clone(), defval);
-                        // @TODO return var
-                        Some(sym.to_rc_value())
+                        // Always into the namespace, even from inside a fn or let
+                        let var = environment.intern(sym);
+                        if let Some(defval) = defval {
+                            var.bind_root(defval);
+                        }
+                        var.set_meta(meta);
+                        Some(Rc::new(Value::Var(var)))
                     }
-                    _ => Some(Rc::new(Value::Condition(std::string::String::from(
-                        "First argument to def must be a symbol",
-                    )))),
+                    _ => Some(Rc::new(error_message::error(
+                        "syntax",
+                        std::string::String::from("First argument to def must be a symbol"),
+                    ))),
+                }
+            }
+            //
+            // (var x), or #'x;  the Var x names, rather than its value
+            //
+            Value::VarMacro => {
+                if args.len() != 1 {
+                    return Some(Rc::new(error_message::wrong_arg_count(
+                        1,
+                        args.len() as usize,
+                    )));
+                }
+                match &*args.nth(0) {
+                    Value::Symbol(sym) => Some(environment.get_var(sym)),
+                    _ => Some(Rc::new(error_message::type_mismatch(
+                        TypeTag::Symbol,
+                        &args.nth(0),
+                    ))),
                 }
             }
             Value::DefmacroMacro => {
@@ -374,10 +720,13 @@ impl Value {
                     .collect::<Vec<Rc<Value>>>();
 
                 if arg_rc_values.len() < 2 || arg_rc_values.is_empty() {
-                    return Some(Rc::new(Value::Condition(format!(
-                        "Wrong number of arguments (Given: {}, Expected: >=2)",
-                        args.len()
-                    ))));
+                    return Some(Rc::new(error_message::error(
+                        "arity",
+                        format!(
+                            "Wrong number of arguments (Given: {}, Expected: >=2)",
+                            args.len()
+                        ),
+                    )));
                 }
                 let macro_name = arg_rc_values.get(0).unwrap();
                 let macro_args = arg_rc_values.get(1).unwrap();
@@ -387,16 +736,43 @@ impl Value {
                 } else {
                     arg_rc_values.get(2..).unwrap()
                 };
-                let mut macro_invokable_body_vec =
-                    vec![Symbol::intern("fn").to_rc_value(), Rc::clone(macro_args)];
-                // vec![do expr1 expr2 expr3]
-                macro_invokable_body_vec.extend_from_slice(macro_body_exprs);
-                let macro_invokable_body = macro_invokable_body_vec
-                    .into_list()
-                    .eval(Rc::clone(&environment));
+                // A macro is a fn that is also given the form it was called with and the
+                // locals around it, as &form and &env, ahead of its arguments;  with
+                // (defmacro m ([x] ..) ([x y] ..)), each arity is
+                let mut macro_invokable_body_vec = vec![Symbol::intern("fn").to_rc_value()];
+                if let Value::PersistentVector(_) = &**macro_args {
+                    macro_invokable_body_vec.push(Value::with_implicit_macro_params(macro_args));
+                    macro_invokable_body_vec.extend_from_slice(macro_body_exprs);
+                } else {
+                    for arity in arg_rc_values.get(1..).unwrap() {
+                        let arity_vals = match &**arity {
+                            Value::PersistentList(plist) => {
+                                Rc::new(plist.clone()).iter().collect::<Vec<Rc<Value>>>()
+                            }
+                            _ => vec![Rc::clone(arity)],
+                        };
+                        let mut arity_vec = vec![];
+                        for (i, val) in arity_vals.iter().enumerate() {
+                            if i == 0 {
+                                arity_vec.push(Value::with_implicit_macro_params(val));
+                            } else {
+                                arity_vec.push(Rc::clone(val));
+                            }
+                        }
+                        macro_invokable_body_vec.push(arity_vec.into_list().to_rc_value());
+                    }
+                }
+                // Its templates are resolved here, so their symbols mean what they
+                // mean where the macro is defined, not wherever it's expanded
+                let macro_invokable_body = syntax_quote::resolve_templates(
+                    &macro_invokable_body_vec.into_list().to_rc_value(),
+                    environment,
+                )
+                .eval(Rc::clone(environment));
                 let macro_value = match &macro_invokable_body {
 		    Value::IFn(ifn) => Rc::new(Value::Macro(Rc::clone(&ifn))),
-		    _ => Rc::new(Value::Condition(std::string::String::from("Compiler Error: your macro_value somehow compiled into something else entirely.  I don't even know how that happened,  this behavior is hardcoded, that's impressive")))
+		    Value::Condition(_) => Rc::new(macro_invokable_body.clone()),
+		    _ => Rc::new(error_message::error("error", std::string::String::from("Compiler Error: your macro_value somehow compiled into something else entirely.  I don't even know how that happened,  this behavior is hardcoded, that's impressive")))
 		};
                 Some(
                     vec![
@@ -410,6 +786,8 @@ impl Value {
             }
             //
             // (fn [x y z] (+ x y z))
+            // (fn name [x y z] (+ x y z))
+            // (fn name ([x] x) ([x y] (+ x y)) ([x y & more] (apply + x y more)))
             //
             // @TODO Rename for* everywhere, define for in terms of for* in
             //       ClojureRS
@@ -419,143 +797,358 @@ impl Value {
                     .collect::<Vec<Rc<Value>>>();
 
                 if arg_rc_values.is_empty() {
-                    return Some(Rc::new(Value::Condition(format!(
-                        "Wrong number of arguments (Given: {}, Expect: >=1",
-                        arg_rc_values.len()
-                    ))));
+                    return Some(Rc::new(error_message::error(
+                        "arity",
+                        format!(
+                            "Wrong number of arguments (Given: {}, Expect: >=1",
+                            arg_rc_values.len()
+                        ),
+                    )));
                 }
-                // Let's not do fn names yet
-                // let fnname = arg_rc_values.get(0).unwrap();
-                let fn_args = arg_rc_values.get(0).unwrap();
+                // (fn name ..) binds name to the fn itself, so it can call itself
+                let (name, fn_tail) = match &**arg_rc_values.first().unwrap() {
+                    Value::Symbol(sym) => (Some(sym.clone()), arg_rc_values.get(1..).unwrap()),
+                    _ => (None, arg_rc_values.get(0..).unwrap()),
+                };
                 // Let's not do docstrings yet
                 // let docstring = ...
-                match &**fn_args {
-                    Value::PersistentVector(PersistentVector { vals }) => {
-                        let mut arg_syms_vec = vec![];
-                        let enclosing_environment =
-                            Rc::new(Environment::new_local_environment(Rc::clone(&environment)));
-                        for val in vals.iter() {
-                            if let Value::Symbol(sym) = &**val {
-                                arg_syms_vec.push(sym.clone());
+                let arities = match fn_tail.first().map(|form| &**form) {
+                    // (fn [x y] ..) is a single arity
+                    Some(Value::PersistentVector(_)) => match Value::fn_arity(fn_tail) {
+                        Ok(arity) => vec![arity],
+                        Err(condition) => return Some(condition),
+                    },
+                    // (fn ([x] ..) ([x y] ..)) is one or more
+                    Some(Value::PersistentList(_)) => {
+                        let mut arities = vec![];
+                        for form in fn_tail.iter() {
+                            let arity_form = match &**form {
+                                Value::PersistentList(plist) => {
+                                    Rc::new(plist.clone()).iter().collect::<Vec<Rc<Value>>>()
+                                }
+                                _ => {
+                                    return Some(Rc::new(error_message::error("syntax", format!(
+                                        "Expected a list like ([args] body) for a fn arity, Found: {}",
+                                        form
+                                    ))))
+                                }
+                            };
+                            match Value::fn_arity(&arity_form) {
+                                Ok(arity) => arities.push(arity),
+                                Err(condition) => return Some(condition),
                             }
                         }
+                        arities
+                    }
+                    _ => {
+                        return Some(Rc::new(error_message::error(
+                            "syntax",
+                            std::string::String::from("Parameter declaration missing"),
+                        )))
+                    }
+                };
+                if arities.iter().filter(|arity| arity.is_variadic()).count() > 1 {
+                    return Some(Rc::new(error_message::error(
+                        "syntax",
+                        std::string::String::from("Can't have more than 1 variadic overload"),
+                    )));
+                }
+                for (i, arity) in arities.iter().enumerate() {
+                    let same_arity = arities.get(i + 1..).unwrap().iter().any(|other| {
+                        !arity.is_variadic()
+                            && !other.is_variadic()
+                            && arity.required_argc() == other.required_argc()
+                    });
+                    if same_arity {
+                        return Some(Rc::new(error_message::error(
+                            "syntax",
+                            std::string::String::from("Can't have 2 overloads with same arity"),
+                        )));
+                    }
+                    let fixed_after_variadic = arities.iter().any(|other| {
+                        arity.is_variadic()
+                            && !other.is_variadic()
+                            && other.required_argc() > arity.required_argc()
+                    });
+                    if fixed_after_variadic {
+                        return Some(Rc::new(error_message::error("syntax", std::string::String::from(
+                            "Can't have fixed arity function with more params than variadic function",
+                        ))));
+                    }
+                }
 
-                        let fn_body =
-			// (fn [x y] ) -> nil 
-			    if arg_rc_values.len() <= 1 {
-				Rc::new(Value::Nil)
-				// (fn [x y] expr) -> expr 
-			    } else if arg_rc_values.len() == 2 {
-				Rc::clone(arg_rc_values.get(1).unwrap())
-				// (fn [x y] expr1 expr2 expr3) -> (do expr1 expr2 expr3) 
-			    } else {
-				// (&[expr1 expr2 expr3] 
-				let body_exprs = arg_rc_values.get(1..).unwrap();
-				// vec![do]
-				let mut do_body = vec![Symbol::intern("do").to_rc_value()];
-				// vec![do expr1 expr2 expr3]
-				do_body.extend_from_slice(body_exprs);
-				// (do expr1 expr2 expr3) 
-				do_body.into_list().to_rc_value()
-			    };
-
-                        Some(Rc::new(
-                            lambda::Fn {
-                                body: fn_body,
-                                enclosing_environment,
-                                arg_syms: arg_syms_vec,
-                            }
-                            .to_value(),
-                        ))
+                let namespace = environment.namespace();
+                let enclosing_environment = Rc::new(Environment::new_fn_environment(
+                    Rc::clone(environment),
+                    namespace.clone(),
+                ));
+                let fn_value = Rc::new(
+                    lambda::Fn {
+                        name: name
+                            .as_ref()
+                            .map(|name| Symbol::intern_with_ns(&namespace.name, name.name())),
+                        arities,
+                        enclosing_environment: Rc::clone(&enclosing_environment),
+                    }
+                    .to_value(),
+                );
+                if let Some(name) = name {
+                    enclosing_environment.insert(name, Rc::clone(&fn_value));
+                }
+                Some(fn_value)
+            }
+            //
+            // (letfn [(f [x] (g x)) (g [x] x)] (f 1))
+            //
+            // Like let, but binds fns that can see each other (and themselves)
+            //
+            Value::LetfnMacro => {
+                let arg_rc_values = PersistentList::iter(args).collect::<Vec<Rc<Value>>>();
+                if arg_rc_values.is_empty() {
+                    return Some(Rc::new(error_message::error("arity", std::string::String::from(
+                        "Wrong number of arguments given to letfn (Given: 0, Expecting: 1 or more)",
+                    ))));
+                }
+                let fn_specs = match &**arg_rc_values.first().unwrap() {
+                    Value::PersistentVector(vector) => vector.to_vec(),
+                    _ => {
+                        return Some(Rc::new(error_message::error(
+                            "syntax",
+                            std::string::String::from("Bindings to letfn should be a vector"),
+                        )))
+                    }
+                };
+                let local_environment =
+                    Rc::new(Environment::new_local_environment(Rc::clone(environment)));
+                for fn_spec in fn_specs.iter() {
+                    // (f [x] ..) is just (fn f [x] ..), bound to f;  as each fn closes over
+                    // our local environment, it sees every other fn bound here once called
+                    let fn_spec_vals = match &**fn_spec {
+                        Value::PersistentList(plist) => {
+                            Rc::new(plist.clone()).iter().collect::<Vec<Rc<Value>>>()
+                        }
+                        _ => vec![],
+                    };
+                    let fn_name = match fn_spec_vals.first().map(|form| &**form) {
+                        Some(Value::Symbol(sym)) => sym.clone(),
+                        _ => {
+                            return Some(Rc::new(error_message::error(
+                                "syntax",
+                                format!(
+                                "Expected a fn spec like (name [args] body) in letfn, Found: {}",
+                                fn_spec
+                            ),
+                            )))
+                        }
+                    };
+                    let mut fn_form = vec![Symbol::intern("fn").to_rc_value()];
+                    fn_form.extend(fn_spec_vals);
+                    let fn_value = fn_form
+                        .into_list()
+                        .to_rc_value()
+                        .eval_to_rc(Rc::clone(&local_environment));
+                    if let Value::Condition(_) = &*fn_value {
+                        return Some(fn_value);
                     }
-                    _ => Some(Rc::new(Value::Condition(std::string::String::from(
-                        "First argument to def must be a symbol",
-                    )))),
+                    local_environment.insert(fn_name, fn_value);
                 }
+                Some(Value::eval_body(
+                    arg_rc_values.get(1..).unwrap(),
+                    &local_environment,
+                    in_tail_position,
+                ))
             }
             Value::LetMacro => {
                 let arg_rc_values = PersistentList::iter(args)
                     .map(|rc_arg| rc_arg)
                     .collect::<Vec<Rc<Value>>>();
-                if arg_rc_values.is_empty() || arg_rc_values.len() > 2 {
-                    // @TODO: we give 0 but it may be 3, 4, 5...
-                    return Some(Rc::new(Value::Condition(std::string::String::from(
-                        "Wrong number of arguments given to let (Given: 0, Expecting: 1 or 2)",
+                if arg_rc_values.is_empty() {
+                    return Some(Rc::new(error_message::error("arity", std::string::String::from(
+                        "Wrong number of arguments given to let (Given: 0, Expecting: 1 or more)",
                     ))));
                 }
                 // Already guaranteed to exist by earlier checks
                 let local_bindings = arg_rc_values.get(0).unwrap();
                 match &**local_bindings {
                     Value::PersistentVector(vector) => {
-                        //let mut local_environment_map : HashMap<Symbol,Rc<Value>> = HashMap::new();
                         let local_environment =
                             Rc::new(Environment::new_local_environment(Rc::clone(environment)));
-                        // let chunk_test2 =
-                        for pair in vector.vals.chunks(2) {
-                            if let Some(rc_sym) = (&*pair).get(0)
-                            //(*pair[0]).clone()
-                            {
-                                let val = (&*pair)
-                                    .get(1)
-                                    .unwrap()
-                                    .eval_to_rc(Rc::clone(&local_environment));
-                                if let Value::Symbol(sym) = &(**rc_sym) {
-                                    local_environment.insert(sym.clone(), val);
-                                    //println!("Sym found: {:?}: {:?}",sym,val)
+                        if let Err(condition) =
+                            Value::bind_local_pairs(&vector.to_vec(), &local_environment)
+                        {
+                            return Some(condition);
+                        }
+                        // (let [..] expr1 expr2 expr3) is an implicit (do expr1 expr2 expr3)
+                        Some(Value::eval_body(
+                            arg_rc_values.get(1..).unwrap(),
+                            &local_environment,
+                            in_tail_position,
+                        ))
+                    }
+                    _ => Some(Rc::new(error_message::error(
+                        "syntax",
+                        std::string::String::from("Bindings to let should be a vector"),
+                    ))),
+                }
+            }
+            //
+            // (do expr1 expr2 expr3)
+            //
+            // do is a special form, rather than a fn that evaluates its arguments
+            // and returns the last one, because its last expression is in tail
+            // position;  we return it as is, so a (recur ..) there can reach its loop
+            //
+            Value::DoMacro => {
+                let arg_rc_values = PersistentList::iter(args).collect::<Vec<Rc<Value>>>();
+                Some(Value::eval_body(
+                    &arg_rc_values,
+                    environment,
+                    in_tail_position,
+                ))
+            }
+            //
+            // (loop [x 0 y 1] expr1 expr2 expr3)
+            //
+            // Its body is evaluated like a let's, except if it evaluates to a (recur ..)
+            // we rebind our locals to the new values and go around again.  Since
+            // the recur has already returned all the way back up to us by then,
+            // this runs in constant stack space no matter how many times we loop
+            //
+            Value::LoopMacro => {
+                let arg_rc_values = PersistentList::iter(args).collect::<Vec<Rc<Value>>>();
+                if arg_rc_values.is_empty() {
+                    return Some(Rc::new(error_message::error("arity", std::string::String::from(
+                        "Wrong number of arguments given to loop (Given: 0, Expecting: 1 or more)",
+                    ))));
+                }
+                let bindings = match &**arg_rc_values.first().unwrap() {
+                    Value::PersistentVector(vector) => vector.to_vec(),
+                    _ => {
+                        return Some(Rc::new(error_message::error(
+                            "syntax",
+                            std::string::String::from("Bindings to loop should be a vector"),
+                        )))
+                    }
+                };
+                if bindings.len() % 2 != 0 {
+                    return Some(Rc::new(error_message::error(
+                        "syntax",
+                        std::string::String::from(
+                            "loop requires an even number of forms in binding vector",
+                        ),
+                    )));
+                }
+                let body = arg_rc_values.get(1..).unwrap();
+
+                let mut local_environment =
+                    Rc::new(Environment::new_local_environment(Rc::clone(environment)));
+                let loop_patterns = match Value::bind_local_pairs(&bindings, &local_environment) {
+                    Ok(loop_patterns) => loop_patterns,
+                    Err(condition) => return Some(condition),
+                };
+                loop {
+                    let result = Value::eval_body(body, &local_environment, in_tail_position);
+                    match &*result {
+                        Value::Recur(recur_args) => {
+                            if recur_args.len() != loop_patterns.len() {
+                                return Some(Rc::new(error_message::recur_arg_count(
+                                    loop_patterns.len(),
+                                    recur_args.len(),
+                                )));
+                            }
+                            // A fresh environment each time around, so that anything that
+                            // closed over the last iteration's locals keeps seeing them
+                            local_environment =
+                                Rc::new(Environment::new_local_environment(Rc::clone(environment)));
+                            for (pattern, val) in loop_patterns.iter().zip(recur_args.iter()) {
+                                if let Err(condition) =
+                                    destructure::bind(pattern, Rc::clone(val), &local_environment)
+                                {
+                                    return Some(condition);
                                 }
-                            } else {
-                                //println!("Nope; pair: {:?}",pair)
                             }
                         }
-                        let body = arg_rc_values.get(1);
-                        if let Some(body_) = body {
-                            Some(body_.eval_to_rc(local_environment))
-                        } else {
-                            Some(Rc::new(Value::Nil))
-                        }
+                        _ => return Some(result),
                     }
-                    _ => Some(Rc::new(Value::Condition(std::string::String::from(
-                        "Bindings to let should be a vector",
-                    )))),
                 }
             }
             //
+            // (recur expr1 expr2)
+            //
+            // Evaluates its arguments and hands them back up to the nearest loop or fn
+            //
+            Value::RecurMacro => match Value::eval_non_tail_forms(args.iter(), environment) {
+                Ok(recur_args) => Some(Rc::new(Value::Recur(recur_args))),
+                Err(condition) => Some(condition),
+            },
+            //
             // Quote is simply a primitive, a macro base case; trying to define quote without
             // quote just involves an infinite loop of macroexpansion. Or so it seems
             //
             Value::QuoteMacro => {
                 match args.len().cmp(&1) {
-                    Ordering::Greater => Some(Rc::new(Value::Condition(format!(
-                        "Wrong number of arguments (Given: {}, Expected: 1)",
-                        args.len()
-                    )))),
+                    Ordering::Greater => Some(Rc::new(error_message::error(
+                        "arity",
+                        format!(
+                            "Wrong number of arguments (Given: {}, Expected: 1)",
+                            args.len()
+                        ),
+                    ))),
                     // @TODO define is_empty()
-                    Ordering::Less => Some(Rc::new(Value::Condition(std::string::String::from(
-                        "Wrong number of arguments (Given: 0, Expected: 1)",
-                    )))),
+                    Ordering::Less => Some(Rc::new(error_message::error(
+                        "arity",
+                        std::string::String::from(
+                            "Wrong number of arguments (Given: 0, Expected: 1)",
+                        ),
+                    ))),
                     Ordering::Equal => Some(args.nth(0)),
                 }
             }
+            //
+            // `(if ~test (do ~@body)), which the reader gives us as
+            // (syntax-quote (if (unquote test) (do (unquote-splicing body))))
+            //
+            Value::SyntaxQuoteMacro => {
+                if args.len() != 1 {
+                    return Some(Rc::new(error_message::error(
+                        "arity",
+                        format!(
+                            "Wrong number of arguments (Given: {}, Expected: 1)",
+                            args.len()
+                        ),
+                    )));
+                }
+                Some(syntax_quote::expand(&args.nth(0), environment))
+            }
             Value::IfMacro => {
                 if args.len() != 2 && args.len() != 3 {
-                    return Some(Rc::new(Value::Condition(format!(
-                        "Wrong number of arguments (Given: {}, Expected: 2 or 3)",
-                        args.len()
-                    ))));
+                    return Some(Rc::new(error_message::error(
+                        "arity",
+                        format!(
+                            "Wrong number of arguments (Given: {}, Expected: 2 or 3)",
+                            args.len()
+                        ),
+                    )));
                 }
                 let arg_refs = PersistentList::iter(args).collect::<Vec<Rc<Value>>>();
                 let condition = arg_refs.get(0).unwrap().eval(Rc::clone(environment));
+                if condition.is_recur() {
+                    return Some(Rc::new(error_message::recur_not_in_tail_position()));
+                }
+                if condition.short_circuits() {
+                    return Some(Rc::new(condition));
+                }
 
-                if condition.is_truthy() {
-                    Some(arg_refs.get(1).unwrap().eval_to_rc(Rc::clone(environment)))
+                let branch = if condition.is_truthy() {
+                    Rc::clone(arg_refs.get(1).unwrap())
                 } else {
-                    Some(
-                        arg_refs
-                            .get(2)
-                            .unwrap_or(&Rc::new(Value::Nil))
-                            .eval_to_rc(Rc::clone(environment)),
-                    )
-                }
+                    Rc::clone(arg_refs.get(2).unwrap_or(&Rc::new(Value::Nil)))
+                };
+                Some(Value::eval_form(
+                    &branch,
+                    Rc::clone(environment),
+                    in_tail_position,
+                ))
             }
             //
             // If we're not a valid IFn
@@ -563,9 +1156,298 @@ impl Value {
             _ => None,
         }
     }
+    /// [x y] -> [&form &env x y], for the params of a macro;  anything else is
+    /// left for fn to complain about
+    fn with_implicit_macro_params(params: &Rc<Value>) -> Rc<Value> {
+        match &**params {
+            Value::PersistentVector(pvector) => {
+                let mut vals = vec![
+                    Symbol::intern("&form").to_rc_value(),
+                    Symbol::intern("&env").to_rc_value(),
+                ];
+                vals.extend(pvector.iter());
+                vals.into_vector().to_rc_value()
+            }
+            _ => Rc::clone(params),
+        }
+    }
+    /// If form is a call to a macro, like (when x y), returns what it expands to, once;
+    /// otherwise None.  A local of the same name shadows the macro, as in Clojure
+    pub fn macroexpand_1(form: &Rc<Value>, environment: &Rc<Environment>) -> Option<Rc<Value>> {
+        if let Value::PersistentList(Cons(head, tail, ..)) = &**form {
+            if let Value::Symbol(sym) = &**head {
+                if let Value::Macro(ifn) = &*environment.get(sym) {
+                    return Some(Value::expand_macro_call(ifn, form, tail, environment));
+                }
+            }
+        }
+        None
+    }
+    /// Calls a macro on the (unevaluated) args of form, giving it &form and &env first
+    fn expand_macro_call(
+        ifn: &Rc<dyn IFn>,
+        form: &Rc<Value>,
+        args: &Rc<PersistentList>,
+        environment: &Rc<Environment>,
+    ) -> Rc<Value> {
+        let mut macro_args = vec![Rc::clone(form), Value::macro_env(environment)];
+        macro_args.extend(PersistentList::iter(args));
+        Rc::new(ifn.invoke(macro_args))
+    }
+    /// As expand_macro_call, but only the first time form is evaluated;  after that, form
+    /// is given what it expanded to then, so long as neither the macro nor its locals have changed
+    fn expand_macro_call_cached(
+        ifn: &Rc<dyn IFn>,
+        form: &Rc<Value>,
+        args: &Rc<PersistentList>,
+        environment: &Rc<Environment>,
+    ) -> Rc<Value> {
+        let locals = environment.local_names();
+        let expansions = environment.macro_expansions();
+        if let Some(expansion) = expansions.get(form, ifn, &locals) {
+            return expansion;
+        }
+        let expansion = Value::expand_macro_call(ifn, form, args, environment);
+        // An error is left to be raised again, should form be evaluated again
+        if !expansion.short_circuits() {
+            expansions.insert(form, ifn, locals, Rc::clone(&expansion));
+        }
+        expansion
+    }
+    /// Expands form until it is no longer a call to a macro
+    pub fn macroexpand(form: &Rc<Value>, environment: &Rc<Environment>) -> Rc<Value> {
+        let mut form = Rc::clone(form);
+        while let Some(expansion) = Value::macroexpand_1(&form, environment) {
+            form = expansion;
+        }
+        form
+    }
+    /// Expands every macro call in form, including those nested inside it;  quoted forms
+    /// are left alone
+    pub fn macroexpand_all(form: &Rc<Value>, environment: &Rc<Environment>) -> Rc<Value> {
+        let form = Value::macroexpand(form, environment);
+        match &*form {
+            Value::PersistentList(Cons(head, ..))
+                if **head == Symbol::intern("quote").to_value() =>
+            {
+                form
+            }
+            Value::PersistentList(plist) => Rc::new(plist.clone())
+                .iter()
+                .map(|form| Value::macroexpand_all(&form, environment))
+                .collect::<PersistentList>()
+                .to_rc_value(),
+            Value::PersistentVector(pvector) => pvector
+                .iter()
+                .map(|form| Value::macroexpand_all(&form, environment))
+                .collect::<PersistentVector>()
+                .to_rc_value(),
+            Value::PersistentListMap(_) | Value::PersistentHashMap(_) => {
+                maps::map_of(form.as_protocol::<protocols::IPersistentMap>().iter().map(
+                    |map_entry| MapEntry {
+                        key: Value::macroexpand_all(&map_entry.key, environment),
+                        val: Value::macroexpand_all(&map_entry.val, environment),
+                    },
+                ))
+                .to_rc_value()
+            }
+            Value::PersistentHashSet(phashset) => phashset
+                .iter()
+                .map(|form| Value::macroexpand_all(&form, environment))
+                .collect::<PersistentHashSet>()
+                .to_rc_value(),
+            Value::PersistentQueue(pqueue) => pqueue
+                .iter()
+                .map(|form| Value::macroexpand_all(&form, environment))
+                .collect::<PersistentQueue>()
+                .to_rc_value(),
+            _ => form,
+        }
+    }
+    /// A macro's &env;  a map of the locals around it, or nil at top level.  As macros
+    /// are expanded before their locals have values, each local stands for itself, as
+    /// in {x x}, in place of Clojure's LocalBinding
+    fn macro_env(environment: &Rc<Environment>) -> Rc<Value> {
+        let local_names = environment.local_names();
+        if local_names.is_empty() {
+            return Rc::new(Value::Nil);
+        }
+        let mut env_map = PersistentListMap::Empty;
+        for sym in local_names.into_iter() {
+            let sym = sym.to_rc_value();
+            // Innermost come first, and shadow the rest
+            if !env_map.contains_key(&sym) {
+                env_map = env_map.assoc(Rc::clone(&sym), sym);
+            }
+        }
+        env_map.to_rc_value()
+    }
+    /// Builds one arity of a fn out of its ([args] body) form, minus the parens
+    fn fn_arity(arity_form: &[Rc<Value>]) -> Result<lambda::FnArity, Rc<Value>> {
+        let (vals, meta) = match arity_form.first().map(|form| &**form) {
+            Some(Value::PersistentVector(pvector)) => (pvector.to_vec(), pvector.meta()),
+            _ => {
+                return Err(Rc::new(error_message::error(
+                    "syntax",
+                    std::string::String::from("Parameter declaration missing"),
+                )))
+            }
+        };
+        // A param to destructure, like [a {:keys [b]}], is given a fresh symbol
+        // of its own, which our body then destructures with a let, like
+        //   (fn [a p__1] (let [{:keys [b]} p__1] ..))
+        let mut arg_syms_vec = vec![];
+        let mut destructured_bindings = vec![];
+        for val in vals.iter() {
+            match &**val {
+                Value::Symbol(sym) => arg_syms_vec.push(sym.clone()),
+                Value::PersistentVector(_)
+                | Value::PersistentListMap(_)
+                | Value::PersistentHashMap(_) => {
+                    let param_sym = Symbol::gensym("p__");
+                    destructured_bindings.push(Rc::clone(val));
+                    destructured_bindings.push(param_sym.to_rc_value());
+                    arg_syms_vec.push(param_sym);
+                }
+                _ => {
+                    return Err(Rc::new(error_message::error(
+                        "syntax",
+                        format!("Unsupported binding form: {}", val),
+                    )))
+                }
+            }
+        }
+
+        let fn_body =
+            // (fn [x y] ) -> nil
+            if arity_form.len() <= 1 {
+                Rc::new(Value::Nil)
+            // (fn [x y] expr) -> expr
+            } else if arity_form.len() == 2 {
+                Rc::clone(arity_form.get(1).unwrap())
+            // (fn [x y] expr1 expr2 expr3) -> (do expr1 expr2 expr3)
+            } else {
+                // (&[expr1 expr2 expr3]
+                let body_exprs = arity_form.get(1..).unwrap();
+                // vec![do]
+                let mut do_body = vec![Symbol::intern("do").to_rc_value()];
+                // vec![do expr1 expr2 expr3]
+                do_body.extend_from_slice(body_exprs);
+                // (do expr1 expr2 expr3)
+                do_body.into_list().to_rc_value()
+            };
+        let fn_body = if destructured_bindings.is_empty() {
+            fn_body
+        } else {
+            vec![
+                Symbol::intern("let").to_rc_value(),
+                destructured_bindings.into_vector().to_rc_value(),
+                fn_body,
+            ]
+            .into_list()
+            .to_rc_value()
+        };
+        // (fn ^{:recur-across "try"} [] ..) is the body of a try, which a macro made into a fn
+        let recur_across = match &*meta.get(&Keyword::intern("recur-across").to_rc_value()) {
+            Value::Nil => None,
+            form => Some(form.to_string()),
+        };
+        Ok(lambda::FnArity {
+            body: fn_body,
+            arg_syms: arg_syms_vec,
+            recur_across,
+        })
+    }
+    /// Evaluates forms that are not in tail position, such as the arguments to a fn,
+    /// in order.  None of these may be a (recur ..), as there would be
+    /// no way for it to return back up to its loop;  should one error or unwind, we stop
+    /// there, and hand that back
+    fn eval_non_tail_forms(
+        forms: impl Iterator<Item = Rc<Value>>,
+        environment: &Rc<Environment>,
+    ) -> Result<Vec<Rc<Value>>, Rc<Value>> {
+        let mut evaled_forms = vec![];
+        for form in forms {
+            let evaled_form = form.eval_to_rc(Rc::clone(environment));
+            if evaled_form.is_recur() {
+                return Err(Rc::new(error_message::recur_not_in_tail_position()));
+            }
+            if evaled_form.short_circuits() {
+                return Err(evaled_form);
+            }
+            evaled_forms.push(evaled_form);
+        }
+        Ok(evaled_forms)
+    }
+    /// Evaluates a body of forms, like that of a (do ..), returning the value of the last one.
+    /// Only that last form is in tail position, and it is returned as is
+    fn eval_body(
+        body: &[Rc<Value>],
+        environment: &Rc<Environment>,
+        in_tail_position: bool,
+    ) -> Rc<Value> {
+        match body.split_last() {
+            Some((last, init)) => {
+                if let Err(condition) =
+                    Value::eval_non_tail_forms(init.iter().cloned(), environment)
+                {
+                    return condition;
+                }
+                Value::eval_form(last, Rc::clone(environment), in_tail_position)
+            }
+            None => Rc::new(Value::Nil),
+        }
+    }
+    /// Binds the pairs of a binding vector, like [x 1 [y z] (range 2)], into a local environment,
+    /// one after the other so each can see the last.  Returns the binding forms that were bound,
+    /// in order
+    fn bind_local_pairs(
+        bindings: &[Rc<Value>],
+        local_environment: &Rc<Environment>,
+    ) -> Result<Vec<Rc<Value>>, Rc<Value>> {
+        let mut bound_patterns = vec![];
+        for pair in bindings.chunks(2) {
+            let val = match pair.get(1) {
+                Some(form) => form.eval_to_rc(Rc::clone(local_environment)),
+                None => Rc::new(Value::Nil),
+            };
+            if val.is_recur() {
+                return Err(Rc::new(error_message::recur_not_in_tail_position()));
+            }
+            if val.short_circuits() {
+                return Err(val);
+            }
+            destructure::bind(&pair[0], val, local_environment)?;
+            bound_patterns.push(Rc::clone(&pair[0]));
+        }
+        Ok(bound_patterns)
+    }
     ////////////////////////////////////////////////////////////////////////////////////////////////////
     // Eval Helper
     ////////////////////////////////////////////////////////////////////////////////////////////////////
+    pub fn is_recur(&self) -> bool {
+        matches!(self, Value::Recur(_))
+    }
+    pub fn is_unwind(&self) -> bool {
+        matches!(self, Value::Unwind(..))
+    }
+    /// Whether this is not a value to go on evaluating with, but one on its way back up
+    /// through evaluation;  an error, or an Unwind.  Whatever is evaluating stops there,
+    /// and hands it back to its caller as is
+    pub fn short_circuits(&self) -> bool {
+        matches!(self, Value::Condition(_) | Value::Unwind(..))
+    }
+    /// Whether we are an ordered collection, that is equal to any other with the same values
+    pub fn is_sequential(&self) -> bool {
+        matches!(
+            self,
+            Value::PersistentList(_)
+                | Value::PersistentVector(_)
+                | Value::PersistentQueue(_)
+                | Value::LazySeq(_)
+                | Value::Cons(_)
+        )
+    }
     pub fn is_truthy(&self) -> bool {
         if let Value::Boolean(false) = self {
             return false;
@@ -598,7 +1480,13 @@ impl ToValue for Rc<Value> {
 
 impl ToValue for i32 {
     fn to_value(&self) -> Value {
-        Value::I32(*self)
+        Value::I64(i64::from(*self))
+    }
+}
+
+impl ToValue for i64 {
+    fn to_value(&self) -> Value {
+        Value::I64(*self)
     }
 }
 
@@ -608,6 +1496,30 @@ impl ToValue for f64 {
     }
 }
 
+impl ToValue for BigInt {
+    fn to_value(&self) -> Value {
+        Value::BigInt(self.clone())
+    }
+}
+
+impl ToValue for BigRational {
+    fn to_value(&self) -> Value {
+        Value::Ratio(self.clone())
+    }
+}
+
+impl ToValue for BigDecimal {
+    fn to_value(&self) -> Value {
+        Value::BigDecimal(self.clone())
+    }
+}
+
+impl ToValue for char {
+    fn to_value(&self) -> Value {
+        Value::Char(*self)
+    }
+}
+
 impl ToValue for bool {
     fn to_value(&self) -> Value {
         Value::Boolean(*self)
@@ -675,11 +1587,40 @@ impl ToValue for PersistentListMap {
     }
 }
 
+impl ToValue for PersistentHashMap {
+    fn to_value(&self) -> Value {
+        Value::PersistentHashMap(self.clone())
+    }
+}
+
+impl ToValue for PersistentHashSet {
+    fn to_value(&self) -> Value {
+        Value::PersistentHashSet(self.clone())
+    }
+}
+
+impl ToValue for PersistentTreeMap {
+    fn to_value(&self) -> Value {
+        Value::PersistentTreeMap(self.clone())
+    }
+}
+
+impl ToValue for PersistentQueue {
+    fn to_value(&self) -> Value {
+        Value::PersistentQueue(self.clone())
+    }
+}
+impl ToValue for PersistentTreeSet {
+    fn to_value(&self) -> Value {
+        Value::PersistentTreeSet(self.clone())
+    }
+}
+
 impl<T: Display, V: ToValue> ToValue for Result<V, T> {
     fn to_value(&self) -> Value {
         match self {
             Ok(val) => val.to_value(),
-            Err(err) => Value::Condition(err.to_string()),
+            Err(err) => error_message::unknown_err(err.to_string()),
         }
     }
 }
@@ -703,43 +1644,96 @@ pub trait Evaluable {
 
 impl Evaluable for Rc<Value> {
     fn eval_to_rc(&self, environment: Rc<Environment>) -> Rc<Value> {
-        match &**self {
+        Value::eval_form(self, environment, false)
+    }
+}
+impl Value {
+    /// Evaluates a form, which may be in tail position.  Only in tail position may a call
+    /// to a fn come back uncalled as a Value::TailCall;  otherwise, we make it here, and
+    /// so everything that evaluates through eval_to_rc gets back a fully evaluated value
+    pub(crate) fn eval_form(
+        form: &Rc<Value>,
+        environment: Rc<Environment>,
+        in_tail_position: bool,
+    ) -> Rc<Value> {
+        match &**form {
             // Evaluating a symbol means grabbing the value its been bound to in our environment
-            Value::Symbol(symbol) => environment.get(symbol),
+            Value::Symbol(symbol) => position::locate(environment.get(symbol), form),
             // Evaluating a vector [a b c] just means [(eval a) (eval b) (eval c)]
             Value::PersistentVector(pvector) => {
                 // Evaluate each Rc<Value> our PersistentVector wraps
                 // and return a new PersistentVector wrapping the new evaluated Values
-                let evaled_vals = pvector
-                    .vals
-                    .iter()
-                    .map(|rc_val| rc_val.eval_to_rc(Rc::clone(&environment)))
-                    .collect::<PersistentVector>();
-                Rc::new(Value::PersistentVector(evaled_vals))
+                match Value::eval_non_tail_forms(pvector.iter(), &environment) {
+                    Ok(evaled_vals) => evaled_vals.into_vector().to_rc_value(),
+                    Err(condition) => condition,
+                }
             }
-            Value::PersistentListMap(plistmap) => {
-                // Evaluate each Rc<Value> our PersistentVector wraps
-                // and return a new PersistentVector wrapping the new evaluated Values
-                let evaled_vals = plistmap
-                    .iter()
-                    .map(|map_entry| MapEntry {
-                        key: map_entry.key.eval_to_rc(Rc::clone(&environment)),
-                        val: map_entry.val.eval_to_rc(Rc::clone(&environment)),
-                    })
-                    .collect::<PersistentListMap>();
-                Rc::new(Value::PersistentListMap(evaled_vals))
+            Value::PersistentListMap(_) | Value::PersistentHashMap(_) => {
+                // Evaluate each key and val our map wraps, and return a new map of
+                // the evaluated entries
+                let mut evaled_vals = vec![];
+                for map_entry in form.as_protocol::<protocols::IPersistentMap>().iter() {
+                    let key_and_val = vec![map_entry.key, map_entry.val];
+                    match Value::eval_non_tail_forms(key_and_val.into_iter(), &environment) {
+                        Ok(evaled_key_and_val) => evaled_vals.push(MapEntry {
+                            key: Rc::clone(&evaled_key_and_val[0]),
+                            val: Rc::clone(&evaled_key_and_val[1]),
+                        }),
+                        Err(condition) => return condition,
+                    }
+                }
+                maps::map_of(evaled_vals).to_rc_value()
+            }
+            // Evaluating a set #{a b} means #{(eval a) (eval b)};  should two come out
+            // the same, we just hold that value once
+            Value::PersistentHashSet(phashset) => {
+                match Value::eval_non_tail_forms(phashset.iter(), &environment) {
+                    Ok(evaled_vals) => evaled_vals
+                        .into_iter()
+                        .collect::<PersistentHashSet>()
+                        .to_rc_value(),
+                    Err(condition) => condition,
+                }
+            }
+            // Likewise #queue [a b] means #queue [(eval a) (eval b)]
+            Value::PersistentQueue(pqueue) => {
+                match Value::eval_non_tail_forms(pqueue.iter(), &environment) {
+                    Ok(evaled_vals) => evaled_vals
+                        .into_iter()
+                        .collect::<PersistentQueue>()
+                        .to_rc_value(),
+                    Err(condition) => condition,
+                }
             }
             // Evaluating a list (a b c) means calling a as a function or macro on arguments b and c
             Value::PersistentList(plist) => match plist {
-                Cons(head, tail, __count) => {
+                Cons(head, tail, ..) => {
                     // First we have to evaluate the head of our list and make sure it is function-like
                     // and can be invoked on our arguments
                     // (ie, a fn, a macro, a keyword ..)
                     // @TODO remove clone if possible
                     let ifn = Rc::clone(head).eval_to_rc(Rc::clone(&environment));
+                    if ifn.short_circuits() {
+                        return ifn;
+                    }
+
+                    // Macros are expanded first;  then, what they expand to is what we evaluate
+                    // (as with macroexpand_1, only a symbol naming a macro is a macro call)
+                    if let (Value::Symbol(_), Value::Macro(macro_ifn)) = (&**head, &*ifn) {
+                        let expansion =
+                            Value::expand_macro_call_cached(macro_ifn, form, tail, &environment);
+                        return position::locate(
+                            Value::eval_form(&expansion, environment, in_tail_position),
+                            form,
+                        );
+                    }
 
-                    let try_apply_ifn =
-                        ifn.apply_to_persistent_list(&Rc::clone(&environment), tail);
+                    let try_apply_ifn = ifn.apply_to_persistent_list(
+                        &Rc::clone(&environment),
+                        form,
+                        tail,
+                        in_tail_position,
+                    );
 
                     // Right now we're using the normal error message, however maybe later we will try
                     //
@@ -748,18 +1742,37 @@ impl Evaluable for Rc<Value> {
                     //
                     // Sounds less correct but also seems clearer; the current error message relies on
                     // you pretty much already knowing when this error message is called
-                    try_apply_ifn.unwrap_or_else(|| {
-                        Rc::new(Value::Condition(format!(
-                            "Execution Error: {} cannot be cast to clojure.lang.IFn",
-                            ifn.type_tag()
-                        )))
-                    })
+                    let result = try_apply_ifn.unwrap_or_else(|| {
+                        Rc::new(error_message::error(
+                            "type-mismatch",
+                            format!(
+                                "Execution Error: {} cannot be cast to clojure.lang.IFn",
+                                ifn.type_tag()
+                            ),
+                        ))
+                    });
+                    position::locate(result, form)
                 }
                 // () evals to ()
                 PersistentList::Empty => Rc::new(Value::PersistentList(PersistentList::Empty)),
             },
+            // A seq, say one a macro built with concat, evals as the list of its values
+            Value::LazySeq(_) | Value::Cons(_) => {
+                let mut vals = vec![];
+                for val in form.as_protocol::<protocols::ISeq>().iter() {
+                    match val {
+                        Ok(val) => vals.push(val),
+                        Err(condition) => return condition,
+                    }
+                }
+                Value::eval_form(
+                    &vals.into_list().to_rc_value(),
+                    environment,
+                    in_tail_position,
+                )
+            }
             // Other types eval to self; (5 => 5,  "cat" => "cat",  #function[+] => #function[+]
-            _ => Rc::clone(&self),
+            _ => Rc::clone(form),
         }
     }
 }
@@ -773,14 +1786,18 @@ impl Evaluable for Value {
         self.to_rc_value().eval_to_rc(environment)
     }
 }
+#[cfg(test)]
 mod tests {
     use crate::environment::Environment;
+    use crate::error_message;
     use crate::keyword::Keyword;
     use crate::maps::MapEntry;
+    use crate::persistent_list::PersistentList;
     use crate::persistent_list_map::IPersistentMap;
     use crate::persistent_list_map::PersistentListMap;
     use crate::protocol::ProtocolCastable;
     use crate::protocols;
+    use crate::repl::Repl;
     use crate::symbol::Symbol;
     use crate::traits::IMeta;
     use crate::value::ToValue;
@@ -796,19 +1813,26 @@ mod tests {
             "dog" => 2
         };
         let a = sym!("a").with_meta(sym_meta);
+        let form = list_val!(sym!("def") a "Docstring" 1).to_rc_value();
+        let args = match &*form {
+            Value::PersistentList(PersistentList::Cons(_, args, ..)) => Rc::clone(args),
+            _ => panic!("Expected a list, found {}", form),
+        };
         let result = Value::DefMacro.apply_to_persistent_list(
             &Rc::new(Environment::new_main_environment()),
-            &Rc::new(list!(a "Docstring" 1)),
+            &form,
+            &args,
+            false,
         );
 
         let final_sym_meta = result.unwrap().as_protocol::<protocols::IMeta>().meta();
 
         assert_eq!(
-            Value::I32(1),
+            Value::I64(1),
             *final_sym_meta.get(&Keyword::intern("cat").to_rc_value())
         );
         assert_eq!(
-            Value::I32(2),
+            Value::I64(2),
             *final_sym_meta.get(&Keyword::intern("dog").to_rc_value())
         );
         assert_eq!(
@@ -816,4 +1840,262 @@ mod tests {
             *final_sym_meta.get(&Keyword::intern("doc").to_rc_value())
         );
     }
+
+    #[test]
+    fn loop_recur_runs_in_constant_stack_space() {
+        let repl = Repl::default();
+        let result = repl.eval(&Repl::read_string(
+            "(loop [i 0 acc 0] (if (= i 100000) acc (recur (inc i) (+ acc 2))))",
+        ));
+        assert_eq!(Value::I64(200000), result);
+    }
+
+    #[test]
+    fn mutual_recursion_runs_in_constant_stack_space() {
+        let repl = Repl::default();
+        repl.eval(&Repl::read_string("(def my-odd? nil)"));
+        repl.eval(&Repl::read_string(
+            "(defn my-even? [n] (if (= n 0) true (let [m (dec n)] (my-odd? m))))",
+        ));
+        repl.eval(&Repl::read_string(
+            "(defn my-odd? [n] (if (= n 0) false (do (my-even? (dec n)))))",
+        ));
+        let result = repl.eval(&Repl::read_string("(my-even? 20000)"));
+        assert_eq!(Value::Boolean(true), result);
+        let result = repl.eval(&Repl::read_string("(my-odd? 20001)"));
+        assert_eq!(Value::Boolean(true), result);
+    }
+
+    #[test]
+    fn trampoline_calls_returned_fns() {
+        let repl = Repl::default();
+        repl.eval(&Repl::read_string(
+            "(defn count-down [n] (if (= n 0) :done (fn [] (count-down (dec n)))))",
+        ));
+        let result = repl.eval(&Repl::read_string("(trampoline count-down 20000)"));
+        assert_eq!(Keyword::intern("done").to_value(), result);
+    }
+
+    #[test]
+    fn multi_arity_fn_dispatches_on_arg_count() {
+        let repl = Repl::default();
+        repl.eval(&Repl::read_string(
+            "(defn foo ([] :none) ([x] x) ([x y] (+ x y)) ([x y & more] more))",
+        ));
+        assert_eq!(
+            Keyword::intern("none").to_value(),
+            repl.eval(&Repl::read_string("(foo)"))
+        );
+        assert_eq!(Value::I64(1), repl.eval(&Repl::read_string("(foo 1)")));
+        assert_eq!(Value::I64(3), repl.eval(&Repl::read_string("(foo 1 2)")));
+        assert_eq!(
+            Repl::read_string("(3 4)"),
+            repl.eval(&Repl::read_string("(foo 1 2 3 4)"))
+        );
+
+        let result = repl.eval(&Repl::read_string("((fn ([x] x) ([x y & more] y)))"));
+        assert_eq!(
+            error_message::wrong_arg_count_described("1 or 2 or more", 0),
+            result
+        );
+    }
+
+    #[test]
+    fn named_fn_can_call_itself() {
+        let repl = Repl::default();
+        let result = repl.eval(&Repl::read_string(
+            "((fn fact [n] (if (= n 0) 1 (* n (fact (dec n))))) 5)",
+        ));
+        assert_eq!(Value::I64(120), result);
+    }
+
+    #[test]
+    fn named_fn_prints_its_name() {
+        let repl = Repl::default();
+        repl.eval(&Repl::read_string("(ns user)"));
+        repl.eval(&Repl::read_string("(defn foo [x] x)"));
+        assert_eq!(
+            "#function[user/foo]",
+            repl.eval(&Repl::read_string("foo")).to_string()
+        );
+        assert_eq!(
+            "#function[]",
+            repl.eval(&Repl::read_string("(fn [x] x)")).to_string()
+        );
+    }
+
+    #[test]
+    fn fns_resolve_symbols_in_the_namespace_they_were_defined_in() {
+        let repl = Repl::default();
+        for form in &[
+            "(ns foo)",
+            "(def x 1)",
+            "(defn g [] x)",
+            "(defn h [] (fn [] (+ x 1)))",
+        ] {
+            repl.eval(&Repl::read_string(form));
+        }
+        repl.eval(&Repl::read_string("(ns bar)"));
+        repl.eval(&Repl::read_string("(def x 10)"));
+        assert_eq!(Value::I64(1), repl.eval(&Repl::read_string("(foo/g)")));
+        assert_eq!(Value::I64(2), repl.eval(&Repl::read_string("((foo/h))")));
+        assert_eq!(Value::I64(10), repl.eval(&Repl::read_string("((fn [] x))")));
+        assert_eq!(
+            Value::I64(1),
+            repl.eval(&Repl::read_string("(let [x 5] (foo/g))"))
+        );
+    }
+
+    #[test]
+    fn letfn_binds_mutually_recursive_fns() {
+        let repl = Repl::default();
+        let result = repl.eval(&Repl::read_string(
+            "(letfn [(ev? [n] (if (= n 0) true (od? (dec n)))) \
+                     (od? [n] (if (= n 0) false (ev? (dec n))))] \
+               (ev? 100))",
+        ));
+        assert_eq!(Value::Boolean(true), result);
+    }
+
+    #[test]
+    fn macroexpand_expands_without_evaluating() {
+        let repl = Repl::default();
+        repl.eval(&Repl::read_string(
+            "(defmacro unless [test & body] \
+               (list 'when (list '= test false) (concat (list 'do) body)))",
+        ));
+        assert_eq!(
+            Repl::read_string("(when (= x false) (do y))"),
+            repl.eval(&Repl::read_string("(macroexpand-1 '(unless x y))"))
+        );
+        assert_eq!(
+            Repl::read_string("(if (= x false) (do (do y)))"),
+            repl.eval(&Repl::read_string("(macroexpand '(unless x y))"))
+        );
+        assert_eq!(
+            Repl::read_string("(fn [x] (if (= x false) (do (do (quote (unless y))))))"),
+            repl.eval(&Repl::read_string(
+                "(clojure.walk/macroexpand-all '(fn [x] (unless x '(unless y))))"
+            ))
+        );
+        assert_eq!(
+            Repl::read_string("(+ 1 2)"),
+            repl.eval(&Repl::read_string("(macroexpand '(+ 1 2))"))
+        );
+    }
+
+    #[test]
+    fn macros_are_given_form_and_env() {
+        let repl = Repl::default();
+        repl.eval(&Repl::read_string(
+            "(defmacro whole-form [x] (list 'quote &form))",
+        ));
+        assert_eq!(
+            Repl::read_string("(whole-form 5)"),
+            repl.eval(&Repl::read_string("(whole-form 5)"))
+        );
+        repl.eval(&Repl::read_string(
+            "(defmacro locals [] (list 'quote &env))",
+        ));
+        assert_eq!(Value::Nil, repl.eval(&Repl::read_string("(locals)")));
+        assert_eq!(
+            Repl::read_string("{a a}"),
+            repl.eval(&Repl::read_string("(let [a 1] (locals))"))
+        );
+    }
+
+    #[test]
+    fn macros_are_expanded_once_per_form() {
+        let repl = Repl::default();
+        repl.eval_str("(def expansions (atom 0))");
+        repl.eval_str("(defmacro counted [x] (swap! expansions inc) x)");
+        repl.eval_str("(defn f [n] (counted n))");
+        assert_eq!(Value::I64(3), repl.eval_str("(+ (f 1) (f 2))"));
+        assert_eq!(Value::I64(1), repl.eval_str("@expansions"));
+        repl.eval_str("(reset! expansions 0)");
+        assert_eq!(
+            Value::I64(100),
+            repl.eval_str("(loop [i 0] (if (= i 100) i (recur (counted (inc i)))))")
+        );
+        assert_eq!(Value::I64(1), repl.eval_str("@expansions"));
+        // The same call, with other locals around it, is expanded again
+        repl.eval_str("(reset! expansions 0)");
+        repl.eval_str("(defmacro twice [x] `(do (let [~'a 1] ~x) (let [~'b 2] ~x)))");
+        repl.eval_str("(twice (counted 0))");
+        assert_eq!(Value::I64(2), repl.eval_str("@expansions"));
+        // As is a call to a macro since redefined
+        repl.eval_str("(defmacro counted [x] (swap! expansions inc) (list 'inc x))");
+        assert_eq!(Value::I64(2), repl.eval_str("(f 1)"));
+    }
+
+    #[test]
+    fn recur_in_fn_body() {
+        let repl = Repl::default();
+        repl.eval(&Repl::read_string(
+            "(defn count-down [n] (if (= n 0) :done (recur (dec n))))",
+        ));
+        let result = repl.eval(&Repl::read_string("(count-down 100000)"));
+        assert_eq!(Keyword::intern("done").to_value(), result);
+
+        repl.eval(&Repl::read_string(
+            "(defn sum-all [acc & xs] (if (= xs nil) acc (recur (+ acc (first xs)) (next xs))))",
+        ));
+        let result = repl.eval(&Repl::read_string("(sum-all 0 1 2 3 4)"));
+        assert_eq!(Value::I64(10), result);
+    }
+
+    #[test]
+    fn recur_through_do_and_let() {
+        let repl = Repl::default();
+        let result = repl.eval(&Repl::read_string(
+            "(loop [i 0] (let [j (inc i)] (if (= j 10) j (do :ignored (recur j)))))",
+        ));
+        assert_eq!(Value::I64(10), result);
+    }
+
+    #[test]
+    fn recur_not_in_tail_position() {
+        let repl = Repl::default();
+        let expected = error_message::recur_not_in_tail_position();
+        assert_eq!(
+            expected,
+            repl.eval(&Repl::read_string("(loop [i 0] (+ 1 (recur i)))"))
+        );
+        assert_eq!(
+            expected,
+            repl.eval(&Repl::read_string("(loop [i 0] (do (recur i) 1))"))
+        );
+        assert_eq!(expected, repl.eval(&Repl::read_string("(recur 1)")));
+    }
+
+    #[test]
+    fn recur_with_wrong_arg_count() {
+        let repl = Repl::default();
+        assert_eq!(
+            error_message::recur_arg_count(1, 2),
+            repl.eval(&Repl::read_string("(loop [i 0] (recur 1 2))"))
+        );
+    }
+
+    #[test]
+    fn conditions_short_circuit() {
+        let repl = Repl::default();
+        let expected = error_message::index_out_of_bounds(3, 1);
+        assert_eq!(
+            expected,
+            repl.eval(&Repl::read_string("(str \"a\" (+ 1 (nth [1] 3)))"))
+        );
+        assert_eq!(
+            expected,
+            repl.eval(&Repl::read_string("(let [x (nth [1] 3)] (def y 1))"))
+        );
+        assert_eq!(
+            expected,
+            repl.eval(&Repl::read_string("[1 {:a (nth [1] 3)}]"))
+        );
+        assert!(matches!(
+            repl.eval(&Repl::read_string("y")),
+            Value::Condition(_)
+        ));
+    }
 }
@@ -1,6 +1,8 @@
+use crate::error_message;
 use crate::ifn::IFn;
 use crate::iterable::Iterable;
-use crate::persistent_list_map::PersistentListMap;
+use crate::keyword::Keyword;
+use crate::persistent_list_map::{IPersistentMap, PersistentListMap};
 use crate::protocol::Protocol;
 use crate::protocol::ProtocolCastable;
 use crate::protocols;
@@ -8,6 +10,7 @@ use crate::symbol::Symbol;
 use crate::traits;
 use crate::value::{ToValue, Value};
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::rc::Rc;
@@ -36,8 +39,19 @@ pub struct Var {
     // immutable, is there value in expressing this mixed mutability in someway
     // without just wrapping these in RefCells?
     meta: RefCell<protocols::IPersistentMap>,
-    pub root: RefCell<Rc<Value>>,
+    // None while we're unbound, as after (declare x)
+    root: RefCell<Option<Rc<Value>>>,
 }
+/// The values dynamic vars are bound to on this thread, as of one frame of bindings;  each
+/// frame holds its enclosing frame's bindings too, so we only ever need look at the innermost.
+/// Keyed by the var's qualified name, as in user/*x*, with each value in its own cell, so
+/// that a set! is seen by everyone sharing the frame, as a bound-fn does
+pub type ThreadBindings = HashMap<String, Rc<RefCell<Rc<Value>>>>;
+
+thread_local! {
+    static THREAD_BINDINGS: RefCell<Vec<Rc<ThreadBindings>>> = const { RefCell::new(vec![]) };
+}
+
 macro_rules! var {
     ($ns:expr, $sym:expr) => {
         Var::intern(sym!($ns), sym!($sym))
@@ -52,17 +66,117 @@ impl Var {
             ns,
             sym,
             meta: RefCell::new(empty_meta.as_protocol::<protocols::IPersistentMap>()),
-            // What do if unbound? Why does unbound exist?
-            root: RefCell::new(Value::Nil.to_rc_value()),
+            root: RefCell::new(None),
         }
     }
 
+    /// Our value on this thread;  what we're bound to by binding, if we are, otherwise our root
     pub fn deref(&self) -> Rc<Value> {
-        self.root.borrow().clone()
+        match self.thread_binding() {
+            Some(binding) => binding.borrow().clone(),
+            None => match &*self.root.borrow() {
+                Some(root) => Rc::clone(root),
+                None => Rc::new(error_message::error(
+                    "illegal-state",
+                    format!("Var {} is unbound.", self.qualified_sym()),
+                )),
+            },
+        }
+    }
+    /// Whether we have a root value, as we don't after a (declare x)
+    pub fn is_bound(&self) -> bool {
+        self.root.borrow().is_some() || self.thread_binding().is_some()
+    }
+    /// user/*x*;  our thread bindings are keyed by its name
+    fn qualified_sym(&self) -> Symbol {
+        Symbol::intern_with_ns(&self.ns.name, &self.sym.name)
+    }
+    /// Whether we were defined ^:dynamic, and so can be given thread bindings
+    pub fn is_dynamic(&self) -> bool {
+        *traits::IMeta::meta(self).get(&Keyword::intern("dynamic").to_rc_value())
+            == Value::Boolean(true)
+    }
+    fn thread_binding(&self) -> Option<Rc<RefCell<Rc<Value>>>> {
+        THREAD_BINDINGS.with(|frames| {
+            frames
+                .borrow()
+                .last()
+                .and_then(|bindings| bindings.get(&self.qualified_sym().to_string()).cloned())
+        })
+    }
+    /// (set! *x* val);  changes our thread binding, which we must have
+    pub fn set(&self, val: Rc<Value>) -> Value {
+        match self.thread_binding() {
+            Some(binding) => {
+                binding.replace(Rc::clone(&val));
+                val.to_value()
+            }
+            None => error_message::error(
+                "illegal-state",
+                format!(
+                    "Can't change/establish root binding of: {} with set",
+                    self.qualified_sym()
+                ),
+            ),
+        }
+    }
+    /// The bindings in effect on this thread
+    pub fn get_thread_bindings() -> Rc<ThreadBindings> {
+        THREAD_BINDINGS.with(|frames| match frames.borrow().last() {
+            Some(bindings) => Rc::clone(bindings),
+            None => Rc::new(HashMap::new()),
+        })
+    }
+    /// Calls body with each var bound to its value on this thread, in a new frame of bindings
+    /// on top of those already in effect;  only dynamic vars can be bound this way
+    pub fn with_bindings(
+        bindings: Vec<(Rc<Var>, Rc<Value>)>,
+        body: impl FnOnce() -> Value,
+    ) -> Value {
+        let mut frame = (*Var::get_thread_bindings()).clone();
+        for (var, val) in bindings {
+            if !var.is_dynamic() {
+                return error_message::error(
+                    "illegal-state",
+                    format!(
+                        "Can't dynamically bind non-dynamic var: {}",
+                        var.qualified_sym()
+                    ),
+                );
+            }
+            frame.insert(var.qualified_sym().to_string(), Rc::new(RefCell::new(val)));
+        }
+        Var::with_thread_bindings(Rc::new(frame), body)
+    }
+    /// Calls body with exactly bindings in effect, as we have for a bound-fn, which
+    /// conveys those it was made under
+    pub fn with_thread_bindings(
+        bindings: Rc<ThreadBindings>,
+        body: impl FnOnce() -> Value,
+    ) -> Value {
+        THREAD_BINDINGS.with(|frames| frames.borrow_mut().push(bindings));
+        let result = body();
+        THREAD_BINDINGS.with(|frames| frames.borrow_mut().pop());
+        result
     }
 
     pub fn bind_root(&self, root: Rc<Value>) {
-        self.root.replace(root);
+        self.root.replace(Some(root));
+    }
+    /// (alter-var-root var f & args);  gives us the root (apply f root args), and returns it
+    pub fn alter_root(&self, f: &Rc<dyn IFn>, args: Vec<Rc<Value>>) -> Value {
+        let root = match &*self.root.borrow() {
+            Some(root) => Rc::clone(root),
+            None => return self.deref().to_value(),
+        };
+        let mut f_args = vec![root];
+        f_args.extend(args);
+        let new_root = f.invoke(f_args);
+        if new_root.short_circuits() {
+            return new_root;
+        }
+        self.bind_root(new_root.to_rc_value());
+        new_root
     }
 
     pub fn set_meta(&self, meta: PersistentListMap) {
@@ -106,6 +220,7 @@ impl traits::IMeta for Var {
         let plist_map_value = self.meta.borrow().unwrap();
         match &*plist_map_value {
             Value::PersistentListMap(plist_map) => plist_map.clone(),
+            Value::PersistentHashMap(phash_map) => phash_map.iter().collect(),
             _ => panic!(
                 "In var.rs, meta(); IPersistentListMap failed to unwrap to PersistentListMap"
             ),
@@ -121,7 +236,7 @@ impl traits::IMeta for Var {
 
 impl fmt::Display for Var {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "#'{}", self.sym)
+        write!(f, "#'{}", self.qualified_sym())
     }
 }
 
@@ -138,12 +253,12 @@ mod tests {
         assert!(a == Symbol::intern("clojure"));
 
         let v = var!("clojure.core", "+");
-        v.bind_root(Rc::new(Value::I32(12)));
+        v.bind_root(Rc::new(Value::I64(12)));
 
-        assert!(*v.deref() == Value::I32(12));
+        assert!(*v.deref() == Value::I64(12));
 
-        v.bind_root(Rc::new(Value::I32(25)));
+        v.bind_root(Rc::new(Value::I64(25)));
 
-        assert!(*v.deref() == Value::I32(25));
+        assert!(*v.deref() == Value::I64(25));
     }
 }